@@ -1,11 +1,10 @@
 use crate::config::Config;
-use crate::memory::{self, Memory, MemoryCategory};
+use crate::memory::{self, Memory, MemoryCategory, MemoryScope};
 use crate::observability::{self, Observer, ObserverEvent};
-use crate::providers::{self, ChatMessage, Provider};
+use crate::providers::{self, reasoning, ChatMessage, Provider};
 use crate::runtime;
 use crate::security::SecurityPolicy;
 use crate::tools::{self, Tool};
-use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
 use std::fmt::Write;
 use std::io::Write as IoWrite;
@@ -24,6 +23,45 @@ fn autosave_memory_key(prefix: &str) -> String {
     format!("{prefix}_{}", Uuid::new_v4())
 }
 
+/// `auto_save` after a turn: extract durable facts, and log the raw
+/// exchange when `[memory.extraction]` asks for it. Failures are logged.
+async fn autosave_turn(
+    config: &Config,
+    mem: &dyn Memory,
+    provider: &dyn Provider,
+    model: &str,
+    user_msg: &str,
+    response: &str,
+) {
+    if !config.memory.auto_save {
+        return;
+    }
+
+    if config.memory.extraction.logs_raw() {
+        let user_key = autosave_memory_key("user_msg");
+        let _ = mem
+            .store(&user_key, user_msg, MemoryCategory::Conversation)
+            .await;
+        let response_key = autosave_memory_key("assistant_resp");
+        let _ = mem
+            .store(&response_key, response, MemoryCategory::Daily)
+            .await;
+    }
+
+    if config.memory.extraction.enabled {
+        let model = memory::extract::extraction_model(&config.memory, model);
+        let extractor = memory::extract::TurnExtractor {
+            memory: mem,
+            provider,
+            model: &model,
+            scope: &MemoryScope::Global,
+        };
+        if let Err(e) = extractor.run(user_msg, response).await {
+            tracing::warn!("memory extraction skipped: {e}");
+        }
+    }
+}
+
 /// Trim conversation history to prevent unbounded growth.
 /// Preserves the system prompt (first message if role=system) and the most recent messages.
 fn trim_history(history: &mut Vec<ChatMessage>) {
@@ -44,24 +82,6 @@ fn trim_history(history: &mut Vec<ChatMessage>) {
     history.drain(start..start + to_remove);
 }
 
-/// Build context preamble by searching memory for relevant entries
-async fn build_context(mem: &dyn Memory, user_msg: &str) -> String {
-    let mut context = String::new();
-
-    // Pull relevant memories for this message
-    if let Ok(entries) = mem.recall(user_msg, 5).await {
-        if !entries.is_empty() {
-            context.push_str("[Memory context]\n");
-            for entry in &entries {
-                let _ = writeln!(context, "- {}: {}", entry.key, entry.content);
-            }
-            context.push('\n');
-        }
-    }
-
-    context
-}
-
 /// Find a tool by name in the registry.
 fn find_tool<'a>(tools: &'a [Box<dyn Tool>], name: &str) -> Option<&'a dyn Tool> {
     tools.iter().find(|t| t.name() == name).map(|t| t.as_ref())
@@ -266,12 +286,24 @@ async fn agent_turn(
     observer: &dyn Observer,
     model: &str,
     temperature: f64,
+    show_reasoning: bool,
 ) -> Result<String> {
+    let tool_specs: Vec<_> = tools_registry.iter().map(|t| t.spec()).collect();
+
     for _iteration in 0..MAX_TOOL_ITERATIONS {
-        let response = provider
-            .chat_with_history(history, model, temperature)
+        let raw_response = provider
+            .chat_with_tools(history, &tool_specs, model, temperature)
             .await?;
 
+        // Reasoning models emit their chain of thought as a `<think>` block.
+        // Keep it out of tool parsing and history; optionally show it.
+        let (thinking, response) = reasoning::split_reasoning(&raw_response);
+        if show_reasoning {
+            if let Some(thinking) = &thinking {
+                println!("\x1b[2m💭 {thinking}\x1b[0m\n");
+            }
+        }
+
         let (text, tool_calls) = parse_tool_calls(&response);
 
         if tool_calls.is_empty() {
@@ -398,6 +430,7 @@ pub async fn run(
         &config.browser,
         &config.agents,
         config.api_key.as_deref(),
+        &providers::ProviderOptions::from_config(&config),
     );
 
     // ── Resolve provider ─────────────────────────────────────────
@@ -417,7 +450,9 @@ pub async fn run(
         &config.reliability,
         &config.model_routes,
         model_name,
+        &providers::ProviderOptions::from_config(&config),
     )?;
+    let provider = providers::cache::wrap_provider(provider, &config, observer.clone());
 
     observer.record_event(&ObserverEvent::AgentStart {
         provider: provider_name.to_string(),
@@ -451,6 +486,14 @@ pub async fn run(
             "memory_forget",
             "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
         ),
+        (
+            "memory_graph_query",
+            "Look up an entity and its relations in the knowledge graph. Use when: asking who owns/works on/depends on something, or following links between people and projects. Don't use when: a plain memory search answers it.",
+        ),
+        (
+            "memory_graph_upsert",
+            "Record entities, observations and relations in the knowledge graph. Use when: learning durable facts about who/what relates to whom. Don't use when: the fact is transient or already recorded.",
+        ),
     ];
     tool_descs.push((
         "screenshot",
@@ -495,16 +538,8 @@ pub async fn run(
     let start = Instant::now();
 
     if let Some(msg) = message {
-        // Auto-save user message to memory
-        if config.memory.auto_save {
-            let user_key = autosave_memory_key("user_msg");
-            let _ = mem
-                .store(&user_key, &msg, MemoryCategory::Conversation)
-                .await;
-        }
-
         // Inject memory context into user message
-        let context = build_context(mem.as_ref(), &msg).await;
+        let context = memory::context_block(mem.as_ref(), &msg, &[]).await;
         let enriched = if context.is_empty() {
             msg.clone()
         } else {
@@ -523,18 +558,20 @@ pub async fn run(
             observer.as_ref(),
             model_name,
             temperature,
+            config.reasoning.show_in_cli,
         )
         .await?;
         println!("{response}");
 
-        // Auto-save assistant response to daily log
-        if config.memory.auto_save {
-            let summary = truncate_with_ellipsis(&response, 100);
-            let response_key = autosave_memory_key("assistant_resp");
-            let _ = mem
-                .store(&response_key, &summary, MemoryCategory::Daily)
-                .await;
-        }
+        autosave_turn(
+            &config,
+            mem.as_ref(),
+            provider.as_ref(),
+            model_name,
+            &msg,
+            &response,
+        )
+        .await;
     } else {
         println!("🦀 ZeroClaw Interactive Mode");
         println!("Type /quit to exit.\n");
@@ -551,16 +588,8 @@ pub async fn run(
         let mut history = vec![ChatMessage::system(&system_prompt)];
 
         while let Some(msg) = rx.recv().await {
-            // Auto-save conversation turns
-            if config.memory.auto_save {
-                let user_key = autosave_memory_key("user_msg");
-                let _ = mem
-                    .store(&user_key, &msg.content, MemoryCategory::Conversation)
-                    .await;
-            }
-
             // Inject memory context into user message
-            let context = build_context(mem.as_ref(), &msg.content).await;
+            let context = memory::context_block(mem.as_ref(), &msg.content, &[]).await;
             let enriched = if context.is_empty() {
                 msg.content.clone()
             } else {
@@ -576,6 +605,7 @@ pub async fn run(
                 observer.as_ref(),
                 model_name,
                 temperature,
+                config.reasoning.show_in_cli,
             )
             .await
             {
@@ -590,13 +620,15 @@ pub async fn run(
             // Prevent unbounded history growth in long interactive sessions
             trim_history(&mut history);
 
-            if config.memory.auto_save {
-                let summary = truncate_with_ellipsis(&response, 100);
-                let response_key = autosave_memory_key("assistant_resp");
-                let _ = mem
-                    .store(&response_key, &summary, MemoryCategory::Daily)
-                    .await;
-            }
+            autosave_turn(
+                &config,
+                mem.as_ref(),
+                provider.as_ref(),
+                model_name,
+                &msg.content,
+                &response,
+            )
+            .await;
         }
 
         listen_handle.abort();
@@ -769,6 +801,21 @@ I will now call the tool with this payload:
         );
     }
 
+    #[test]
+    fn parse_tool_calls_after_reasoning_split() {
+        let raw = r#"<think>I need the file list</think>
+<tool_call>
+{"name": "shell", "arguments": {"command": "ls"}}
+</tool_call>"#;
+
+        let (thinking, response) = reasoning::split_reasoning(raw);
+        let (text, calls) = parse_tool_calls(&response);
+        assert_eq!(thinking.as_deref(), Some("I need the file list"));
+        assert!(text.is_empty());
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "shell");
+    }
+
     #[test]
     fn build_tool_instructions_includes_all_tools() {
         use crate::security::SecurityPolicy;
@@ -848,4 +895,52 @@ I will now call the tool with this payload:
         let recalled = mem.recall("45", 5).await.unwrap();
         assert!(recalled.iter().any(|entry| entry.content.contains("45")));
     }
+
+    /// Answers every prompt with the same extraction.
+    struct FactProvider;
+
+    #[async_trait::async_trait]
+    impl Provider for FactProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            Ok(r#"{"facts": [{"key": "user_age", "fact": "User is 45."}]}"#.into())
+        }
+    }
+
+    #[tokio::test]
+    async fn autosave_turn_extracts_facts_without_raw_log() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let config = Config::default();
+
+        autosave_turn(&config, &mem, &FactProvider, "m", "I'm 45", "Noted!").await;
+        autosave_turn(&config, &mem, &FactProvider, "m", "Did I say 45?", "Yes.").await;
+
+        let all = mem.list(None).await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].key, "user_age");
+        assert_eq!(all[0].category, MemoryCategory::Core);
+    }
+
+    #[tokio::test]
+    async fn autosave_turn_logs_full_reply_when_extraction_is_off() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let mut config = Config::default();
+        config.memory.extraction.enabled = false;
+        let reply = "a".repeat(300);
+
+        autosave_turn(&config, &mem, &FactProvider, "m", "hi", &reply).await;
+
+        let daily = mem.list(Some(&MemoryCategory::Daily)).await.unwrap();
+        assert_eq!(daily.len(), 1);
+        assert_eq!(daily[0].content, reply);
+        assert_eq!(mem.count().await.unwrap(), 2);
+        assert!(mem.get("user_age").await.unwrap().is_none());
+    }
 }
@@ -40,7 +40,13 @@ impl Channel for CliChannel {
             let msg = ChannelMessage {
                 id: Uuid::new_v4().to_string(),
                 sender: "user".to_string(),
+                author: "user".to_string(),
                 content: line,
+                attachments: Vec::new(),
+                message_id: None,
+                thread_id: None,
+                reply_to: None,
+                raw: None,
                 channel: "cli".to_string(),
                 timestamp: std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
@@ -90,7 +96,13 @@ mod tests {
         let msg = ChannelMessage {
             id: "test-id".into(),
             sender: "user".into(),
+            author: "user".into(),
             content: "hello".into(),
+            attachments: Vec::new(),
+            message_id: None,
+            thread_id: None,
+            reply_to: None,
+            raw: None,
             channel: "cli".into(),
             timestamp: 1_234_567_890,
         };
@@ -106,7 +118,13 @@ mod tests {
         let msg = ChannelMessage {
             id: "id".into(),
             sender: "s".into(),
+            author: "s".into(),
             content: "c".into(),
+            attachments: Vec::new(),
+            message_id: None,
+            thread_id: None,
+            reply_to: None,
+            raw: None,
             channel: "ch".into(),
             timestamp: 0,
         };
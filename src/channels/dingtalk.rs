@@ -150,9 +150,15 @@ impl Channel for DingTalkChannel {
                 let channel_msg = ChannelMessage {
                     id: robot_msg.msg_id.clone(),
                     sender: webhook.clone(), // Use webhook URL as sender for reply
+                    author: sender_id.clone(),
                     content: content.clone(),
+                    attachments: Vec::new(),
+                    message_id: None,
+                    thread_id: None,
+                    reply_to: None,
+                    raw: None,
                     channel: "DingTalk".to_string(),
-                    timestamp: robot_msg.create_at as u64,
+                    timestamp: robot_msg.create_at.cast_unsigned(),
                 };
 
                 tracing::info!("Sending message to handler: user={}, webhook={}, content={}",
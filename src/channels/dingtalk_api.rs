@@ -4,7 +4,7 @@ use std::time::{Duration, Instant};
 
 /// DingTalk API client for Stream mode and message sending
 pub struct DingTalkApi {
-    client_id: String,
+    pub(crate) client_id: String,
     client_secret: String,
     client: reqwest::Client,
     /// Cached access token
@@ -147,7 +147,7 @@ impl DingTalkApi {
         Self {
             client_id,
             client_secret,
-            client: reqwest::Client::new(),
+            client: crate::http::client("dingtalk", crate::http::Defaults::NONE),
             access_token: None,
         }
     }
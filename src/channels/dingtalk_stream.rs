@@ -1,7 +1,7 @@
 use super::dingtalk_api::{AckMessage, DingTalkApi, StreamMessage};
 use anyhow::Result;
 use futures_util::{SinkExt, StreamExt};
-use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tokio_tungstenite::tungstenite::Message;
 
 /// DingTalk Stream WebSocket client
 pub struct StreamClient {
@@ -30,7 +30,7 @@ impl StreamClient {
 
         // 3. Connect to WebSocket
         tracing::info!("Connecting to WebSocket...");
-        let (ws_stream, _) = connect_async(&ws_url).await?;
+        let ws_stream = crate::http::connect_websocket("dingtalk", &ws_url).await?;
         tracing::info!("WebSocket connected successfully");
 
         let (mut write, mut read) = ws_stream.split();
@@ -1,10 +1,14 @@
-use super::traits::{Channel, ChannelMessage};
+use super::traits::{Attachment, Channel, ChannelCapabilities, ChannelMessage, RichMessage};
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
+use reqwest::multipart::{Form, Part};
 use serde_json::json;
 use tokio_tungstenite::tungstenite::Message;
 use uuid::Uuid;
 
+/// Files Discord accepts on a single message
+const MAX_FILES_PER_MESSAGE: usize = 10;
+
 /// Discord channel — connects via Gateway WebSocket for real-time messages
 pub struct DiscordChannel {
     bot_token: String,
@@ -20,7 +24,7 @@ impl DiscordChannel {
             bot_token,
             guild_id,
             allowed_users,
-            client: reqwest::Client::new(),
+            client: crate::http::client("discord", crate::http::Defaults::NONE),
             typing_handle: std::sync::Mutex::new(None),
         }
     }
@@ -139,12 +143,31 @@ impl Channel for DiscordChannel {
     }
 
     async fn send(&self, message: &str, channel_id: &str) -> anyhow::Result<()> {
-        let chunks = split_message_for_discord(message);
+        self.send_rich(&RichMessage::text(message), channel_id)
+            .await
+    }
 
-        for (i, chunk) in chunks.iter().enumerate() {
-            let url = format!("https://discord.com/api/v10/channels/{channel_id}/messages");
-            let body = json!({ "content": chunk });
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities {
+            attachments: true,
+            replies: true,
+            threads: true,
+        }
+    }
+
+    async fn send_rich(&self, message: &RichMessage, channel_id: &str) -> anyhow::Result<()> {
+        // Discord threads are channels of their own.
+        let channel_id = message.thread_id.as_deref().unwrap_or(channel_id);
+        let url = format!("https://discord.com/api/v10/channels/{channel_id}/messages");
+        let mut reply_to = message.reply_to.as_deref();
 
+        let chunks = if message.text.is_empty() {
+            Vec::new()
+        } else {
+            split_message_for_discord(&message.text)
+        };
+        for (i, chunk) in chunks.iter().enumerate() {
+            let body = message_body(chunk, reply_to.take());
             let resp = self
                 .client
                 .post(&url)
@@ -152,15 +175,7 @@ impl Channel for DiscordChannel {
                 .json(&body)
                 .send()
                 .await?;
-
-            if !resp.status().is_success() {
-                let status = resp.status();
-                let err = resp
-                    .text()
-                    .await
-                    .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
-                anyhow::bail!("Discord send message failed ({status}): {err}");
-            }
+            check_response(resp).await?;
 
             // Add a small delay between chunks to avoid rate limiting
             if i < chunks.len() - 1 {
@@ -168,6 +183,24 @@ impl Channel for DiscordChannel {
             }
         }
 
+        for batch in message.attachments.chunks(MAX_FILES_PER_MESSAGE) {
+            let mut form = Form::new().text(
+                "payload_json",
+                message_body("", reply_to.take()).to_string(),
+            );
+            for (i, attachment) in batch.iter().enumerate() {
+                form = form.part(format!("files[{i}]"), file_part(attachment).await?);
+            }
+            let resp = self
+                .client
+                .post(&url)
+                .header("Authorization", format!("Bot {}", self.bot_token))
+                .multipart(form)
+                .send()
+                .await?;
+            check_response(resp).await?;
+        }
+
         Ok(())
     }
 
@@ -193,7 +226,7 @@ impl Channel for DiscordChannel {
         let ws_url = format!("{gw_url}/?v=10&encoding=json");
         tracing::info!("Discord: connecting to gateway...");
 
-        let (ws_stream, _) = tokio_tungstenite::connect_async(&ws_url).await?;
+        let ws_stream = crate::http::connect_websocket("discord", &ws_url).await?;
         let (mut write, mut read) = ws_stream.split();
 
         // Read Hello (opcode 10)
@@ -341,7 +374,17 @@ impl Channel for DiscordChannel {
                     let channel_msg = ChannelMessage {
                         id: Uuid::new_v4().to_string(),
                         sender: channel_id,
+                        author: author_id.to_string(),
                         content: content.to_string(),
+                        attachments: Vec::new(),
+                        message_id: d.get("id").and_then(|i| i.as_str()).map(ToString::to_string),
+                        thread_id: None,
+                        reply_to: d
+                            .get("message_reference")
+                            .and_then(|r| r.get("message_id"))
+                            .and_then(|i| i.as_str())
+                            .map(ToString::to_string),
+                        raw: Some(d.clone()),
                         channel: "discord".to_string(),
                         timestamp: std::time::SystemTime::now()
                             .duration_since(std::time::UNIX_EPOCH)
@@ -405,6 +448,42 @@ impl Channel for DiscordChannel {
     }
 }
 
+/// Message JSON with an optional reply reference.
+fn message_body(content: &str, reply_to: Option<&str>) -> serde_json::Value {
+    let mut body = json!({ "content": content });
+    if let Some(id) = reply_to {
+        body["message_reference"] = json!({ "message_id": id, "fail_if_not_exists": false });
+    }
+    body
+}
+
+async fn file_part(attachment: &Attachment) -> anyhow::Result<Part> {
+    let file_name = attachment
+        .file_name
+        .clone()
+        .or_else(|| {
+            attachment
+                .path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+        })
+        .unwrap_or_else(|| "file".into());
+    let bytes = tokio::fs::read(&attachment.path).await?;
+    Ok(Part::bytes(bytes).file_name(file_name))
+}
+
+async fn check_response(resp: reqwest::Response) -> anyhow::Result<()> {
+    if !resp.status().is_success() {
+        let status = resp.status();
+        let err = resp
+            .text()
+            .await
+            .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
+        anyhow::bail!("Discord send message failed ({status}): {err}");
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -688,4 +767,12 @@ mod tests {
         let guard = ch.typing_handle.lock().unwrap();
         assert!(guard.is_some());
     }
+
+    #[test]
+    fn message_body_adds_reply_reference() {
+        let body = message_body("hi", Some("123"));
+        assert_eq!(body["content"], "hi");
+        assert_eq!(body["message_reference"]["message_id"], "123");
+        assert!(message_body("hi", None).get("message_reference").is_none());
+    }
 }
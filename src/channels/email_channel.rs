@@ -10,6 +10,8 @@
 
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
+use lettre::message::header::ContentType;
+use lettre::message::{Attachment as MailAttachment, MultiPart, SinglePart};
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
 use mail_parser::{MessageParser, MimeHeaders};
@@ -24,7 +26,20 @@ use tokio::time::{interval, sleep};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
-use super::traits::{Channel, ChannelMessage};
+use super::traits::{Channel, ChannelCapabilities, ChannelMessage, RichMessage};
+
+/// An unseen email fetched over IMAP
+struct FetchedEmail {
+    id: String,
+    sender: String,
+    content: String,
+    ts: u64,
+    message_id: Option<String>,
+    in_reply_to: Option<String>,
+    thread_id: Option<String>,
+    /// Parsed headers, passed on as `ChannelMessage::raw`
+    raw: serde_json::Value,
+}
 
 /// Email channel configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -178,7 +193,7 @@ impl EmailChannel {
     }
 
     /// Fetch unseen emails via IMAP (blocking, run in spawn_blocking)
-    fn fetch_unseen_imap(config: &EmailConfig) -> Result<Vec<(String, String, String, u64)>> {
+    fn fetch_unseen_imap(config: &EmailConfig) -> Result<Vec<FetchedEmail>> {
         use rustls::ClientConfig as TlsConfig;
         use rustls_pki_types::ServerName;
         use std::sync::Arc;
@@ -290,10 +305,16 @@ impl EmailChannel {
                 let subject = parsed.subject().unwrap_or("(no subject)").to_string();
                 let body = Self::extract_text(&parsed);
                 let content = format!("Subject: {}\n\n{}", subject, body);
-                let msg_id = parsed
-                    .message_id()
-                    .map(|s| s.to_string())
+                let message_id = parsed.message_id().map(|s| s.to_string());
+                let msg_id = message_id
+                    .clone()
                     .unwrap_or_else(|| format!("gen-{}", Uuid::new_v4()));
+                let in_reply_to = parsed.in_reply_to().as_text().map(|s| s.to_string());
+                let references: Vec<String> = parsed
+                    .references()
+                    .as_text_list()
+                    .map(|l| l.iter().map(|r| r.to_string()).collect())
+                    .unwrap_or_default();
                 #[allow(clippy::cast_sign_loss)]
                 let ts = parsed
                     .date()
@@ -319,7 +340,24 @@ impl EmailChannel {
                             .unwrap_or(0)
                     });
 
-                results.push((msg_id, sender, content, ts));
+                results.push(FetchedEmail {
+                    raw: serde_json::json!({
+                        "message_id": message_id,
+                        "from": sender,
+                        "subject": subject,
+                        "in_reply_to": in_reply_to,
+                        "references": references,
+                    }),
+                    // The thread root is the first reference, or the message
+                    // being replied to when References is missing.
+                    thread_id: references.first().cloned().or_else(|| in_reply_to.clone()),
+                    message_id,
+                    in_reply_to,
+                    id: msg_id,
+                    sender,
+                    content,
+                    ts,
+                });
             }
 
             // Mark as seen with unique tag
@@ -363,21 +401,58 @@ impl Channel for EmailChannel {
     }
 
     async fn send(&self, message: &str, recipient: &str) -> Result<()> {
-        let (subject, body) = if message.starts_with("Subject: ") {
-            if let Some(pos) = message.find('\n') {
-                (&message[9..pos], message[pos + 1..].trim())
-            } else {
-                ("ZeroClaw Message", message)
-            }
-        } else {
-            ("ZeroClaw Message", message)
-        };
+        self.send_rich(&RichMessage::text(message), recipient).await
+    }
+
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities {
+            attachments: true,
+            replies: true,
+            threads: true,
+        }
+    }
+
+    async fn send_rich(&self, message: &RichMessage, recipient: &str) -> Result<()> {
+        let (subject, body) = split_subject(&message.text);
 
-        let email = Message::builder()
+        let mut builder = Message::builder()
             .from(self.config.from_address.parse()?)
             .to(recipient.parse()?)
-            .subject(subject)
-            .body(body.to_string())?;
+            .subject(subject);
+        if let Some(id) = message.reply_to.as_deref().or(message.thread_id.as_deref()) {
+            builder = builder.in_reply_to(angle_id(id));
+        }
+        if let Some(references) = references_header(message) {
+            builder = builder.references(references);
+        }
+
+        let email = if message.attachments.is_empty() {
+            builder.body(body.to_string())?
+        } else {
+            let mut parts = MultiPart::mixed().singlepart(SinglePart::plain(body.to_string()));
+            for attachment in &message.attachments {
+                let file_name = attachment
+                    .file_name
+                    .clone()
+                    .or_else(|| {
+                        attachment
+                            .path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().into_owned())
+                    })
+                    .unwrap_or_else(|| "file".into());
+                let content_type = ContentType::parse(
+                    attachment
+                        .mime_type
+                        .as_deref()
+                        .unwrap_or("application/octet-stream"),
+                )
+                .unwrap_or_else(|_| ContentType::parse("application/octet-stream").unwrap());
+                let bytes = tokio::fs::read(&attachment.path).await?;
+                parts = parts.singlepart(MailAttachment::new(file_name).body(bytes, content_type));
+            }
+            builder.multipart(parts)?
+        };
 
         let transport = self.create_smtp_transport()?;
         transport.send(&email)?;
@@ -398,7 +473,17 @@ impl Channel for EmailChannel {
             let cfg = config.clone();
             match tokio::task::spawn_blocking(move || Self::fetch_unseen_imap(&cfg)).await {
                 Ok(Ok(messages)) => {
-                    for (id, sender, content, ts) in messages {
+                    for email in messages {
+                        let FetchedEmail {
+                            id,
+                            sender,
+                            content,
+                            ts,
+                            message_id,
+                            in_reply_to,
+                            thread_id,
+                            raw,
+                        } = email;
                         {
                             let mut seen = self.seen_messages.lock().unwrap();
                             if seen.contains(&id) {
@@ -412,8 +497,14 @@ impl Channel for EmailChannel {
                         } // MutexGuard dropped before await
                         let msg = ChannelMessage {
                             id,
+                            author: sender.clone(),
                             sender,
                             content,
+                            attachments: Vec::new(),
+                            message_id,
+                            thread_id,
+                            reply_to: in_reply_to,
+                            raw: Some(raw),
                             channel: "email".to_string(),
                             timestamp: ts,
                         };
@@ -444,3 +535,62 @@ impl Channel for EmailChannel {
         .unwrap_or_default()
     }
 }
+
+/// Split a leading `Subject: ...` line off a message body.
+fn split_subject(message: &str) -> (&str, &str) {
+    if message.starts_with("Subject: ") {
+        if let Some(pos) = message.find('\n') {
+            (&message[9..pos], message[pos + 1..].trim())
+        } else {
+            ("ZeroClaw Message", message)
+        }
+    } else {
+        ("ZeroClaw Message", message)
+    }
+}
+
+/// Wrap a bare Message-ID in angle brackets for In-Reply-To/References.
+fn angle_id(id: &str) -> String {
+    let id = id.trim().trim_start_matches('<').trim_end_matches('>');
+    format!("<{id}>")
+}
+
+/// `References` for a reply: the thread root, then the message replied to.
+fn references_header(message: &RichMessage) -> Option<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for id in [message.thread_id.as_deref(), message.reply_to.as_deref()]
+        .into_iter()
+        .flatten()
+    {
+        let id = angle_id(id);
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    (!ids.is_empty()).then(|| ids.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn references_header_lists_root_then_reply() {
+        let message = RichMessage {
+            thread_id: Some("root@example.com".into()),
+            reply_to: Some("<last@example.com>".into()),
+            ..RichMessage::text("hi")
+        };
+        assert_eq!(
+            references_header(&message).as_deref(),
+            Some("<root@example.com> <last@example.com>")
+        );
+        assert!(references_header(&RichMessage::text("hi")).is_none());
+    }
+
+    #[test]
+    fn split_subject_reads_leading_subject_line() {
+        assert_eq!(split_subject("Subject: Hi\nBody"), ("Hi", "Body"));
+        assert_eq!(split_subject("Body"), ("ZeroClaw Message", "Body"));
+    }
+}
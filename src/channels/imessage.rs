@@ -172,7 +172,13 @@ end tell"#
                         let msg = ChannelMessage {
                             id: rowid.to_string(),
                             sender: sender.clone(),
+                            author: sender.clone(),
                             content: text,
+                            attachments: Vec::new(),
+                            message_id: None,
+                            thread_id: None,
+                            reply_to: None,
+                            raw: None,
                             channel: "imessage".to_string(),
                             timestamp: std::time::SystemTime::now()
                                 .duration_since(std::time::UNIX_EPOCH)
@@ -555,7 +555,13 @@ impl Channel for IrcChannel {
                     let channel_msg = ChannelMessage {
                         id: format!("irc_{}_{seq}", chrono::Utc::now().timestamp_millis()),
                         sender: reply_to,
+                        author: sender_nick.to_string(),
                         content,
+                        attachments: Vec::new(),
+                        message_id: None,
+                        thread_id: None,
+                        reply_to: None,
+                        raw: None,
                         channel: "irc".to_string(),
                         timestamp: std::time::SystemTime::now()
                             .duration_since(std::time::UNIX_EPOCH)
@@ -1,7 +1,9 @@
-use crate::channels::traits::{Channel, ChannelMessage};
+use crate::channels::traits::{
+    Attachment, AttachmentKind, Channel, ChannelCapabilities, ChannelMessage, RichMessage,
+};
 use async_trait::async_trait;
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
 /// Matrix channel using the Client-Server API (no SDK needed).
@@ -40,21 +42,63 @@ struct Timeline {
     events: Vec<TimelineEvent>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 struct TimelineEvent {
     #[serde(rename = "type")]
     event_type: String,
     sender: String,
     #[serde(default)]
+    event_id: Option<String>,
+    #[serde(default)]
     content: EventContent,
+    /// Remaining event fields, kept for `ChannelMessage::raw`
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
 }
 
-#[derive(Debug, Deserialize, Default)]
+#[derive(Debug, Deserialize, Serialize, Default)]
 struct EventContent {
     #[serde(default)]
     body: Option<String>,
     #[serde(default)]
     msgtype: Option<String>,
+    #[serde(
+        rename = "m.relates_to",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    relates_to: Option<serde_json::Value>,
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl EventContent {
+    /// Root event of the thread this event belongs to.
+    fn thread_root(&self) -> Option<String> {
+        let relates_to = self.relates_to.as_ref()?;
+        if relates_to.get("rel_type").and_then(|r| r.as_str()) != Some("m.thread") {
+            return None;
+        }
+        relates_to
+            .get("event_id")
+            .and_then(|e| e.as_str())
+            .map(ToString::to_string)
+    }
+
+    /// Event this one replies to.
+    fn in_reply_to(&self) -> Option<String> {
+        self.relates_to
+            .as_ref()?
+            .get("m.in_reply_to")?
+            .get("event_id")?
+            .as_str()
+            .map(ToString::to_string)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadResponse {
+    content_uri: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -79,7 +123,7 @@ impl MatrixChannel {
             access_token,
             room_id,
             allowed_users,
-            client: Client::new(),
+            client: crate::http::client("matrix", crate::http::Defaults::long_poll(30)),
         }
     }
 
@@ -109,31 +153,19 @@ impl MatrixChannel {
         let who: WhoAmIResponse = resp.json().await?;
         Ok(who.user_id)
     }
-}
-
-#[async_trait]
-impl Channel for MatrixChannel {
-    fn name(&self) -> &str {
-        "matrix"
-    }
 
-    async fn send(&self, message: &str, _target: &str) -> anyhow::Result<()> {
-        let txn_id = format!("zc_{}", chrono::Utc::now().timestamp_millis());
+    async fn send_event(&self, content: &serde_json::Value) -> anyhow::Result<()> {
+        let txn_id = format!("zc_{}", uuid::Uuid::new_v4().simple());
         let url = format!(
             "{}/_matrix/client/v3/rooms/{}/send/m.room.message/{}",
             self.homeserver, self.room_id, txn_id
         );
 
-        let body = serde_json::json!({
-            "msgtype": "m.text",
-            "body": message
-        });
-
         let resp = self
             .client
             .put(&url)
             .header("Authorization", format!("Bearer {}", self.access_token))
-            .json(&body)
+            .json(content)
             .send()
             .await?;
 
@@ -145,6 +177,117 @@ impl Channel for MatrixChannel {
         Ok(())
     }
 
+    /// Upload a file to the media repository and return its `mxc://` URI.
+    async fn upload(&self, attachment: &Attachment, file_name: &str) -> anyhow::Result<String> {
+        let bytes = tokio::fs::read(&attachment.path).await?;
+        let url = format!("{}/_matrix/media/v3/upload", self.homeserver);
+        let resp = self
+            .client
+            .post(&url)
+            .query(&[("filename", file_name)])
+            .header("Authorization", format!("Bearer {}", self.access_token))
+            .header(
+                "Content-Type",
+                attachment
+                    .mime_type
+                    .as_deref()
+                    .unwrap_or("application/octet-stream"),
+            )
+            .body(bytes)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let err = resp.text().await?;
+            anyhow::bail!("Matrix upload failed: {err}");
+        }
+
+        let upload: UploadResponse = resp.json().await?;
+        Ok(upload.content_uri)
+    }
+}
+
+/// `m.room.message` content with thread and reply relations from `message`.
+fn message_content(mut content: serde_json::Value, message: &RichMessage) -> serde_json::Value {
+    let relates_to = match (&message.thread_id, &message.reply_to) {
+        (Some(thread), reply) => Some(serde_json::json!({
+            "rel_type": "m.thread",
+            "event_id": thread,
+            "is_falling_back": reply.is_none(),
+            "m.in_reply_to": { "event_id": reply.as_ref().unwrap_or(thread) }
+        })),
+        (None, Some(reply)) => Some(serde_json::json!({
+            "m.in_reply_to": { "event_id": reply }
+        })),
+        (None, None) => None,
+    };
+    if let Some(relates_to) = relates_to {
+        content["m.relates_to"] = relates_to;
+    }
+    content
+}
+
+#[async_trait]
+impl Channel for MatrixChannel {
+    fn name(&self) -> &str {
+        "matrix"
+    }
+
+    async fn send(&self, message: &str, target: &str) -> anyhow::Result<()> {
+        self.send_rich(&RichMessage::text(message), target).await
+    }
+
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities {
+            attachments: true,
+            replies: true,
+            threads: true,
+        }
+    }
+
+    async fn send_rich(&self, message: &RichMessage, _target: &str) -> anyhow::Result<()> {
+        if !message.text.is_empty() || message.attachments.is_empty() {
+            let content = serde_json::json!({
+                "msgtype": "m.text",
+                "body": message.text
+            });
+            self.send_event(&message_content(content, message)).await?;
+        }
+
+        for attachment in &message.attachments {
+            let file_name = attachment
+                .file_name
+                .clone()
+                .or_else(|| {
+                    attachment
+                        .path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                })
+                .unwrap_or_else(|| "file".into());
+            let uri = self.upload(attachment, &file_name).await?;
+            let msgtype = match attachment.kind {
+                AttachmentKind::Image => "m.image",
+                AttachmentKind::Audio | AttachmentKind::Voice => "m.audio",
+                AttachmentKind::Video => "m.video",
+                AttachmentKind::Document => "m.file",
+            };
+            let mut info = serde_json::json!({ "size": attachment.size });
+            if let Some(mime) = &attachment.mime_type {
+                info["mimetype"] = mime.as_str().into();
+            }
+            let content = serde_json::json!({
+                "msgtype": msgtype,
+                "body": file_name,
+                "url": uri,
+                "info": info
+            });
+            self.send_event(&message_content(content, message)).await?;
+        }
+
+        Ok(())
+    }
+
     async fn listen(&self, tx: mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         tracing::info!("Matrix channel listening on room {}...", self.room_id);
 
@@ -230,7 +373,13 @@ impl Channel for MatrixChannel {
                     let msg = ChannelMessage {
                         id: format!("mx_{}", chrono::Utc::now().timestamp_millis()),
                         sender: event.sender.clone(),
+                        author: event.sender.clone(),
                         content: body.clone(),
+                        attachments: Vec::new(),
+                        message_id: event.event_id.clone(),
+                        thread_id: event.content.thread_root(),
+                        reply_to: event.content.in_reply_to(),
+                        raw: serde_json::to_value(event).ok(),
                         channel: "matrix".to_string(),
                         timestamp: std::time::SystemTime::now()
                             .duration_since(std::time::UNIX_EPOCH)
@@ -464,4 +613,59 @@ mod tests {
         let resp: SyncResponse = serde_json::from_str(json).unwrap();
         assert!(resp.rooms.join.is_empty());
     }
+
+    #[test]
+    fn event_relations_are_parsed() {
+        let event: TimelineEvent = serde_json::from_value(serde_json::json!({
+            "type": "m.room.message",
+            "sender": "@alice:matrix.org",
+            "event_id": "$reply",
+            "origin_server_ts": 1,
+            "content": {
+                "msgtype": "m.text",
+                "body": "hi",
+                "m.relates_to": {
+                    "rel_type": "m.thread",
+                    "event_id": "$root",
+                    "m.in_reply_to": { "event_id": "$prev" }
+                }
+            }
+        }))
+        .unwrap();
+        assert_eq!(event.content.thread_root().as_deref(), Some("$root"));
+        assert_eq!(event.content.in_reply_to().as_deref(), Some("$prev"));
+        let raw = serde_json::to_value(&event).unwrap();
+        assert_eq!(raw["origin_server_ts"], 1);
+        assert_eq!(raw["content"]["m.relates_to"]["event_id"], "$root");
+    }
+
+    #[test]
+    fn message_content_adds_thread_and_reply_relations() {
+        let body = serde_json::json!({ "msgtype": "m.text", "body": "hi" });
+        let threaded = RichMessage {
+            thread_id: Some("$root".into()),
+            ..RichMessage::text("hi")
+        };
+        let content = message_content(body.clone(), &threaded);
+        assert_eq!(content["m.relates_to"]["rel_type"], "m.thread");
+        assert_eq!(content["m.relates_to"]["is_falling_back"], true);
+        assert_eq!(
+            content["m.relates_to"]["m.in_reply_to"]["event_id"],
+            "$root"
+        );
+
+        let reply = RichMessage {
+            reply_to: Some("$prev".into()),
+            ..RichMessage::text("hi")
+        };
+        let content = message_content(body.clone(), &reply);
+        assert!(content["m.relates_to"].get("rel_type").is_none());
+        assert_eq!(
+            content["m.relates_to"]["m.in_reply_to"]["event_id"],
+            "$prev"
+        );
+
+        let plain = message_content(body, &RichMessage::text("hi"));
+        assert!(plain.get("m.relates_to").is_none());
+    }
 }
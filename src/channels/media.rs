@@ -0,0 +1,410 @@
+//! Turns media attached to channel messages into model input.
+//!
+//! Images are base64-encoded for a vision model, text documents are ingested
+//! into memory and voice notes are transcribed. Anything else is described
+//! with its local path so the agent can open it with `file_read`.
+
+use super::traits::{Attachment, AttachmentKind, ChannelMessage};
+use crate::config::{Config, MediaConfig};
+use crate::memory::{self, Memory};
+use crate::providers::ImageInput;
+use anyhow::Context;
+use base64::Engine;
+use reqwest::multipart::{Form, Part};
+use std::path::{Path, PathBuf};
+
+/// Message text with media folded in, plus images for a vision model.
+#[derive(Debug, Default)]
+pub struct PreparedMessage {
+    pub text: String,
+    pub images: Vec<ImageInput>,
+}
+
+/// Speech-to-text over an OpenAI-compatible `/audio/transcriptions` endpoint.
+pub struct Transcriber {
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    client: reqwest::Client,
+}
+
+impl Transcriber {
+    /// Build the transcriber selected by `transcription_provider`, or `None`
+    /// when transcription is off.
+    pub fn from_config(
+        config: &MediaConfig,
+        fallback_api_key: Option<&str>,
+    ) -> anyhow::Result<Option<Self>> {
+        let (base_url, env_key) = match config.transcription_provider.trim() {
+            "" | "none" => return Ok(None),
+            "openai" => ("https://api.openai.com/v1", Some("OPENAI_API_KEY")),
+            "groq" => ("https://api.groq.com/openai/v1", Some("GROQ_API_KEY")),
+            other => match other.strip_prefix("custom:") {
+                Some(url) if !url.trim().is_empty() => (url.trim(), None),
+                _ => anyhow::bail!(
+                    "Unknown transcription_provider '{other}'. Use none, openai, groq or custom:<url>."
+                ),
+            },
+        };
+
+        let api_key = config
+            .transcription_api_key
+            .clone()
+            .or_else(|| env_key.and_then(|k| std::env::var(k).ok()))
+            .or_else(|| fallback_api_key.map(ToString::to_string))
+            .filter(|k| !k.trim().is_empty());
+        if api_key.is_none() && env_key.is_some() {
+            anyhow::bail!(
+                "No API key for {} transcription. Set transcription_api_key in [media].",
+                config.transcription_provider
+            );
+        }
+
+        Ok(Some(Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key,
+            model: config.transcription_model.clone(),
+            client: crate::http::client("transcription", crate::http::Defaults::PROVIDER),
+        }))
+    }
+
+    fn endpoint(&self) -> String {
+        format!("{}/audio/transcriptions", self.base_url)
+    }
+
+    /// Transcribe an audio file and return its text.
+    pub async fn transcribe(&self, attachment: &Attachment) -> anyhow::Result<String> {
+        let bytes = tokio::fs::read(&attachment.path).await?;
+        let file_name = attachment
+            .path
+            .file_name()
+            .map_or_else(|| "audio.ogg".into(), |n| n.to_string_lossy().into_owned());
+        let mut part = Part::bytes(bytes).file_name(file_name);
+        if let Some(mime) = &attachment.mime_type {
+            part = part.mime_str(mime)?;
+        }
+        let form = Form::new()
+            .part("file", part)
+            .text("model", self.model.clone());
+
+        let mut request = self.client.post(self.endpoint()).multipart(form);
+        if let Some(key) = &self.api_key {
+            request = request.bearer_auth(key);
+        }
+        let response = request.send().await?;
+        if !response.status().is_success() {
+            return Err(crate::providers::api_error("Transcription", response).await);
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        body.get("text")
+            .and_then(serde_json::Value::as_str)
+            .map(|t| t.trim().to_string())
+            .context("transcription response has no text")
+    }
+}
+
+/// Prepares attachments according to `[media]`.
+pub struct MediaPreparer {
+    config: MediaConfig,
+    workspace_dir: PathBuf,
+    chunk_max_tokens: usize,
+    transcriber: Option<Transcriber>,
+}
+
+impl MediaPreparer {
+    pub fn new(config: &Config) -> anyhow::Result<Self> {
+        Ok(Self {
+            config: config.media.clone(),
+            workspace_dir: config.workspace_dir.clone(),
+            chunk_max_tokens: config.memory.chunk_max_tokens,
+            transcriber: Transcriber::from_config(&config.media, config.api_key.as_deref())?,
+        })
+    }
+
+    /// Fold a message's attachments into its text and collect its images.
+    /// Failures become notes in the text instead of dropping the message.
+    pub async fn prepare(&self, msg: &ChannelMessage, memory: &dyn Memory) -> PreparedMessage {
+        let mut prepared = PreparedMessage {
+            text: msg.content.clone(),
+            images: Vec::new(),
+        };
+
+        for attachment in &msg.attachments {
+            let note = match self
+                .prepare_one(attachment, memory, &mut prepared.images)
+                .await
+            {
+                Ok(Some(note)) => note,
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::warn!("media: {} failed: {e}", attachment.path.display());
+                    format!(
+                        "[{} saved to {} could not be processed: {e}]",
+                        label(attachment),
+                        attachment.path.display()
+                    )
+                }
+            };
+            if !prepared.text.is_empty() {
+                prepared.text.push('\n');
+            }
+            prepared.text.push_str(&note);
+        }
+        prepared
+    }
+
+    async fn prepare_one(
+        &self,
+        attachment: &Attachment,
+        memory: &dyn Memory,
+        images: &mut Vec<ImageInput>,
+    ) -> anyhow::Result<Option<String>> {
+        let path = attachment.path.display();
+        match attachment.kind {
+            _ if image_mime(attachment).is_some() => {
+                let bytes = tokio::fs::read(&attachment.path).await?;
+                images.push(ImageInput {
+                    mime_type: image_mime(attachment).unwrap_or("image/jpeg").to_string(),
+                    data: base64::engine::general_purpose::STANDARD.encode(bytes),
+                });
+                Ok(None)
+            }
+            AttachmentKind::Document
+                if self.config.ingest_documents
+                    && memory::ingest::is_document(&attachment.path) =>
+            {
+                let source = attachment.path.to_string_lossy().into_owned();
+                let report = memory::ingest::ingest(
+                    memory,
+                    &self.workspace_dir,
+                    &[source],
+                    self.chunk_max_tokens,
+                    false,
+                )
+                .await?;
+                Ok(Some(format!(
+                    "[{} saved to {path} and added to memory ({} chunks); use file_read for the full text]",
+                    label(attachment),
+                    report.chunks_stored
+                )))
+            }
+            AttachmentKind::Voice | AttachmentKind::Audio => match &self.transcriber {
+                Some(transcriber) => {
+                    let text = transcriber.transcribe(attachment).await?;
+                    Ok(Some(format!("[{} transcript]: {text}", label(attachment))))
+                }
+                None => Ok(Some(format!(
+                    "[{} saved to {path}; transcription is not configured]",
+                    label(attachment)
+                ))),
+            },
+            _ => Ok(Some(format!(
+                "[{} saved to {path}; use file_read to open it]",
+                label(attachment)
+            ))),
+        }
+    }
+}
+
+/// Image MIME type of an attachment sent as a photo or as an image file.
+fn image_mime(attachment: &Attachment) -> Option<&str> {
+    if let Some(mime) = attachment.mime_type.as_deref() {
+        if mime.starts_with("image/") {
+            return Some(mime);
+        }
+    }
+    let by_extension = match attachment
+        .path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase)
+        .as_deref()
+    {
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("png") => "image/png",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        _ => return None,
+    };
+    (attachment.kind == AttachmentKind::Image || attachment.mime_type.is_none())
+        .then_some(by_extension)
+}
+
+fn label(attachment: &Attachment) -> String {
+    let kind = match attachment.kind {
+        AttachmentKind::Image => "Image",
+        AttachmentKind::Document => "Document",
+        AttachmentKind::Audio => "Audio",
+        AttachmentKind::Voice => "Voice note",
+        AttachmentKind::Video => "Video",
+    };
+    let name = attachment
+        .file_name
+        .as_deref()
+        .or_else(|| attachment.path.file_name().and_then(|n| n.to_str()));
+    match name {
+        Some(name) => format!("{kind} {name}"),
+        None => kind.to_string(),
+    }
+}
+
+/// Directory a channel saves received media to.
+pub fn media_dir(workspace_dir: &Path, channel: &str) -> PathBuf {
+    workspace_dir.join("media").join(channel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    fn preparer(workspace: &Path, media: MediaConfig) -> MediaPreparer {
+        MediaPreparer {
+            transcriber: Transcriber::from_config(&media, None).unwrap(),
+            config: media,
+            workspace_dir: workspace.to_path_buf(),
+            chunk_max_tokens: 512,
+        }
+    }
+
+    fn message(content: &str, attachments: Vec<Attachment>) -> ChannelMessage {
+        ChannelMessage {
+            id: "1".into(),
+            sender: "42".into(),
+            author: "42".into(),
+            content: content.into(),
+            attachments,
+            message_id: None,
+            thread_id: None,
+            reply_to: None,
+            raw: None,
+            channel: "telegram".into(),
+            timestamp: 0,
+        }
+    }
+
+    fn attachment(kind: AttachmentKind, path: PathBuf, mime: Option<&str>) -> Attachment {
+        Attachment {
+            kind,
+            size: std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+            path,
+            file_name: None,
+            mime_type: mime.map(ToString::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn photo_becomes_vision_input() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let photo = tmp.path().join("cat.jpg");
+        std::fs::write(&photo, b"jpeg-bytes").unwrap();
+
+        let msg = message(
+            "what is this?",
+            vec![attachment(AttachmentKind::Image, photo, Some("image/jpeg"))],
+        );
+        let prepared = preparer(tmp.path(), MediaConfig::default())
+            .prepare(&msg, &mem)
+            .await;
+
+        assert_eq!(prepared.text, "what is this?");
+        assert_eq!(prepared.images.len(), 1);
+        assert_eq!(prepared.images[0].mime_type, "image/jpeg");
+        assert_eq!(prepared.images[0].data, "anBlZy1ieXRlcw==");
+    }
+
+    #[tokio::test]
+    async fn text_document_is_ingested_into_memory() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let doc = tmp.path().join("notes.md");
+        std::fs::write(&doc, "# Trip\nThe ferry leaves at 9am from pier 4.").unwrap();
+
+        let msg = message(
+            "",
+            vec![attachment(
+                AttachmentKind::Document,
+                doc,
+                Some("text/markdown"),
+            )],
+        );
+        let prepared = preparer(tmp.path(), MediaConfig::default())
+            .prepare(&msg, &mem)
+            .await;
+
+        assert!(
+            prepared.text.contains("added to memory"),
+            "{}",
+            prepared.text
+        );
+        assert!(prepared.images.is_empty());
+        assert!(mem.count().await.unwrap() > 0);
+    }
+
+    #[tokio::test]
+    async fn documents_are_only_referenced_when_ingest_is_off() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let doc = tmp.path().join("notes.md");
+        std::fs::write(&doc, "pier 4").unwrap();
+
+        let media = MediaConfig {
+            ingest_documents: false,
+            ..MediaConfig::default()
+        };
+        let msg = message("", vec![attachment(AttachmentKind::Document, doc, None)]);
+        let prepared = preparer(tmp.path(), media).prepare(&msg, &mem).await;
+
+        assert!(prepared.text.contains("use file_read to open it"));
+        assert_eq!(mem.count().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn voice_note_without_transcriber_is_noted() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let voice = tmp.path().join("voice.oga");
+        std::fs::write(&voice, b"ogg").unwrap();
+
+        let msg = message(
+            "",
+            vec![attachment(AttachmentKind::Voice, voice, Some("audio/ogg"))],
+        );
+        let prepared = preparer(tmp.path(), MediaConfig::default())
+            .prepare(&msg, &mem)
+            .await;
+
+        assert!(prepared.text.starts_with("[Voice note voice.oga saved to"));
+        assert!(prepared.text.ends_with("transcription is not configured]"));
+    }
+
+    #[test]
+    fn transcriber_selection() {
+        let mut media = MediaConfig::default();
+        assert!(Transcriber::from_config(&media, Some("key"))
+            .unwrap()
+            .is_none());
+
+        media.transcription_provider = "custom:http://localhost:8000/v1/".into();
+        let t = Transcriber::from_config(&media, None).unwrap().unwrap();
+        assert_eq!(
+            t.endpoint(),
+            "http://localhost:8000/v1/audio/transcriptions"
+        );
+        assert!(t.api_key.is_none());
+
+        media.transcription_provider = "groq".into();
+        media.transcription_api_key = Some("gsk".into());
+        let t = Transcriber::from_config(&media, None).unwrap().unwrap();
+        assert_eq!(
+            t.endpoint(),
+            "https://api.groq.com/openai/v1/audio/transcriptions"
+        );
+
+        media.transcription_provider = "whisperer".into();
+        assert!(Transcriber::from_config(&media, None).is_err());
+    }
+}
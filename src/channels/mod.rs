@@ -7,6 +7,7 @@ pub mod email_channel;
 pub mod imessage;
 pub mod irc;
 pub mod matrix;
+pub mod media;
 pub mod slack;
 pub mod telegram;
 pub mod traits;
@@ -29,11 +30,10 @@ pub use whatsapp::WhatsAppChannel;
 
 use crate::config::Config;
 use crate::identity;
-use crate::memory::{self, Memory};
+use crate::memory::{self, Memory, MemoryScope};
 use crate::providers::{self, Provider};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
-use std::fmt::Write;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -48,20 +48,18 @@ fn conversation_memory_key(msg: &traits::ChannelMessage) -> String {
     format!("{}_{}_{}", msg.channel, msg.sender, msg.id)
 }
 
-async fn build_memory_context(mem: &dyn Memory, user_msg: &str) -> String {
-    let mut context = String::new();
-
-    if let Ok(entries) = mem.recall(user_msg, 5).await {
-        if !entries.is_empty() {
-            context.push_str("[Memory context]\n");
-            for entry in &entries {
-                let _ = writeln!(context, "- {}: {}", entry.key, entry.content);
-            }
-            context.push('\n');
-        }
+/// Memory scopes visible to a message, most specific first (global is implicit).
+/// The first scope is where the message's own memories are stored.
+pub(crate) fn memory_scopes(mode: &str, msg: &traits::ChannelMessage) -> Vec<MemoryScope> {
+    let channel = MemoryScope::Channel(msg.channel.clone());
+    match mode {
+        "global" => Vec::new(),
+        "channel" => vec![channel],
+        _ => vec![
+            MemoryScope::User(format!("{}:{}", msg.channel, msg.author)),
+            channel,
+        ],
     }
-
-    context
 }
 
 fn spawn_supervised_listener(
@@ -531,12 +529,19 @@ pub async fn doctor_channels(config: Config) -> Result<()> {
 
 /// Start all configured channels and route messages to the agent
 #[allow(clippy::too_many_lines)]
-pub async fn start_channels(config: Config) -> Result<()> {
-    let provider: Arc<dyn Provider> = Arc::from(providers::create_resilient_provider(
+pub async fn start_channels(
+    config: Config,
+    observer: Arc<dyn crate::observability::Observer>,
+) -> Result<()> {
+    let provider = providers::create_resilient_provider_with_options(
         config.default_provider.as_deref().unwrap_or("openrouter"),
         config.api_key.as_deref(),
         &config.reliability,
-    )?);
+        &providers::ProviderOptions::from_config(&config),
+    )?;
+    let provider: Arc<dyn Provider> = Arc::from(providers::cache::wrap_channel_provider(
+        provider, &config, observer,
+    ));
 
     // Warm up the provider connection pool (TLS handshake, DNS, HTTP/2 setup)
     // so the first real message doesn't hit a cold-start timeout.
@@ -585,6 +590,14 @@ pub async fn start_channels(config: Config) -> Result<()> {
             "memory_forget",
             "Delete a memory entry. Use when: memory is incorrect/stale or explicitly requested for removal. Don't use when: impact is uncertain.",
         ),
+        (
+            "memory_graph_query",
+            "Look up an entity and its relations in the knowledge graph. Use when: asking who owns/works on/depends on something, or following links between people and projects. Don't use when: a plain memory search answers it.",
+        ),
+        (
+            "memory_graph_upsert",
+            "Record entities, observations and relations in the knowledge graph. Use when: learning durable facts about who/what relates to whom. Don't use when: the fact is transient or already recorded.",
+        ),
     ];
 
     if config.browser.enabled {
@@ -617,10 +630,14 @@ pub async fn start_channels(config: Config) -> Result<()> {
     let mut channels: Vec<Arc<dyn Channel>> = Vec::new();
 
     if let Some(ref tg) = config.channels_config.telegram {
-        channels.push(Arc::new(TelegramChannel::new(
-            tg.bot_token.clone(),
-            tg.allowed_users.clone(),
-        )));
+        channels.push(Arc::new(
+            TelegramChannel::new(tg.bot_token.clone(), tg.allowed_users.clone())
+                .with_media(
+                    media::media_dir(&config.workspace_dir, "telegram"),
+                    config.media.max_file_bytes,
+                )
+                .with_webhook(tg.uses_webhook()),
+        ));
     }
 
     if let Some(ref dc) = config.channels_config.discord {
@@ -632,11 +649,15 @@ pub async fn start_channels(config: Config) -> Result<()> {
     }
 
     if let Some(ref sl) = config.channels_config.slack {
-        channels.push(Arc::new(SlackChannel::new(
-            sl.bot_token.clone(),
-            sl.channel_id.clone(),
-            sl.allowed_users.clone(),
-        )));
+        channels.push(Arc::new(
+            SlackChannel::new(
+                sl.bot_token.clone(),
+                sl.channel_id.clone(),
+                sl.allowed_users.clone(),
+            )
+            .with_app_token(sl.app_token.clone())
+            .with_events_api(sl.uses_events_api()),
+        ));
     }
 
     if let Some(ref im) = config.channels_config.imessage {
@@ -723,6 +744,9 @@ pub async fn start_channels(config: Config) -> Result<()> {
         .channel_max_backoff_secs
         .max(DEFAULT_CHANNEL_MAX_BACKOFF_SECS);
 
+    let media = media::MediaPreparer::new(&config)?;
+    let vision_model = config.media.vision_model.as_deref().unwrap_or(&model);
+
     // Single message bus — all channels send messages here
     let (tx, mut rx) = tokio::sync::mpsc::channel::<traits::ChannelMessage>(100);
 
@@ -747,24 +771,27 @@ pub async fn start_channels(config: Config) -> Result<()> {
             truncate_with_ellipsis(&msg.content, 80)
         );
 
-        let memory_context = build_memory_context(mem.as_ref(), &msg.content).await;
+        let scopes = memory_scopes(&config.memory.channel_scope, &msg);
+        let prepared = media.prepare(&msg, mem.as_ref()).await;
+        let memory_context = memory::context_block(mem.as_ref(), &prepared.text, &scopes).await;
 
-        // Auto-save to memory
-        if config.memory.auto_save {
+        // Raw auto-save, private to the sender unless configured otherwise
+        if config.memory.auto_save && config.memory.extraction.logs_raw() {
             let autosave_key = conversation_memory_key(&msg);
             let _ = mem
-                .store(
+                .store_scoped(
                     &autosave_key,
-                    &msg.content,
+                    &prepared.text,
                     crate::memory::MemoryCategory::Conversation,
+                    scopes.first().unwrap_or(&MemoryScope::Global),
                 )
                 .await;
         }
 
         let enriched_message = if memory_context.is_empty() {
-            msg.content.clone()
+            prepared.text.clone()
         } else {
-            format!("{memory_context}{}", msg.content)
+            format!("{memory_context}{}", prepared.text)
         };
 
         let target_channel = channels.iter().find(|ch| ch.name() == msg.channel);
@@ -782,7 +809,17 @@ pub async fn start_channels(config: Config) -> Result<()> {
 
         let llm_result = tokio::time::timeout(
             Duration::from_secs(CHANNEL_MESSAGE_TIMEOUT_SECS),
-            provider.chat_with_system(Some(&system_prompt), &enriched_message, &model, temperature),
+            provider.chat_with_images(
+                Some(&system_prompt),
+                &enriched_message,
+                &prepared.images,
+                if prepared.images.is_empty() {
+                    &model
+                } else {
+                    vision_model
+                },
+                temperature,
+            ),
         )
         .await;
 
@@ -795,16 +832,34 @@ pub async fn start_channels(config: Config) -> Result<()> {
 
         match llm_result {
             Ok(Ok(response)) => {
+                // Never deliver a reasoning model's chain of thought to users.
+                let response = providers::reasoning::strip_reasoning(&response);
                 println!(
                     "  🤖 Reply ({}ms): {}",
                     started_at.elapsed().as_millis(),
                     truncate_with_ellipsis(&response, 80)
                 );
                 if let Some(ch) = target_channel {
-                    if let Err(e) = ch.send(&response, &msg.sender).await {
+                    if let Err(e) = ch
+                        .send_rich(
+                            &traits::RichMessage::reply(&msg, response.as_str()),
+                            &msg.sender,
+                        )
+                        .await
+                    {
                         eprintln!("  ❌ Failed to reply on {}: {e}", ch.name());
                     }
                 }
+                if config.memory.auto_save && config.memory.extraction.enabled {
+                    memory::extract::spawn(
+                        mem.clone(),
+                        provider.clone(),
+                        memory::extract::extraction_model(&config.memory, &model),
+                        scopes.first().cloned().unwrap_or(MemoryScope::Global),
+                        prepared.text.clone(),
+                        response.clone(),
+                    );
+                }
             }
             Ok(Err(e)) => {
                 eprintln!(
@@ -1079,7 +1134,13 @@ mod tests {
         let msg = traits::ChannelMessage {
             id: "msg_abc123".into(),
             sender: "U123".into(),
+            author: "U123".into(),
             content: "hello".into(),
+            attachments: Vec::new(),
+            message_id: None,
+            thread_id: None,
+            reply_to: None,
+            raw: None,
             channel: "slack".into(),
             timestamp: 1,
         };
@@ -1092,14 +1153,26 @@ mod tests {
         let msg1 = traits::ChannelMessage {
             id: "msg_1".into(),
             sender: "U123".into(),
+            author: "U123".into(),
             content: "first".into(),
+            attachments: Vec::new(),
+            message_id: None,
+            thread_id: None,
+            reply_to: None,
+            raw: None,
             channel: "slack".into(),
             timestamp: 1,
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
             sender: "U123".into(),
+            author: "U123".into(),
             content: "second".into(),
+            attachments: Vec::new(),
+            message_id: None,
+            thread_id: None,
+            reply_to: None,
+            raw: None,
             channel: "slack".into(),
             timestamp: 2,
         };
@@ -1118,14 +1191,26 @@ mod tests {
         let msg1 = traits::ChannelMessage {
             id: "msg_1".into(),
             sender: "U123".into(),
+            author: "U123".into(),
             content: "I'm Paul".into(),
+            attachments: Vec::new(),
+            message_id: None,
+            thread_id: None,
+            reply_to: None,
+            raw: None,
             channel: "slack".into(),
             timestamp: 1,
         };
         let msg2 = traits::ChannelMessage {
             id: "msg_2".into(),
             sender: "U123".into(),
+            author: "U123".into(),
             content: "I'm 45".into(),
+            attachments: Vec::new(),
+            message_id: None,
+            thread_id: None,
+            reply_to: None,
+            raw: None,
             channel: "slack".into(),
             timestamp: 2,
         };
@@ -1159,11 +1244,123 @@ mod tests {
             .await
             .unwrap();
 
-        let context = build_memory_context(&mem, "age").await;
+        let context = memory::context_block(&mem, "age", &[]).await;
         assert!(context.contains("[Memory context]"));
         assert!(context.contains("Age is 45"));
     }
 
+    #[tokio::test]
+    async fn build_memory_context_adds_graph_neighbors_of_recalled_entities() {
+        use crate::memory::graph::{GraphUpdate, Relation};
+
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store(
+            "outage",
+            "Billing Service was down on Monday",
+            MemoryCategory::Core,
+        )
+        .await
+        .unwrap();
+        mem.graph_upsert(
+            &GraphUpdate {
+                entities: vec![],
+                relations: vec![Relation {
+                    source: "Alice".into(),
+                    relation: "owns".into(),
+                    target: "Billing Service".into(),
+                }],
+            },
+            &MemoryScope::Global,
+        )
+        .await
+        .unwrap();
+
+        // The message doesn't name the service; the recalled memory does.
+        let context = memory::context_block(&mem, "outage Monday", &[]).await;
+        assert!(context.contains("[Knowledge graph]"));
+        assert!(context.contains("Alice —owns→ Billing Service"));
+    }
+
+    fn message_from(sender: &str, content: &str) -> traits::ChannelMessage {
+        traits::ChannelMessage {
+            id: format!("msg_{sender}"),
+            sender: sender.into(),
+            author: sender.into(),
+            content: content.into(),
+            attachments: Vec::new(),
+            message_id: None,
+            thread_id: None,
+            reply_to: None,
+            raw: None,
+            channel: "telegram".into(),
+            timestamp: 1,
+        }
+    }
+
+    #[test]
+    fn memory_scopes_follow_config_mode() {
+        let msg = message_from("alice", "hi");
+        assert_eq!(
+            memory_scopes("user", &msg),
+            vec![
+                MemoryScope::User("telegram:alice".into()),
+                MemoryScope::Channel("telegram".into())
+            ]
+        );
+        assert_eq!(
+            memory_scopes("channel", &msg),
+            vec![MemoryScope::Channel("telegram".into())]
+        );
+        assert!(memory_scopes("global", &msg).is_empty());
+    }
+
+    #[test]
+    fn user_scope_keys_on_author_not_group_chat() {
+        let mut alice = message_from("-100200", "hi");
+        alice.author = "alice".into();
+        let mut bob = message_from("-100200", "hi");
+        bob.author = "bob".into();
+        assert_eq!(
+            memory_scopes("user", &alice)[0],
+            MemoryScope::User("telegram:alice".into())
+        );
+        assert_ne!(memory_scopes("user", &alice), memory_scopes("user", &bob));
+    }
+
+    #[tokio::test]
+    async fn build_memory_context_does_not_leak_between_senders() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let alice = message_from("alice", "my locker code is 4821");
+        let bob = message_from("bob", "what is the locker code?");
+        let alice_scopes = memory_scopes("user", &alice);
+        let bob_scopes = memory_scopes("user", &bob);
+
+        mem.store_scoped(
+            &conversation_memory_key(&alice),
+            &alice.content,
+            MemoryCategory::Conversation,
+            &alice_scopes[0],
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "office",
+            "The office locker room is on floor 2",
+            MemoryCategory::Core,
+        )
+        .await
+        .unwrap();
+
+        let for_bob = memory::context_block(&mem, "locker code", &bob_scopes).await;
+        assert!(!for_bob.contains("4821"));
+        assert!(for_bob.contains("floor 2"));
+
+        let for_alice = memory::context_block(&mem, "locker code", &alice_scopes).await;
+        assert!(for_alice.contains("4821"));
+    }
+
     // ── AIEOS Identity Tests (Issue #168) ─────────────────────────
 
     #[test]
@@ -1385,4 +1582,68 @@ mod tests {
             .contains("listen boom"));
         assert!(calls.load(Ordering::SeqCst) >= 1);
     }
+
+    struct RecordingChannel {
+        sent: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Channel for RecordingChannel {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn send(&self, message: &str, _recipient: &str) -> anyhow::Result<()> {
+            self.sent.lock().unwrap().push(message.to_string());
+            Ok(())
+        }
+
+        async fn listen(
+            &self,
+            _tx: tokio::sync::mpsc::Sender<traits::ChannelMessage>,
+        ) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn send_rich_falls_back_to_text_on_plain_channels() {
+        let channel = RecordingChannel {
+            sent: std::sync::Mutex::new(Vec::new()),
+        };
+        assert_eq!(
+            channel.capabilities(),
+            traits::ChannelCapabilities::default()
+        );
+
+        let message = traits::RichMessage {
+            attachments: vec![traits::Attachment {
+                kind: traits::AttachmentKind::Document,
+                path: "/tmp/report.pdf".into(),
+                file_name: None,
+                mime_type: None,
+                size: 0,
+            }],
+            reply_to: Some("1".into()),
+            ..traits::RichMessage::text("Here it is")
+        };
+        channel.send_rich(&message, "someone").await.unwrap();
+
+        assert_eq!(
+            channel.sent.lock().unwrap().as_slice(),
+            ["Here it is\n[Attachment not sent: report.pdf]"]
+        );
+    }
+
+    #[test]
+    fn rich_reply_stays_in_the_incoming_thread() {
+        let mut msg = message_from("U1", "hi");
+        msg.thread_id = Some("1700000000.000100".into());
+        msg.message_id = Some("1700000001.000200".into());
+
+        let reply = traits::RichMessage::reply(&msg, "hello");
+        assert_eq!(reply.text, "hello");
+        assert_eq!(reply.thread_id.as_deref(), Some("1700000000.000100"));
+        assert!(reply.reply_to.is_none());
+    }
 }
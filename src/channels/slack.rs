@@ -1,12 +1,39 @@
-use super::traits::{Channel, ChannelMessage};
+use super::traits::{Attachment, Channel, ChannelCapabilities, ChannelMessage, RichMessage};
 use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tokio_tungstenite::tungstenite::Message;
 use uuid::Uuid;
 
-/// Slack channel — polls conversations.history via Web API
+/// How many thread roots to remember for following up on thread replies.
+const MAX_ACTIVE_THREADS: usize = 500;
+
+/// Inbox for Events API callbacks received by the gateway (`/slack/events`).
+static EVENTS_INBOX: std::sync::Mutex<Option<tokio::sync::mpsc::Sender<serde_json::Value>>> =
+    std::sync::Mutex::new(None);
+
+/// Pass an Events API `event` to the listening channel. Returns `false` when
+/// no Events API listener is running (Slack retries the delivery later).
+pub fn deliver_event(event: serde_json::Value) -> bool {
+    let inbox = EVENTS_INBOX
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone();
+    inbox.is_some_and(|tx| tx.try_send(event).is_ok())
+}
+
+/// Slack channel — receives events over Socket Mode (app token) or the
+/// gateway's Events API endpoint, and falls back to polling
+/// `conversations.history` for a single channel.
 pub struct SlackChannel {
     bot_token: String,
+    app_token: Option<String>,
+    events_api: bool,
     channel_id: Option<String>,
     allowed_users: Vec<String>,
+    /// Threads the bot has been pulled into; replies there need no mention.
+    active_threads: Mutex<VecDeque<String>>,
     client: reqwest::Client,
 }
 
@@ -14,12 +41,28 @@ impl SlackChannel {
     pub fn new(bot_token: String, channel_id: Option<String>, allowed_users: Vec<String>) -> Self {
         Self {
             bot_token,
+            app_token: None,
+            events_api: false,
             channel_id,
             allowed_users,
-            client: reqwest::Client::new(),
+            active_threads: Mutex::new(VecDeque::new()),
+            client: crate::http::client("slack", crate::http::Defaults::NONE),
         }
     }
 
+    /// Listen over Socket Mode with an app-level token (`xapp-...`).
+    pub fn with_app_token(mut self, app_token: Option<String>) -> Self {
+        self.app_token = app_token.filter(|t| !t.trim().is_empty());
+        self
+    }
+
+    /// Take events from the gateway's `/slack/events` route instead of
+    /// polling. Ignored when an app token enables Socket Mode.
+    pub fn with_events_api(mut self, enabled: bool) -> Self {
+        self.events_api = enabled;
+        self
+    }
+
     /// Check if a Slack user ID is in the allowlist.
     /// Empty list means deny everyone until explicitly configured.
     /// `"*"` means allow everyone.
@@ -44,28 +87,13 @@ impl SlackChannel {
             .and_then(|u| u.as_str())
             .map(String::from)
     }
-}
-
-#[async_trait]
-impl Channel for SlackChannel {
-    fn name(&self) -> &str {
-        "slack"
-    }
-
-    async fn send(&self, message: &str, channel: &str) -> anyhow::Result<()> {
-        let body = serde_json::json!({
-            "channel": channel,
-            "text": message
-        });
-
-        let resp = self
-            .client
-            .post("https://slack.com/api/chat.postMessage")
-            .bearer_auth(&self.bot_token)
-            .json(&body)
-            .send()
-            .await?;
 
+    /// Check a Web API response; Slack reports most errors as `"ok": false`
+    /// with HTTP 200.
+    async fn check_response(
+        method: &str,
+        resp: reqwest::Response,
+    ) -> anyhow::Result<serde_json::Value> {
         let status = resp.status();
         let body = resp
             .text()
@@ -73,23 +101,345 @@ impl Channel for SlackChannel {
             .unwrap_or_else(|e| format!("<failed to read response body: {e}>"));
 
         if !status.is_success() {
-            anyhow::bail!("Slack chat.postMessage failed ({status}): {body}");
+            anyhow::bail!("Slack {method} failed ({status}): {body}");
         }
 
-        // Slack returns 200 for most app-level errors; check JSON "ok" field
         let parsed: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
         if parsed.get("ok") == Some(&serde_json::Value::Bool(false)) {
             let err = parsed
                 .get("error")
                 .and_then(|e| e.as_str())
                 .unwrap_or("unknown");
-            anyhow::bail!("Slack chat.postMessage failed: {err}");
+            anyhow::bail!("Slack {method} failed: {err}");
+        }
+
+        Ok(parsed)
+    }
+
+    /// Turn a Socket Mode / Events API `event` into a channel message.
+    ///
+    /// Accepted: `app_mention`s, direct messages, and messages in the
+    /// configured channel or in threads the bot already answered in. Channel
+    /// messages are threaded under their root so replies stay in the thread.
+    fn parse_event(&self, event: &serde_json::Value, bot_user_id: &str) -> Option<ChannelMessage> {
+        let kind = event.get("type").and_then(|t| t.as_str())?;
+        if kind != "message" && kind != "app_mention" {
+            return None;
+        }
+        // Edits, joins, bot posts etc. carry a subtype; file shares are still messages.
+        if event
+            .get("subtype")
+            .and_then(|s| s.as_str())
+            .is_some_and(|s| s != "file_share")
+            || event.get("bot_id").is_some()
+        {
+            return None;
+        }
+
+        let user = event.get("user").and_then(|u| u.as_str())?;
+        let channel = event.get("channel").and_then(|c| c.as_str())?;
+        let ts = event.get("ts").and_then(|t| t.as_str())?;
+        let text = event.get("text").and_then(|t| t.as_str()).unwrap_or("");
+        let thread_ts = event.get("thread_ts").and_then(|t| t.as_str());
+        if user == bot_user_id {
+            return None;
+        }
+
+        let is_dm = event.get("channel_type").and_then(|c| c.as_str()) == Some("im");
+        let mention = format!("<@{bot_user_id}>");
+        let mentioned = !bot_user_id.is_empty() && text.contains(&mention);
+
+        if kind == "message" && !is_dm {
+            // Mentions arrive again as `app_mention`; handle them once there.
+            if mentioned {
+                return None;
+            }
+            let in_active_thread = thread_ts.is_some_and(|root| self.is_active_thread(root));
+            if !in_active_thread && self.channel_id.as_deref() != Some(channel) {
+                return None;
+            }
+        }
+
+        if !self.is_user_allowed(user) {
+            tracing::warn!("Slack: ignoring message from unauthorized user: {user}");
+            return None;
+        }
+
+        let content = if mentioned {
+            text.replace(&mention, "").trim().to_string()
+        } else {
+            text.trim().to_string()
+        };
+        if content.is_empty() {
+            return None;
+        }
+
+        let thread_id = if is_dm {
+            thread_ts.map(ToString::to_string)
+        } else {
+            let root = thread_ts.unwrap_or(ts).to_string();
+            self.remember_thread(&root);
+            Some(root)
+        };
+
+        Some(ChannelMessage {
+            id: Uuid::new_v4().to_string(),
+            sender: channel.to_string(),
+            author: user.to_string(),
+            content,
+            attachments: Vec::new(),
+            message_id: Some(ts.to_string()),
+            thread_id,
+            reply_to: None,
+            raw: Some(event.clone()),
+            channel: "slack".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        })
+    }
+
+    fn is_active_thread(&self, root: &str) -> bool {
+        self.active_threads
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .iter()
+            .any(|t| t == root)
+    }
+
+    fn remember_thread(&self, root: &str) {
+        let mut threads = self
+            .active_threads
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if threads.iter().any(|t| t == root) {
+            return;
+        }
+        if threads.len() >= MAX_ACTIVE_THREADS {
+            threads.pop_front();
+        }
+        threads.push_back(root.to_string());
+    }
+
+    /// Ask Slack for a Socket Mode WebSocket URL.
+    async fn open_socket_url(&self, app_token: &str) -> anyhow::Result<String> {
+        let resp = self
+            .client
+            .post("https://slack.com/api/apps.connections.open")
+            .bearer_auth(app_token)
+            .send()
+            .await?;
+        let data = Self::check_response("apps.connections.open", resp).await?;
+        data.get("url")
+            .and_then(|u| u.as_str())
+            .map(String::from)
+            .ok_or_else(|| anyhow::anyhow!("Slack apps.connections.open returned no url"))
+    }
+
+    /// Socket Mode: acknowledge every envelope and forward `events_api`
+    /// payloads. Slack asks clients to reconnect periodically.
+    async fn listen_socket(
+        &self,
+        app_token: &str,
+        tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+    ) -> anyhow::Result<()> {
+        let bot_user_id = self.get_bot_user_id().await.unwrap_or_default();
+
+        loop {
+            let url = self.open_socket_url(app_token).await?;
+            let ws_stream = crate::http::connect_websocket("slack", &url).await?;
+            let (mut write, mut read) = ws_stream.split();
+            tracing::info!("Slack channel connected over Socket Mode");
+
+            while let Some(frame) = read.next().await {
+                let text = match frame? {
+                    Message::Text(text) => text,
+                    Message::Ping(data) => {
+                        write.send(Message::Pong(data)).await?;
+                        continue;
+                    }
+                    Message::Close(_) => break,
+                    _ => continue,
+                };
+                let Ok(envelope) = serde_json::from_str::<serde_json::Value>(&text) else {
+                    continue;
+                };
+
+                if let Some(envelope_id) = envelope.get("envelope_id").and_then(|e| e.as_str()) {
+                    let ack = serde_json::json!({ "envelope_id": envelope_id });
+                    write.send(Message::Text(ack.to_string())).await?;
+                }
+
+                match envelope.get("type").and_then(|t| t.as_str()) {
+                    Some("disconnect") => break,
+                    Some("events_api") => {
+                        let Some(event) = envelope.pointer("/payload/event") else {
+                            continue;
+                        };
+                        if let Some(msg) = self.parse_event(event, &bot_user_id) {
+                            if tx.send(msg).await.is_err() {
+                                return Ok(());
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            tracing::info!("Slack Socket Mode connection closed, reconnecting...");
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+    }
+
+    /// Events API: consume events handed over by the gateway.
+    async fn listen_events(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+    ) -> anyhow::Result<()> {
+        let (inbox_tx, mut inbox) = tokio::sync::mpsc::channel(100);
+        *EVENTS_INBOX
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(inbox_tx);
+        let bot_user_id = self.get_bot_user_id().await.unwrap_or_default();
+
+        tracing::info!("Slack channel waiting for Events API callbacks from the gateway...");
+
+        while let Some(event) = inbox.recv().await {
+            if let Some(msg) = self.parse_event(&event, &bot_user_id) {
+                if tx.send(msg).await.is_err() {
+                    break;
+                }
+            }
+        }
+        EVENTS_INBOX
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take();
+        Ok(())
+    }
+
+    /// Upload a file with the external upload flow and share it in `channel`.
+    async fn upload_file(
+        &self,
+        attachment: &Attachment,
+        channel: &str,
+        thread_ts: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let file_name = attachment
+            .file_name
+            .clone()
+            .or_else(|| {
+                attachment
+                    .path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "file".into());
+        let bytes = tokio::fs::read(&attachment.path).await?;
+
+        let resp = self
+            .client
+            .get("https://slack.com/api/files.getUploadURLExternal")
+            .bearer_auth(&self.bot_token)
+            .query(&[
+                ("filename", file_name.clone()),
+                ("length", bytes.len().to_string()),
+            ])
+            .send()
+            .await?;
+        let ticket = Self::check_response("files.getUploadURLExternal", resp).await?;
+        let (Some(upload_url), Some(file_id)) = (
+            ticket.get("upload_url").and_then(|u| u.as_str()),
+            ticket.get("file_id").and_then(|f| f.as_str()),
+        ) else {
+            anyhow::bail!("Slack files.getUploadURLExternal returned no upload_url");
+        };
+
+        let resp = self.client.post(upload_url).body(bytes).send().await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("Slack file upload failed ({})", resp.status());
+        }
+
+        let mut complete = serde_json::json!({
+            "files": [{ "id": file_id, "title": file_name }],
+            "channel_id": channel,
+        });
+        if let Some(ts) = thread_ts {
+            complete["thread_ts"] = ts.into();
+        }
+        let resp = self
+            .client
+            .post("https://slack.com/api/files.completeUploadExternal")
+            .bearer_auth(&self.bot_token)
+            .json(&complete)
+            .send()
+            .await?;
+        Self::check_response("files.completeUploadExternal", resp).await?;
+        Ok(())
+    }
+}
+
+/// `chat.postMessage` body; replies go into the thread of `thread_ts`.
+fn post_body(channel: &str, text: &str, thread_ts: Option<&str>) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "channel": channel,
+        "text": text
+    });
+    if let Some(ts) = thread_ts {
+        body["thread_ts"] = ts.into();
+    }
+    body
+}
+
+#[async_trait]
+impl Channel for SlackChannel {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    async fn send(&self, message: &str, channel: &str) -> anyhow::Result<()> {
+        self.send_rich(&RichMessage::text(message), channel).await
+    }
+
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities {
+            attachments: true,
+            replies: true,
+            threads: true,
+        }
+    }
+
+    async fn send_rich(&self, message: &RichMessage, channel: &str) -> anyhow::Result<()> {
+        // Slack has no quoted replies; replying means posting in the thread.
+        let thread_ts = message.thread_id.as_deref().or(message.reply_to.as_deref());
+
+        if !message.text.is_empty() || message.attachments.is_empty() {
+            let resp = self
+                .client
+                .post("https://slack.com/api/chat.postMessage")
+                .bearer_auth(&self.bot_token)
+                .json(&post_body(channel, &message.text, thread_ts))
+                .send()
+                .await?;
+            Self::check_response("chat.postMessage", resp).await?;
+        }
+
+        for attachment in &message.attachments {
+            self.upload_file(attachment, channel, thread_ts).await?;
         }
 
         Ok(())
     }
 
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        if let Some(ref app_token) = self.app_token {
+            return self.listen_socket(app_token, tx).await;
+        }
+        if self.events_api {
+            return self.listen_events(tx).await;
+        }
+
         let channel_id = self
             .channel_id
             .clone()
@@ -162,7 +512,16 @@ impl Channel for SlackChannel {
                     let channel_msg = ChannelMessage {
                         id: Uuid::new_v4().to_string(),
                         sender: channel_id.clone(),
+                        author: user.to_string(),
                         content: text.to_string(),
+                        attachments: Vec::new(),
+                        message_id: Some(ts.to_string()),
+                        thread_id: msg
+                            .get("thread_ts")
+                            .and_then(|t| t.as_str())
+                            .map(ToString::to_string),
+                        reply_to: None,
+                        raw: Some(msg.clone()),
                         channel: "slack".to_string(),
                         timestamp: std::time::SystemTime::now()
                             .duration_since(std::time::UNIX_EPOCH)
@@ -252,4 +611,132 @@ mod tests {
         assert!(ch.is_user_allowed("U111"));
         assert!(ch.is_user_allowed("anyone"));
     }
+
+    #[test]
+    fn post_body_threads_replies() {
+        let body = post_body("C1", "hi", Some("1700000000.000100"));
+        assert_eq!(body["channel"], "C1");
+        assert_eq!(body["thread_ts"], "1700000000.000100");
+        assert!(post_body("C1", "hi", None).get("thread_ts").is_none());
+    }
+
+    fn event(kind: &str, channel_type: &str, text: &str) -> serde_json::Value {
+        serde_json::json!({
+            "type": kind,
+            "channel_type": channel_type,
+            "channel": "C9",
+            "user": "U111",
+            "text": text,
+            "ts": "1700000000.000100"
+        })
+    }
+
+    #[test]
+    fn app_mention_strips_mention_and_threads_under_message() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec!["*".into()]);
+        let msg = ch
+            .parse_event(&event("app_mention", "channel", "<@UBOT> status?"), "UBOT")
+            .unwrap();
+        assert_eq!(msg.content, "status?");
+        assert_eq!(msg.sender, "C9");
+        assert_eq!(msg.thread_id.as_deref(), Some("1700000000.000100"));
+        assert_eq!(msg.message_id.as_deref(), Some("1700000000.000100"));
+    }
+
+    #[test]
+    fn direct_messages_stay_flat() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec!["*".into()]);
+        let msg = ch
+            .parse_event(&event("message", "im", "hello"), "UBOT")
+            .unwrap();
+        assert_eq!(msg.content, "hello");
+        assert!(msg.thread_id.is_none());
+    }
+
+    #[test]
+    fn channel_messages_need_mention_thread_or_configured_channel() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec!["*".into()]);
+        assert!(ch
+            .parse_event(&event("message", "channel", "chatter"), "UBOT")
+            .is_none());
+        // Mentions are handled through the matching app_mention event
+        assert!(ch
+            .parse_event(&event("message", "channel", "<@UBOT> hi"), "UBOT")
+            .is_none());
+
+        let ch = SlackChannel::new("xoxb-fake".into(), Some("C9".into()), vec!["*".into()]);
+        assert!(ch
+            .parse_event(&event("message", "channel", "chatter"), "UBOT")
+            .is_some());
+    }
+
+    #[test]
+    fn thread_replies_follow_threads_the_bot_joined() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec!["*".into()]);
+        let mut reply = event("message", "channel", "and another thing");
+        reply["ts"] = "1700000001.000200".into();
+        reply["thread_ts"] = "1700000000.000100".into();
+        assert!(ch.parse_event(&reply, "UBOT").is_none());
+
+        ch.parse_event(&event("app_mention", "channel", "<@UBOT> hi"), "UBOT")
+            .unwrap();
+        let msg = ch.parse_event(&reply, "UBOT").unwrap();
+        assert_eq!(msg.thread_id.as_deref(), Some("1700000000.000100"));
+        assert_eq!(msg.message_id.as_deref(), Some("1700000001.000200"));
+    }
+
+    #[test]
+    fn bot_edits_and_unauthorized_events_are_ignored() {
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec!["U222".into()]);
+        assert!(ch
+            .parse_event(&event("message", "im", "hello"), "UBOT")
+            .is_none());
+
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec!["*".into()]);
+        let mut edited = event("message", "im", "hello");
+        edited["subtype"] = "message_changed".into();
+        assert!(ch.parse_event(&edited, "UBOT").is_none());
+        let mut bot = event("message", "im", "hello");
+        bot["bot_id"] = "B1".into();
+        assert!(ch.parse_event(&bot, "UBOT").is_none());
+        assert!(ch
+            .parse_event(&event("message", "im", "hello"), "U111")
+            .is_none());
+    }
+
+    #[test]
+    fn socket_mode_takes_precedence_and_blank_app_token_is_ignored() {
+        let ch =
+            SlackChannel::new("xoxb-fake".into(), None, vec![]).with_app_token(Some(" ".into()));
+        assert!(ch.app_token.is_none());
+        let ch = SlackChannel::new("xoxb-fake".into(), None, vec![])
+            .with_app_token(Some("xapp-1".into()));
+        assert_eq!(ch.app_token.as_deref(), Some("xapp-1"));
+    }
+
+    #[tokio::test]
+    async fn events_api_callbacks_join_the_message_bus() {
+        let ch =
+            SlackChannel::new("xoxb-fake".into(), None, vec!["*".into()]).with_events_api(true);
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let listener = tokio::spawn(async move { ch.listen(tx).await });
+
+        let mut delivered = false;
+        for _ in 0..50 {
+            if deliver_event(event("message", "im", "via events api")) {
+                delivered = true;
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(delivered);
+
+        let msg = tokio::time::timeout(std::time::Duration::from_secs(10), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.content, "via events api");
+        assert_eq!(msg.channel, "slack");
+        listener.abort();
+    }
 }
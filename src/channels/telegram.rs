@@ -1,14 +1,77 @@
-use super::traits::{Channel, ChannelMessage};
+use super::traits::{
+    Attachment, AttachmentKind, Channel, ChannelCapabilities, ChannelMessage, RichMessage,
+};
+use crate::config::TelegramConfig;
+use anyhow::Context;
 use async_trait::async_trait;
 use reqwest::multipart::{Form, Part};
-use std::path::Path;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
+/// Longest caption Telegram accepts on a photo or file
+const CAPTION_LIMIT: usize = 1024;
+
+/// Updates the gateway received on `/telegram/<secret>`, handed to the
+/// webhook-mode listener so they join the same message bus as polling.
+static WEBHOOK_INBOX: std::sync::Mutex<Option<tokio::sync::mpsc::Sender<serde_json::Value>>> =
+    std::sync::Mutex::new(None);
+
+/// Pass a webhook update to the listening channel. Returns `false` when no
+/// webhook-mode listener is running (Telegram retries the update later).
+pub fn deliver_webhook_update(update: serde_json::Value) -> bool {
+    let inbox = WEBHOOK_INBOX
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone();
+    inbox.is_some_and(|tx| tx.try_send(update).is_ok())
+}
+
+/// Secret used in the webhook path and `X-Telegram-Bot-Api-Secret-Token`.
+/// Derived from the bot token unless configured, so the gateway and the
+/// channel agree without extra setup.
+pub fn webhook_secret(config: &TelegramConfig) -> String {
+    if let Some(secret) = config.webhook_secret.as_deref().map(str::trim) {
+        if !secret.is_empty() {
+            return secret.to_string();
+        }
+    }
+    let digest = Sha256::digest(format!("zeroclaw-telegram-webhook:{}", config.bot_token));
+    hex::encode(&digest[..16])
+}
+
+/// Full webhook URL for a public base URL.
+pub fn webhook_url(base_url: &str, secret: &str) -> String {
+    format!("{}/telegram/{secret}", base_url.trim_end_matches('/'))
+}
+
 /// Telegram channel — long-polls the Bot API for updates
 pub struct TelegramChannel {
     bot_token: String,
     allowed_users: Vec<String>,
     client: reqwest::Client,
+    /// Where received media is saved; `None` ignores photos, files and voice notes
+    media_dir: Option<PathBuf>,
+    max_file_bytes: u64,
+    /// Receive updates from the gateway webhook instead of polling
+    webhook: bool,
+}
+
+/// A file referenced by an incoming message, before it is downloaded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IncomingFile {
+    file_id: String,
+    kind: AttachmentKind,
+    file_name: Option<String>,
+    mime_type: Option<String>,
+}
+
+/// Text and media found in an incoming message.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct IncomingMessage {
+    /// Message text or media caption, plus location and skipped-file notes
+    text: String,
+    files: Vec<IncomingFile>,
 }
 
 impl TelegramChannel {
@@ -16,14 +79,154 @@ impl TelegramChannel {
         Self {
             bot_token,
             allowed_users,
-            client: reqwest::Client::new(),
+            client: crate::http::client("telegram", crate::http::Defaults::long_poll(30)),
+            media_dir: None,
+            max_file_bytes: 0,
+            webhook: false,
+        }
+    }
+
+    /// Take updates from the gateway's `/telegram/<secret>` route instead of
+    /// long-polling `getUpdates`.
+    pub fn with_webhook(mut self, enabled: bool) -> Self {
+        self.webhook = enabled;
+        self
+    }
+
+    /// Point Telegram at `url`; it will send `secret` with every update.
+    pub async fn set_webhook(&self, url: &str, secret: &str) -> anyhow::Result<()> {
+        let body = serde_json::json!({
+            "url": url,
+            "secret_token": secret,
+            "allowed_updates": ["message"]
+        });
+        let resp = self
+            .client
+            .post(self.api_url("setWebhook"))
+            .json(&body)
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let err = resp.text().await?;
+            anyhow::bail!("Telegram setWebhook failed: {err}");
+        }
+        Ok(())
+    }
+
+    /// Remove the webhook so polling (or another host) can take over.
+    pub async fn delete_webhook(&self) -> anyhow::Result<()> {
+        let resp = self
+            .client
+            .post(self.api_url("deleteWebhook"))
+            .send()
+            .await?;
+        if !resp.status().is_success() {
+            let err = resp.text().await?;
+            anyhow::bail!("Telegram deleteWebhook failed: {err}");
+        }
+        Ok(())
+    }
+
+    /// Process updates delivered by the gateway until the bus closes.
+    async fn listen_webhook(
+        &self,
+        tx: tokio::sync::mpsc::Sender<ChannelMessage>,
+    ) -> anyhow::Result<()> {
+        let (inbox_tx, mut inbox) = tokio::sync::mpsc::channel(100);
+        *WEBHOOK_INBOX
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(inbox_tx);
+
+        tracing::info!("Telegram channel waiting for webhook updates from the gateway...");
+
+        while let Some(update) = inbox.recv().await {
+            if !self.handle_update(&update, &tx).await {
+                break;
+            }
         }
+        WEBHOOK_INBOX
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .take();
+        Ok(())
+    }
+
+    /// Download photos, documents and voice notes up to `max_file_bytes`
+    /// into `dir` and attach them to incoming messages.
+    pub fn with_media(mut self, dir: PathBuf, max_file_bytes: u64) -> Self {
+        self.media_dir = Some(dir);
+        self.max_file_bytes = max_file_bytes;
+        self
     }
 
     fn api_url(&self, method: &str) -> String {
         format!("https://api.telegram.org/bot{}/{method}", self.bot_token)
     }
 
+    fn file_url(&self, file_path: &str) -> String {
+        format!(
+            "https://api.telegram.org/file/bot{}/{file_path}",
+            self.bot_token
+        )
+    }
+
+    /// Resolve a file with `getFile` and save it under `dir`.
+    async fn download_file(&self, file: &IncomingFile, dir: &Path) -> anyhow::Result<Attachment> {
+        let resp: serde_json::Value = self
+            .client
+            .post(self.api_url("getFile"))
+            .json(&serde_json::json!({ "file_id": file.file_id }))
+            .send()
+            .await?
+            .json()
+            .await?;
+        let file_path = resp
+            .get("result")
+            .and_then(|r| r.get("file_path"))
+            .and_then(serde_json::Value::as_str)
+            .context("Telegram getFile returned no file_path")?;
+
+        let bytes = self
+            .client
+            .get(self.file_url(file_path))
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        let size = bytes.len() as u64;
+        anyhow::ensure!(
+            size <= self.max_file_bytes,
+            "file is {size} bytes, over the {} byte limit",
+            self.max_file_bytes
+        );
+
+        let name = file
+            .file_name
+            .clone()
+            .or_else(|| {
+                Path::new(file_path)
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "file".into());
+        tokio::fs::create_dir_all(dir).await?;
+        let path = dir.join(format!(
+            "{}-{}",
+            &Uuid::new_v4().simple().to_string()[..8],
+            safe_file_name(&name)
+        ));
+        tokio::fs::write(&path, &bytes).await?;
+
+        Ok(Attachment {
+            kind: file.kind,
+            path,
+            file_name: file.file_name.clone(),
+            mime_type: file.mime_type.clone(),
+            size,
+        })
+    }
+
     fn is_user_allowed(&self, username: &str) -> bool {
         self.allowed_users.iter().any(|u| u == "*" || u == username)
     }
@@ -35,6 +238,230 @@ impl TelegramChannel {
         identities.into_iter().any(|id| self.is_user_allowed(id))
     }
 
+    /// `sendMessage` with Markdown, retried as plain text if Telegram rejects
+    /// the markup. `extra` adds fields such as `message_thread_id`.
+    async fn send_text(
+        &self,
+        chat_id: &str,
+        message: &str,
+        extra: &serde_json::Map<String, serde_json::Value>,
+    ) -> anyhow::Result<()> {
+        let mut markdown_body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": message,
+            "parse_mode": "Markdown"
+        });
+        extend_object(&mut markdown_body, extra);
+
+        let markdown_resp = self
+            .client
+            .post(self.api_url("sendMessage"))
+            .json(&markdown_body)
+            .send()
+            .await?;
+
+        if markdown_resp.status().is_success() {
+            return Ok(());
+        }
+
+        let markdown_status = markdown_resp.status();
+        let markdown_err = markdown_resp.text().await.unwrap_or_default();
+        tracing::warn!(
+            status = ?markdown_status,
+            "Telegram sendMessage with Markdown failed; retrying without parse_mode"
+        );
+
+        // Retry without parse_mode as a compatibility fallback.
+        let mut plain_body = serde_json::json!({
+            "chat_id": chat_id,
+            "text": message,
+        });
+        extend_object(&mut plain_body, extra);
+        let plain_resp = self
+            .client
+            .post(self.api_url("sendMessage"))
+            .json(&plain_body)
+            .send()
+            .await?;
+
+        if !plain_resp.status().is_success() {
+            let plain_status = plain_resp.status();
+            let plain_err = plain_resp.text().await.unwrap_or_default();
+            anyhow::bail!(
+                "Telegram sendMessage failed (markdown {}: {}; plain {}: {})",
+                markdown_status,
+                markdown_err,
+                plain_status,
+                plain_err
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Upload a local file with the send method matching its kind.
+    async fn send_attachment(
+        &self,
+        chat_id: &str,
+        attachment: &Attachment,
+        caption: Option<&str>,
+        extra: &serde_json::Map<String, serde_json::Value>,
+    ) -> anyhow::Result<()> {
+        let (method, field) = match attachment.kind {
+            AttachmentKind::Image => ("sendPhoto", "photo"),
+            AttachmentKind::Document => ("sendDocument", "document"),
+            AttachmentKind::Audio => ("sendAudio", "audio"),
+            AttachmentKind::Voice => ("sendVoice", "voice"),
+            AttachmentKind::Video => ("sendVideo", "video"),
+        };
+        let file_name = attachment
+            .file_name
+            .clone()
+            .or_else(|| {
+                attachment
+                    .path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+            })
+            .unwrap_or_else(|| "file".into());
+
+        let file_bytes = tokio::fs::read(&attachment.path).await?;
+        let mut form = Form::new()
+            .text("chat_id", chat_id.to_string())
+            .part(field, Part::bytes(file_bytes).file_name(file_name.clone()));
+        if let Some(cap) = caption {
+            form = form.text("caption", cap.to_string());
+        }
+        for (key, value) in extra {
+            let value = match value {
+                serde_json::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            form = form.text(key.clone(), value);
+        }
+
+        let resp = self
+            .client
+            .post(self.api_url(method))
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let err = resp.text().await?;
+            anyhow::bail!("Telegram {method} failed: {err}");
+        }
+
+        tracing::info!("Telegram {field} sent to {chat_id}: {file_name}");
+        Ok(())
+    }
+
+    /// Turn one update into a `ChannelMessage` on the bus. Returns `false`
+    /// once the bus is closed.
+    async fn handle_update(
+        &self,
+        update: &serde_json::Value,
+        tx: &tokio::sync::mpsc::Sender<ChannelMessage>,
+    ) -> bool {
+        let Some(message) = update.get("message") else {
+            return true;
+        };
+
+        let incoming = parse_message(message, self.max_file_bytes);
+        let media_enabled = self.media_dir.is_some();
+        if incoming.text.is_empty() && (incoming.files.is_empty() || !media_enabled) {
+            return true;
+        }
+
+        let username_opt = message
+            .get("from")
+            .and_then(|f| f.get("username"))
+            .and_then(|u| u.as_str());
+        let username = username_opt.unwrap_or("unknown");
+
+        let user_id = message
+            .get("from")
+            .and_then(|f| f.get("id"))
+            .and_then(serde_json::Value::as_i64);
+        let user_id_str = user_id.map(|id| id.to_string());
+
+        let mut identities = vec![username];
+        if let Some(ref id) = user_id_str {
+            identities.push(id.as_str());
+        }
+
+        if !self.is_any_user_allowed(identities.iter().copied()) {
+            tracing::warn!(
+                "Telegram: ignoring message from unauthorized user: username={username}, user_id={}. \
+Allowlist Telegram @username or numeric user ID, then run `zeroclaw onboard --channels-only`.",
+                user_id_str.as_deref().unwrap_or("unknown")
+            );
+            return true;
+        }
+
+        let chat_id = message
+            .get("chat")
+            .and_then(|c| c.get("id"))
+            .and_then(serde_json::Value::as_i64)
+            .map(|id| id.to_string())
+            .unwrap_or_default();
+
+        // Send "typing" indicator immediately when we receive a message
+        let typing_body = serde_json::json!({
+            "chat_id": &chat_id,
+            "action": "typing"
+        });
+        let _ = self
+            .client
+            .post(self.api_url("sendChatAction"))
+            .json(&typing_body)
+            .send()
+            .await; // Ignore errors for typing indicator
+
+        let mut content = incoming.text;
+        let mut attachments = Vec::new();
+        if let Some(dir) = self.media_dir.as_deref() {
+            for file in &incoming.files {
+                match self.download_file(file, dir).await {
+                    Ok(attachment) => attachments.push(attachment),
+                    Err(e) => {
+                        tracing::warn!("Telegram: failed to download media: {e}");
+                        push_note(
+                            &mut content,
+                            &format!("[{} could not be downloaded]", file.describe()),
+                        );
+                    }
+                }
+            }
+        }
+
+        let msg = ChannelMessage {
+            id: Uuid::new_v4().to_string(),
+            sender: chat_id,
+            author: user_id_str.clone().unwrap_or_else(|| username.to_string()),
+            content,
+            attachments,
+            message_id: message_id(Some(message)),
+            thread_id: message
+                .get("is_topic_message")
+                .and_then(serde_json::Value::as_bool)
+                .unwrap_or(false)
+                .then(|| message.get("message_thread_id"))
+                .flatten()
+                .and_then(serde_json::Value::as_i64)
+                .map(|id| id.to_string()),
+            reply_to: message_id(message.get("reply_to_message")),
+            raw: Some(message.clone()),
+            channel: "telegram".to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        };
+
+        tx.send(msg).await.is_ok()
+    }
+
     /// Send a document/file to a Telegram chat
     pub async fn send_document(
         &self,
@@ -370,58 +797,40 @@ impl Channel for TelegramChannel {
     }
 
     async fn send(&self, message: &str, chat_id: &str) -> anyhow::Result<()> {
-        let markdown_body = serde_json::json!({
-            "chat_id": chat_id,
-            "text": message,
-            "parse_mode": "Markdown"
-        });
-
-        let markdown_resp = self
-            .client
-            .post(self.api_url("sendMessage"))
-            .json(&markdown_body)
-            .send()
-            .await?;
+        self.send_text(chat_id, message, &serde_json::Map::new())
+            .await
+    }
 
-        if markdown_resp.status().is_success() {
-            return Ok(());
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities {
+            attachments: true,
+            replies: true,
+            threads: true,
         }
+    }
 
-        let markdown_status = markdown_resp.status();
-        let markdown_err = markdown_resp.text().await.unwrap_or_default();
-        tracing::warn!(
-            status = ?markdown_status,
-            "Telegram sendMessage with Markdown failed; retrying without parse_mode"
-        );
-
-        // Retry without parse_mode as a compatibility fallback.
-        let plain_body = serde_json::json!({
-            "chat_id": chat_id,
-            "text": message,
-        });
-        let plain_resp = self
-            .client
-            .post(self.api_url("sendMessage"))
-            .json(&plain_body)
-            .send()
-            .await?;
-
-        if !plain_resp.status().is_success() {
-            let plain_status = plain_resp.status();
-            let plain_err = plain_resp.text().await.unwrap_or_default();
-            anyhow::bail!(
-                "Telegram sendMessage failed (markdown {}: {}; plain {}: {})",
-                markdown_status,
-                markdown_err,
-                plain_status,
-                plain_err
-            );
+    async fn send_rich(&self, message: &RichMessage, chat_id: &str) -> anyhow::Result<()> {
+        let extra = rich_fields(message);
+        // A single file carries the text as its caption when it fits.
+        let caption_fits =
+            message.attachments.len() == 1 && message.text.chars().count() <= CAPTION_LIMIT;
+        if !message.text.is_empty() && !caption_fits {
+            self.send_text(chat_id, &message.text, &extra).await?;
+        }
+        for (i, attachment) in message.attachments.iter().enumerate() {
+            let caption = (i == 0 && caption_fits && !message.text.is_empty())
+                .then_some(message.text.as_str());
+            self.send_attachment(chat_id, attachment, caption, &extra)
+                .await?;
         }
-
         Ok(())
     }
 
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
+        if self.webhook {
+            return self.listen_webhook(tx).await;
+        }
+
         let mut offset: i64 = 0;
 
         tracing::info!("Telegram channel listening for messages...");
@@ -459,71 +868,7 @@ impl Channel for TelegramChannel {
                         offset = uid + 1;
                     }
 
-                    let Some(message) = update.get("message") else {
-                        continue;
-                    };
-
-                    let Some(text) = message.get("text").and_then(serde_json::Value::as_str) else {
-                        continue;
-                    };
-
-                    let username_opt = message
-                        .get("from")
-                        .and_then(|f| f.get("username"))
-                        .and_then(|u| u.as_str());
-                    let username = username_opt.unwrap_or("unknown");
-
-                    let user_id = message
-                        .get("from")
-                        .and_then(|f| f.get("id"))
-                        .and_then(serde_json::Value::as_i64);
-                    let user_id_str = user_id.map(|id| id.to_string());
-
-                    let mut identities = vec![username];
-                    if let Some(ref id) = user_id_str {
-                        identities.push(id.as_str());
-                    }
-
-                    if !self.is_any_user_allowed(identities.iter().copied()) {
-                        tracing::warn!(
-                            "Telegram: ignoring message from unauthorized user: username={username}, user_id={}. \
-Allowlist Telegram @username or numeric user ID, then run `zeroclaw onboard --channels-only`.",
-                            user_id_str.as_deref().unwrap_or("unknown")
-                        );
-                        continue;
-                    }
-
-                    let chat_id = message
-                        .get("chat")
-                        .and_then(|c| c.get("id"))
-                        .and_then(serde_json::Value::as_i64)
-                        .map(|id| id.to_string())
-                        .unwrap_or_default();
-
-                    // Send "typing" indicator immediately when we receive a message
-                    let typing_body = serde_json::json!({
-                        "chat_id": &chat_id,
-                        "action": "typing"
-                    });
-                    let _ = self
-                        .client
-                        .post(self.api_url("sendChatAction"))
-                        .json(&typing_body)
-                        .send()
-                        .await; // Ignore errors for typing indicator
-
-                    let msg = ChannelMessage {
-                        id: Uuid::new_v4().to_string(),
-                        sender: chat_id,
-                        content: text.to_string(),
-                        channel: "telegram".to_string(),
-                        timestamp: std::time::SystemTime::now()
-                            .duration_since(std::time::UNIX_EPOCH)
-                            .unwrap_or_default()
-                            .as_secs(),
-                    };
-
-                    if tx.send(msg).await.is_err() {
+                    if !self.handle_update(update, &tx).await {
                         return Ok(());
                     }
                 }
@@ -541,6 +886,183 @@ Allowlist Telegram @username or numeric user ID, then run `zeroclaw onboard --ch
     }
 }
 
+impl IncomingFile {
+    fn from_json(value: &serde_json::Value, kind: AttachmentKind) -> Option<Self> {
+        let str_field = |key: &str| {
+            value
+                .get(key)
+                .and_then(serde_json::Value::as_str)
+                .map(ToString::to_string)
+        };
+        Some(Self {
+            file_id: str_field("file_id")?,
+            kind,
+            file_name: str_field("file_name"),
+            mime_type: str_field("mime_type")
+                .or_else(|| (kind == AttachmentKind::Image).then(|| "image/jpeg".to_string())),
+        })
+    }
+
+    fn describe(&self) -> String {
+        let kind = match self.kind {
+            AttachmentKind::Image => "Photo",
+            AttachmentKind::Document => "Document",
+            AttachmentKind::Audio => "Audio",
+            AttachmentKind::Voice => "Voice note",
+            AttachmentKind::Video => "Video",
+        };
+        match &self.file_name {
+            Some(name) => format!("{kind} {name}"),
+            None => kind.to_string(),
+        }
+    }
+}
+
+fn file_size(value: &serde_json::Value) -> Option<u64> {
+    value.get("file_size").and_then(serde_json::Value::as_u64)
+}
+
+fn push_note(text: &mut String, note: &str) {
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    text.push_str(note);
+}
+
+/// Extract text, caption, location and downloadable files from a Telegram
+/// `message` object. Files larger than `max_file_bytes` are replaced by a note.
+fn parse_message(message: &serde_json::Value, max_file_bytes: u64) -> IncomingMessage {
+    let mut incoming = IncomingMessage {
+        text: message
+            .get("text")
+            .or_else(|| message.get("caption"))
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        files: Vec::new(),
+    };
+
+    // Telegram sends every resolution of a photo; keep the largest that fits.
+    if let Some(sizes) = message.get("photo").and_then(serde_json::Value::as_array) {
+        let fitting = sizes
+            .iter()
+            .filter(|p| file_size(p).is_none_or(|size| size <= max_file_bytes))
+            .max_by_key(|p| {
+                let dim = |key: &str| p.get(key).and_then(serde_json::Value::as_u64);
+                dim("width").unwrap_or(0) * dim("height").unwrap_or(0)
+            });
+        match fitting.and_then(|p| IncomingFile::from_json(p, AttachmentKind::Image)) {
+            Some(file) => incoming.files.push(file),
+            None => push_note(&mut incoming.text, "[Photo skipped: too large]"),
+        }
+    }
+
+    for (key, kind) in [
+        ("document", AttachmentKind::Document),
+        ("voice", AttachmentKind::Voice),
+        ("audio", AttachmentKind::Audio),
+        ("video", AttachmentKind::Video),
+    ] {
+        let Some(value) = message.get(key) else {
+            continue;
+        };
+        let Some(file) = IncomingFile::from_json(value, kind) else {
+            continue;
+        };
+        match file_size(value) {
+            Some(size) if size > max_file_bytes => push_note(
+                &mut incoming.text,
+                &format!(
+                    "[{} skipped: {size} bytes exceeds the {max_file_bytes} byte limit]",
+                    file.describe()
+                ),
+            ),
+            _ => incoming.files.push(file),
+        }
+    }
+
+    let location = message
+        .get("venue")
+        .and_then(|v| v.get("location"))
+        .or_else(|| message.get("location"));
+    if let Some(location) = location {
+        let coord = |key: &str| location.get(key).and_then(serde_json::Value::as_f64);
+        if let (Some(lat), Some(lon)) = (coord("latitude"), coord("longitude")) {
+            let venue = message.get("venue").map(|v| {
+                let field = |key: &str| v.get(key).and_then(serde_json::Value::as_str);
+                [field("title"), field("address")]
+                    .into_iter()
+                    .flatten()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            });
+            let note = match venue.filter(|v| !v.is_empty()) {
+                Some(venue) => format!("📍 Location: {venue} ({lat}, {lon})"),
+                None => format!("📍 Location: {lat}, {lon}"),
+            };
+            push_note(&mut incoming.text, &note);
+        }
+    }
+
+    incoming
+}
+
+fn message_id(message: Option<&serde_json::Value>) -> Option<String> {
+    message
+        .and_then(|m| m.get("message_id"))
+        .and_then(serde_json::Value::as_i64)
+        .map(|id| id.to_string())
+}
+
+/// `message_thread_id` and `reply_parameters` for a rich message.
+fn rich_fields(message: &RichMessage) -> serde_json::Map<String, serde_json::Value> {
+    let mut fields = serde_json::Map::new();
+    if let Some(thread) = message
+        .thread_id
+        .as_deref()
+        .and_then(|t| t.parse::<i64>().ok())
+    {
+        fields.insert("message_thread_id".into(), thread.into());
+    }
+    if let Some(reply) = message
+        .reply_to
+        .as_deref()
+        .and_then(|r| r.parse::<i64>().ok())
+    {
+        fields.insert(
+            "reply_parameters".into(),
+            serde_json::json!({ "message_id": reply, "allow_sending_without_reply": true }),
+        );
+    }
+    fields
+}
+
+fn extend_object(body: &mut serde_json::Value, extra: &serde_json::Map<String, serde_json::Value>) {
+    if let Some(object) = body.as_object_mut() {
+        object.extend(extra.clone());
+    }
+}
+
+/// Keep a received file name safe to use as a single path component.
+fn safe_file_name(name: &str) -> String {
+    let cleaned: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.');
+    if cleaned.is_empty() {
+        "file".into()
+    } else {
+        cleaned.to_string()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -866,4 +1388,166 @@ mod tests {
         // Should not panic
         assert!(result.is_err());
     }
+
+    #[test]
+    fn telegram_parse_text_message() {
+        let msg = serde_json::json!({ "text": "hello" });
+        let parsed = parse_message(&msg, 1024);
+        assert_eq!(parsed.text, "hello");
+        assert!(parsed.files.is_empty());
+    }
+
+    #[test]
+    fn telegram_parse_photo_picks_largest_size_within_limit() {
+        let msg = serde_json::json!({
+            "caption": "what is this?",
+            "photo": [
+                { "file_id": "small", "width": 90, "height": 90, "file_size": 1000 },
+                { "file_id": "medium", "width": 320, "height": 320, "file_size": 20000 },
+                { "file_id": "large", "width": 1280, "height": 1280, "file_size": 900_000 }
+            ]
+        });
+        let parsed = parse_message(&msg, 100_000);
+        assert_eq!(parsed.text, "what is this?");
+        assert_eq!(parsed.files.len(), 1);
+        assert_eq!(parsed.files[0].file_id, "medium");
+        assert_eq!(parsed.files[0].kind, AttachmentKind::Image);
+        assert_eq!(parsed.files[0].mime_type.as_deref(), Some("image/jpeg"));
+    }
+
+    #[test]
+    fn telegram_parse_document_and_voice() {
+        let msg = serde_json::json!({
+            "document": { "file_id": "doc", "file_name": "notes.md", "mime_type": "text/markdown", "file_size": 10 },
+            "voice": { "file_id": "v", "mime_type": "audio/ogg", "file_size": 10 }
+        });
+        let parsed = parse_message(&msg, 1024);
+        assert!(parsed.text.is_empty());
+        let kinds: Vec<_> = parsed.files.iter().map(|f| f.kind).collect();
+        assert_eq!(kinds, [AttachmentKind::Document, AttachmentKind::Voice]);
+        assert_eq!(parsed.files[0].file_name.as_deref(), Some("notes.md"));
+    }
+
+    #[test]
+    fn telegram_parse_oversized_file_becomes_note() {
+        let msg = serde_json::json!({
+            "caption": "report",
+            "document": { "file_id": "doc", "file_name": "big.pdf", "file_size": 5000 }
+        });
+        let parsed = parse_message(&msg, 1024);
+        assert!(parsed.files.is_empty());
+        assert!(parsed.text.starts_with("report\n[Document big.pdf skipped"));
+    }
+
+    #[test]
+    fn telegram_parse_location_and_venue() {
+        let msg = serde_json::json!({ "location": { "latitude": 38.7, "longitude": -9.1 } });
+        assert_eq!(parse_message(&msg, 0).text, "📍 Location: 38.7, -9.1");
+
+        let msg = serde_json::json!({
+            "venue": {
+                "location": { "latitude": 38.7, "longitude": -9.1 },
+                "title": "Cafe",
+                "address": "Rua Augusta 1"
+            },
+            "location": { "latitude": 38.7, "longitude": -9.1 }
+        });
+        assert_eq!(
+            parse_message(&msg, 0).text,
+            "📍 Location: Cafe, Rua Augusta 1 (38.7, -9.1)"
+        );
+    }
+
+    #[test]
+    fn telegram_safe_file_name_strips_paths() {
+        assert_eq!(safe_file_name("../../etc/passwd"), "_.._etc_passwd");
+        assert_eq!(safe_file_name("my report.pdf"), "my_report.pdf");
+        assert_eq!(safe_file_name(""), "file");
+    }
+
+    #[test]
+    fn telegram_rich_fields_carry_thread_and_reply() {
+        let message = RichMessage {
+            thread_id: Some("7".into()),
+            reply_to: Some("42".into()),
+            ..RichMessage::text("hi")
+        };
+        let fields = rich_fields(&message);
+        assert_eq!(fields["message_thread_id"], 7);
+        assert_eq!(fields["reply_parameters"]["message_id"], 42);
+
+        assert!(rich_fields(&RichMessage::text("hi")).is_empty());
+    }
+
+    #[test]
+    fn telegram_supports_rich_messages() {
+        let ch = TelegramChannel::new("t".into(), vec![]);
+        let caps = ch.capabilities();
+        assert!(caps.attachments && caps.replies && caps.threads);
+    }
+
+    fn telegram_config(secret: Option<&str>) -> TelegramConfig {
+        TelegramConfig {
+            bot_token: "123:ABC".into(),
+            allowed_users: vec!["*".into()],
+            mode: "webhook".into(),
+            webhook_url: None,
+            webhook_secret: secret.map(ToString::to_string),
+        }
+    }
+
+    #[test]
+    fn telegram_webhook_secret_is_stable_and_url_safe() {
+        let derived = webhook_secret(&telegram_config(None));
+        assert_eq!(derived, webhook_secret(&telegram_config(None)));
+        assert_eq!(derived.len(), 32);
+        assert!(derived.chars().all(|c| c.is_ascii_hexdigit()));
+        assert!(!derived.contains("123:ABC"));
+
+        assert_eq!(webhook_secret(&telegram_config(Some("s3cret"))), "s3cret");
+        assert_eq!(webhook_secret(&telegram_config(Some("  "))), derived);
+    }
+
+    #[test]
+    fn telegram_webhook_url_joins_base_and_secret() {
+        assert_eq!(
+            webhook_url("https://bot.example.com/", "abc"),
+            "https://bot.example.com/telegram/abc"
+        );
+    }
+
+    #[tokio::test]
+    async fn telegram_webhook_updates_join_the_message_bus() {
+        let ch = TelegramChannel::new("123:ABC".into(), vec!["*".into()]).with_webhook(true);
+        let (tx, mut rx) = tokio::sync::mpsc::channel(4);
+        let listener = tokio::spawn(async move { ch.listen(tx).await });
+
+        let update = serde_json::json!({
+            "update_id": 1,
+            "message": {
+                "message_id": 5,
+                "from": { "id": 42, "username": "alice" },
+                "chat": { "id": 42 },
+                "text": "hello from webhook"
+            }
+        });
+        let mut delivered = false;
+        for _ in 0..50 {
+            if deliver_webhook_update(update.clone()) {
+                delivered = true;
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert!(delivered);
+
+        let msg = tokio::time::timeout(std::time::Duration::from_secs(10), rx.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.content, "hello from webhook");
+        assert_eq!(msg.sender, "42");
+        assert_eq!(msg.message_id.as_deref(), Some("5"));
+        listener.abort();
+    }
 }
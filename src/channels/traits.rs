@@ -1,15 +1,110 @@
 use async_trait::async_trait;
+use std::fmt::Write;
+use std::path::PathBuf;
 
 /// A message received from or sent to a channel
 #[derive(Debug, Clone)]
 pub struct ChannelMessage {
     pub id: String,
+    /// Reply target: a chat, channel or webhook on most platforms
     pub sender: String,
+    /// Platform id of the person who wrote the message. Equals `sender` in
+    /// direct messages; differs in group chats and shared channels.
+    pub author: String,
     pub content: String,
+    /// Media downloaded with the message (photos, documents, voice notes, ...)
+    pub attachments: Vec<Attachment>,
+    /// Platform id of this message, the target when replying to it
+    pub message_id: Option<String>,
+    /// Thread or topic the message belongs to (Slack `thread_ts`, Matrix
+    /// thread root, Telegram forum topic, email `References` root)
+    pub thread_id: Option<String>,
+    /// Platform id of the message this one replies to
+    pub reply_to: Option<String>,
+    /// Original platform payload, for channel-specific handling
+    pub raw: Option<serde_json::Value>,
     pub channel: String,
     pub timestamp: u64,
 }
 
+/// What kind of media an attachment holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttachmentKind {
+    Image,
+    Document,
+    Audio,
+    Voice,
+    Video,
+}
+
+/// A file received with a channel message, saved to local disk
+#[derive(Debug, Clone)]
+pub struct Attachment {
+    pub kind: AttachmentKind,
+    pub path: PathBuf,
+    pub file_name: Option<String>,
+    pub mime_type: Option<String>,
+    pub size: u64,
+}
+
+/// An outgoing message with files, a reply target or a thread
+#[derive(Debug, Clone, Default)]
+pub struct RichMessage {
+    pub text: String,
+    /// Local files to upload with the message
+    pub attachments: Vec<Attachment>,
+    /// Platform message id to reply to (quote)
+    pub reply_to: Option<String>,
+    /// Thread or topic to post into
+    pub thread_id: Option<String>,
+}
+
+impl RichMessage {
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Self::default()
+        }
+    }
+
+    /// A response to `msg`, posted into the thread it came from.
+    pub fn reply(msg: &ChannelMessage, text: impl Into<String>) -> Self {
+        Self {
+            thread_id: msg.thread_id.clone(),
+            ..Self::text(text)
+        }
+    }
+
+    /// Plain text for channels without rich support: the text followed by
+    /// one line per attachment that could not be sent.
+    pub fn fallback_text(&self) -> String {
+        let mut text = self.text.clone();
+        for attachment in &self.attachments {
+            let name = attachment
+                .file_name
+                .as_deref()
+                .or_else(|| attachment.path.file_name().and_then(|n| n.to_str()))
+                .unwrap_or("file");
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            let _ = write!(text, "[Attachment not sent: {name}]");
+        }
+        text
+    }
+}
+
+/// What a channel can do beyond plain text in `send_rich`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChannelCapabilities {
+    /// Uploads files and images
+    pub attachments: bool,
+    /// Quotes a specific message (`RichMessage::reply_to`)
+    pub replies: bool,
+    /// Posts into threads or topics (`RichMessage::thread_id`)
+    pub threads: bool,
+}
+
 /// Core channel trait — implement for any messaging platform
 #[async_trait]
 pub trait Channel: Send + Sync {
@@ -22,6 +117,17 @@ pub trait Channel: Send + Sync {
     /// Start listening for incoming messages (long-running)
     async fn listen(&self, tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()>;
 
+    /// Rich features `send_rich` supports on this channel
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities::default()
+    }
+
+    /// Send a message with attachments, a reply target or a thread. Channels
+    /// without rich support send `RichMessage::fallback_text` instead.
+    async fn send_rich(&self, message: &RichMessage, recipient: &str) -> anyhow::Result<()> {
+        self.send(&message.fallback_text(), recipient).await
+    }
+
     /// Check if channel is healthy
     async fn health_check(&self) -> bool {
         true
@@ -46,7 +46,7 @@ impl WeComChannel {
             token,
             crypto,
             allowed_users,
-            client: reqwest::Client::new(),
+            client: crate::http::client("wecom", crate::http::Defaults::NONE),
             access_token_cache: Arc::new(Mutex::new(None)),
         })
     }
@@ -146,7 +146,7 @@ impl WeComChannel {
         let encrypted = self.crypto.encrypt(content)?;
 
         // Build signature
-        let mut items = vec![&self.token, timestamp, nonce, &encrypted];
+        let mut items = [&self.token, timestamp, nonce, &encrypted];
         items.sort_unstable();
         let concat = items.join("");
         let mut hasher = Sha256::new();
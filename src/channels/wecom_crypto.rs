@@ -1,4 +1,4 @@
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
 use sha2::{Digest, Sha256};
 
@@ -17,7 +17,7 @@ impl WeComCrypto {
         // Decode the base64 key (43 chars + '=' padding -> 32 bytes)
         // WeCom uses base64 without padding, so add it if needed
         let mut padded_key = encoding_aes_key.to_string();
-        while padded_key.len() % 4 != 0 {
+        while !padded_key.len().is_multiple_of(4) {
             padded_key.push('=');
         }
 
@@ -111,7 +111,8 @@ impl WeComCrypto {
 
         // 2. Message length (big-endian u32)
         let msg_bytes = plaintext.as_bytes();
-        plaintext_bytes.extend_from_slice(&(msg_bytes.len() as u32).to_be_bytes());
+        let msg_len = u32::try_from(msg_bytes.len()).context("Message too large to encrypt")?;
+        plaintext_bytes.extend_from_slice(&msg_len.to_be_bytes());
 
         // 3. Message
         plaintext_bytes.extend_from_slice(msg_bytes);
@@ -147,8 +148,8 @@ impl WeComCrypto {
         encrypted: &str,
         signature: &str,
     ) -> Result<bool> {
-        let mut items = vec![token, timestamp, nonce, encrypted];
-        items.sort();
+        let mut items = [token, timestamp, nonce, encrypted];
+        items.sort_unstable();
 
         let concat = items.join("");
         let mut hasher = Sha256::new();
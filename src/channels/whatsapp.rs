@@ -1,5 +1,8 @@
-use super::traits::{Channel, ChannelMessage};
+use super::traits::{
+    Attachment, AttachmentKind, Channel, ChannelCapabilities, ChannelMessage, RichMessage,
+};
 use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
 use uuid::Uuid;
 
 /// `WhatsApp` channel — uses `WhatsApp` Business Cloud API
@@ -28,7 +31,7 @@ impl WhatsAppChannel {
             phone_number_id,
             verify_token,
             allowed_numbers,
-            client: reqwest::Client::new(),
+            client: crate::http::client("whatsapp", crate::http::Defaults::NONE),
         }
     }
 
@@ -119,8 +122,21 @@ impl WhatsAppChannel {
 
                     messages.push(ChannelMessage {
                         id: Uuid::new_v4().to_string(),
-                        sender: normalized_from,
+                        sender: normalized_from.clone(),
+                        author: normalized_from,
                         content,
+                        attachments: Vec::new(),
+                        message_id: msg
+                            .get("id")
+                            .and_then(|i| i.as_str())
+                            .map(ToString::to_string),
+                        thread_id: None,
+                        reply_to: msg
+                            .get("context")
+                            .and_then(|c| c.get("id"))
+                            .and_then(|i| i.as_str())
+                            .map(ToString::to_string),
+                        raw: Some(msg.clone()),
                         channel: "whatsapp".to_string(),
                         timestamp,
                     });
@@ -130,41 +146,21 @@ impl WhatsAppChannel {
 
         messages
     }
-}
 
-#[async_trait]
-impl Channel for WhatsAppChannel {
-    fn name(&self) -> &str {
-        "whatsapp"
-    }
-
-    async fn send(&self, message: &str, recipient: &str) -> anyhow::Result<()> {
-        // WhatsApp Cloud API: POST to /v18.0/{phone_number_id}/messages
-        let url = format!(
+    fn messages_url(&self) -> String {
+        format!(
             "https://graph.facebook.com/v18.0/{}/messages",
             self.phone_number_id
-        );
-
-        // Normalize recipient (remove leading + if present for API)
-        let to = recipient.strip_prefix('+').unwrap_or(recipient);
-
-        let body = serde_json::json!({
-            "messaging_product": "whatsapp",
-            "recipient_type": "individual",
-            "to": to,
-            "type": "text",
-            "text": {
-                "preview_url": false,
-                "body": message
-            }
-        });
+        )
+    }
 
+    async fn post_message(&self, body: &serde_json::Value) -> anyhow::Result<()> {
         let resp = self
             .client
-            .post(&url)
+            .post(self.messages_url())
             .header("Authorization", format!("Bearer {}", self.access_token))
             .header("Content-Type", "application/json")
-            .json(&body)
+            .json(body)
             .send()
             .await?;
 
@@ -178,6 +174,137 @@ impl Channel for WhatsAppChannel {
         Ok(())
     }
 
+    /// Upload a file to the media endpoint and return its media id.
+    async fn upload_media(
+        &self,
+        attachment: &Attachment,
+        file_name: &str,
+    ) -> anyhow::Result<String> {
+        let mime = attachment
+            .mime_type
+            .clone()
+            .unwrap_or_else(|| "application/octet-stream".into());
+        let bytes = tokio::fs::read(&attachment.path).await?;
+        let form = Form::new()
+            .text("messaging_product", "whatsapp")
+            .text("type", mime.clone())
+            .part(
+                "file",
+                Part::bytes(bytes)
+                    .file_name(file_name.to_string())
+                    .mime_str(&mime)?,
+            );
+
+        let resp = self
+            .client
+            .post(format!(
+                "https://graph.facebook.com/v18.0/{}/media",
+                self.phone_number_id
+            ))
+            .header("Authorization", format!("Bearer {}", self.access_token))
+            .multipart(form)
+            .send()
+            .await?;
+
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let error_body = resp.text().await.unwrap_or_default();
+            tracing::error!("WhatsApp media upload failed: {status} — {error_body}");
+            anyhow::bail!("WhatsApp media upload error: {status}");
+        }
+
+        let body: serde_json::Value = resp.json().await?;
+        body.get("id")
+            .and_then(|i| i.as_str())
+            .map(ToString::to_string)
+            .ok_or_else(|| anyhow::anyhow!("WhatsApp media upload returned no id"))
+    }
+}
+
+/// Cloud API message body. `payload` is the `text` object or a media object
+/// keyed by `kind`; `reply_to` quotes an earlier message.
+fn message_body(
+    to: &str,
+    kind: &str,
+    payload: serde_json::Value,
+    reply_to: Option<&str>,
+) -> serde_json::Value {
+    // Normalize recipient (remove leading + if present for API)
+    let to = to.strip_prefix('+').unwrap_or(to);
+    let mut body = serde_json::json!({
+        "messaging_product": "whatsapp",
+        "recipient_type": "individual",
+        "to": to,
+        "type": kind,
+    });
+    body[kind] = payload;
+    if let Some(id) = reply_to {
+        body["context"] = serde_json::json!({ "message_id": id });
+    }
+    body
+}
+
+#[async_trait]
+impl Channel for WhatsAppChannel {
+    fn name(&self) -> &str {
+        "whatsapp"
+    }
+
+    async fn send(&self, message: &str, recipient: &str) -> anyhow::Result<()> {
+        self.send_rich(&RichMessage::text(message), recipient).await
+    }
+
+    fn capabilities(&self) -> ChannelCapabilities {
+        ChannelCapabilities {
+            attachments: true,
+            replies: true,
+            threads: false,
+        }
+    }
+
+    async fn send_rich(&self, message: &RichMessage, recipient: &str) -> anyhow::Result<()> {
+        // WhatsApp Cloud API: POST to /v18.0/{phone_number_id}/messages
+        let mut reply_to = message.reply_to.as_deref();
+
+        if !message.text.is_empty() || message.attachments.is_empty() {
+            let text = serde_json::json!({
+                "preview_url": false,
+                "body": message.text
+            });
+            self.post_message(&message_body(recipient, "text", text, reply_to.take()))
+                .await?;
+        }
+
+        for attachment in &message.attachments {
+            let file_name = attachment
+                .file_name
+                .clone()
+                .or_else(|| {
+                    attachment
+                        .path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().into_owned())
+                })
+                .unwrap_or_else(|| "file".into());
+            let media_id = self.upload_media(attachment, &file_name).await?;
+            let (kind, payload) = match attachment.kind {
+                AttachmentKind::Image => ("image", serde_json::json!({ "id": media_id })),
+                AttachmentKind::Audio | AttachmentKind::Voice => {
+                    ("audio", serde_json::json!({ "id": media_id }))
+                }
+                AttachmentKind::Video => ("video", serde_json::json!({ "id": media_id })),
+                AttachmentKind::Document => (
+                    "document",
+                    serde_json::json!({ "id": media_id, "filename": file_name }),
+                ),
+            };
+            self.post_message(&message_body(recipient, kind, payload, reply_to.take()))
+                .await?;
+        }
+
+        Ok(())
+    }
+
     async fn listen(&self, _tx: tokio::sync::mpsc::Sender<ChannelMessage>) -> anyhow::Result<()> {
         // WhatsApp uses webhooks (push-based), not polling.
         // Messages are received via the gateway's /whatsapp endpoint.
@@ -1107,4 +1234,41 @@ mod tests {
             "<script>alert('xss')</script> & \"quotes\" 'apostrophe'"
         );
     }
+
+    #[test]
+    fn whatsapp_message_body_quotes_reply() {
+        let body = message_body(
+            "+1234567890",
+            "text",
+            serde_json::json!({ "body": "hi" }),
+            Some("wamid.abc"),
+        );
+        assert_eq!(body["to"], "1234567890");
+        assert_eq!(body["type"], "text");
+        assert_eq!(body["text"]["body"], "hi");
+        assert_eq!(body["context"]["message_id"], "wamid.abc");
+
+        let body = message_body("1", "image", serde_json::json!({ "id": "m1" }), None);
+        assert_eq!(body["image"]["id"], "m1");
+        assert!(body.get("context").is_none());
+    }
+
+    #[test]
+    fn whatsapp_parse_keeps_ids_and_raw_payload() {
+        let ch = make_channel();
+        let payload = serde_json::json!({
+            "entry": [{ "changes": [{ "value": { "messages": [{
+                "from": "1234567890",
+                "id": "wamid.2",
+                "context": { "id": "wamid.1" },
+                "timestamp": "1",
+                "type": "text",
+                "text": { "body": "hi" }
+            }]}}]}]
+        });
+        let msgs = ch.parse_webhook_payload(&payload);
+        assert_eq!(msgs[0].message_id.as_deref(), Some("wamid.2"));
+        assert_eq!(msgs[0].reply_to.as_deref(), Some("wamid.1"));
+        assert_eq!(msgs[0].raw.as_ref().unwrap()["type"], "text");
+    }
 }
@@ -1,9 +1,10 @@
 pub mod schema;
 
 pub use schema::{
-    AutonomyConfig, BrowserConfig, ChannelsConfig, ComposioConfig, Config, DelegateAgentConfig,
-    DingTalkConfig, DiscordConfig, DockerRuntimeConfig, GatewayConfig, HeartbeatConfig,
-    IMessageConfig, IdentityConfig, LarkConfig, MatrixConfig, MemoryConfig, ModelRouteConfig,
-    ObservabilityConfig, ReliabilityConfig, RuntimeConfig, SecretsConfig, SlackConfig,
-    TelegramConfig, TunnelConfig, WeComConfig, WebhookConfig,
+    AutonomyConfig, AzureOpenAiConfig, BrowserConfig, ChannelsConfig, ComposioConfig, Config,
+    ConsolidationConfig, DelegateAgentConfig, DiscordConfig, DockerRuntimeConfig, ExtractionConfig,
+    GatewayConfig, GraphConfig, HeartbeatConfig, HttpClientConfig, HttpConfig, IMessageConfig,
+    IdentityConfig, MatrixConfig, MediaConfig, MemoryConfig, ModelRouteConfig, ObservabilityConfig,
+    OllamaConfig, RankingConfig, ReasoningConfig, ReliabilityConfig, ResponseCacheConfig,
+    RuntimeConfig, SecretsConfig, SlackConfig, TelegramConfig, TunnelConfig, WebhookConfig,
 };
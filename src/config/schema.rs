@@ -38,12 +38,34 @@ pub struct Config {
     #[serde(default)]
     pub model_routes: Vec<ModelRouteConfig>,
 
+    #[serde(default)]
+    pub reasoning: ReasoningConfig,
+
+    /// Azure OpenAI resource and deployment mapping, used by the `azure` provider.
+    #[serde(default)]
+    pub azure_openai: AzureOpenAiConfig,
+
+    #[serde(default)]
+    pub ollama: OllamaConfig,
+
+    #[serde(default)]
+    pub response_cache: ResponseCacheConfig,
+
+    /// Outbound HTTP settings (proxy, CA bundle, timeouts, user agent)
+    /// shared by providers, channels and tools.
+    #[serde(default)]
+    pub http: HttpConfig,
+
     #[serde(default)]
     pub heartbeat: HeartbeatConfig,
 
     #[serde(default)]
     pub channels_config: ChannelsConfig,
 
+    /// Inbound media handling for channels (downloads, vision, transcription).
+    #[serde(default)]
+    pub media: MediaConfig,
+
     #[serde(default)]
     pub memory: MemoryConfig,
 
@@ -292,7 +314,7 @@ pub struct MemoryConfig {
     /// For sqlite backend: prune conversation rows older than this many days
     #[serde(default = "default_conversation_retention_days")]
     pub conversation_retention_days: u32,
-    /// Embedding provider: "none" | "openai" | "custom:URL"
+    /// Embedding provider: "none" | "openai" | "custom:URL" | "local" | "ollama" | "ollama:URL"
     #[serde(default = "default_embedding_provider")]
     pub embedding_provider: String,
     /// Embedding model name (e.g. "text-embedding-3-small")
@@ -313,6 +335,209 @@ pub struct MemoryConfig {
     /// Max tokens per chunk for document splitting
     #[serde(default = "default_chunk_size")]
     pub chunk_max_tokens: usize,
+    /// Vector recall index for the sqlite backend: "hnsw" | "none" (full scan)
+    #[serde(default = "default_vector_index")]
+    pub vector_index: String,
+    /// Memory scope for channel messages: "user" (per sender) | "channel" | "global"
+    #[serde(default = "default_channel_scope")]
+    pub channel_scope: String,
+    /// LLM consolidation of conversation/daily entries into core facts
+    #[serde(default)]
+    pub consolidation: ConsolidationConfig,
+    /// Entity/relation extraction into the knowledge graph (sqlite backend)
+    #[serde(default)]
+    pub graph: GraphConfig,
+    /// What `auto_save` keeps from each turn: extracted facts and, optionally, raw text
+    #[serde(default)]
+    pub extraction: ExtractionConfig,
+    /// Recency, importance, category and access weighting applied to recall scores
+    #[serde(default)]
+    pub ranking: RankingConfig,
+    /// Encrypt memory content in `brain.db` with a key derived from `.secret_key`
+    #[serde(default)]
+    pub encrypt: bool,
+}
+
+/// Recall re-ranking (`[memory.ranking]`). Relevance from hybrid search is
+/// multiplied by the category boost and by `1 + Σ weight × signal`, where each
+/// signal is in [0, 1].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankingConfig {
+    /// Age in days at which the recency signal halves; 0 disables decay (default: 30)
+    #[serde(default = "default_recency_half_life_days")]
+    pub recency_half_life_days: f64,
+    /// Weight of the recency signal (default: 0.3)
+    #[serde(default = "default_recency_weight")]
+    pub recency_weight: f64,
+    /// Weight of stored importance; unset importance counts as 0.5 (default: 0.2)
+    #[serde(default = "default_importance_weight")]
+    pub importance_weight: f64,
+    /// Weight of how often an entry has been recalled (default: 0.1)
+    #[serde(default = "default_access_weight")]
+    pub access_weight: f64,
+    /// Score multiplier per category; unlisted categories use 1.0
+    #[serde(default = "default_category_boosts")]
+    pub category_boosts: HashMap<String, f64>,
+}
+
+fn default_recency_half_life_days() -> f64 {
+    30.0
+}
+
+fn default_recency_weight() -> f64 {
+    0.3
+}
+
+fn default_importance_weight() -> f64 {
+    0.2
+}
+
+fn default_access_weight() -> f64 {
+    0.1
+}
+
+fn default_category_boosts() -> HashMap<String, f64> {
+    HashMap::from([
+        ("core".into(), 1.2),
+        ("daily".into(), 1.0),
+        ("conversation".into(), 0.8),
+    ])
+}
+
+impl Default for RankingConfig {
+    fn default() -> Self {
+        Self {
+            recency_half_life_days: default_recency_half_life_days(),
+            recency_weight: default_recency_weight(),
+            importance_weight: default_importance_weight(),
+            access_weight: default_access_weight(),
+            category_boosts: default_category_boosts(),
+        }
+    }
+}
+
+/// Periodic LLM consolidation (`[memory.consolidation]`): summarizes recent
+/// conversation and daily entries into concise core facts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsolidationConfig {
+    /// Run consolidation from the heartbeat (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Minimum hours between runs (default: 24)
+    #[serde(default = "default_consolidation_interval_hours")]
+    pub interval_hours: u32,
+    /// Model used for summarizing; defaults to `default_model`. A cheap model is enough.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Embedding similarity at which a new fact merges into an existing one (default: 0.9)
+    #[serde(default = "default_consolidation_merge_similarity")]
+    pub merge_similarity: f64,
+    /// Maximum source entries summarized per run (default: 40)
+    #[serde(default = "default_consolidation_max_entries")]
+    pub max_entries: usize,
+}
+
+fn default_consolidation_interval_hours() -> u32 {
+    24
+}
+
+fn default_consolidation_merge_similarity() -> f64 {
+    0.9
+}
+
+fn default_consolidation_max_entries() -> usize {
+    40
+}
+
+impl Default for ConsolidationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: default_consolidation_interval_hours(),
+            model: None,
+            merge_similarity: default_consolidation_merge_similarity(),
+            max_entries: default_consolidation_max_entries(),
+        }
+    }
+}
+
+/// Knowledge graph extraction (`[memory.graph]`): a model reads new or
+/// changed memories into entities, relations and observations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphConfig {
+    /// Run extraction from the heartbeat (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Minimum hours between runs (default: 6)
+    #[serde(default = "default_graph_interval_hours")]
+    pub interval_hours: u32,
+    /// Model used for extraction; defaults to `default_model`
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Maximum memories read per run (default: 40)
+    #[serde(default = "default_graph_max_entries")]
+    pub max_entries: usize,
+}
+
+fn default_graph_interval_hours() -> u32 {
+    6
+}
+
+fn default_graph_max_entries() -> usize {
+    40
+}
+
+impl Default for GraphConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_hours: default_graph_interval_hours(),
+            model: None,
+            max_entries: default_graph_max_entries(),
+        }
+    }
+}
+
+/// Per-turn fact extraction (`[memory.extraction]`): with `auto_save` on, a
+/// model reads each exchange and stores durable facts and preferences as core
+/// memories under stable keys.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractionConfig {
+    /// Extract facts after each turn (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Model used for extraction; defaults to the consolidation model, then the chat model
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Also store raw messages under per-turn keys (default: false). Raw
+    /// logging is what `auto_save` does when extraction is disabled.
+    #[serde(default)]
+    pub raw_log: bool,
+}
+
+impl ExtractionConfig {
+    /// Whether `auto_save` stores raw messages.
+    pub fn logs_raw(&self) -> bool {
+        self.raw_log || !self.enabled
+    }
+}
+
+impl Default for ExtractionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            model: None,
+            raw_log: false,
+        }
+    }
+}
+
+fn default_vector_index() -> String {
+    "hnsw".into()
+}
+
+fn default_channel_scope() -> String {
+    "user".into()
 }
 
 fn default_embedding_provider() -> String {
@@ -365,6 +590,13 @@ impl Default for MemoryConfig {
             keyword_weight: default_keyword_weight(),
             embedding_cache_size: default_cache_size(),
             chunk_max_tokens: default_chunk_size(),
+            vector_index: default_vector_index(),
+            channel_scope: default_channel_scope(),
+            consolidation: ConsolidationConfig::default(),
+            graph: GraphConfig::default(),
+            extraction: ExtractionConfig::default(),
+            ranking: RankingConfig::default(),
+            encrypt: false,
         }
     }
 }
@@ -623,6 +855,7 @@ impl Default for ReliabilityConfig {
 /// hint = "reasoning"
 /// provider = "openrouter"
 /// model = "anthropic/claude-opus-4-20250514"
+/// reasoning_effort = "high"
 ///
 /// [[model_routes]]
 /// hint = "fast"
@@ -642,6 +875,260 @@ pub struct ModelRouteConfig {
     /// Optional API key override for this route's provider
     #[serde(default)]
     pub api_key: Option<String>,
+    /// Reasoning effort for thinking models on this route
+    /// (`minimal`, `low`, `medium`, `high`). Ignored by providers without support.
+    #[serde(default)]
+    pub reasoning_effort: Option<String>,
+}
+
+// ── Reasoning output ─────────────────────────────────────────────
+
+/// How reasoning-model output (`<think>` blocks, `reasoning_content`) is surfaced.
+/// Reasoning is always stripped before replies are delivered to channels.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReasoningConfig {
+    /// Print the model's reasoning in the CLI agent before each answer (default: false)
+    #[serde(default)]
+    pub show_in_cli: bool,
+}
+
+// ── Azure OpenAI ─────────────────────────────────────────────────
+
+/// Azure OpenAI settings. Azure exposes each model as a named deployment on a
+/// per-resource endpoint, so these cannot be encoded in a `custom:` URL.
+///
+/// ```toml
+/// [azure_openai]
+/// resource = "contoso"
+/// api_version = "2024-10-21"
+///
+/// [azure_openai.deployments]
+/// "gpt-4o" = "prod-gpt4o"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AzureOpenAiConfig {
+    /// Resource name; the endpoint becomes `https://{resource}.openai.azure.com`
+    #[serde(default)]
+    pub resource: Option<String>,
+    /// Full endpoint URL (overrides `resource`, e.g. for API gateways)
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    /// `api-version` query parameter sent with every request
+    #[serde(default = "default_azure_api_version")]
+    pub api_version: String,
+    /// Model name → deployment name. Unmapped models use the model name.
+    #[serde(default)]
+    pub deployments: HashMap<String, String>,
+    /// Resource key; takes precedence over the top-level `api_key`, then
+    /// `AZURE_OPENAI_API_KEY` and the generic key env vars
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+fn default_azure_api_version() -> String {
+    "2024-10-21".into()
+}
+
+impl Default for AzureOpenAiConfig {
+    fn default() -> Self {
+        Self {
+            resource: None,
+            endpoint: None,
+            api_version: default_azure_api_version(),
+            deployments: HashMap::new(),
+            api_key: None,
+        }
+    }
+}
+
+// ── Ollama ───────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OllamaConfig {
+    /// Ollama API base URL (default: `OLLAMA_HOST`, then `http://localhost:11434`)
+    #[serde(default)]
+    pub base_url: Option<String>,
+}
+
+// ── Media ────────────────────────────────────────────────────────
+
+/// How channels handle photos, documents and voice notes sent by users.
+///
+/// Files are saved under `{workspace}/media/<channel>/`. Images go to a
+/// vision-capable model, text documents are ingested into memory and voice
+/// notes are transcribed before the agent sees the message.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaConfig {
+    /// Largest file downloaded from a channel, in bytes (default: 20 MB,
+    /// the Telegram Bot API limit). Larger files are skipped with a note.
+    #[serde(default = "default_media_max_file_bytes")]
+    pub max_file_bytes: u64,
+    /// Model used for turns that carry images. Unset = the default model.
+    #[serde(default)]
+    pub vision_model: Option<String>,
+    /// Ingest received text documents into memory (default: true)
+    #[serde(default = "default_true")]
+    pub ingest_documents: bool,
+    /// Speech-to-text backend for voice notes: "none", "openai", "groq" or
+    /// "custom:<base url>" (any OpenAI-compatible `/audio/transcriptions`).
+    #[serde(default = "default_transcription_provider")]
+    pub transcription_provider: String,
+    #[serde(default = "default_transcription_model")]
+    pub transcription_model: String,
+    /// API key for transcription. Falls back to `OPENAI_API_KEY` /
+    /// `GROQ_API_KEY`, then the top-level `api_key`.
+    #[serde(default)]
+    pub transcription_api_key: Option<String>,
+}
+
+fn default_media_max_file_bytes() -> u64 {
+    20 * 1024 * 1024
+}
+
+fn default_transcription_provider() -> String {
+    "none".into()
+}
+
+fn default_transcription_model() -> String {
+    "whisper-1".into()
+}
+
+impl Default for MediaConfig {
+    fn default() -> Self {
+        Self {
+            max_file_bytes: default_media_max_file_bytes(),
+            vision_model: None,
+            ingest_documents: true,
+            transcription_provider: default_transcription_provider(),
+            transcription_model: default_transcription_model(),
+            transcription_api_key: None,
+        }
+    }
+}
+
+// ── Response cache ───────────────────────────────────────────────
+
+/// Optional SQLite cache for provider responses (`{workspace}/cache/responses.db`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResponseCacheConfig {
+    /// Enable the response cache (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seconds a cached response stays valid; 0 = never expires
+    #[serde(default = "default_response_cache_ttl_secs")]
+    pub ttl_secs: u64,
+    /// Maximum cached responses before the oldest are evicted
+    #[serde(default = "default_response_cache_max_entries")]
+    pub max_entries: usize,
+    /// Also cache requests with temperature > 0 (default: false — such
+    /// requests are expected to vary). Only temperature-0 requests are cached
+    /// otherwise, so with the default `default_temperature = 0.7` agent,
+    /// heartbeat and cron turns need this (or temperature 0) to hit.
+    #[serde(default)]
+    pub cache_nonzero_temperature: bool,
+    /// Also cache turns that carry tool definitions; the tool names and
+    /// schemas are part of the key (default: false)
+    #[serde(default)]
+    pub cache_tool_turns: bool,
+    /// Cosine similarity (0.0–1.0) above which a semantically similar question
+    /// reuses a cached answer. Uses the `[memory]` embedding provider.
+    /// Unset = exact-match only.
+    #[serde(default)]
+    pub similarity_threshold: Option<f64>,
+}
+
+fn default_response_cache_ttl_secs() -> u64 {
+    3600
+}
+
+fn default_response_cache_max_entries() -> usize {
+    1000
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_secs: default_response_cache_ttl_secs(),
+            max_entries: default_response_cache_max_entries(),
+            cache_nonzero_temperature: false,
+            cache_tool_turns: false,
+            similarity_threshold: None,
+        }
+    }
+}
+
+// ── HTTP client ──────────────────────────────────────────────────
+
+/// Settings for one outbound HTTP client. Unset fields fall back to the
+/// `[http]` defaults, then to the component's built-in values.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpClientConfig {
+    /// Proxy URL for all requests (`http://`, `https://` or `socks5://`).
+    /// WebSocket channels (Discord, DingTalk, Slack Socket Mode) tunnel
+    /// through `http://` proxies only.
+    #[serde(default)]
+    pub proxy: Option<String>,
+    /// Comma-separated hosts that bypass the proxy (e.g. "localhost,127.0.0.1")
+    #[serde(default)]
+    pub no_proxy: Option<String>,
+    /// PEM file with extra root certificates (e.g. a corporate MITM proxy CA)
+    #[serde(default)]
+    pub ca_bundle: Option<String>,
+    /// TCP connect timeout in seconds
+    #[serde(default)]
+    pub connect_timeout_secs: Option<u64>,
+    /// Whole-request timeout in seconds; long-polling channels add their
+    /// poll duration on top
+    #[serde(default)]
+    pub timeout_secs: Option<u64>,
+    /// `User-Agent` header value
+    #[serde(default)]
+    pub user_agent: Option<String>,
+}
+
+impl HttpClientConfig {
+    /// Layer `other` on top of `self`: fields set in `other` win.
+    pub fn merged_with(&self, other: &HttpClientConfig) -> HttpClientConfig {
+        HttpClientConfig {
+            proxy: other.proxy.clone().or_else(|| self.proxy.clone()),
+            no_proxy: other.no_proxy.clone().or_else(|| self.no_proxy.clone()),
+            ca_bundle: other.ca_bundle.clone().or_else(|| self.ca_bundle.clone()),
+            connect_timeout_secs: other.connect_timeout_secs.or(self.connect_timeout_secs),
+            timeout_secs: other.timeout_secs.or(self.timeout_secs),
+            user_agent: other.user_agent.clone().or_else(|| self.user_agent.clone()),
+        }
+    }
+}
+
+/// `[http]` section. Top-level keys apply to every client; `[http.overrides.<component>]`
+/// tables override them for one component (a provider, channel or tool name such as
+/// `openai`, `telegram` or `composio`).
+///
+/// ```toml
+/// [http]
+/// proxy = "http://proxy.corp:3128"
+/// ca_bundle = "/etc/ssl/corp-ca.pem"
+///
+/// [http.overrides.ollama]
+/// proxy = ""   # empty string disables the proxy for this component
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HttpConfig {
+    #[serde(flatten)]
+    pub defaults: HttpClientConfig,
+    #[serde(default)]
+    pub overrides: HashMap<String, HttpClientConfig>,
+}
+
+impl HttpConfig {
+    /// Effective settings for `component` (defaults plus its override, if any).
+    pub fn for_component(&self, component: &str) -> HttpClientConfig {
+        match self.overrides.get(component) {
+            Some(o) => self.defaults.merged_with(o),
+            None => self.defaults.clone(),
+        }
+    }
 }
 
 // ── Heartbeat ────────────────────────────────────────────────────
@@ -770,6 +1257,26 @@ impl Default for ChannelsConfig {
 pub struct TelegramConfig {
     pub bot_token: String,
     pub allowed_users: Vec<String>,
+    /// "polling" (default) long-polls `getUpdates`; "webhook" receives
+    /// updates through the gateway at `/telegram/<secret>`
+    #[serde(default = "default_telegram_mode")]
+    pub mode: String,
+    /// Public base URL for the webhook when no tunnel is running
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Webhook path and header secret; derived from the bot token when unset
+    #[serde(default)]
+    pub webhook_secret: Option<String>,
+}
+
+fn default_telegram_mode() -> String {
+    "polling".into()
+}
+
+impl TelegramConfig {
+    pub fn uses_webhook(&self) -> bool {
+        self.mode.eq_ignore_ascii_case("webhook")
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -783,10 +1290,22 @@ pub struct DiscordConfig {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SlackConfig {
     pub bot_token: String,
+    /// App-level token (`xapp-...`); enables Socket Mode
     pub app_token: Option<String>,
     pub channel_id: Option<String>,
     #[serde(default)]
     pub allowed_users: Vec<String>,
+    /// Signing secret; enables the gateway's `/slack/events` Events API endpoint
+    #[serde(default)]
+    pub signing_secret: Option<String>,
+}
+
+impl SlackConfig {
+    /// Events API via the gateway; Socket Mode wins when an app token is set.
+    pub fn uses_events_api(&self) -> bool {
+        let set = |v: &Option<String>| v.as_deref().is_some_and(|v| !v.trim().is_empty());
+        !set(&self.app_token) && set(&self.signing_secret)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -930,8 +1449,14 @@ impl Default for Config {
             runtime: RuntimeConfig::default(),
             reliability: ReliabilityConfig::default(),
             model_routes: Vec::new(),
+            reasoning: ReasoningConfig::default(),
+            azure_openai: AzureOpenAiConfig::default(),
+            ollama: OllamaConfig::default(),
+            response_cache: ResponseCacheConfig::default(),
+            http: HttpConfig::default(),
             heartbeat: HeartbeatConfig::default(),
             channels_config: ChannelsConfig::default(),
+            media: MediaConfig::default(),
             memory: MemoryConfig::default(),
             tunnel: TunnelConfig::default(),
             gateway: GatewayConfig::default(),
@@ -1258,6 +1783,11 @@ mod tests {
             },
             reliability: ReliabilityConfig::default(),
             model_routes: Vec::new(),
+            reasoning: ReasoningConfig::default(),
+            azure_openai: AzureOpenAiConfig::default(),
+            ollama: OllamaConfig::default(),
+            response_cache: ResponseCacheConfig::default(),
+            http: HttpConfig::default(),
             heartbeat: HeartbeatConfig {
                 enabled: true,
                 interval_minutes: 15,
@@ -1267,6 +1797,9 @@ mod tests {
                 telegram: Some(TelegramConfig {
                     bot_token: "123:ABC".into(),
                     allowed_users: vec!["user1".into()],
+                    mode: "polling".into(),
+                    webhook_url: None,
+                    webhook_secret: None,
                 }),
                 discord: None,
                 slack: None,
@@ -1280,6 +1813,7 @@ mod tests {
                 wecom: None,
                 dingtalk: None,
             },
+            media: MediaConfig::default(),
             memory: MemoryConfig::default(),
             tunnel: TunnelConfig::default(),
             gateway: GatewayConfig::default(),
@@ -1350,8 +1884,14 @@ default_temperature = 0.7
             runtime: RuntimeConfig::default(),
             reliability: ReliabilityConfig::default(),
             model_routes: Vec::new(),
+            reasoning: ReasoningConfig::default(),
+            azure_openai: AzureOpenAiConfig::default(),
+            ollama: OllamaConfig::default(),
+            response_cache: ResponseCacheConfig::default(),
+            http: HttpConfig::default(),
             heartbeat: HeartbeatConfig::default(),
             channels_config: ChannelsConfig::default(),
+            media: MediaConfig::default(),
             memory: MemoryConfig::default(),
             tunnel: TunnelConfig::default(),
             gateway: GatewayConfig::default(),
@@ -1412,6 +1952,9 @@ default_temperature = 0.7
         let tc = TelegramConfig {
             bot_token: "123:XYZ".into(),
             allowed_users: vec!["alice".into(), "bob".into()],
+            mode: "polling".into(),
+            webhook_url: None,
+            webhook_secret: None,
         };
         let json = serde_json::to_string(&tc).unwrap();
         let parsed: TelegramConfig = serde_json::from_str(&json).unwrap();
@@ -1530,7 +2073,6 @@ default_temperature = 0.7
             lark: None,
             wecom: None,
             dingtalk: None,
-            wecom: None,
         };
         let toml_str = toml::to_string_pretty(&c).unwrap();
         let parsed: ChannelsConfig = toml::from_str(&toml_str).unwrap();
@@ -1578,6 +2120,21 @@ default_temperature = 0.7
         assert_eq!(parsed.allowed_users, vec!["U111"]);
     }
 
+    #[test]
+    fn slack_events_api_only_without_socket_mode() {
+        let json = r#"{"bot_token":"xoxb-tok","signing_secret":"shh"}"#;
+        let parsed: SlackConfig = serde_json::from_str(json).unwrap();
+        assert!(parsed.uses_events_api());
+
+        let json = r#"{"bot_token":"xoxb-tok","app_token":"xapp-1","signing_secret":"shh"}"#;
+        let parsed: SlackConfig = serde_json::from_str(json).unwrap();
+        assert!(!parsed.uses_events_api());
+
+        let json = r#"{"bot_token":"xoxb-tok"}"#;
+        let parsed: SlackConfig = serde_json::from_str(json).unwrap();
+        assert!(!parsed.uses_events_api());
+    }
+
     #[test]
     fn discord_config_toml_backward_compat() {
         let toml_str = r#"
@@ -2283,7 +2840,7 @@ temperature = 0.3
                 max_depth: 3,
             },
         );
-        let mut config = Config {
+        let config = Config {
             config_path: config_path.clone(),
             workspace_dir: zeroclaw_dir.join("workspace"),
             secrets: SecretsConfig { encrypt: true },
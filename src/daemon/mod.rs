@@ -3,6 +3,7 @@ use anyhow::Result;
 use chrono::Utc;
 use std::future::Future;
 use std::path::PathBuf;
+use std::sync::Arc;
 use tokio::task::JoinHandle;
 use tokio::time::Duration;
 
@@ -23,11 +24,16 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
                 .await;
     }
 
+    // One observer for every component, so metrics aren't split across instances.
+    let observer: Arc<dyn crate::observability::Observer> =
+        Arc::from(crate::observability::create_observer(&config.observability));
+
     let mut handles: Vec<JoinHandle<()>> = vec![spawn_state_writer(config.clone())];
 
     {
         let gateway_cfg = config.clone();
         let gateway_host = host.clone();
+        let gateway_observer = observer.clone();
         handles.push(spawn_component_supervisor(
             "gateway",
             initial_backoff,
@@ -35,7 +41,8 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
             move || {
                 let cfg = gateway_cfg.clone();
                 let host = gateway_host.clone();
-                async move { crate::gateway::run_gateway(&host, port, cfg).await }
+                let observer = gateway_observer.clone();
+                async move { crate::gateway::run_gateway(&host, port, cfg, observer).await }
             },
         ));
     }
@@ -43,13 +50,15 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     {
         if has_supervised_channels(&config) {
             let channels_cfg = config.clone();
+            let channels_observer = observer.clone();
             handles.push(spawn_component_supervisor(
                 "channels",
                 initial_backoff,
                 max_backoff,
                 move || {
                     let cfg = channels_cfg.clone();
-                    async move { crate::channels::start_channels(cfg).await }
+                    let observer = channels_observer.clone();
+                    async move { crate::channels::start_channels(cfg, observer).await }
                 },
             ));
         } else {
@@ -60,13 +69,15 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
 
     if config.heartbeat.enabled {
         let heartbeat_cfg = config.clone();
+        let heartbeat_observer = observer.clone();
         handles.push(spawn_component_supervisor(
             "heartbeat",
             initial_backoff,
             max_backoff,
             move || {
                 let cfg = heartbeat_cfg.clone();
-                async move { run_heartbeat_worker(cfg).await }
+                let observer = heartbeat_observer.clone();
+                async move { run_heartbeat_worker(cfg, observer).await }
             },
         ));
     }
@@ -92,6 +103,23 @@ pub async fn run(config: Config, host: String, port: u16) -> Result<()> {
     tokio::signal::ctrl_c().await?;
     crate::health::mark_component_error("daemon", "shutdown requested");
 
+    // Unregister the Telegram webhook before the gateway goes away so the
+    // next start (in either mode) sees a clean bot.
+    if let Some(tg) = config
+        .channels_config
+        .telegram
+        .as_ref()
+        .filter(|tg| tg.uses_webhook())
+    {
+        let channel =
+            crate::channels::TelegramChannel::new(tg.bot_token.clone(), tg.allowed_users.clone());
+        match tokio::time::timeout(Duration::from_secs(5), channel.delete_webhook()).await {
+            Ok(Err(e)) => tracing::warn!("Telegram deleteWebhook failed: {e}"),
+            Err(_) => tracing::warn!("Telegram deleteWebhook timed out"),
+            Ok(Ok(())) => {}
+        }
+    }
+
     for handle in &handles {
         handle.abort();
     }
@@ -170,9 +198,10 @@ where
     })
 }
 
-async fn run_heartbeat_worker(config: Config) -> Result<()> {
-    let observer: std::sync::Arc<dyn crate::observability::Observer> =
-        std::sync::Arc::from(crate::observability::create_observer(&config.observability));
+async fn run_heartbeat_worker(
+    config: Config,
+    observer: Arc<dyn crate::observability::Observer>,
+) -> Result<()> {
     let engine = crate::heartbeat::engine::HeartbeatEngine::new(
         config.heartbeat.clone(),
         config.workspace_dir.clone(),
@@ -185,6 +214,13 @@ async fn run_heartbeat_worker(config: Config) -> Result<()> {
     loop {
         interval.tick().await;
 
+        if let Err(e) = crate::memory::consolidate::run_if_due(&config).await {
+            tracing::warn!("Memory consolidation failed: {e}");
+        }
+        if let Err(e) = crate::memory::graph::run_if_due(&config).await {
+            tracing::warn!("Knowledge graph extraction failed: {e}");
+        }
+
         let tasks = engine.collect_tasks().await?;
         if tasks.is_empty() {
             continue;
@@ -288,6 +324,9 @@ mod tests {
         config.channels_config.telegram = Some(crate::config::TelegramConfig {
             bot_token: "token".into(),
             allowed_users: vec![],
+            mode: "polling".into(),
+            webhook_url: None,
+            webhook_secret: None,
         });
         assert!(has_supervised_channels(&config));
     }
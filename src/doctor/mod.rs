@@ -7,9 +7,16 @@ const SCHEDULER_STALE_SECONDS: i64 = 120;
 const CHANNEL_STALE_SECONDS: i64 = 300;
 
 pub fn run(config: &Config) -> Result<()> {
+    println!("🩺 ZeroClaw Doctor");
+    check_daemon(config)?;
+    println!();
+    check_local_servers(config);
+    Ok(())
+}
+
+fn check_daemon(config: &Config) -> Result<()> {
     let state_file = crate::daemon::state_file_path(config);
     if !state_file.exists() {
-        println!("🩺 ZeroClaw Doctor");
         println!("  ❌ daemon state file not found: {}", state_file.display());
         println!("  💡 Start daemon with: zeroclaw daemon");
         return Ok(());
@@ -20,7 +27,6 @@ pub fn run(config: &Config) -> Result<()> {
     let snapshot: serde_json::Value = serde_json::from_str(&raw)
         .with_context(|| format!("Failed to parse {}", state_file.display()))?;
 
-    println!("🩺 ZeroClaw Doctor");
     println!("  State file: {}", state_file.display());
 
     let updated_at = snapshot
@@ -109,6 +115,73 @@ pub fn run(config: &Config) -> Result<()> {
     Ok(())
 }
 
+fn check_local_servers(config: &Config) {
+    use crate::providers::discovery;
+
+    println!("  Local LLM servers:");
+    let servers = discovery::discover_blocking(&discovery::default_candidates(
+        config.ollama.base_url.as_deref(),
+    ));
+
+    if servers.is_empty() {
+        println!("  ℹ️ none found");
+    }
+    for server in &servers {
+        let models = if server.models.is_empty() {
+            "no models".to_string()
+        } else {
+            server.models.join(", ")
+        };
+        println!(
+            "  ✅ {} at {} — {models}",
+            server.kind.label(),
+            server.base_url
+        );
+    }
+
+    let provider = config.default_provider.as_deref().unwrap_or("openrouter");
+    let Some(expected) = local_base_url(config, provider) else {
+        return;
+    };
+    match servers.iter().find(|s| s.base_url == expected) {
+        None => println!("  ❌ configured provider {provider} is not reachable at {expected}"),
+        Some(server) => {
+            if let Some(model) = config.default_model.as_deref() {
+                if !server.models.is_empty() && !server.models.iter().any(|m| m == model) {
+                    println!(
+                        "  ⚠️ default model {model} is not loaded on {}",
+                        server.kind.label()
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Base URL the configured provider expects to reach, if it is a local server.
+fn local_base_url(config: &Config, provider: &str) -> Option<String> {
+    use crate::providers::discovery;
+
+    if provider == "ollama" {
+        return Some(
+            config
+                .ollama
+                .base_url
+                .as_deref()
+                .and_then(discovery::normalize_ollama_url)
+                .or_else(discovery::ollama_host_from_env)
+                .unwrap_or_else(|| discovery::DEFAULT_OLLAMA_URL.to_string()),
+        );
+    }
+    let url = provider.strip_prefix("custom:")?.trim_end_matches('/');
+    let url = url.strip_suffix("/v1").unwrap_or(url);
+    let is_local = ["://localhost", "://127.0.0.1", "://[::1]"]
+        .iter()
+        .any(|h| url.contains(h));
+    // Discovery probes `localhost`, so compare on that spelling.
+    is_local.then(|| url.replace("://127.0.0.1", "://localhost"))
+}
+
 fn parse_rfc3339(raw: &str) -> Option<DateTime<Utc>> {
     DateTime::parse_from_rfc3339(raw)
         .ok()
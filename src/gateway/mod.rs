@@ -7,16 +7,16 @@
 //! - Request timeouts (30s) to prevent slow-loris attacks
 //! - Header sanitization (handled by axum/hyper)
 
-use crate::channels::{Channel, WhatsAppChannel};
+use crate::channels::{slack, telegram, Channel, TelegramChannel, WhatsAppChannel};
 use crate::config::Config;
-use crate::memory::{self, Memory, MemoryCategory};
+use crate::memory::{self, Memory, MemoryCategory, MemoryScope};
 use crate::providers::{self, Provider};
 use crate::security::pairing::{constant_time_eq, is_public_bind, PairingGuard};
 use crate::util::truncate_with_ellipsis;
 use anyhow::Result;
 use axum::{
     body::Bytes,
-    extract::{Query, State},
+    extract::{Path, Query, State},
     http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Json},
     routing::{get, post},
@@ -141,6 +141,14 @@ impl IdempotencyStore {
         keys.insert(key.to_owned(), now);
         true
     }
+
+    /// Drop a recorded key so a later retry is processed again.
+    fn forget(&self, key: &str) {
+        self.keys
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(key);
+    }
 }
 
 fn client_key_from_headers(headers: &HeaderMap) -> String {
@@ -162,7 +170,12 @@ pub struct AppState {
     pub model: String,
     pub temperature: f64,
     pub mem: Arc<dyn Memory>,
+    /// Store raw incoming messages (`auto_save` with raw logging)
     pub auto_save: bool,
+    /// Model for per-turn fact extraction; `None` when `auto_save` doesn't extract
+    pub extraction_model: Option<String>,
+    /// `[memory].channel_scope` — how channel messages are scoped in memory
+    pub channel_scope: String,
     pub webhook_secret: Option<Arc<str>>,
     pub pairing: Arc<PairingGuard>,
     pub rate_limiter: Arc<GatewayRateLimiter>,
@@ -170,11 +183,21 @@ pub struct AppState {
     pub whatsapp: Option<Arc<WhatsAppChannel>>,
     /// `WhatsApp` app secret for webhook signature verification (`X-Hub-Signature-256`)
     pub whatsapp_app_secret: Option<Arc<str>>,
+    /// Telegram webhook secret (path segment and `X-Telegram-Bot-Api-Secret-Token`);
+    /// `None` unless Telegram runs in webhook mode
+    pub telegram_webhook_secret: Option<Arc<str>>,
+    /// Slack signing secret for `/slack/events`; `None` unless Slack uses the Events API
+    pub slack_signing_secret: Option<Arc<str>>,
 }
 
 /// Run the HTTP gateway using axum with proper HTTP/1.1 compliance.
 #[allow(clippy::too_many_lines)]
-pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
+pub async fn run_gateway(
+    host: &str,
+    port: u16,
+    config: Config,
+    observer: Arc<dyn crate::observability::Observer>,
+) -> Result<()> {
     // ── Security: refuse public bind without tunnel or explicit opt-in ──
     if is_public_bind(host) && config.tunnel.provider == "none" && !config.gateway.allow_public_bind
     {
@@ -190,11 +213,14 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     let actual_port = listener.local_addr()?.port();
     let display_addr = format!("{host}:{actual_port}");
 
-    let provider: Arc<dyn Provider> = Arc::from(providers::create_resilient_provider(
+    let provider = providers::create_resilient_provider_with_options(
         config.default_provider.as_deref().unwrap_or("openrouter"),
         config.api_key.as_deref(),
         &config.reliability,
-    )?);
+        &providers::ProviderOptions::from_config(&config),
+    )?;
+    let provider: Arc<dyn Provider> =
+        Arc::from(providers::cache::wrap_provider(provider, &config, observer));
     let model = config
         .default_model
         .clone()
@@ -244,6 +270,15 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         })
         .map(Arc::from);
 
+    // Slack Events API signing secret (only when Slack isn't on Socket Mode)
+    let slack_signing_secret: Option<Arc<str>> = config
+        .channels_config
+        .slack
+        .as_ref()
+        .filter(|sl| sl.uses_events_api())
+        .and_then(|sl| sl.signing_secret.as_deref())
+        .map(|secret| Arc::from(secret.trim()));
+
     // ── Pairing guard ──────────────────────────────────────
     let pairing = Arc::new(PairingGuard::new(
         config.gateway.require_pairing,
@@ -275,6 +310,37 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         }
     }
 
+    // ── Telegram webhook mode ─────────────────────────────────
+    let telegram_webhook = config
+        .channels_config
+        .telegram
+        .as_ref()
+        .filter(|tg| tg.uses_webhook())
+        .map(|tg| {
+            (
+                TelegramChannel::new(tg.bot_token.clone(), tg.allowed_users.clone()),
+                telegram::webhook_secret(tg),
+                tunnel_url.clone().or_else(|| tg.webhook_url.clone()),
+            )
+        });
+    let mut telegram_webhook_set = false;
+    if let Some((ref tg, ref secret, ref base_url)) = telegram_webhook {
+        match base_url {
+            Some(base_url) => {
+                match tg
+                    .set_webhook(&telegram::webhook_url(base_url, secret), secret)
+                    .await
+                {
+                    Ok(()) => telegram_webhook_set = true,
+                    Err(e) => println!("⚠️  Telegram setWebhook failed: {e}"),
+                }
+            }
+            None => println!(
+                "⚠️  Telegram webhook mode needs a tunnel or [channels_config.telegram] webhook_url"
+            ),
+        }
+    }
+
     println!("🦀 ZeroClaw Gateway listening on http://{display_addr}");
     if let Some(ref url) = tunnel_url {
         println!("  🌐 Public URL: {url}");
@@ -285,6 +351,12 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         println!("  GET  /whatsapp  — Meta webhook verification");
         println!("  POST /whatsapp  — WhatsApp message webhook");
     }
+    if telegram_webhook_set {
+        println!("  POST /telegram/<secret> — Telegram webhook (registered)");
+    }
+    if slack_signing_secret.is_some() {
+        println!("  POST /slack/events — Slack Events API");
+    }
     println!("  GET  /health    — health check");
     if let Some(code) = pairing.pairing_code() {
         println!();
@@ -305,19 +377,28 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
 
     crate::health::mark_component_ok("gateway");
 
+    let extraction_model = (config.memory.auto_save && config.memory.extraction.enabled)
+        .then(|| memory::extract::extraction_model(&config.memory, &model));
+
     // Build shared state
     let state = AppState {
         provider,
         model,
         temperature,
         mem,
-        auto_save: config.memory.auto_save,
+        auto_save: config.memory.auto_save && config.memory.extraction.logs_raw(),
+        extraction_model,
+        channel_scope: config.memory.channel_scope.clone(),
         webhook_secret,
         pairing,
         rate_limiter,
         idempotency_store,
         whatsapp: whatsapp_channel,
         whatsapp_app_secret,
+        telegram_webhook_secret: telegram_webhook
+            .as_ref()
+            .map(|(_, secret, _)| Arc::from(secret.as_str())),
+        slack_signing_secret,
     };
 
     // Build router with middleware
@@ -327,6 +408,8 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .route("/webhook", post(handle_webhook))
         .route("/whatsapp", get(handle_whatsapp_verify))
         .route("/whatsapp", post(handle_whatsapp_message))
+        .route("/telegram/:secret", post(handle_telegram_update))
+        .route("/slack/events", post(handle_slack_events))
         .with_state(state)
         .layer(RequestBodyLimitLayer::new(MAX_BODY_SIZE))
         .layer(TimeoutLayer::with_status_code(
@@ -334,8 +417,18 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
             Duration::from_secs(REQUEST_TIMEOUT_SECS),
         ));
 
-    // Run the server
-    axum::serve(listener, app).await?;
+    // Run the server until Ctrl+C, then hand Telegram back to polling
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+
+    if let (true, Some((tg, _, _))) = (telegram_webhook_set, telegram_webhook) {
+        if let Err(e) = tg.delete_webhook().await {
+            tracing::warn!("Telegram deleteWebhook failed: {e}");
+        }
+    }
 
     Ok(())
 }
@@ -403,6 +496,9 @@ async fn handle_pair(State(state): State<AppState>, headers: HeaderMap) -> impl
 #[derive(serde::Deserialize)]
 pub struct WebhookBody {
     pub message: String,
+    /// Optional caller session; memories saved from this request stay private to it
+    #[serde(default)]
+    pub session_id: Option<String>,
 }
 
 /// POST /webhook — main webhook endpoint
@@ -483,11 +579,20 @@ async fn handle_webhook(
 
     let message = &webhook_body.message;
 
+    let scope = webhook_body
+        .session_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map_or(MemoryScope::Global, |id| {
+            MemoryScope::Session(id.to_string())
+        });
+
     if state.auto_save {
         let key = webhook_memory_key();
         let _ = state
             .mem
-            .store(&key, message, MemoryCategory::Conversation)
+            .store_scoped(&key, message, MemoryCategory::Conversation, &scope)
             .await;
     }
 
@@ -497,6 +602,17 @@ async fn handle_webhook(
         .await
     {
         Ok(response) => {
+            let response = providers::reasoning::strip_reasoning(&response);
+            if let Some(model) = &state.extraction_model {
+                memory::extract::spawn(
+                    state.mem.clone(),
+                    state.provider.clone(),
+                    model.clone(),
+                    scope,
+                    message.clone(),
+                    response.clone(),
+                );
+            }
             let body = serde_json::json!({"response": response, "model": state.model});
             (StatusCode::OK, Json(body))
         }
@@ -575,6 +691,177 @@ pub fn verify_whatsapp_signature(app_secret: &str, body: &[u8], signature_header
     mac.verify_slice(&expected).is_ok()
 }
 
+/// Maximum age of a Slack request timestamp, to limit replay.
+const SLACK_MAX_REQUEST_AGE_SECS: i64 = 300;
+
+/// Verify a Slack request signature (`X-Slack-Signature`), computed as
+/// `v0=` + hex HMAC-SHA256 of `v0:<timestamp>:<body>`.
+/// See: <https://api.slack.com/authentication/verifying-requests-from-slack>
+pub fn verify_slack_signature(
+    signing_secret: &str,
+    timestamp: &str,
+    body: &[u8],
+    signature_header: &str,
+) -> bool {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+
+    let Some(hex_sig) = signature_header.strip_prefix("v0=") else {
+        return false;
+    };
+    let Ok(expected) = hex::decode(hex_sig) else {
+        return false;
+    };
+    let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(signing_secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(format!("v0:{timestamp}:").as_bytes());
+    mac.update(body);
+
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// POST /slack/events — Slack Events API callbacks
+async fn handle_slack_events(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let Some(ref signing_secret) = state.slack_signing_secret else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Slack Events API not configured"})),
+        );
+    };
+
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("")
+    };
+    let timestamp = header("X-Slack-Request-Timestamp");
+    let fresh = timestamp
+        .parse::<i64>()
+        .is_ok_and(|ts| (chrono::Utc::now().timestamp() - ts).abs() <= SLACK_MAX_REQUEST_AGE_SECS);
+    if !fresh
+        || !verify_slack_signature(
+            signing_secret,
+            timestamp,
+            &body,
+            header("X-Slack-Signature"),
+        )
+    {
+        tracing::warn!("Slack events: rejected request with invalid or stale signature");
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Invalid signature"})),
+        );
+    }
+
+    let Ok(payload) = serde_json::from_slice::<serde_json::Value>(&body) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid JSON payload"})),
+        );
+    };
+
+    match payload.get("type").and_then(|t| t.as_str()) {
+        // One-off handshake when the Request URL is saved in the Slack app config
+        Some("url_verification") => (
+            StatusCode::OK,
+            Json(serde_json::json!({"challenge": payload.get("challenge")})),
+        ),
+        Some("event_callback") => {
+            let Some(event) = payload.get("event").cloned() else {
+                return (
+                    StatusCode::OK,
+                    Json(serde_json::json!({"status": "ignored"})),
+                );
+            };
+            // Slack redelivers (X-Slack-Retry-Num) when the first ack was slow;
+            // the event_id stays the same, so answer retries without acting twice.
+            let dedup_key = payload
+                .get("event_id")
+                .and_then(|id| id.as_str())
+                .map(|id| format!("slack:{id}"));
+            if let Some(ref key) = dedup_key {
+                if !state.idempotency_store.record_if_new(key) {
+                    tracing::info!(
+                        "Slack events: duplicate {key} ignored (retry {})",
+                        header("X-Slack-Retry-Num")
+                    );
+                    return (
+                        StatusCode::OK,
+                        Json(serde_json::json!({"status": "duplicate"})),
+                    );
+                }
+            }
+            // Non-2xx makes Slack retry while the channel listener is (re)starting.
+            if !slack::deliver_event(event) {
+                if let Some(ref key) = dedup_key {
+                    state.idempotency_store.forget(key);
+                }
+                return (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    Json(serde_json::json!({"error": "Slack channel not listening"})),
+                );
+            }
+            (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
+        }
+        _ => (
+            StatusCode::OK,
+            Json(serde_json::json!({"status": "ignored"})),
+        ),
+    }
+}
+
+/// POST /telegram/<secret> — Telegram webhook updates (webhook mode)
+async fn handle_telegram_update(
+    State(state): State<AppState>,
+    Path(path_secret): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> impl IntoResponse {
+    let Some(ref secret) = state.telegram_webhook_secret else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({"error": "Telegram webhook not configured"})),
+        );
+    };
+
+    // Both the unguessable path and Telegram's secret header must match
+    let header_secret = headers
+        .get("X-Telegram-Bot-Api-Secret-Token")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    if !constant_time_eq(&path_secret, secret) || !constant_time_eq(header_secret, secret) {
+        tracing::warn!("Telegram webhook: rejected request with invalid secret");
+        return (
+            StatusCode::UNAUTHORIZED,
+            Json(serde_json::json!({"error": "Invalid secret"})),
+        );
+    }
+
+    let Ok(update) = serde_json::from_slice::<serde_json::Value>(&body) else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid JSON payload"})),
+        );
+    };
+
+    // Non-2xx makes Telegram retry, so an update is not lost while the
+    // channel listener is (re)starting.
+    if !telegram::deliver_webhook_update(update) {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(serde_json::json!({"error": "Telegram channel not listening"})),
+        );
+    }
+
+    (StatusCode::OK, Json(serde_json::json!({"status": "ok"})))
+}
+
 /// POST /whatsapp — incoming message webhook
 async fn handle_whatsapp_message(
     State(state): State<AppState>,
@@ -635,12 +922,19 @@ async fn handle_whatsapp_message(
             truncate_with_ellipsis(&msg.content, 50)
         );
 
-        // Auto-save to memory
+        let scopes = crate::channels::memory_scopes(&state.channel_scope, msg);
+
+        // Raw auto-save
         if state.auto_save {
             let key = whatsapp_memory_key(msg);
             let _ = state
                 .mem
-                .store(&key, &msg.content, MemoryCategory::Conversation)
+                .store_scoped(
+                    &key,
+                    &msg.content,
+                    MemoryCategory::Conversation,
+                    scopes.first().unwrap_or(&MemoryScope::Global),
+                )
                 .await;
         }
 
@@ -651,10 +945,21 @@ async fn handle_whatsapp_message(
             .await
         {
             Ok(response) => {
+                let response = providers::reasoning::strip_reasoning(&response);
                 // Send reply via WhatsApp
                 if let Err(e) = wa.send(&response, &msg.sender).await {
                     tracing::error!("Failed to send WhatsApp reply: {e}");
                 }
+                if let Some(model) = &state.extraction_model {
+                    memory::extract::spawn(
+                        state.mem.clone(),
+                        state.provider.clone(),
+                        model.clone(),
+                        scopes.into_iter().next().unwrap_or(MemoryScope::Global),
+                        msg.content.clone(),
+                        response.clone(),
+                    );
+                }
             }
             Err(e) => {
                 tracing::error!("LLM error for WhatsApp message: {e:#}");
@@ -754,7 +1059,13 @@ mod tests {
         let msg = ChannelMessage {
             id: "wamid-123".into(),
             sender: "+1234567890".into(),
+            author: "+1234567890".into(),
             content: "hello".into(),
+            attachments: Vec::new(),
+            message_id: None,
+            thread_id: None,
+            reply_to: None,
+            raw: None,
             channel: "whatsapp".into(),
             timestamp: 1,
         };
@@ -897,12 +1208,16 @@ mod tests {
             temperature: 0.0,
             mem: memory,
             auto_save: false,
+            extraction_model: None,
+            channel_scope: "user".into(),
             webhook_secret: None,
             pairing: Arc::new(PairingGuard::new(false, &[])),
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300))),
             whatsapp: None,
             whatsapp_app_secret: None,
+            telegram_webhook_secret: None,
+            slack_signing_secret: None,
         };
 
         let mut headers = HeaderMap::new();
@@ -910,6 +1225,7 @@ mod tests {
 
         let body = Ok(Json(WebhookBody {
             message: "hello".into(),
+            session_id: None,
         }));
         let first = handle_webhook(State(state.clone()), headers.clone(), body)
             .await
@@ -918,6 +1234,7 @@ mod tests {
 
         let body = Ok(Json(WebhookBody {
             message: "hello".into(),
+            session_id: None,
         }));
         let second = handle_webhook(State(state), headers, body)
             .await
@@ -945,18 +1262,23 @@ mod tests {
             temperature: 0.0,
             mem: memory,
             auto_save: true,
+            extraction_model: None,
+            channel_scope: "user".into(),
             webhook_secret: None,
             pairing: Arc::new(PairingGuard::new(false, &[])),
             rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100)),
             idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300))),
             whatsapp: None,
             whatsapp_app_secret: None,
+            telegram_webhook_secret: None,
+            slack_signing_secret: None,
         };
 
         let headers = HeaderMap::new();
 
         let body1 = Ok(Json(WebhookBody {
             message: "hello one".into(),
+            session_id: None,
         }));
         let first = handle_webhook(State(state.clone()), headers.clone(), body1)
             .await
@@ -965,6 +1287,7 @@ mod tests {
 
         let body2 = Ok(Json(WebhookBody {
             message: "hello two".into(),
+            session_id: None,
         }));
         let second = handle_webhook(State(state), headers, body2)
             .await
@@ -983,6 +1306,268 @@ mod tests {
         assert_eq!(provider_impl.calls.load(Ordering::SeqCst), 2);
     }
 
+    #[tokio::test]
+    async fn webhook_autosave_with_session_is_scoped() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let sqlite = Arc::new(crate::memory::SqliteMemory::new(tmp.path()).unwrap());
+        let memory: Arc<dyn Memory> = sqlite.clone();
+
+        let state = AppState {
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: memory,
+            auto_save: true,
+            extraction_model: None,
+            channel_scope: "user".into(),
+            webhook_secret: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300))),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            telegram_webhook_secret: None,
+            slack_signing_secret: None,
+        };
+
+        let body = Ok(Json(WebhookBody {
+            message: "my api token is hunter2".into(),
+            session_id: Some("client-7".into()),
+        }));
+        let response = handle_webhook(State(state), HeaderMap::new(), body)
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        assert!(sqlite.recall("hunter2", 5).await.unwrap().is_empty());
+        let scope = MemoryScope::Session("client-7".into());
+        let hits = sqlite
+            .recall_scoped("hunter2", 5, std::slice::from_ref(&scope))
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].session_id.as_deref(), Some("session:client-7"));
+    }
+
+    fn webhook_channel_state(secret: Option<&str>) -> AppState {
+        AppState {
+            provider: Arc::new(MockProvider::default()),
+            model: "test-model".into(),
+            temperature: 0.0,
+            mem: Arc::new(MockMemory),
+            auto_save: false,
+            extraction_model: None,
+            channel_scope: "user".into(),
+            webhook_secret: None,
+            pairing: Arc::new(PairingGuard::new(false, &[])),
+            rate_limiter: Arc::new(GatewayRateLimiter::new(100, 100)),
+            idempotency_store: Arc::new(IdempotencyStore::new(Duration::from_secs(300))),
+            whatsapp: None,
+            whatsapp_app_secret: None,
+            telegram_webhook_secret: secret.map(Arc::from),
+            slack_signing_secret: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn telegram_webhook_is_404_when_not_in_webhook_mode() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "X-Telegram-Bot-Api-Secret-Token",
+            HeaderValue::from_static("s3cret"),
+        );
+        let response = handle_telegram_update(
+            State(webhook_channel_state(None)),
+            Path("s3cret".into()),
+            headers,
+            Bytes::from_static(b"{}"),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn telegram_webhook_rejects_bad_path_or_header_secret() {
+        let state = webhook_channel_state(Some("s3cret"));
+
+        let mut good = HeaderMap::new();
+        good.insert(
+            "X-Telegram-Bot-Api-Secret-Token",
+            HeaderValue::from_static("s3cret"),
+        );
+        let mut bad = HeaderMap::new();
+        bad.insert(
+            "X-Telegram-Bot-Api-Secret-Token",
+            HeaderValue::from_static("nope"),
+        );
+
+        for (path, headers) in [
+            ("s3cret", HeaderMap::new()),
+            ("s3cret", bad),
+            ("wrong", good.clone()),
+        ] {
+            let response = handle_telegram_update(
+                State(state.clone()),
+                Path(path.into()),
+                headers,
+                Bytes::from_static(b"{}"),
+            )
+            .await
+            .into_response();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        let response = handle_telegram_update(
+            State(state),
+            Path("s3cret".into()),
+            good,
+            Bytes::from_static(b"not json"),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    fn slack_signature(secret: &str, timestamp: &str, body: &[u8]) -> String {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("v0:{timestamp}:").as_bytes());
+        mac.update(body);
+        format!("v0={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    fn slack_headers(secret: &str, timestamp: &str, body: &[u8]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "X-Slack-Request-Timestamp",
+            HeaderValue::from_str(timestamp).unwrap(),
+        );
+        headers.insert(
+            "X-Slack-Signature",
+            HeaderValue::from_str(&slack_signature(secret, timestamp, body)).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn slack_signature_covers_timestamp_and_body() {
+        let sig = slack_signature("shh", "1700000000", b"payload");
+        assert!(verify_slack_signature(
+            "shh",
+            "1700000000",
+            b"payload",
+            &sig
+        ));
+        assert!(!verify_slack_signature(
+            "shh",
+            "1700000001",
+            b"payload",
+            &sig
+        ));
+        assert!(!verify_slack_signature(
+            "shh",
+            "1700000000",
+            b"tampered",
+            &sig
+        ));
+        assert!(!verify_slack_signature(
+            "other",
+            "1700000000",
+            b"payload",
+            &sig
+        ));
+        assert!(!verify_slack_signature(
+            "shh",
+            "1700000000",
+            b"payload",
+            sig.trim_start_matches("v0=")
+        ));
+    }
+
+    #[tokio::test]
+    async fn slack_events_answers_url_verification() {
+        let mut state = webhook_channel_state(None);
+        state.slack_signing_secret = Some(Arc::from("shh"));
+        let body = br#"{"type":"url_verification","challenge":"abc123"}"#;
+        let now = chrono::Utc::now().timestamp().to_string();
+
+        let response = handle_slack_events(
+            State(state),
+            slack_headers("shh", &now, body),
+            Bytes::from_static(body),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed["challenge"], "abc123");
+    }
+
+    #[tokio::test]
+    async fn slack_events_rejects_bad_or_stale_signatures() {
+        let body = br#"{"type":"url_verification","challenge":"abc123"}"#;
+        let now = chrono::Utc::now().timestamp().to_string();
+
+        let response = handle_slack_events(
+            State(webhook_channel_state(None)),
+            slack_headers("shh", &now, body),
+            Bytes::from_static(body),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let mut state = webhook_channel_state(None);
+        state.slack_signing_secret = Some(Arc::from("shh"));
+        let stale = (chrono::Utc::now().timestamp() - 600).to_string();
+        for headers in [
+            HeaderMap::new(),
+            slack_headers("wrong", &now, body),
+            slack_headers("shh", &stale, body),
+        ] {
+            let response =
+                handle_slack_events(State(state.clone()), headers, Bytes::from_static(body))
+                    .await
+                    .into_response();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    #[tokio::test]
+    async fn slack_events_acknowledges_retries_without_redelivery() {
+        let mut state = webhook_channel_state(None);
+        state.slack_signing_secret = Some(Arc::from("shh"));
+        // The first delivery of Ev1 was already accepted.
+        assert!(state.idempotency_store.record_if_new("slack:Ev1"));
+
+        let body =
+            br#"{"type":"event_callback","event_id":"Ev1","event":{"type":"message","text":"hi"}}"#;
+        let now = chrono::Utc::now().timestamp().to_string();
+        let mut headers = slack_headers("shh", &now, body);
+        headers.insert("X-Slack-Retry-Num", HeaderValue::from_static("1"));
+
+        let response = handle_slack_events(State(state), headers, Bytes::from_static(body))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+        let payload = response.into_body().collect().await.unwrap().to_bytes();
+        let parsed: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(parsed["status"], "duplicate");
+    }
+
+    #[test]
+    fn idempotency_store_forget_allows_reprocessing() {
+        let store = IdempotencyStore::new(Duration::from_secs(300));
+        assert!(store.record_if_new("slack:Ev2"));
+        assert!(!store.record_if_new("slack:Ev2"));
+        store.forget("slack:Ev2");
+        assert!(store.record_if_new("slack:Ev2"));
+    }
+
     // ══════════════════════════════════════════════════════════
     // WhatsApp Signature Verification Tests (CWE-345 Prevention)
     // ══════════════════════════════════════════════════════════
@@ -0,0 +1,625 @@
+//! Shared outbound HTTP client factory.
+//!
+//! Every provider, channel and tool builds its `reqwest::Client` through
+//! [`client`], and WebSocket channels connect through [`connect_websocket`],
+//! so the `[http]` config section (proxy, CA bundle, timeouts, user agent)
+//! applies everywhere. The config is validated and installed once at startup
+//! with [`init`]; until then clients use the built-in defaults.
+
+use crate::config::{HttpClientConfig, HttpConfig};
+use anyhow::Context;
+use reqwest::{Certificate, Client, NoProxy, Proxy, Url};
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+static CONFIG: OnceLock<HttpConfig> = OnceLock::new();
+
+/// Validate and install the process-wide `[http]` settings. Later calls are
+/// ignored. Fails on an unusable proxy URL or CA bundle, so a bad network
+/// config stops startup instead of silently sending traffic around it.
+pub fn init(config: &HttpConfig) -> anyhow::Result<()> {
+    resolve(&config.defaults, Defaults::NONE).context("invalid [http] settings")?;
+    for name in config.overrides.keys() {
+        resolve(&config.for_component(name), Defaults::NONE)
+            .with_context(|| format!("invalid [http.overrides.{name}] settings"))?;
+    }
+    let _ = CONFIG.set(config.clone());
+    Ok(())
+}
+
+/// Effective settings for `component` under the installed config.
+pub fn settings(component: &str) -> HttpClientConfig {
+    CONFIG
+        .get()
+        .map(|c| c.for_component(component))
+        .unwrap_or_default()
+}
+
+/// Built-in timeouts a component uses when `[http]` does not set them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Defaults {
+    pub timeout: Option<Duration>,
+    pub connect_timeout: Option<Duration>,
+    /// Server-side long-poll duration. Added on top of a configured
+    /// `timeout_secs` so a poll is never cut off before the server answers.
+    pub poll: Option<Duration>,
+}
+
+impl Defaults {
+    /// No timeouts (webhooks, streaming sockets).
+    pub const NONE: Self = Self {
+        timeout: None,
+        connect_timeout: None,
+        poll: None,
+    };
+
+    /// LLM provider calls: 120s per request, 10s to connect.
+    pub const PROVIDER: Self = Self {
+        timeout: Some(Duration::from_secs(120)),
+        connect_timeout: Some(Duration::from_secs(10)),
+        poll: None,
+    };
+
+    /// Long-polling channels (Telegram `getUpdates`, Matrix `/sync`) that
+    /// hold requests open for `poll_secs`. No timeout unless configured.
+    pub const fn long_poll(poll_secs: u64) -> Self {
+        Self {
+            timeout: None,
+            connect_timeout: None,
+            poll: Some(Duration::from_secs(poll_secs)),
+        }
+    }
+}
+
+/// Build a client for `component` from the installed `[http]` config.
+///
+/// # Panics
+///
+/// If the settings no longer build a client although [`init`] accepted them
+/// (e.g. the CA bundle was deleted). Falling back to a default client would
+/// silently drop the proxy, CA bundle and timeouts.
+pub fn client(component: &str, defaults: Defaults) -> Client {
+    build_client(&settings(component), defaults)
+        .unwrap_or_else(|e| panic!("[http] settings for {component} are unusable: {e:#}"))
+}
+
+/// Blocking counterpart of [`client`], for synchronous code such as the
+/// onboarding wizard's connection checks.
+///
+/// # Panics
+///
+/// Under the same conditions as [`client`].
+pub fn blocking_client(component: &str, defaults: Defaults) -> reqwest::blocking::Client {
+    build_blocking_client(&settings(component), defaults)
+        .unwrap_or_else(|e| panic!("[http] settings for {component} are unusable: {e:#}"))
+}
+
+/// Build a client from explicit settings.
+pub fn build_client(settings: &HttpClientConfig, defaults: Defaults) -> anyhow::Result<Client> {
+    let resolved = resolve(settings, defaults)?;
+    let mut builder = Client::builder().user_agent(resolved.user_agent);
+    if let Some(timeout) = resolved.timeout {
+        builder = builder.timeout(timeout);
+    }
+    if let Some(timeout) = resolved.connect_timeout {
+        builder = builder.connect_timeout(timeout);
+    }
+    match resolved.proxy {
+        ProxySetting::Env => {}
+        ProxySetting::Disabled => builder = builder.no_proxy(),
+        ProxySetting::Proxy(proxy) => builder = builder.proxy(*proxy),
+    }
+    for cert in resolved.certs {
+        builder = builder.add_root_certificate(cert);
+    }
+    builder.build().context("failed to build HTTP client")
+}
+
+/// Build a blocking client from explicit settings.
+pub fn build_blocking_client(
+    settings: &HttpClientConfig,
+    defaults: Defaults,
+) -> anyhow::Result<reqwest::blocking::Client> {
+    let resolved = resolve(settings, defaults)?;
+    // The blocking builder defaults to a 30s timeout; `None` means none.
+    let mut builder = reqwest::blocking::Client::builder()
+        .user_agent(resolved.user_agent)
+        .timeout(resolved.timeout);
+    if let Some(timeout) = resolved.connect_timeout {
+        builder = builder.connect_timeout(timeout);
+    }
+    match resolved.proxy {
+        ProxySetting::Env => {}
+        ProxySetting::Disabled => builder = builder.no_proxy(),
+        ProxySetting::Proxy(proxy) => builder = builder.proxy(*proxy),
+    }
+    for cert in resolved.certs {
+        builder = builder.add_root_certificate(cert);
+    }
+    builder.build().context("failed to build HTTP client")
+}
+
+enum ProxySetting {
+    /// Use `HTTP(S)_PROXY` from the environment (reqwest default)
+    Env,
+    /// Explicitly empty `proxy`: bypass both config and env proxies
+    Disabled,
+    Proxy(Box<Proxy>),
+}
+
+/// Client settings with the proxy parsed and the CA bundle loaded.
+struct Resolved {
+    user_agent: String,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    proxy: ProxySetting,
+    certs: Vec<Certificate>,
+}
+
+fn resolve(settings: &HttpClientConfig, defaults: Defaults) -> anyhow::Result<Resolved> {
+    let proxy = match settings.proxy.as_deref().map(str::trim) {
+        Some("") => ProxySetting::Disabled,
+        Some(url) => {
+            let proxy = Proxy::all(url).with_context(|| format!("invalid proxy URL: {url}"))?;
+            ProxySetting::Proxy(Box::new(
+                proxy.no_proxy(settings.no_proxy.as_deref().and_then(NoProxy::from_string)),
+            ))
+        }
+        None => ProxySetting::Env,
+    };
+
+    let certs = match settings.ca_bundle.as_deref() {
+        Some(path) => {
+            let pem =
+                std::fs::read(path).with_context(|| format!("failed to read CA bundle {path}"))?;
+            Certificate::from_pem_bundle(&pem)
+                .with_context(|| format!("failed to parse CA bundle {path}"))?
+        }
+        None => Vec::new(),
+    };
+
+    Ok(Resolved {
+        user_agent: settings
+            .user_agent
+            .clone()
+            .unwrap_or_else(|| format!("zeroclaw/{}", env!("CARGO_PKG_VERSION"))),
+        timeout: request_timeout(settings, defaults),
+        connect_timeout: settings
+            .connect_timeout_secs
+            .map(Duration::from_secs)
+            .or(defaults.connect_timeout),
+        proxy,
+        certs,
+    })
+}
+
+/// Whole-request timeout: the configured `timeout_secs` (plus the long-poll
+/// duration for polling clients), else the component default.
+fn request_timeout(settings: &HttpClientConfig, defaults: Defaults) -> Option<Duration> {
+    settings
+        .timeout_secs
+        .map(|secs| Duration::from_secs(secs) + defaults.poll.unwrap_or_default())
+        .or(defaults.timeout)
+}
+
+pub type WebSocket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// Open a WebSocket for `component` under the installed `[http]` config.
+pub async fn connect_websocket(component: &str, url: &str) -> anyhow::Result<WebSocket> {
+    connect_websocket_with(&settings(component), url).await
+}
+
+/// Open a WebSocket with explicit settings.
+///
+/// `http://` proxies are tunnelled with `CONNECT`; the CA bundle is added to
+/// the TLS roots and `connect_timeout_secs` bounds the connect and handshake.
+/// `socks5://` proxies are not supported for WebSockets: the socket
+/// connects directly and a warning is logged.
+pub async fn connect_websocket_with(
+    settings: &HttpClientConfig,
+    url: &str,
+) -> anyhow::Result<WebSocket> {
+    let target = Url::parse(url).with_context(|| format!("invalid WebSocket URL: {url}"))?;
+    let host = target
+        .host_str()
+        .ok_or_else(|| anyhow::anyhow!("WebSocket URL has no host: {url}"))?
+        .to_string();
+    let port = target
+        .port_or_known_default()
+        .ok_or_else(|| anyhow::anyhow!("WebSocket URL has no port: {url}"))?;
+
+    let proxy = settings
+        .proxy
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty() && !bypasses_proxy(settings.no_proxy.as_deref(), &host));
+
+    let connect = async {
+        let stream = match proxy {
+            Some(proxy) if proxy.starts_with("http://") => {
+                tunnel_through_proxy(proxy, &host, port).await?
+            }
+            Some(proxy) => {
+                tracing::warn!(
+                    "WebSocket connections do not support proxy {proxy}; connecting to {host} directly"
+                );
+                TcpStream::connect((host.as_str(), port)).await?
+            }
+            None => TcpStream::connect((host.as_str(), port)).await?,
+        };
+        let connector = match settings.ca_bundle.as_deref() {
+            Some(path) => Some(tokio_tungstenite::Connector::Rustls(Arc::new(
+                tls_config_with_bundle(path)?,
+            ))),
+            None => None,
+        };
+        let (socket, _) =
+            tokio_tungstenite::client_async_tls_with_config(url, stream, None, connector).await?;
+        anyhow::Ok(socket)
+    };
+
+    match settings.connect_timeout_secs {
+        Some(secs) => tokio::time::timeout(Duration::from_secs(secs), connect)
+            .await
+            .with_context(|| format!("WebSocket connect to {host} timed out"))?,
+        None => connect.await,
+    }
+}
+
+/// `no_proxy` match: exact host, domain suffix (`example.com` or
+/// `.example.com` covers subdomains) or `*`.
+fn bypasses_proxy(no_proxy: Option<&str>, host: &str) -> bool {
+    no_proxy.is_some_and(|list| {
+        list.split(',').map(str::trim).any(|entry| {
+            let domain = entry.trim_start_matches('.');
+            entry == "*"
+                || (!domain.is_empty()
+                    && (host.eq_ignore_ascii_case(domain)
+                        || host
+                            .to_ascii_lowercase()
+                            .ends_with(&format!(".{}", domain.to_ascii_lowercase()))))
+        })
+    })
+}
+
+/// Open a TCP tunnel to `host:port` through an HTTP proxy.
+async fn tunnel_through_proxy(proxy: &str, host: &str, port: u16) -> anyhow::Result<TcpStream> {
+    use base64::Engine;
+
+    let proxy_url = Url::parse(proxy).with_context(|| format!("invalid proxy URL: {proxy}"))?;
+    let proxy_host = proxy_url
+        .host_str()
+        .ok_or_else(|| anyhow::anyhow!("proxy URL has no host: {proxy}"))?;
+    let proxy_port = proxy_url.port_or_known_default().unwrap_or(80);
+
+    let mut stream = TcpStream::connect((proxy_host, proxy_port)).await?;
+    let mut request = format!("CONNECT {host}:{port} HTTP/1.1\r\nHost: {host}:{port}\r\n");
+    if !proxy_url.username().is_empty() {
+        let credentials = format!(
+            "{}:{}",
+            proxy_url.username(),
+            proxy_url.password().unwrap_or("")
+        );
+        request.push_str("Proxy-Authorization: Basic ");
+        request.push_str(&base64::engine::general_purpose::STANDARD.encode(credentials));
+        request.push_str("\r\n");
+    }
+    request.push_str("\r\n");
+    stream.write_all(request.as_bytes()).await?;
+
+    // Read the proxy's response headers byte by byte so no tunnelled data is consumed.
+    let mut response = Vec::new();
+    while !response.ends_with(b"\r\n\r\n") {
+        if response.len() > 8192 {
+            anyhow::bail!("proxy {proxy_host} sent an oversized CONNECT response");
+        }
+        let byte = stream.read_u8().await?;
+        response.push(byte);
+    }
+    let status_line = String::from_utf8_lossy(&response);
+    let status_line = status_line.lines().next().unwrap_or("");
+    if status_line.split_whitespace().nth(1) != Some("200") {
+        anyhow::bail!("proxy {proxy_host} refused CONNECT to {host}:{port}: {status_line}");
+    }
+    Ok(stream)
+}
+
+/// rustls config trusting the bundled web roots plus the certificates in `path`.
+fn tls_config_with_bundle(path: &str) -> anyhow::Result<rustls::ClientConfig> {
+    use rustls::pki_types::{pem::PemObject, CertificateDer};
+
+    let mut roots = rustls::RootCertStore {
+        roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+    };
+    let pem = std::fs::read(path).with_context(|| format!("failed to read CA bundle {path}"))?;
+    for cert in CertificateDer::pem_slice_iter(&pem) {
+        let cert = cert.with_context(|| format!("failed to parse CA bundle {path}"))?;
+        roots
+            .add(cert)
+            .with_context(|| format!("invalid certificate in CA bundle {path}"))?;
+    }
+    Ok(rustls::ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn override_fields_win_over_defaults() {
+        let config = HttpConfig {
+            defaults: HttpClientConfig {
+                proxy: Some("http://proxy:3128".into()),
+                timeout_secs: Some(30),
+                ..HttpClientConfig::default()
+            },
+            overrides: HashMap::from([(
+                "ollama".to_string(),
+                HttpClientConfig {
+                    proxy: Some(String::new()),
+                    ..HttpClientConfig::default()
+                },
+            )]),
+        };
+
+        let ollama = config.for_component("ollama");
+        assert_eq!(ollama.proxy.as_deref(), Some(""));
+        assert_eq!(ollama.timeout_secs, Some(30));
+
+        let telegram = config.for_component("telegram");
+        assert_eq!(telegram.proxy.as_deref(), Some("http://proxy:3128"));
+    }
+
+    #[test]
+    fn builds_with_proxy_and_timeouts() {
+        let settings = HttpClientConfig {
+            proxy: Some("http://proxy.example:3128".into()),
+            no_proxy: Some("localhost,127.0.0.1".into()),
+            connect_timeout_secs: Some(5),
+            timeout_secs: Some(60),
+            user_agent: Some("custom-agent/1.0".into()),
+            ..HttpClientConfig::default()
+        };
+        assert!(build_client(&settings, Defaults::PROVIDER).is_ok());
+    }
+
+    #[test]
+    fn empty_proxy_disables_proxying() {
+        let settings = HttpClientConfig {
+            proxy: Some(String::new()),
+            ..HttpClientConfig::default()
+        };
+        assert!(build_client(&settings, Defaults::NONE).is_ok());
+    }
+
+    #[test]
+    fn invalid_proxy_errors() {
+        let settings = HttpClientConfig {
+            proxy: Some("not a url".into()),
+            ..HttpClientConfig::default()
+        };
+        let err = build_client(&settings, Defaults::NONE).unwrap_err();
+        assert!(err.to_string().contains("invalid proxy URL"));
+    }
+
+    #[test]
+    fn missing_ca_bundle_errors() {
+        let settings = HttpClientConfig {
+            ca_bundle: Some("/nonexistent/ca.pem".into()),
+            ..HttpClientConfig::default()
+        };
+        let err = build_client(&settings, Defaults::NONE).unwrap_err();
+        assert!(err.to_string().contains("failed to read CA bundle"));
+    }
+
+    #[test]
+    fn init_rejects_unusable_override_without_installing() {
+        let mut config = HttpConfig::default();
+        config.overrides.insert(
+            "telegram".into(),
+            HttpClientConfig {
+                ca_bundle: Some("/nonexistent/ca.pem".into()),
+                ..HttpClientConfig::default()
+            },
+        );
+        let err = init(&config).unwrap_err();
+        assert!(format!("{err:#}").contains("[http.overrides.telegram]"));
+        assert!(format!("{err:#}").contains("failed to read CA bundle"));
+    }
+
+    #[test]
+    fn blocking_client_honors_the_same_settings() {
+        let settings = HttpClientConfig {
+            proxy: Some("http://proxy.example:3128".into()),
+            timeout_secs: Some(60),
+            ..HttpClientConfig::default()
+        };
+        assert!(build_blocking_client(&settings, Defaults::PROVIDER).is_ok());
+        let bad = HttpClientConfig {
+            proxy: Some("not a url".into()),
+            ..HttpClientConfig::default()
+        };
+        assert!(build_blocking_client(&bad, Defaults::NONE).is_err());
+    }
+
+    #[test]
+    fn malformed_ca_bundle_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("ca.pem");
+        std::fs::write(
+            &path,
+            "-----BEGIN CERTIFICATE-----\nnot base64!\n-----END CERTIFICATE-----\n",
+        )
+        .unwrap();
+        let settings = HttpClientConfig {
+            ca_bundle: Some(path.to_string_lossy().into_owned()),
+            ..HttpClientConfig::default()
+        };
+        assert!(build_client(&settings, Defaults::NONE).is_err());
+    }
+
+    #[test]
+    fn long_poll_clients_add_poll_duration_to_configured_timeout() {
+        let configured = HttpClientConfig {
+            timeout_secs: Some(10),
+            ..HttpClientConfig::default()
+        };
+        assert_eq!(
+            request_timeout(&configured, Defaults::long_poll(30)),
+            Some(Duration::from_secs(40))
+        );
+        assert_eq!(
+            request_timeout(&configured, Defaults::NONE),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            request_timeout(&HttpClientConfig::default(), Defaults::long_poll(30)),
+            None
+        );
+        assert_eq!(
+            request_timeout(&HttpClientConfig::default(), Defaults::PROVIDER),
+            Some(Duration::from_secs(120))
+        );
+    }
+
+    #[test]
+    fn no_proxy_matches_hosts_and_subdomains() {
+        let list = Some("localhost, .internal.example,slack.com");
+        assert!(bypasses_proxy(list, "localhost"));
+        assert!(bypasses_proxy(list, "ws.internal.example"));
+        assert!(bypasses_proxy(list, "wss-primary.SLACK.com"));
+        assert!(!bypasses_proxy(list, "notslack.com"));
+        assert!(!bypasses_proxy(None, "localhost"));
+        assert!(bypasses_proxy(Some("*"), "gateway.discord.gg"));
+    }
+
+    /// WebSocket echo server plus an HTTP `CONNECT` proxy in front of it.
+    async fn spawn_echo_behind_proxy(
+        accept_connect: bool,
+    ) -> (String, String, Arc<std::sync::Mutex<Vec<String>>>) {
+        use futures_util::{SinkExt, StreamExt};
+
+        let ws_listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_addr = ws_listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = ws_listener.accept().await {
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                    while let Some(Ok(msg)) = ws.next().await {
+                        if msg.is_text() && ws.send(msg).await.is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+
+        let requests = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let proxy_listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_addr = proxy_listener.local_addr().unwrap();
+        let seen = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((mut client, _)) = proxy_listener.accept().await {
+                let seen = seen.clone();
+                tokio::spawn(async move {
+                    let mut head = Vec::new();
+                    while !head.ends_with(b"\r\n\r\n") {
+                        head.push(client.read_u8().await.unwrap());
+                    }
+                    let head = String::from_utf8_lossy(&head).into_owned();
+                    seen.lock().unwrap().push(head.clone());
+                    if !accept_connect {
+                        let _ = client.write_all(b"HTTP/1.1 403 Forbidden\r\n\r\n").await;
+                        return;
+                    }
+                    let target = head.split_whitespace().nth(1).unwrap().to_string();
+                    let mut upstream = TcpStream::connect(target).await.unwrap();
+                    client
+                        .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+                        .await
+                        .unwrap();
+                    let _ = tokio::io::copy_bidirectional(&mut client, &mut upstream).await;
+                });
+            }
+        });
+
+        (
+            format!("ws://{ws_addr}/socket"),
+            format!("http://user:pass@{proxy_addr}"),
+            requests,
+        )
+    }
+
+    #[tokio::test]
+    async fn websocket_tunnels_through_http_proxy() {
+        use futures_util::{SinkExt, StreamExt};
+        use tokio_tungstenite::tungstenite::Message;
+
+        let (ws_url, proxy, requests) = spawn_echo_behind_proxy(true).await;
+        let settings = HttpClientConfig {
+            proxy: Some(proxy),
+            connect_timeout_secs: Some(5),
+            ..HttpClientConfig::default()
+        };
+        let mut ws = connect_websocket_with(&settings, &ws_url).await.unwrap();
+        ws.send(Message::Text("ping".into())).await.unwrap();
+        let reply = ws.next().await.unwrap().unwrap();
+        assert_eq!(reply.into_text().unwrap(), "ping");
+
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("CONNECT 127.0.0.1:"));
+        // base64("user:pass")
+        assert!(requests[0].contains("Proxy-Authorization: Basic dXNlcjpwYXNz"));
+    }
+
+    #[tokio::test]
+    async fn websocket_reports_refused_connect_and_honors_no_proxy() {
+        let (ws_url, proxy, requests) = spawn_echo_behind_proxy(false).await;
+        let mut settings = HttpClientConfig {
+            proxy: Some(proxy),
+            ..HttpClientConfig::default()
+        };
+        let err = connect_websocket_with(&settings, &ws_url)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("refused CONNECT"));
+
+        settings.no_proxy = Some("127.0.0.1".into());
+        assert!(connect_websocket_with(&settings, &ws_url).await.is_ok());
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn websocket_tls_config_rejects_missing_bundle() {
+        let err = tls_config_with_bundle("/nonexistent/ca.pem").unwrap_err();
+        assert!(err.to_string().contains("failed to read CA bundle"));
+    }
+
+    #[test]
+    fn http_section_parses_from_toml() {
+        let toml_str = r#"
+proxy = "socks5://127.0.0.1:1080"
+timeout_secs = 90
+
+[overrides.telegram]
+proxy = "http://tg-proxy:8080"
+"#;
+        let config: HttpConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.defaults.proxy.as_deref(),
+            Some("socks5://127.0.0.1:1080")
+        );
+        assert_eq!(config.defaults.timeout_secs, Some(90));
+        assert_eq!(
+            config.for_component("telegram").proxy.as_deref(),
+            Some("http://tg-proxy:8080")
+        );
+    }
+}
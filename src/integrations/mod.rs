@@ -133,7 +133,14 @@ fn show_integration_info(config: &Config, name: &str) -> Result<()> {
             println!("  Setup:");
             println!("    1. Install: brew install ollama");
             println!("    2. Pull a model: ollama pull llama3");
-            println!("    3. Set provider to 'ollama' in config.toml");
+            println!("    3. Run: zeroclaw onboard --interactive (auto-detects the server)");
+            println!("    Non-default host? Set [ollama] base_url or OLLAMA_HOST.");
+        }
+        "LM Studio" | "Hugging Face" => {
+            println!("  Setup:");
+            println!("    1. Start the local server (LM Studio, or TGI on port 8080/3000)");
+            println!("    2. Run: zeroclaw onboard --interactive → Local / private");
+            println!("    Check detection any time with: zeroclaw doctor");
         }
         "iMessage" => {
             println!("  Setup (macOS only):");
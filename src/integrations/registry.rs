@@ -248,15 +248,24 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
         },
         IntegrationEntry {
             name: "Hugging Face",
-            description: "Open-source models",
+            description: "Open-source models via local TGI (auto-detected)",
             category: IntegrationCategory::AiModel,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |_| IntegrationStatus::Available,
         },
         IntegrationEntry {
             name: "LM Studio",
-            description: "Local model server",
+            description: "Local model server (auto-detected)",
             category: IntegrationCategory::AiModel,
-            status_fn: |_| IntegrationStatus::ComingSoon,
+            status_fn: |c| {
+                if c.default_provider
+                    .as_deref()
+                    .is_some_and(|p| p.starts_with("custom:") && p.contains(":1234"))
+                {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
         },
         IntegrationEntry {
             name: "Venice",
@@ -378,6 +387,21 @@ pub fn all_integrations() -> Vec<IntegrationEntry> {
                 }
             },
         },
+        IntegrationEntry {
+            name: "Azure OpenAI",
+            description: "OpenAI models via Azure deployments",
+            category: IntegrationCategory::AiModel,
+            status_fn: |c| {
+                if c.default_provider
+                    .as_deref()
+                    .is_some_and(|p| p == "azure" || p.starts_with("azure:"))
+                {
+                    IntegrationStatus::Active
+                } else {
+                    IntegrationStatus::Available
+                }
+            },
+        },
         IntegrationEntry {
             name: "Qianfan",
             description: "Baidu AI models",
@@ -742,6 +766,9 @@ mod tests {
         config.channels_config.telegram = Some(TelegramConfig {
             bot_token: "123:ABC".into(),
             allowed_users: vec!["user".into()],
+            mode: "polling".into(),
+            webhook_url: None,
+            webhook_secret: None,
         });
         let entries = all_integrations();
         let tg = entries.iter().find(|e| e.name == "Telegram").unwrap();
@@ -47,6 +47,7 @@ pub mod doctor;
 pub mod gateway;
 pub mod health;
 pub mod heartbeat;
+pub mod http;
 pub mod identity;
 pub mod integrations;
 pub mod memory;
@@ -132,6 +133,194 @@ pub enum MigrateCommands {
         #[arg(long)]
         dry_run: bool,
     },
+
+    /// Import a ChatGPT data export (`conversations.json` or its folder)
+    Chatgpt {
+        /// Path to `conversations.json` or the unzipped export folder
+        source: std::path::PathBuf,
+
+        /// Validate and preview migration without writing any data
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Distill each conversation into core facts with the model instead of importing transcripts
+        #[arg(long)]
+        distill: bool,
+    },
+
+    /// Import a Claude data export (`conversations.json` or its folder)
+    Claude {
+        /// Path to `conversations.json` or the unzipped export folder
+        source: std::path::PathBuf,
+
+        /// Validate and preview migration without writing any data
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Distill each conversation into core facts with the model instead of importing transcripts
+        #[arg(long)]
+        distill: bool,
+    },
+
+    /// Import chat messages from JSONL: one message (`role`, `content`, optional
+    /// `conversation`, `timestamp`) or one conversation (`title`, `messages`) per line
+    Jsonl {
+        /// Path to the `.jsonl` file
+        source: std::path::PathBuf,
+
+        /// Validate and preview migration without writing any data
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Distill each conversation into core facts with the model instead of importing transcripts
+        #[arg(long)]
+        distill: bool,
+    },
+
+    /// Import a folder of markdown notes (e.g. an Obsidian vault), one memory per note section
+    MarkdownDir {
+        /// Notes folder; hidden folders such as `.obsidian` are skipped
+        source: std::path::PathBuf,
+
+        /// Validate and preview migration without writing any data
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+/// Memory subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MemoryCommands {
+    /// List memories, newest first
+    List {
+        /// Only this category (core, daily, conversation, document, ...)
+        #[arg(long)]
+        category: Option<String>,
+        /// Only this session (e.g. telegram_alice)
+        #[arg(long)]
+        session: Option<String>,
+        /// Only entries on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only entries on or before this date (YYYY-MM-DD)
+        #[arg(long)]
+        until: Option<String>,
+        /// Max entries to show
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Search memories by relevance
+    Search {
+        /// Search query
+        query: String,
+        /// Max results
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        /// Only this category
+        #[arg(long)]
+        category: Option<String>,
+        /// Only this session
+        #[arg(long)]
+        session: Option<String>,
+        /// Only entries on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only entries on or before this date (YYYY-MM-DD)
+        #[arg(long)]
+        until: Option<String>,
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show a single memory by key
+    Get {
+        /// Memory key
+        key: String,
+        /// Print JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Forget a memory by key, or every match of --query after confirmation
+    Forget {
+        /// Memory key
+        #[arg(required_unless_present = "query", conflicts_with = "query")]
+        key: Option<String>,
+        /// Forget all memories matching this search query
+        #[arg(long)]
+        query: Option<String>,
+        /// Max matches to forget with --query
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Only this session with --query; a scope label (e.g. user:telegram:42) searches that scope
+        #[arg(long, requires = "query")]
+        session: Option<String>,
+        /// Skip the confirmation prompt
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Show entry counts per category and backend health
+    Stats {
+        /// Print JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export memories (with embeddings) in the versioned JSONL interchange format
+    Export {
+        /// Output file (defaults to stdout)
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+        /// Only this category
+        #[arg(long)]
+        category: Option<String>,
+        /// Read from this backend instead of the configured one (sqlite, markdown)
+        #[arg(long)]
+        backend: Option<String>,
+    },
+    /// Import memories from a `memory export` file
+    Import {
+        /// JSONL (or JSON array) file
+        path: std::path::PathBuf,
+        /// When a key already exists with different content: skip, overwrite or rename
+        #[arg(long, default_value = "skip")]
+        on_conflict: String,
+        /// Write to this backend instead of the configured one (sqlite, markdown)
+        #[arg(long)]
+        backend: Option<String>,
+    },
+    /// Rebuild search indexes and embed memories missing vectors
+    Reindex {
+        /// Re-embed every memory, not just those missing vectors
+        #[arg(long)]
+        all: bool,
+        /// Memories per embedding request
+        #[arg(long, default_value_t = 32)]
+        batch_size: usize,
+    },
+    /// Rewrite markdown memory files without forgotten entries (kept in an audit log)
+    Compact,
+    /// Encrypt existing memory content in brain.db (see `[memory] encrypt`)
+    Encrypt,
+    /// Decrypt memory content in brain.db back to plaintext
+    Decrypt,
+    /// Distill recent conversation entries into core facts using the LLM
+    Consolidate {
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Chunk markdown/text files into memory so the agent can recall and cite them
+    Ingest {
+        /// Files, directories or glob patterns (e.g. "runbooks/**/*.md")
+        #[arg(required = true)]
+        paths: Vec<String>,
+
+        /// Re-ingest files even if their content is unchanged
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 /// Cron subcommands
@@ -34,6 +34,7 @@
 
 use anyhow::{bail, Result};
 use clap::{Parser, Subcommand};
+use std::sync::Arc;
 use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
@@ -46,6 +47,7 @@ mod doctor;
 mod gateway;
 mod health;
 mod heartbeat;
+mod http;
 mod identity;
 mod integrations;
 mod memory;
@@ -191,6 +193,12 @@ enum Commands {
         skill_command: SkillCommands,
     },
 
+    /// Manage agent memory
+    Memory {
+        #[command(subcommand)]
+        memory_command: MemoryCommands,
+    },
+
     /// Migrate data from other agent runtimes
     Migrate {
         #[command(subcommand)]
@@ -210,6 +218,193 @@ enum MigrateCommands {
         #[arg(long)]
         dry_run: bool,
     },
+
+    /// Import a ChatGPT data export (`conversations.json` or its folder)
+    Chatgpt {
+        /// Path to `conversations.json` or the unzipped export folder
+        source: std::path::PathBuf,
+
+        /// Validate and preview migration without writing any data
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Distill each conversation into core facts with the model instead of importing transcripts
+        #[arg(long)]
+        distill: bool,
+    },
+
+    /// Import a Claude data export (`conversations.json` or its folder)
+    Claude {
+        /// Path to `conversations.json` or the unzipped export folder
+        source: std::path::PathBuf,
+
+        /// Validate and preview migration without writing any data
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Distill each conversation into core facts with the model instead of importing transcripts
+        #[arg(long)]
+        distill: bool,
+    },
+
+    /// Import chat messages from JSONL: one message (`role`, `content`, optional
+    /// `conversation`, `timestamp`) or one conversation (`title`, `messages`) per line
+    Jsonl {
+        /// Path to the `.jsonl` file
+        source: std::path::PathBuf,
+
+        /// Validate and preview migration without writing any data
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Distill each conversation into core facts with the model instead of importing transcripts
+        #[arg(long)]
+        distill: bool,
+    },
+
+    /// Import a folder of markdown notes (e.g. an Obsidian vault), one memory per note section
+    MarkdownDir {
+        /// Notes folder; hidden folders such as `.obsidian` are skipped
+        source: std::path::PathBuf,
+
+        /// Validate and preview migration without writing any data
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum MemoryCommands {
+    /// List memories, newest first
+    List {
+        /// Only this category (core, daily, conversation, document, ...)
+        #[arg(long)]
+        category: Option<String>,
+        /// Only this session (e.g. telegram_alice)
+        #[arg(long)]
+        session: Option<String>,
+        /// Only entries on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only entries on or before this date (YYYY-MM-DD)
+        #[arg(long)]
+        until: Option<String>,
+        /// Max entries to show
+        #[arg(long, default_value_t = 50)]
+        limit: usize,
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Search memories by relevance
+    Search {
+        /// Search query
+        query: String,
+        /// Max results
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+        /// Only this category
+        #[arg(long)]
+        category: Option<String>,
+        /// Only this session
+        #[arg(long)]
+        session: Option<String>,
+        /// Only entries on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only entries on or before this date (YYYY-MM-DD)
+        #[arg(long)]
+        until: Option<String>,
+        /// Print JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+    /// Show a single memory by key
+    Get {
+        /// Memory key
+        key: String,
+        /// Print JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Forget a memory by key, or every match of --query after confirmation
+    Forget {
+        /// Memory key
+        #[arg(required_unless_present = "query", conflicts_with = "query")]
+        key: Option<String>,
+        /// Forget all memories matching this search query
+        #[arg(long)]
+        query: Option<String>,
+        /// Max matches to forget with --query
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Only this session with --query; a scope label (e.g. user:telegram:42) searches that scope
+        #[arg(long, requires = "query")]
+        session: Option<String>,
+        /// Skip the confirmation prompt
+        #[arg(long, short)]
+        yes: bool,
+    },
+    /// Show entry counts per category and backend health
+    Stats {
+        /// Print JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Export memories (with embeddings) in the versioned JSONL interchange format
+    Export {
+        /// Output file (defaults to stdout)
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+        /// Only this category
+        #[arg(long)]
+        category: Option<String>,
+        /// Read from this backend instead of the configured one (sqlite, markdown)
+        #[arg(long)]
+        backend: Option<String>,
+    },
+    /// Import memories from a `memory export` file
+    Import {
+        /// JSONL (or JSON array) file
+        path: std::path::PathBuf,
+        /// When a key already exists with different content: skip, overwrite or rename
+        #[arg(long, default_value = "skip")]
+        on_conflict: String,
+        /// Write to this backend instead of the configured one (sqlite, markdown)
+        #[arg(long)]
+        backend: Option<String>,
+    },
+    /// Rebuild search indexes and embed memories missing vectors
+    Reindex {
+        /// Re-embed every memory, not just those missing vectors
+        #[arg(long)]
+        all: bool,
+        /// Memories per embedding request
+        #[arg(long, default_value_t = 32)]
+        batch_size: usize,
+    },
+    /// Rewrite markdown memory files without forgotten entries (kept in an audit log)
+    Compact,
+    /// Encrypt existing memory content in brain.db (see `[memory] encrypt`)
+    Encrypt,
+    /// Decrypt memory content in brain.db back to plaintext
+    Decrypt,
+    /// Distill recent conversation entries into core facts using the LLM
+    Consolidate {
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Chunk markdown/text files into memory so the agent can recall and cite them
+    Ingest {
+        /// Files, directories or glob patterns (e.g. "runbooks/**/*.md")
+        #[arg(required = true)]
+        paths: Vec<String>,
+
+        /// Re-ingest files even if their content is unchanged
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -321,13 +516,16 @@ async fn main() -> Result<()> {
         };
         // Auto-start channels if user said yes during wizard
         if std::env::var("ZEROCLAW_AUTOSTART_CHANNELS").as_deref() == Ok("1") {
-            channels::start_channels(config).await?;
+            http::init(&config.http)?;
+            let observer = Arc::from(observability::create_observer(&config.observability));
+            channels::start_channels(config, observer).await?;
         }
         return Ok(());
     }
 
     // All other commands need config loaded first
     let config = Config::load_or_init()?;
+    http::init(&config.http)?;
 
     match cli.command {
         Commands::Onboard { .. } => unreachable!(),
@@ -345,7 +543,8 @@ async fn main() -> Result<()> {
             } else {
                 info!("🚀 Starting ZeroClaw Gateway on {host}:{port}");
             }
-            gateway::run_gateway(&host, port, config).await
+            let observer = Arc::from(observability::create_observer(&config.observability));
+            gateway::run_gateway(&host, port, config, observer).await
         }
 
         Commands::Daemon { port, host } => {
@@ -433,7 +632,10 @@ async fn main() -> Result<()> {
         Commands::Doctor => doctor::run(&config),
 
         Commands::Channel { channel_command } => match channel_command {
-            ChannelCommands::Start => channels::start_channels(config).await,
+            ChannelCommands::Start => {
+                let observer = Arc::from(observability::create_observer(&config.observability));
+                channels::start_channels(config, observer).await
+            }
             ChannelCommands::Doctor => channels::doctor_channels(config).await,
             other => channels::handle_command(other, &config),
         },
@@ -446,6 +648,10 @@ async fn main() -> Result<()> {
             skills::handle_command(skill_command, &config.workspace_dir)
         }
 
+        Commands::Memory { memory_command } => {
+            memory::handle_command(memory_command, &config).await
+        }
+
         Commands::Migrate { migrate_command } => {
             migration::handle_command(migrate_command, &config).await
         }
@@ -0,0 +1,719 @@
+// `zeroclaw memory` — inspect and manage memory from the command line.
+//
+// Everything goes through the `Memory` trait so the same commands work for
+// the SQLite and Markdown backends. Filters (category, session, date range)
+// are a `RecallQuery`, applied by the backend for searches and client-side
+// on listed entries.
+
+use super::interchange::{self, ConflictStrategy, ImportReport};
+use super::traits::{self, Memory, MemoryCategory, MemoryEntry, MemoryScope, RecallQuery};
+use super::{consolidate, create_memory, create_sqlite_memory, ingest, MarkdownMemory};
+use crate::config::Config;
+use crate::util::truncate_with_ellipsis;
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Build the query behind `list`, `search` and `forget --query`. A session
+/// naming a memory scope (e.g. `user:telegram:42`) also makes that scope
+/// visible, so scoped entries can be searched.
+pub fn build_query(
+    query: &str,
+    limit: usize,
+    category: Option<&str>,
+    session: Option<String>,
+    since: Option<&str>,
+    until: Option<&str>,
+) -> Result<RecallQuery> {
+    Ok(RecallQuery {
+        scopes: session
+            .as_deref()
+            .and_then(|s| s.parse::<MemoryScope>().ok())
+            .filter(|s| *s != MemoryScope::Global)
+            .into_iter()
+            .collect(),
+        category: category.map(|c| c.parse().unwrap_or_else(|e| match e {})),
+        since: since
+            .map(|s| traits::parse_time_bound(s, false))
+            .transpose()?,
+        until: until
+            .map(|s| traits::parse_time_bound(s, true))
+            .transpose()?,
+        session,
+        ..RecallQuery::new(query, limit)
+    })
+}
+
+pub async fn handle_command(command: crate::MemoryCommands, config: &Config) -> Result<()> {
+    // Opened directly so the factory doesn't start a competing background job.
+    match command {
+        crate::MemoryCommands::Reindex { all, batch_size } => {
+            return reindex(config, all, batch_size).await;
+        }
+        crate::MemoryCommands::Compact => return compact(config).await,
+        crate::MemoryCommands::Encrypt => return encryption(config, true),
+        crate::MemoryCommands::Decrypt => return encryption(config, false),
+        crate::MemoryCommands::Consolidate { dry_run } => {
+            return consolidate(config, dry_run).await
+        }
+        _ => {}
+    }
+
+    // `export`/`import --backend` move memories between backends.
+    let mut memory_config = config.memory.clone();
+    if let crate::MemoryCommands::Export {
+        backend: Some(backend),
+        ..
+    }
+    | crate::MemoryCommands::Import {
+        backend: Some(backend),
+        ..
+    } = &command
+    {
+        memory_config.backend.clone_from(backend);
+    }
+    let mem = create_memory(
+        &memory_config,
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    let mem = mem.as_ref();
+
+    match command {
+        crate::MemoryCommands::List {
+            category,
+            session,
+            since,
+            until,
+            limit,
+            json,
+        } => {
+            let query = build_query(
+                "",
+                limit,
+                category.as_deref(),
+                session,
+                since.as_deref(),
+                until.as_deref(),
+            )?;
+            let entries = list(mem, &query).await?;
+            print_entries(&entries, json)
+        }
+        crate::MemoryCommands::Search {
+            query,
+            limit,
+            category,
+            session,
+            since,
+            until,
+            json,
+        } => {
+            let query = build_query(
+                &query,
+                limit,
+                category.as_deref(),
+                session,
+                since.as_deref(),
+                until.as_deref(),
+            )?;
+            let entries = mem.recall_query(&query).await?;
+            print_entries(&entries, json)
+        }
+        crate::MemoryCommands::Get { key, json } => match mem.get(&key).await? {
+            Some(entry) if json => {
+                println!("{}", serde_json::to_string_pretty(&entry)?);
+                Ok(())
+            }
+            Some(entry) => {
+                println!("Key:       {}", entry.key);
+                println!("Category:  {}", entry.category);
+                println!("Timestamp: {}", entry.timestamp);
+                if let Some(importance) = entry.importance {
+                    println!("Importance: {importance:.2}");
+                }
+                if let Some(session) = &entry.session_id {
+                    println!("Session:   {session}");
+                }
+                if let Some(origin) = consolidate::provenance(&config.workspace_dir, &entry.key)? {
+                    println!("Sources:   {}", origin.sources.join(", "));
+                }
+                println!();
+                println!("{}", entry.content);
+                Ok(())
+            }
+            None => bail!("No memory found with key '{key}'"),
+        },
+        crate::MemoryCommands::Forget {
+            key,
+            query,
+            limit,
+            session,
+            yes,
+        } => {
+            if let Some(key) = key {
+                if mem.forget(&key).await? {
+                    println!("🗑️  Forgot '{key}'");
+                } else {
+                    println!("Nothing removed for '{key}' ({} backend)", mem.name());
+                }
+                return Ok(());
+            }
+            let Some(query) = query else {
+                bail!("Pass a key or --query <text>");
+            };
+
+            let matches = mem
+                .recall_query(&build_query(&query, limit, None, session, None, None)?)
+                .await?;
+            if matches.is_empty() {
+                println!("No memories match '{query}'");
+                return Ok(());
+            }
+            print_entries(&matches, false)?;
+
+            if !yes
+                && !dialoguer::Confirm::new()
+                    .with_prompt(format!("Forget these {} memories?", matches.len()))
+                    .default(false)
+                    .interact()?
+            {
+                println!("Aborted.");
+                return Ok(());
+            }
+
+            let removed = forget_all(mem, &matches).await?;
+            println!("🗑️  Forgot {removed} of {} memories", matches.len());
+            if removed < matches.len() {
+                println!(
+                    "  The {} backend kept {} entries.",
+                    mem.name(),
+                    matches.len() - removed
+                );
+            }
+            Ok(())
+        }
+        crate::MemoryCommands::Stats { json } => {
+            let stats = stats(mem).await?;
+            if json {
+                println!("{}", serde_json::to_string_pretty(&stats)?);
+            } else {
+                print!("{}", render_stats(&stats));
+            }
+            Ok(())
+        }
+        crate::MemoryCommands::Export {
+            output, category, ..
+        } => {
+            let category = category.map(|c| c.parse().unwrap_or_else(|e| match e {}));
+            let (body, count) = export(mem, category.as_ref()).await?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, body)
+                        .with_context(|| format!("failed to write {}", path.display()))?;
+                    println!("📤 Exported {count} memories to {}", path.display());
+                }
+                None => print!("{body}"),
+            }
+            Ok(())
+        }
+        crate::MemoryCommands::Import {
+            path, on_conflict, ..
+        } => {
+            let report = import_file(mem, &path, on_conflict.parse()?).await?;
+            println!(
+                "📥 Imported {} new memories into {} ({} overwritten, {} renamed, {} skipped, {} unchanged)",
+                report.imported,
+                mem.name(),
+                report.overwritten,
+                report.renamed,
+                report.skipped,
+                report.unchanged
+            );
+            Ok(())
+        }
+        crate::MemoryCommands::Reindex { .. }
+        | crate::MemoryCommands::Compact
+        | crate::MemoryCommands::Encrypt
+        | crate::MemoryCommands::Decrypt
+        | crate::MemoryCommands::Consolidate { .. } => unreachable!(),
+        crate::MemoryCommands::Ingest { paths, force } => {
+            let report = ingest::ingest(
+                mem,
+                &config.workspace_dir,
+                &paths,
+                config.memory.chunk_max_tokens,
+                force,
+            )
+            .await?;
+
+            println!("✅ Ingested documents into {} memory", mem.name());
+            println!("  Files ingested:  {}", report.files_ingested);
+            println!("  Files unchanged: {}", report.files_unchanged);
+            if report.files_skipped > 0 {
+                println!("  Files skipped:   {}", report.files_skipped);
+            }
+            println!("  Chunks stored:   {}", report.chunks_stored);
+            println!("  Chunks removed:  {}", report.chunks_removed);
+            Ok(())
+        }
+    }
+}
+
+async fn compact(config: &Config) -> Result<()> {
+    if config.memory.backend != "markdown" {
+        bail!(
+            "Compaction applies to the markdown memory backend (current: {})",
+            config.memory.backend
+        );
+    }
+    let mem = MarkdownMemory::new(&config.workspace_dir);
+    let report = mem.compact().await?;
+    if report.entries_removed == 0 {
+        println!("Nothing to compact — no forgotten entries.");
+    } else {
+        println!("✅ Compacted markdown memory");
+        println!("  Entries removed: {}", report.entries_removed);
+        println!("  Files rewritten: {}", report.files_rewritten);
+        println!("  Audit log:       {}", mem.audit_path().display());
+    }
+    Ok(())
+}
+
+/// Seal or open every row of `brain.db` in place.
+fn encryption(config: &Config, encrypt: bool) -> Result<()> {
+    if config.memory.backend != "sqlite" {
+        bail!(
+            "Encryption at rest applies to the sqlite memory backend (current: {})",
+            config.memory.backend
+        );
+    }
+    let mut memory_config = config.memory.clone();
+    memory_config.encrypt = encrypt;
+    let mem = create_sqlite_memory(
+        &memory_config,
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    let changed = mem.migrate_encryption(encrypt)?;
+    let verb = if encrypt { "Encrypted" } else { "Decrypted" };
+    println!("✅ {verb} {changed} memories");
+    if config.memory.encrypt != encrypt {
+        println!("  Set [memory] encrypt = {encrypt} so new memories are stored the same way.");
+    }
+    Ok(())
+}
+
+async fn consolidate(config: &Config, dry_run: bool) -> Result<()> {
+    let report = consolidate::run_with_config(config, dry_run).await?;
+    if report.entries_considered == 0 {
+        println!("Nothing to consolidate — no new conversation entries.");
+        return Ok(());
+    }
+    if dry_run {
+        println!("Dry run — no changes written");
+    } else {
+        println!("✅ Consolidated memory");
+    }
+    println!("  Entries considered:     {}", report.entries_considered);
+    println!("  Facts created:          {}", report.facts_created);
+    println!("  Facts merged:           {}", report.facts_merged);
+    println!(
+        "  Contradictions flagged: {}",
+        report.contradictions_flagged
+    );
+    if report.contradictions_flagged > 0 {
+        println!(
+            "  Review them with: zeroclaw memory list --category {}",
+            consolidate::REVIEW_CATEGORY
+        );
+    }
+    Ok(())
+}
+
+async fn reindex(config: &Config, all: bool, batch_size: usize) -> Result<()> {
+    use std::io::Write as _;
+
+    if config.memory.backend != "sqlite" {
+        bail!(
+            "Reindexing needs the sqlite memory backend (current: {})",
+            config.memory.backend
+        );
+    }
+    let mem = create_sqlite_memory(
+        &config.memory,
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    if all {
+        mem.reset_embeddings()?;
+    }
+
+    let count = mem
+        .reindex_with_progress(batch_size, &|p| {
+            print!("\r  Re-embedded {}/{}", p.done, p.total);
+            let _ = std::io::stdout().flush();
+        })
+        .await?;
+    if count > 0 {
+        println!();
+    }
+
+    if config.memory.embedding_provider == "none" {
+        println!("✅ Rebuilt keyword index (embeddings disabled)");
+    } else {
+        println!("✅ Reindexed memory: {count} entries re-embedded");
+    }
+    Ok(())
+}
+
+/// Every entry matching the filters of `query`; its search text is ignored.
+pub async fn list(mem: &dyn Memory, query: &RecallQuery) -> Result<Vec<MemoryEntry>> {
+    Ok(mem
+        .list(query.category.as_ref())
+        .await?
+        .into_iter()
+        .filter(|e| query.matches(e))
+        .take(query.limit)
+        .collect())
+}
+
+pub async fn forget_all(mem: &dyn Memory, entries: &[MemoryEntry]) -> Result<usize> {
+    let mut removed = 0;
+    for entry in entries {
+        if mem.forget(&entry.key).await? {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MemoryStats {
+    pub backend: String,
+    pub healthy: bool,
+    pub total: usize,
+    pub by_category: BTreeMap<String, usize>,
+    pub oldest: Option<String>,
+    pub newest: Option<String>,
+}
+
+pub async fn stats(mem: &dyn Memory) -> Result<MemoryStats> {
+    let entries = mem.list(None).await?;
+    let mut by_category = BTreeMap::new();
+    for entry in &entries {
+        *by_category.entry(entry.category.to_string()).or_insert(0) += 1;
+    }
+    let mut dates: Vec<&str> = entries.iter().map(|e| e.timestamp.as_str()).collect();
+    dates.sort_unstable();
+
+    Ok(MemoryStats {
+        backend: mem.name().to_string(),
+        healthy: mem.health_check().await,
+        total: mem.count().await?,
+        by_category,
+        oldest: dates.first().map(ToString::to_string),
+        newest: dates.last().map(ToString::to_string),
+    })
+}
+
+fn render_stats(stats: &MemoryStats) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "🧠 Memory stats");
+    let _ = writeln!(
+        out,
+        "  Backend:  {} ({})",
+        stats.backend,
+        if stats.healthy {
+            "healthy"
+        } else {
+            "unhealthy"
+        }
+    );
+    let _ = writeln!(out, "  Entries:  {}", stats.total);
+    for (category, count) in &stats.by_category {
+        let _ = writeln!(out, "    - {category:<14} {count}");
+    }
+    if let (Some(oldest), Some(newest)) = (&stats.oldest, &stats.newest) {
+        let _ = writeln!(out, "  Oldest:   {oldest}");
+        let _ = writeln!(out, "  Newest:   {newest}");
+    }
+    out
+}
+
+fn print_entries(entries: &[MemoryEntry], json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string_pretty(entries)?);
+    } else if entries.is_empty() {
+        println!("No memories found.");
+    } else {
+        print!("{}", render_table(entries));
+    }
+    Ok(())
+}
+
+fn render_table(entries: &[MemoryEntry]) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:<32} {:<14} {:<20} CONTENT",
+        "KEY", "CATEGORY", "TIMESTAMP"
+    );
+    for entry in entries {
+        let _ = writeln!(
+            out,
+            "{:<32} {:<14} {:<20} {}",
+            truncate_with_ellipsis(&entry.key, 29),
+            truncate_with_ellipsis(&entry.category.to_string(), 11),
+            entry.timestamp.get(..19).unwrap_or(&entry.timestamp),
+            truncate_with_ellipsis(&entry.content.replace('\n', " "), 60)
+        );
+    }
+    let _ = writeln!(out, "\n{} entries", entries.len());
+    out
+}
+
+/// Snapshot `mem` in the interchange format (see [`interchange`]),
+/// optionally only one category. Returns the file body and record count.
+pub async fn export(
+    mem: &dyn Memory,
+    category: Option<&MemoryCategory>,
+) -> Result<(String, usize)> {
+    let mut snapshot = mem.snapshot().await?;
+    if let Some(category) = category {
+        snapshot.records.retain(|r| &r.category == category);
+    }
+    let count = snapshot.records.len();
+    Ok((interchange::write(&snapshot, mem.name())?, count))
+}
+
+/// Import a `memory export` file, current or legacy format.
+pub async fn import_file(
+    mem: &dyn Memory,
+    path: &Path,
+    strategy: ConflictStrategy,
+) -> Result<ImportReport> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let snapshot =
+        interchange::parse(&raw).with_context(|| format!("failed to parse {}", path.display()))?;
+    interchange::import(mem, &snapshot, strategy).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MarkdownMemory, SqliteMemory};
+    use tempfile::TempDir;
+
+    fn entry(key: &str, category: MemoryCategory, timestamp: &str) -> MemoryEntry {
+        MemoryEntry {
+            id: key.into(),
+            key: key.into(),
+            content: format!("content of {key}"),
+            category,
+            timestamp: timestamp.into(),
+            session_id: None,
+            score: None,
+            importance: None,
+            score_breakdown: None,
+        }
+    }
+
+    #[test]
+    fn query_filters_by_category_session_and_date() {
+        let e = entry(
+            "telegram_alice_42",
+            MemoryCategory::Conversation,
+            "2025-03-10T12:00:00+00:00",
+        );
+        let query = |category, session: Option<&str>, since, until| {
+            build_query("", 10, category, session.map(String::from), since, until).unwrap()
+        };
+
+        assert!(query(None, None, None, None).matches(&e));
+        assert!(query(Some("conversation"), None, None, None).matches(&e));
+        assert!(!query(Some("core"), None, None, None).matches(&e));
+
+        assert!(query(None, Some("telegram_alice"), None, None).matches(&e));
+        assert!(!query(None, Some("telegram_bob"), None, None).matches(&e));
+
+        assert!(query(None, None, Some("2025-03-01"), Some("2025-03-10")).matches(&e));
+        assert!(!query(None, None, Some("2025-03-11"), None).matches(&e));
+
+        // Undated entries never satisfy a date filter.
+        let core = entry("note", MemoryCategory::Core, "MEMORY");
+        assert!(!query(None, None, Some("2000-01-01"), None).matches(&core));
+
+        // A session naming a scope makes the scope visible.
+        let scoped = query(None, Some("user:telegram:alice"), None, None);
+        assert_eq!(
+            scoped.scopes,
+            vec![MemoryScope::User("telegram:alice".into())]
+        );
+        assert!(query(None, Some("telegram_alice"), None, None)
+            .scopes
+            .is_empty());
+    }
+
+    #[test]
+    fn invalid_date_is_rejected() {
+        let err = build_query("", 10, None, None, Some("03/10/2025"), None).unwrap_err();
+        assert!(err.to_string().contains("expected YYYY-MM-DD"));
+    }
+
+    #[test]
+    fn table_truncates_long_fields() {
+        let mut e = entry("k", MemoryCategory::Core, "2025-03-10T12:00:00+00:00");
+        e.content = "x".repeat(200);
+        let table = render_table(&[e]);
+        assert!(table.starts_with("KEY"));
+        assert!(table.contains("2025-03-10T12:00:00 "));
+        assert!(table.contains("..."));
+        assert!(table.contains("1 entries"));
+    }
+
+    #[tokio::test]
+    async fn export_import_roundtrip_between_backends() {
+        let src_dir = TempDir::new().unwrap();
+        let src = SqliteMemory::new(src_dir.path()).unwrap();
+        src.store("lang", "Prefers Rust", MemoryCategory::Core)
+            .await
+            .unwrap();
+        src.store("tz", "Lives in UTC+2", MemoryCategory::Core)
+            .await
+            .unwrap();
+        src.store("standup", "moved to 10am", MemoryCategory::Daily)
+            .await
+            .unwrap();
+
+        let (body, count) = export(&src, Some(&MemoryCategory::Core)).await.unwrap();
+        assert_eq!(count, 2);
+        assert!(body.starts_with(r#"{"format":"zeroclaw-memory","version":1"#));
+        let path = src_dir.path().join("export.jsonl");
+        std::fs::write(&path, body).unwrap();
+
+        let dst_dir = TempDir::new().unwrap();
+        let dst = SqliteMemory::new(dst_dir.path()).unwrap();
+        let report = import_file(&dst, &path, ConflictStrategy::Skip)
+            .await
+            .unwrap();
+        assert_eq!(report.imported, 2);
+        let report = import_file(&dst, &path, ConflictStrategy::Skip)
+            .await
+            .unwrap();
+        assert_eq!((report.imported, report.unchanged), (0, 2));
+
+        let md_dir = TempDir::new().unwrap();
+        let md = MarkdownMemory::new(md_dir.path());
+        let report = import_file(&md, &path, ConflictStrategy::Skip)
+            .await
+            .unwrap();
+        assert_eq!(report.imported, 2);
+        assert_eq!(md.count().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn import_keeps_entry_scope_and_reads_legacy_exports() {
+        let src_dir = TempDir::new().unwrap();
+        let path = src_dir.path().join("export.jsonl");
+        // Pre-versioning exports were bare `MemoryEntry` lines.
+        std::fs::write(
+            &path,
+            r#"{"id":"1","key":"user:telegram:alice/pin","content":"door pin 5512","category":"core","timestamp":"2025-01-01T00:00:00+00:00","session_id":"user:telegram:alice","score":null}"#,
+        )
+        .unwrap();
+
+        let dst_dir = TempDir::new().unwrap();
+        let dst = SqliteMemory::new(dst_dir.path()).unwrap();
+        let report = import_file(&dst, &path, ConflictStrategy::Skip)
+            .await
+            .unwrap();
+        assert_eq!(report.imported, 1);
+        assert!(dst.recall("door pin", 5).await.unwrap().is_empty());
+
+        let query = build_query(
+            "door pin",
+            5,
+            None,
+            Some("user:telegram:alice".into()),
+            None,
+            None,
+        )
+        .unwrap();
+        let hits = dst.recall_query(&query).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].key, "user:telegram:alice/pin");
+        assert_eq!(hits[0].timestamp, "2025-01-01T00:00:00+00:00");
+    }
+
+    #[tokio::test]
+    async fn search_and_bulk_forget() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("a", "deploy script lives in ops/", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("b", "deploy happens on Fridays", MemoryCategory::Daily)
+            .await
+            .unwrap();
+        mem.store("c", "favourite colour is green", MemoryCategory::Core)
+            .await
+            .unwrap();
+
+        let alice = MemoryScope::User("telegram:alice".into());
+        mem.store_scoped(
+            "d",
+            "deploy key is in the vault",
+            MemoryCategory::Core,
+            &alice,
+        )
+        .await
+        .unwrap();
+
+        let core_only = build_query("deploy", 10, Some("core"), None, None, None).unwrap();
+        let hits = mem.recall_query(&core_only).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].key, "a");
+
+        let hits = mem
+            .recall_query(&build_query("deploy", 10, None, None, None, None).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(forget_all(&mem, &hits).await.unwrap(), 2);
+        assert_eq!(mem.count().await.unwrap(), 2);
+
+        // `forget --query --session <scope>` reaches scoped entries.
+        let scoped = build_query(
+            "deploy",
+            10,
+            None,
+            Some("user:telegram:alice".into()),
+            None,
+            None,
+        )
+        .unwrap();
+        let hits = mem.recall_query(&scoped).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(forget_all(&mem, &hits).await.unwrap(), 1);
+        assert_eq!(mem.count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn stats_count_categories() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("a", "one", MemoryCategory::Core).await.unwrap();
+        mem.store("b", "two", MemoryCategory::Core).await.unwrap();
+        mem.store("c", "three", MemoryCategory::Daily)
+            .await
+            .unwrap();
+
+        let s = stats(&mem).await.unwrap();
+        assert_eq!(s.backend, "sqlite");
+        assert_eq!(s.total, 3);
+        assert_eq!(s.by_category.get("core"), Some(&2));
+        assert_eq!(s.by_category.get("daily"), Some(&1));
+        assert!(render_stats(&s).contains("Entries:  3"));
+    }
+}
@@ -0,0 +1,694 @@
+//! LLM-driven consolidation: recent conversation/daily entries → concise core facts.
+//!
+//! Each run summarizes unprocessed entries per scope, merges facts that restate
+//! an existing one (same key or embedding similarity), files contradictions under
+//! the `review` category instead of overwriting, and records which source
+//! entries every core fact came from.
+
+use super::embeddings::EmbeddingProvider;
+use super::traits::{Memory, MemoryCategory, MemoryEntry, MemoryScope};
+use super::{ingest, vector};
+use crate::config::{Config, ConsolidationConfig};
+use crate::providers::Provider;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const STATE_FILE: &str = "memory_consolidation_state.json";
+const PROVENANCE_FILE: &str = "memory_provenance.json";
+
+/// Existing facts shown to the model so it can reuse keys and spot conflicts.
+const MAX_EXISTING_FACTS: usize = 100;
+
+/// Category of contradiction flags awaiting human review.
+pub const REVIEW_CATEGORY: &str = "review";
+
+const SYSTEM_PROMPT: &str = "You maintain an assistant's long-term memory. From the new \
+entries, extract durable facts, preferences and decisions worth remembering beyond this \
+conversation. Skip small talk, one-off requests and anything an existing fact already \
+covers unless the entry adds or changes information.\n\n\
+Reply with JSON only:\n\
+{\"facts\": [{\"key\": \"snake_case_topic\", \"fact\": \"one concise sentence\", \
+\"sources\": [\"entry keys\"], \"contradicts\": null}]}\n\n\
+Reuse an existing fact's key when restating or refining it. If a new entry conflicts \
+with an existing fact, set \"contradicts\" to that fact's key rather than replacing it. \
+Return {\"facts\": []} when nothing is worth keeping.";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ConsolidationState {
+    last_run_at: Option<String>,
+    /// Source entries already summarized (pruned once they leave memory)
+    processed: BTreeSet<String>,
+}
+
+/// Where a core fact came from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Keys of the conversation/daily entries the fact was distilled from
+    pub sources: Vec<String>,
+    pub updated_at: String,
+}
+
+/// Outcome of one consolidation run.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConsolidationReport {
+    pub entries_considered: usize,
+    pub facts_created: usize,
+    pub facts_merged: usize,
+    pub contradictions_flagged: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct Proposal {
+    #[serde(default)]
+    facts: Vec<ProposedFact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProposedFact {
+    key: String,
+    fact: String,
+    #[serde(default)]
+    sources: Vec<String>,
+    #[serde(default)]
+    contradicts: Option<String>,
+}
+
+/// A core fact of the scope being consolidated, with its embedding if any.
+struct Fact {
+    key: String,
+    content: String,
+    embedding: Option<Vec<f32>>,
+}
+
+pub struct Consolidator<'a> {
+    pub memory: &'a dyn Memory,
+    pub provider: &'a dyn Provider,
+    pub embedder: &'a dyn EmbeddingProvider,
+    pub model: &'a str,
+    pub config: &'a ConsolidationConfig,
+    pub workspace_dir: &'a Path,
+}
+
+impl Consolidator<'_> {
+    /// Consolidate unprocessed entries. With `dry_run` nothing is written.
+    pub async fn run(&self, dry_run: bool) -> Result<ConsolidationReport> {
+        let mut state = load_json::<ConsolidationState>(&state_path(self.workspace_dir))?;
+        let mut provenance =
+            load_json::<BTreeMap<String, Provenance>>(&provenance_path(self.workspace_dir))?;
+        let mut report = ConsolidationReport::default();
+
+        let mut candidates = Vec::new();
+        for category in [MemoryCategory::Conversation, MemoryCategory::Daily] {
+            candidates.extend(
+                self.memory
+                    .list(Some(&category))
+                    .await?
+                    .into_iter()
+                    .filter(|e| ingest::document_source(&e.key).is_none()),
+            );
+        }
+        let present: BTreeSet<&str> = candidates.iter().map(|e| e.key.as_str()).collect();
+        state.processed.retain(|k| present.contains(k.as_str()));
+        candidates.retain(|e| !state.processed.contains(&e.key));
+        candidates.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        candidates.truncate(self.config.max_entries);
+        report.entries_considered = candidates.len();
+
+        if !candidates.is_empty() {
+            let core: Vec<MemoryEntry> = self
+                .memory
+                .list(Some(&MemoryCategory::Core))
+                .await?
+                .into_iter()
+                .filter(|e| ingest::document_source(&e.key).is_none())
+                .collect();
+
+            // Never mix scopes: one user's entries only feed that user's facts.
+            let mut groups: BTreeMap<String, (MemoryScope, Vec<MemoryEntry>)> = BTreeMap::new();
+            for entry in candidates {
+                let scope = MemoryScope::of(&entry);
+                groups
+                    .entry(scope.to_string())
+                    .or_insert_with(|| (scope, Vec::new()))
+                    .1
+                    .push(entry);
+            }
+
+            for (scope, entries) in groups.into_values() {
+                self.consolidate_scope(
+                    &scope,
+                    &entries,
+                    &core,
+                    &mut provenance,
+                    &mut report,
+                    dry_run,
+                )
+                .await?;
+                state.processed.extend(entries.into_iter().map(|e| e.key));
+            }
+        }
+
+        if !dry_run {
+            state.last_run_at = Some(Utc::now().to_rfc3339());
+            save_json(&state_path(self.workspace_dir), &state)?;
+            save_json(&provenance_path(self.workspace_dir), &provenance)?;
+        }
+        Ok(report)
+    }
+
+    async fn consolidate_scope(
+        &self,
+        scope: &MemoryScope,
+        entries: &[MemoryEntry],
+        core: &[MemoryEntry],
+        provenance: &mut BTreeMap<String, Provenance>,
+        report: &mut ConsolidationReport,
+        dry_run: bool,
+    ) -> Result<()> {
+        let mut facts: Vec<Fact> = core
+            .iter()
+            .filter(|e| MemoryScope::of(e) == *scope)
+            .take(MAX_EXISTING_FACTS)
+            .map(|e| Fact {
+                key: scope.unqualify(&e.key).to_string(),
+                content: e.content.clone(),
+                embedding: None,
+            })
+            .collect();
+        self.embed_facts(&mut facts).await;
+
+        let proposal = self.propose(scope, entries, &facts).await?;
+        let source_keys: BTreeSet<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+
+        for proposed in proposal.facts {
+            let key = sanitize_key(&proposed.key);
+            let text = proposed.fact.trim();
+            if key.is_empty() || text.is_empty() {
+                continue;
+            }
+
+            // Unknown sources are dropped; a fact citing none is attributed to the batch.
+            let mut sources: Vec<String> = proposed
+                .sources
+                .iter()
+                .map(|s| scope.qualify(s.trim()))
+                .filter(|s| source_keys.contains(s.as_str()))
+                .collect();
+            if sources.is_empty() {
+                sources = source_keys.iter().map(ToString::to_string).collect();
+            }
+
+            let conflict = proposed
+                .contradicts
+                .as_deref()
+                .map(sanitize_key)
+                .and_then(|c| facts.iter().find(|f| f.key == c))
+                .filter(|f| f.content != text);
+            if let Some(existing) = conflict {
+                report.contradictions_flagged += 1;
+                if !dry_run {
+                    let flag = format!(
+                        "\"{text}\" conflicts with [{}] \"{}\" (sources: {})",
+                        existing.key,
+                        existing.content,
+                        sources.join(", ")
+                    );
+                    self.memory
+                        .store_scoped(
+                            &format!("contradiction_{}", existing.key),
+                            &flag,
+                            MemoryCategory::Custom(REVIEW_CATEGORY.into()),
+                            scope,
+                        )
+                        .await?;
+                }
+                continue;
+            }
+
+            let embedding = self.embed_one(text).await;
+            let target = facts.iter().position(|f| f.key == key).or_else(|| {
+                let query = embedding.as_deref()?;
+                facts
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, f)| {
+                        let sim = vector::cosine_similarity(query, f.embedding.as_deref()?);
+                        (f64::from(sim) >= self.config.merge_similarity).then_some((i, sim))
+                    })
+                    .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+                    .map(|(i, _)| i)
+            });
+
+            let fact_key = match target {
+                Some(i) => {
+                    report.facts_merged += 1;
+                    facts[i].content = text.to_string();
+                    facts[i].embedding = embedding;
+                    facts[i].key.clone()
+                }
+                None => {
+                    report.facts_created += 1;
+                    facts.push(Fact {
+                        key: key.clone(),
+                        content: text.to_string(),
+                        embedding,
+                    });
+                    key
+                }
+            };
+
+            if !dry_run {
+                self.memory
+                    .store_scoped(&fact_key, text, MemoryCategory::Core, scope)
+                    .await?;
+                let record = provenance.entry(scope.qualify(&fact_key)).or_default();
+                for source in sources {
+                    if !record.sources.contains(&source) {
+                        record.sources.push(source);
+                    }
+                }
+                record.updated_at = Utc::now().to_rfc3339();
+            }
+        }
+        Ok(())
+    }
+
+    async fn propose(
+        &self,
+        scope: &MemoryScope,
+        entries: &[MemoryEntry],
+        facts: &[Fact],
+    ) -> Result<Proposal> {
+        let mut prompt = String::from("Existing facts:\n");
+        if facts.is_empty() {
+            prompt.push_str("(none)\n");
+        }
+        for fact in facts {
+            let _ = writeln!(prompt, "- [{}] {}", fact.key, fact.content);
+        }
+        prompt.push_str("\nNew entries:\n");
+        for entry in entries {
+            let _ = writeln!(
+                prompt,
+                "- [{}] ({}) {}",
+                scope.unqualify(&entry.key),
+                entry.timestamp,
+                entry.content
+            );
+        }
+
+        let raw = self
+            .provider
+            .chat_with_system(Some(SYSTEM_PROMPT), &prompt, self.model, 0.2)
+            .await?;
+        parse_proposal(&raw)
+    }
+
+    /// Best-effort: without vectors, merging falls back to matching keys.
+    async fn embed_facts(&self, facts: &mut [Fact]) {
+        if self.embedder.dimensions() == 0 || facts.is_empty() {
+            return;
+        }
+        let texts: Vec<&str> = facts.iter().map(|f| f.content.as_str()).collect();
+        match self.embedder.embed(&texts).await {
+            Ok(vectors) => {
+                for (fact, v) in facts.iter_mut().zip(vectors) {
+                    fact.embedding = Some(v);
+                }
+            }
+            Err(e) => tracing::warn!("Consolidation: embedding existing facts failed: {e}"),
+        }
+    }
+
+    async fn embed_one(&self, text: &str) -> Option<Vec<f32>> {
+        if self.embedder.dimensions() == 0 {
+            return None;
+        }
+        self.embedder.embed_one(text).await.ok()
+    }
+}
+
+/// Run consolidation from the heartbeat when enabled and the interval has elapsed.
+///
+/// Best-effort like hygiene: callers should log and continue on failure.
+pub async fn run_if_due(config: &Config) -> Result<Option<ConsolidationReport>> {
+    let settings = &config.memory.consolidation;
+    if !settings.enabled {
+        return Ok(None);
+    }
+    let state = load_json::<ConsolidationState>(&state_path(&config.workspace_dir))?;
+    if !is_due(state.last_run_at.as_deref(), settings.interval_hours) {
+        return Ok(None);
+    }
+
+    let report = run_with_config(config, false).await?;
+    if report != ConsolidationReport::default() {
+        tracing::info!(
+            "memory consolidation complete: considered={} created={} merged={} contradictions={}",
+            report.entries_considered,
+            report.facts_created,
+            report.facts_merged,
+            report.contradictions_flagged,
+        );
+    }
+    Ok(Some(report))
+}
+
+/// Build memory, provider and embedder from config and consolidate once.
+pub async fn run_with_config(config: &Config, dry_run: bool) -> Result<ConsolidationReport> {
+    let memory = super::create_memory(
+        &config.memory,
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    let provider = crate::providers::create_resilient_provider_with_options(
+        config.default_provider.as_deref().unwrap_or("openrouter"),
+        config.api_key.as_deref(),
+        &config.reliability,
+        &crate::providers::ProviderOptions::from_config(config),
+    )?;
+    let embedder = super::embeddings::create_embedding_provider(
+        &config.memory.embedding_provider,
+        config.api_key.as_deref(),
+        &config.memory.embedding_model,
+        config.memory.embedding_dimensions,
+    );
+    let model = config
+        .memory
+        .consolidation
+        .model
+        .clone()
+        .or_else(|| config.default_model.clone())
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
+
+    Consolidator {
+        memory: memory.as_ref(),
+        provider: provider.as_ref(),
+        embedder: embedder.as_ref(),
+        model: &model,
+        config: &config.memory.consolidation,
+        workspace_dir: &config.workspace_dir,
+    }
+    .run(dry_run)
+    .await
+}
+
+/// Source entries recorded for a core fact key, if it was consolidated.
+pub fn provenance(workspace_dir: &Path, key: &str) -> Result<Option<Provenance>> {
+    let mut all = load_json::<BTreeMap<String, Provenance>>(&provenance_path(workspace_dir))?;
+    Ok(all.remove(key))
+}
+
+fn is_due(last_run_at: Option<&str>, interval_hours: u32) -> bool {
+    let Some(last) = last_run_at.and_then(|ts| DateTime::parse_from_rfc3339(ts).ok()) else {
+        return true;
+    };
+    Utc::now().signed_duration_since(last.with_timezone(&Utc))
+        >= Duration::hours(i64::from(interval_hours))
+}
+
+/// Extract the JSON object from a model reply (tolerates code fences and prose).
+fn parse_proposal(raw: &str) -> Result<Proposal> {
+    let start = raw
+        .find('{')
+        .context("consolidation reply contains no JSON")?;
+    let end = raw
+        .rfind('}')
+        .context("consolidation reply contains no JSON")?;
+    serde_json::from_str(&raw[start..=end]).context("invalid consolidation reply")
+}
+
+/// Normalize a model-chosen key to `snake_case`.
+pub(crate) fn sanitize_key(raw: &str) -> String {
+    let mut key = String::new();
+    for c in raw.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            key.push(c.to_ascii_lowercase());
+        } else if !key.ends_with('_') {
+            key.push('_');
+        }
+    }
+    key.trim_matches('_').chars().take(64).collect()
+}
+
+fn state_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(STATE_FILE)
+}
+
+fn provenance_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(PROVENANCE_FILE)
+}
+
+fn load_json<T: Default + serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let raw = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&raw).unwrap_or_default())
+}
+
+fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_vec_pretty(value)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::embeddings::{LocalEmbedding, NoopEmbedding};
+    use crate::memory::SqliteMemory;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Replies with queued responses and records every prompt.
+    struct ScriptedProvider {
+        replies: Mutex<Vec<String>>,
+        prompts: Mutex<Vec<String>>,
+    }
+
+    impl ScriptedProvider {
+        fn new(replies: &[&str]) -> Self {
+            Self {
+                replies: Mutex::new(replies.iter().rev().map(ToString::to_string).collect()),
+                prompts: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.prompts.lock().unwrap().push(message.to_string());
+            Ok(self
+                .replies
+                .lock()
+                .unwrap()
+                .pop()
+                .unwrap_or_else(|| r#"{"facts": []}"#.into()))
+        }
+    }
+
+    async fn run(
+        tmp: &TempDir,
+        mem: &dyn Memory,
+        provider: &ScriptedProvider,
+        embedder: &dyn EmbeddingProvider,
+        dry_run: bool,
+    ) -> ConsolidationReport {
+        let config = ConsolidationConfig::default();
+        Consolidator {
+            memory: mem,
+            provider,
+            embedder,
+            model: "cheap",
+            config: &config,
+            workspace_dir: tmp.path(),
+        }
+        .run(dry_run)
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn creates_core_facts_with_provenance() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store(
+            "conv_1",
+            "I moved to Lisbon last month",
+            MemoryCategory::Conversation,
+        )
+        .await
+        .unwrap();
+        mem.store("conv_2", "thanks!", MemoryCategory::Conversation)
+            .await
+            .unwrap();
+
+        let provider = ScriptedProvider::new(&[r#"```json
+            {"facts": [{"key": "Home City", "fact": "User lives in Lisbon.", "sources": ["conv_1", "bogus"]}]}
+            ```"#]);
+        let report = run(&tmp, &mem, &provider, &NoopEmbedding, false).await;
+        assert_eq!(report.entries_considered, 2);
+        assert_eq!(report.facts_created, 1);
+
+        let fact = mem.get("home_city").await.unwrap().unwrap();
+        assert_eq!(fact.category, MemoryCategory::Core);
+        assert_eq!(fact.content, "User lives in Lisbon.");
+        let origin = provenance(tmp.path(), "home_city").unwrap().unwrap();
+        assert_eq!(origin.sources, vec!["conv_1".to_string()]);
+
+        // Processed entries are not summarized again.
+        let again = run(&tmp, &mem, &provider, &NoopEmbedding, false).await;
+        assert_eq!(again.entries_considered, 0);
+        assert_eq!(provider.prompts.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn merges_near_duplicates_by_embedding() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store(
+            "editor",
+            "User prefers the Helix editor",
+            MemoryCategory::Core,
+        )
+        .await
+        .unwrap();
+        mem.store("conv_1", "I always use Helix", MemoryCategory::Conversation)
+            .await
+            .unwrap();
+
+        let provider = ScriptedProvider::new(&[
+            r#"{"facts": [{"key": "preferred_editor", "fact": "User prefers the Helix editor.", "sources": ["conv_1"]}]}"#,
+        ]);
+        let embedder = LocalEmbedding::new(256);
+        let report = run(&tmp, &mem, &provider, &embedder, false).await;
+        assert_eq!(report.facts_merged, 1);
+        assert_eq!(report.facts_created, 0);
+        assert!(mem.get("preferred_editor").await.unwrap().is_none());
+        assert_eq!(
+            mem.get("editor").await.unwrap().unwrap().content,
+            "User prefers the Helix editor."
+        );
+        assert!(provenance(tmp.path(), "editor").unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn contradictions_are_flagged_not_applied() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("diet", "User is vegetarian", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store(
+            "conv_1",
+            "had a great steak tonight",
+            MemoryCategory::Conversation,
+        )
+        .await
+        .unwrap();
+
+        let provider = ScriptedProvider::new(&[
+            r#"{"facts": [{"key": "diet", "fact": "User eats meat.", "sources": ["conv_1"], "contradicts": "diet"}]}"#,
+        ]);
+        let report = run(&tmp, &mem, &provider, &NoopEmbedding, false).await;
+        assert_eq!(report.contradictions_flagged, 1);
+        assert_eq!(
+            mem.get("diet").await.unwrap().unwrap().content,
+            "User is vegetarian"
+        );
+
+        let review = mem
+            .list(Some(&MemoryCategory::Custom(REVIEW_CATEGORY.into())))
+            .await
+            .unwrap();
+        assert_eq!(review.len(), 1);
+        assert!(review[0].content.contains("User eats meat."));
+        assert!(review[0].content.contains("conv_1"));
+    }
+
+    #[tokio::test]
+    async fn scoped_entries_stay_in_their_scope() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let alice = MemoryScope::User("telegram:alice".into());
+        mem.store_scoped(
+            "msg_1",
+            "my cat is called Miso",
+            MemoryCategory::Conversation,
+            &alice,
+        )
+        .await
+        .unwrap();
+
+        let provider = ScriptedProvider::new(&[
+            r#"{"facts": [{"key": "pet", "fact": "Has a cat named Miso.", "sources": ["msg_1"]}]}"#,
+        ]);
+        run(&tmp, &mem, &provider, &NoopEmbedding, false).await;
+
+        assert!(mem.get("pet").await.unwrap().is_none());
+        let fact = mem.get("user:telegram:alice/pet").await.unwrap().unwrap();
+        assert_eq!(fact.category, MemoryCategory::Core);
+        let origin = provenance(tmp.path(), "user:telegram:alice/pet")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            origin.sources,
+            vec!["user:telegram:alice/msg_1".to_string()]
+        );
+        // The prompt shows the sender's keys unqualified.
+        assert!(provider.prompts.lock().unwrap()[0].contains("[msg_1]"));
+    }
+
+    #[tokio::test]
+    async fn dry_run_writes_nothing() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("conv_1", "I moved to Lisbon", MemoryCategory::Conversation)
+            .await
+            .unwrap();
+
+        let provider = ScriptedProvider::new(&[
+            r#"{"facts": [{"key": "city", "fact": "Lives in Lisbon.", "sources": ["conv_1"]}]}"#,
+        ]);
+        let report = run(&tmp, &mem, &provider, &NoopEmbedding, true).await;
+        assert_eq!(report.facts_created, 1);
+        assert!(mem.get("city").await.unwrap().is_none());
+        assert!(!state_path(tmp.path()).exists());
+    }
+
+    #[test]
+    fn parse_proposal_rejects_non_json() {
+        assert!(parse_proposal("no facts today").is_err());
+        assert!(parse_proposal(r#"{"facts": []}"#).unwrap().facts.is_empty());
+    }
+
+    #[test]
+    fn sanitize_key_snake_cases() {
+        assert_eq!(sanitize_key("  Home City! "), "home_city");
+        assert_eq!(sanitize_key("a--b__c"), "a_b_c");
+        assert_eq!(sanitize_key("???"), "");
+    }
+
+    #[test]
+    fn due_after_interval() {
+        assert!(is_due(None, 24));
+        assert!(!is_due(Some(&Utc::now().to_rfc3339()), 24));
+        let old = (Utc::now() - Duration::hours(25)).to_rfc3339();
+        assert!(is_due(Some(&old), 24));
+    }
+}
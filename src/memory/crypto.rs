@@ -0,0 +1,155 @@
+//! Field-level encryption of memory content (`[memory] encrypt = true`).
+//!
+//! Content is sealed with ChaCha20-Poly1305 under a key derived from the
+//! `SecretStore` key file, so `brain.db` alone reveals no conversation text.
+//! FTS5 cannot index ciphertext, so each encrypted row also carries a blind
+//! index: its words, lowercased and replaced by truncated HMAC-SHA256 tags
+//! under a second derived key. Queries are blinded the same way, so BM25
+//! keyword search keeps working on exact words.
+//!
+//! Not hidden: keys, categories, timestamps, embeddings (which can leak
+//! meaning), and word repetition within and across entries.
+
+use crate::security::SecretStore;
+use anyhow::{Context, Result};
+use chacha20poly1305::aead::{Aead, KeyInit, OsRng};
+use chacha20poly1305::{AeadCore, ChaCha20Poly1305, Key, Nonce};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Prefix of sealed content: `menc1:<hex(nonce ‖ ciphertext ‖ tag)>`.
+const PREFIX: &str = "menc1:";
+
+const NONCE_LEN: usize = 12;
+
+/// Hex characters kept from each blinded word's tag (64 bits).
+const TAG_HEX_LEN: usize = 16;
+
+pub struct ContentCipher {
+    cipher: ChaCha20Poly1305,
+    index_key: [u8; 32],
+}
+
+impl ContentCipher {
+    pub fn new(content_key: &[u8; 32], index_key: [u8; 32]) -> Self {
+        Self {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(content_key)),
+            index_key,
+        }
+    }
+
+    /// Keys derived from the secret key file (created if missing).
+    pub fn from_secret_store(store: &SecretStore) -> Result<Self> {
+        Ok(Self::new(
+            &store.derive_key("zeroclaw-memory-content-v1")?,
+            store.derive_key("zeroclaw-memory-index-v1")?,
+        ))
+    }
+
+    pub fn is_encrypted(value: &str) -> bool {
+        value.starts_with(PREFIX)
+    }
+
+    pub fn encrypt(&self, plaintext: &str) -> Result<String> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let sealed = self
+            .cipher
+            .encrypt(&nonce, plaintext.as_bytes())
+            .map_err(|e| anyhow::anyhow!("Memory encryption failed: {e}"))?;
+        let mut blob = nonce.to_vec();
+        blob.extend_from_slice(&sealed);
+        Ok(format!("{PREFIX}{}", hex::encode(blob)))
+    }
+
+    /// Open sealed content; anything without the prefix is plaintext.
+    pub fn decrypt(&self, value: &str) -> Result<String> {
+        let Some(hex_str) = value.strip_prefix(PREFIX) else {
+            return Ok(value.to_string());
+        };
+        let blob = hex::decode(hex_str).context("Encrypted memory is corrupt (bad hex)")?;
+        anyhow::ensure!(blob.len() > NONCE_LEN, "Encrypted memory is truncated");
+        let (nonce, sealed) = blob.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), sealed)
+            .map_err(|_| {
+                anyhow::anyhow!("Memory decryption failed — wrong key or tampered data")
+            })?;
+        String::from_utf8(plaintext).context("Decrypted memory is not valid UTF-8")
+    }
+
+    fn tag(&self, data: &str) -> String {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.index_key)
+            .expect("HMAC accepts any key length");
+        mac.update(data.as_bytes());
+        let mut tag = hex::encode(mac.finalize().into_bytes());
+        tag.truncate(TAG_HEX_LEN);
+        tag
+    }
+
+    /// Blind tags of the words in `text`, in order (repeats kept for BM25).
+    pub fn blind_words(&self, text: &str) -> Vec<String> {
+        words(text)
+            .iter()
+            .map(|w| format!("b{}", self.tag(w)))
+            .collect()
+    }
+
+    /// The blind index stored next to sealed content.
+    pub fn blind_index(&self, text: &str) -> String {
+        self.blind_words(text).join(" ")
+    }
+
+    /// Keyed stand-in for the plaintext hash used by the embedding cache,
+    /// which would otherwise confirm guesses of stored content.
+    pub fn cache_key(&self, text: &str) -> String {
+        format!("k{}", self.tag(&format!("cache\u{0}{text}")))
+    }
+}
+
+/// Words as FTS5's default tokenizer roughly sees them: lowercased runs of
+/// alphanumeric characters.
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher() -> ContentCipher {
+        ContentCipher::new(&[7; 32], [9; 32])
+    }
+
+    #[test]
+    fn seal_and_open() {
+        let c = cipher();
+        let sealed = c.encrypt("door pin is 5512").unwrap();
+        assert!(ContentCipher::is_encrypted(&sealed));
+        assert!(!sealed.contains("5512"));
+        assert_ne!(sealed, c.encrypt("door pin is 5512").unwrap());
+        assert_eq!(c.decrypt(&sealed).unwrap(), "door pin is 5512");
+        assert_eq!(c.decrypt("plain text").unwrap(), "plain text");
+
+        let other = ContentCipher::new(&[8; 32], [9; 32]);
+        assert!(other.decrypt(&sealed).is_err());
+    }
+
+    #[test]
+    fn blind_index_matches_case_insensitively() {
+        let c = cipher();
+        let index = c.blind_index("Deploy via ops/deploy.sh");
+        let tags: Vec<&str> = index.split(' ').collect();
+        assert_eq!(tags.len(), 5);
+        assert_eq!(tags[0], tags[3]);
+        assert_eq!(c.blind_words("DEPLOY")[0], tags[0]);
+        assert!(!index.contains("deploy"));
+        assert_ne!(
+            ContentCipher::new(&[7; 32], [1; 32]).blind_index("deploy"),
+            c.blind_index("deploy")
+        );
+    }
+}
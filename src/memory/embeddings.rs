@@ -6,6 +6,11 @@ pub trait EmbeddingProvider: Send + Sync {
     /// Provider name
     fn name(&self) -> &str;
 
+    /// Model identifier, recorded with stored vectors to detect model changes
+    fn model(&self) -> &str {
+        ""
+    }
+
     /// Embedding dimensions
     fn dimensions(&self) -> usize;
 
@@ -53,7 +58,7 @@ pub struct OpenAiEmbedding {
 impl OpenAiEmbedding {
     pub fn new(base_url: &str, api_key: &str, model: &str, dims: usize) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client: crate::http::client("embeddings", crate::http::Defaults::NONE),
             base_url: base_url.trim_end_matches('/').to_string(),
             api_key: api_key.to_string(),
             model: model.to_string(),
@@ -68,6 +73,10 @@ impl EmbeddingProvider for OpenAiEmbedding {
         "openai"
     }
 
+    fn model(&self) -> &str {
+        &self.model
+    }
+
     fn dimensions(&self) -> usize {
         self.dims
     }
@@ -123,6 +132,185 @@ impl EmbeddingProvider for OpenAiEmbedding {
     }
 }
 
+// ── Local hashed n-gram embedder (offline) ───────────────────
+
+/// Words skipped for word/bigram features — they carry no topic signal.
+const STOPWORDS: &[&str] = &[
+    "a", "an", "and", "are", "as", "at", "be", "by", "for", "from", "has", "have", "i", "in", "is",
+    "it", "its", "of", "on", "or", "that", "the", "this", "to", "was", "were", "will", "with",
+];
+
+/// Default vector size when `embedding_dimensions` is 0.
+const LOCAL_DEFAULT_DIMS: usize = 384;
+
+/// Offline embedder: signed feature hashing of words, word bigrams and
+/// character trigrams with sublinear term weighting, L2-normalized.
+///
+/// No model files or network needed. Captures lexical and morphological
+/// overlap (e.g. "deploy" ~ "deployment"), not deep semantics.
+pub struct LocalEmbedding {
+    dims: usize,
+}
+
+impl LocalEmbedding {
+    pub fn new(dims: usize) -> Self {
+        Self {
+            dims: if dims == 0 { LOCAL_DEFAULT_DIMS } else { dims },
+        }
+    }
+
+    fn embed_text(&self, text: &str) -> Vec<f32> {
+        let lower = text.to_lowercase();
+        let words: Vec<&str> = lower
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect();
+
+        let mut features: std::collections::HashMap<u64, f32> = std::collections::HashMap::new();
+        let mut add = |feature: &str, weight: f32| {
+            *features.entry(fnv1a(feature.as_bytes())).or_insert(0.0) += weight;
+        };
+
+        let content: Vec<&str> = words
+            .iter()
+            .copied()
+            .filter(|w| !STOPWORDS.contains(w))
+            .collect();
+        for word in &content {
+            add(&format!("w:{word}"), 1.0);
+            let padded: Vec<char> = format!("#{word}#").chars().collect();
+            for tri in padded.windows(3) {
+                add(&format!("c:{}", tri.iter().collect::<String>()), 0.5);
+            }
+        }
+        for pair in content.windows(2) {
+            add(&format!("b:{} {}", pair[0], pair[1]), 0.5);
+        }
+
+        let mut v = vec![0.0_f32; self.dims];
+        for (hash, tf) in features {
+            #[allow(clippy::cast_possible_truncation)]
+            let idx = (hash % self.dims as u64) as usize;
+            let sign = if hash >> 63 == 0 { 1.0 } else { -1.0 };
+            v[idx] += sign * (1.0 + tf.ln_1p());
+        }
+
+        let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+        if norm > 0.0 {
+            for x in &mut v {
+                *x /= norm;
+            }
+        }
+        v
+    }
+}
+
+/// 64-bit FNV-1a — stable across builds, unlike `DefaultHasher`.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |h, &b| {
+        (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[async_trait]
+impl EmbeddingProvider for LocalEmbedding {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    fn model(&self) -> &str {
+        "hashed-ngram-v1"
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dims
+    }
+
+    async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+        Ok(texts.iter().map(|t| self.embed_text(t)).collect())
+    }
+}
+
+// ── Ollama embedding provider ────────────────────────────────
+
+pub struct OllamaEmbedding {
+    client: reqwest::Client,
+    base_url: String,
+    model: String,
+    dims: usize,
+}
+
+impl OllamaEmbedding {
+    pub fn new(base_url: &str, model: &str, dims: usize) -> Self {
+        Self {
+            client: crate::http::client("embeddings", crate::http::Defaults::NONE),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            dims,
+        }
+    }
+}
+
+/// Extract the vector from an Ollama `/api/embeddings` response.
+fn parse_ollama_embedding(json: &serde_json::Value) -> anyhow::Result<Vec<f32>> {
+    let values = json
+        .get("embedding")
+        .and_then(|e| e.as_array())
+        .ok_or_else(|| anyhow::anyhow!("Invalid Ollama embedding response: missing 'embedding'"))?;
+
+    #[allow(clippy::cast_possible_truncation)]
+    Ok(values
+        .iter()
+        .filter_map(|v| v.as_f64().map(|f| f as f32))
+        .collect())
+}
+
+#[async_trait]
+impl EmbeddingProvider for OllamaEmbedding {
+    fn name(&self) -> &str {
+        "ollama"
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dims
+    }
+
+    async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+        // `/api/embeddings` takes one prompt per request.
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for text in texts {
+            let resp = self
+                .client
+                .post(format!("{}/api/embeddings", self.base_url))
+                .json(&serde_json::json!({ "model": self.model, "prompt": text }))
+                .send()
+                .await?;
+
+            if !resp.status().is_success() {
+                let status = resp.status();
+                let text = resp.text().await.unwrap_or_default();
+                anyhow::bail!("Ollama embedding error {status}: {text}");
+            }
+
+            let vec = parse_ollama_embedding(&resp.json().await?)?;
+            if vec.len() != self.dims {
+                anyhow::bail!(
+                    "Ollama model '{}' returned {}-dim embeddings but embedding_dimensions = {}",
+                    self.model,
+                    vec.len(),
+                    self.dims
+                );
+            }
+            embeddings.push(vec);
+        }
+        Ok(embeddings)
+    }
+}
+
 // ── Factory ──────────────────────────────────────────────────
 
 pub fn create_embedding_provider(
@@ -146,6 +334,18 @@ pub fn create_embedding_provider(
             let key = api_key.unwrap_or("");
             Box::new(OpenAiEmbedding::new(base_url, key, model, dims))
         }
+        "local" => Box::new(LocalEmbedding::new(dims)),
+        "ollama" => {
+            let base_url = crate::providers::discovery::ollama_host_from_env()
+                .unwrap_or_else(|| crate::providers::discovery::DEFAULT_OLLAMA_URL.to_string());
+            Box::new(OllamaEmbedding::new(&base_url, model, dims))
+        }
+        name if name.starts_with("ollama:") => {
+            let raw = name.strip_prefix("ollama:").unwrap_or("");
+            let base_url = crate::providers::discovery::normalize_ollama_url(raw)
+                .unwrap_or_else(|| crate::providers::discovery::DEFAULT_OLLAMA_URL.to_string());
+            Box::new(OllamaEmbedding::new(&base_url, model, dims))
+        }
         _ => Box::new(NoopEmbedding),
     }
 }
@@ -249,4 +449,85 @@ mod tests {
         let p = OpenAiEmbedding::new("http://localhost", "k", "m", 384);
         assert_eq!(p.dimensions(), 384);
     }
+
+    // ── Local embedder ───────────────────────────────────────────
+
+    fn cosine(a: &[f32], b: &[f32]) -> f32 {
+        a.iter().zip(b).map(|(x, y)| x * y).sum()
+    }
+
+    #[test]
+    fn factory_local() {
+        let p = create_embedding_provider("local", None, "ignored", 256);
+        assert_eq!(p.name(), "local");
+        assert_eq!(p.model(), "hashed-ngram-v1");
+        assert_eq!(p.dimensions(), 256);
+    }
+
+    #[test]
+    fn local_zero_dims_uses_default() {
+        assert_eq!(LocalEmbedding::new(0).dimensions(), LOCAL_DEFAULT_DIMS);
+    }
+
+    #[tokio::test]
+    async fn local_embeddings_are_deterministic_and_normalized() {
+        let p = LocalEmbedding::new(256);
+        let a = p.embed_one("Deploy the gateway to staging").await.unwrap();
+        let b = p.embed_one("Deploy the gateway to staging").await.unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 256);
+        assert!((cosine(&a, &a) - 1.0).abs() < 1e-5);
+    }
+
+    #[tokio::test]
+    async fn local_related_texts_score_higher() {
+        let p = LocalEmbedding::new(512);
+        let v = p
+            .embed(&[
+                "how do we deploy the service",
+                "deployment checklist for services",
+                "favourite pasta recipes",
+            ])
+            .await
+            .unwrap();
+        assert!(cosine(&v[0], &v[1]) > cosine(&v[0], &v[2]) + 0.1);
+    }
+
+    #[tokio::test]
+    async fn local_empty_text_is_zero_vector() {
+        let p = LocalEmbedding::new(64);
+        let v = p.embed_one("  the  ").await.unwrap();
+        assert!(v.iter().all(|x| *x == 0.0));
+    }
+
+    #[test]
+    fn fnv1a_is_stable() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+
+    // ── Ollama ───────────────────────────────────────────────────
+
+    #[test]
+    fn factory_ollama_with_url() {
+        let p = create_embedding_provider("ollama:10.0.0.5:11434/", None, "nomic-embed-text", 768);
+        assert_eq!(p.name(), "ollama");
+        assert_eq!(p.model(), "nomic-embed-text");
+        assert_eq!(p.dimensions(), 768);
+        let o = OllamaEmbedding::new("http://10.0.0.5:11434/", "m", 768);
+        assert_eq!(o.base_url, "http://10.0.0.5:11434");
+    }
+
+    #[test]
+    fn factory_ollama_default() {
+        let p = create_embedding_provider("ollama", None, "nomic-embed-text", 768);
+        assert_eq!(p.name(), "ollama");
+    }
+
+    #[test]
+    fn parse_ollama_response() {
+        let json = serde_json::json!({ "embedding": [0.5, -1.0, 2.0] });
+        assert_eq!(parse_ollama_embedding(&json).unwrap(), vec![0.5, -1.0, 2.0]);
+        assert!(parse_ollama_embedding(&serde_json::json!({ "error": "x" })).is_err());
+    }
 }
@@ -0,0 +1,318 @@
+//! Per-turn fact extraction for `auto_save`.
+//!
+//! After each exchange a (cheap) model reads the user's message and the reply
+//! and returns durable facts and preferences. They are stored as core memories
+//! under stable `snake_case` keys in the turn's scope, so restating or refining
+//! a fact updates it in place instead of adding another copy. Raw transcripts
+//! are only kept when `[memory.extraction]` asks for them.
+
+use super::consolidate::sanitize_key;
+use super::traits::{Memory, MemoryCategory, MemoryScope, RecallQuery};
+use crate::config::MemoryConfig;
+use crate::providers::Provider;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::fmt::Write;
+use std::sync::Arc;
+
+/// Existing facts shown to the model so it reuses their keys.
+const MAX_EXISTING_FACTS: usize = 10;
+
+/// Reply characters sent to the model; answers matter less than what the user said.
+const MAX_REPLY_CHARS: usize = 4_000;
+
+const SYSTEM_PROMPT: &str = "You update an assistant's long-term memory after each \
+exchange. Extract durable facts about the user worth remembering in future \
+conversations: preferences, background, projects, decisions, people and recurring \
+needs, plus any conclusion the assistant reached that the user will want again. Skip \
+small talk, one-off requests and general knowledge.\n\n\
+Reply with JSON only:\n\
+{\"facts\": [{\"key\": \"snake_case_topic\", \"fact\": \"one concise sentence\"}]}\n\n\
+Reuse an existing fact's key when the exchange restates, refines or changes it. \
+Return {\"facts\": []} when nothing is worth keeping.";
+
+#[derive(Debug, Deserialize)]
+struct Extraction {
+    #[serde(default)]
+    facts: Vec<ExtractedFact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ExtractedFact {
+    key: String,
+    fact: String,
+}
+
+/// Outcome of extracting one turn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TurnReport {
+    pub facts_created: usize,
+    pub facts_updated: usize,
+    pub facts_unchanged: usize,
+}
+
+pub struct TurnExtractor<'a> {
+    pub memory: &'a dyn Memory,
+    pub provider: &'a dyn Provider,
+    pub model: &'a str,
+    /// Scope the turn's facts are stored in
+    pub scope: &'a MemoryScope,
+}
+
+impl TurnExtractor<'_> {
+    /// Extract facts from one exchange and store them as core memories.
+    pub async fn run(&self, user_message: &str, reply: &str) -> Result<TurnReport> {
+        let mut report = TurnReport::default();
+        let existing = self.existing_facts(user_message).await?;
+
+        let mut prompt = String::from("Existing facts:\n");
+        if existing.is_empty() {
+            prompt.push_str("(none)\n");
+        }
+        for (key, content) in &existing {
+            let _ = writeln!(prompt, "- [{key}] {content}");
+        }
+        let _ = write!(
+            prompt,
+            "\nUser: {}\n\nAssistant: {}\n",
+            user_message.trim(),
+            truncate_chars(reply.trim(), MAX_REPLY_CHARS)
+        );
+
+        let raw = self
+            .provider
+            .chat_with_system(Some(SYSTEM_PROMPT), &prompt, self.model, 0.2)
+            .await?;
+
+        for fact in parse_extraction(&raw)?.facts {
+            let key = sanitize_key(&fact.key);
+            let text = fact.fact.trim();
+            if key.is_empty() || text.is_empty() {
+                continue;
+            }
+
+            match self.memory.get(&self.scope.qualify(&key)).await? {
+                Some(current) if current.content.trim() == text => {
+                    report.facts_unchanged += 1;
+                    continue;
+                }
+                Some(_) => report.facts_updated += 1,
+                None => report.facts_created += 1,
+            }
+            self.memory
+                .store_scoped(&key, text, MemoryCategory::Core, self.scope)
+                .await?;
+        }
+        Ok(report)
+    }
+
+    /// Core facts of this scope related to the message, as (key, content).
+    async fn existing_facts(&self, user_message: &str) -> Result<Vec<(String, String)>> {
+        let query = RecallQuery {
+            category: Some(MemoryCategory::Core),
+            scopes: vec![self.scope.clone()],
+            ..RecallQuery::new(user_message, MAX_EXISTING_FACTS)
+        };
+        Ok(self
+            .memory
+            .recall_query(&query)
+            .await?
+            .into_iter()
+            .filter(|e| MemoryScope::of(e) == *self.scope)
+            .map(|e| (self.scope.unqualify(&e.key).to_string(), e.content))
+            .collect())
+    }
+}
+
+/// Model for extraction: `[memory.extraction] model`, then the consolidation
+/// model, then the model that answered the turn.
+pub fn extraction_model(config: &MemoryConfig, chat_model: &str) -> String {
+    config
+        .extraction
+        .model
+        .clone()
+        .or_else(|| config.consolidation.model.clone())
+        .unwrap_or_else(|| chat_model.to_string())
+}
+
+/// Extract a turn in the background so replies aren't delayed. Failures are logged.
+pub fn spawn(
+    memory: Arc<dyn Memory>,
+    provider: Arc<dyn Provider>,
+    model: String,
+    scope: MemoryScope,
+    user_message: String,
+    reply: String,
+) {
+    tokio::spawn(async move {
+        let extractor = TurnExtractor {
+            memory: memory.as_ref(),
+            provider: provider.as_ref(),
+            model: &model,
+            scope: &scope,
+        };
+        if let Err(e) = extractor.run(&user_message, &reply).await {
+            tracing::warn!("memory extraction skipped: {e}");
+        }
+    });
+}
+
+/// Extract the JSON object from a model reply (tolerates code fences and prose).
+fn parse_extraction(raw: &str) -> Result<Extraction> {
+    let start = raw.find('{').context("extraction reply contains no JSON")?;
+    let end = raw
+        .rfind('}')
+        .context("extraction reply contains no JSON")?;
+    serde_json::from_str(&raw[start..=end]).context("invalid extraction reply")
+}
+
+fn truncate_chars(text: &str, max: usize) -> &str {
+    text.char_indices()
+        .nth(max)
+        .map_or(text, |(i, _)| &text[..i])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Replies with queued responses and records every prompt.
+    struct ScriptedProvider {
+        replies: Mutex<Vec<String>>,
+        prompts: Mutex<Vec<String>>,
+    }
+
+    impl ScriptedProvider {
+        fn new(replies: &[&str]) -> Self {
+            Self {
+                replies: Mutex::new(replies.iter().rev().map(ToString::to_string).collect()),
+                prompts: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            self.prompts.lock().unwrap().push(message.to_string());
+            Ok(self
+                .replies
+                .lock()
+                .unwrap()
+                .pop()
+                .unwrap_or_else(|| r#"{"facts": []}"#.into()))
+        }
+    }
+
+    async fn extract(
+        mem: &dyn Memory,
+        provider: &ScriptedProvider,
+        scope: &MemoryScope,
+        user_message: &str,
+    ) -> TurnReport {
+        TurnExtractor {
+            memory: mem,
+            provider,
+            model: "cheap",
+            scope,
+        }
+        .run(user_message, "Sure.")
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn stores_facts_under_stable_keys() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let provider = ScriptedProvider::new(&[r#"```json
+            {"facts": [{"key": "Preferred Editor", "fact": "User edits code in Helix."},
+                       {"key": "", "fact": "dropped"}]}
+            ```"#]);
+
+        let report = extract(&mem, &provider, &MemoryScope::Global, "I use Helix").await;
+        assert_eq!(report.facts_created, 1);
+
+        let fact = mem.get("preferred_editor").await.unwrap().unwrap();
+        assert_eq!(fact.category, MemoryCategory::Core);
+        assert_eq!(fact.content, "User edits code in Helix.");
+        assert_eq!(mem.count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn restated_fact_updates_instead_of_duplicating() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("home_city", "User lives in Porto.", MemoryCategory::Core)
+            .await
+            .unwrap();
+        let provider = ScriptedProvider::new(&[
+            r#"{"facts": [{"key": "home_city", "fact": "User lives in Lisbon."}]}"#,
+            r#"{"facts": [{"key": "home_city", "fact": "User lives in Lisbon."}]}"#,
+        ]);
+
+        let first = extract(&mem, &provider, &MemoryScope::Global, "I moved to Lisbon").await;
+        assert_eq!(first.facts_updated, 1);
+        let again = extract(&mem, &provider, &MemoryScope::Global, "Lisbon is great").await;
+        assert_eq!(again.facts_unchanged, 1);
+
+        assert_eq!(mem.count().await.unwrap(), 1);
+        let fact = mem.get("home_city").await.unwrap().unwrap();
+        assert_eq!(fact.content, "User lives in Lisbon.");
+        // The model saw the existing fact and its key.
+        assert!(provider.prompts.lock().unwrap()[0].contains("[home_city] User lives in Porto."));
+    }
+
+    #[tokio::test]
+    async fn facts_stay_in_the_turn_scope() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let scope = MemoryScope::User("telegram:alice".into());
+        let provider =
+            ScriptedProvider::new(&[r#"{"facts": [{"key": "diet", "fact": "User is vegan."}]}"#]);
+
+        extract(&mem, &provider, &scope, "I'm vegan").await;
+
+        assert!(mem.get("diet").await.unwrap().is_none());
+        assert!(mem.get(&scope.qualify("diet")).await.unwrap().is_some());
+        assert!(mem.recall("vegan", 5).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn invalid_reply_is_an_error_and_stores_nothing() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let provider = ScriptedProvider::new(&["I couldn't find anything."]);
+
+        let result = TurnExtractor {
+            memory: &mem,
+            provider: &provider,
+            model: "cheap",
+            scope: &MemoryScope::Global,
+        }
+        .run("hi", "hello")
+        .await;
+        assert!(result.is_err());
+        assert_eq!(mem.count().await.unwrap(), 0);
+    }
+
+    #[test]
+    fn extraction_model_falls_back_to_consolidation_then_chat_model() {
+        let mut config = MemoryConfig::default();
+        assert_eq!(extraction_model(&config, "chat"), "chat");
+        config.consolidation.model = Some("summarizer".into());
+        assert_eq!(extraction_model(&config, "chat"), "summarizer");
+        config.extraction.model = Some("extractor".into());
+        assert_eq!(extraction_model(&config, "chat"), "extractor");
+    }
+}
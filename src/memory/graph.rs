@@ -0,0 +1,977 @@
+//! Knowledge graph layer: entities, typed relations and observations kept in
+//! `brain.db` next to the flat memories, so questions like "who owns the
+//! billing service?" are answered by following edges instead of hoping a
+//! sentence matches.
+//!
+//! Entities are shared nodes identified by their normalized name. Relations
+//! and observations carry the scope of the memory they came from and are
+//! only visible from that scope (or everywhere when global); an entity shows
+//! up only through something visible about it.
+//!
+//! Facts arrive from the `memory_graph_upsert` tool or from the extractor,
+//! which asks a model to read stored memories (`[memory.graph]`).
+
+use super::crypto::ContentCipher;
+use super::ingest;
+use super::traits::{Memory, MemoryEntry, MemoryScope};
+use crate::config::{Config, GraphConfig};
+use crate::providers::Provider;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Local, Utc};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const STATE_FILE: &str = "memory_graph_state.json";
+
+/// Observations shown per entity.
+const MAX_OBSERVATIONS: usize = 5;
+
+/// Relations merged into the `[Memory context]` preamble.
+pub const CONTEXT_RELATIONS: usize = 10;
+
+/// Shorter names match too much inside ordinary words.
+const MIN_MENTION_LEN: usize = 3;
+
+const SYSTEM_PROMPT: &str = "You maintain a knowledge graph of the people, projects, \
+services, organizations and other entities in an assistant's memory. From the entries, \
+extract entities (with a short kind such as person, project, service, organization, \
+place), typed relations between them, and short observations: atomic facts about one \
+entity. Use snake_case relation types in active voice (owns, works_on, decided, \
+reports_to, uses, located_in). Write an entity's name the same way every time. Only \
+extract what the entries state.\n\n\
+Reply with JSON only:\n\
+{\"entities\": [{\"name\": \"Alice\", \"kind\": \"person\", \"observations\": \
+[\"Leads the payments team\"]}], \"relations\": [{\"source\": \"Alice\", \
+\"relation\": \"owns\", \"target\": \"Billing Service\"}]}\n\n\
+Return {\"entities\": [], \"relations\": []} when there is nothing to extract.";
+
+/// A node with what is known about it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entity {
+    pub name: String,
+    /// person, project, service, … (empty when unknown)
+    #[serde(default)]
+    pub kind: String,
+    #[serde(default)]
+    pub observations: Vec<String>,
+}
+
+/// A typed, directed edge: `source —relation→ target`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Relation {
+    pub source: String,
+    pub relation: String,
+    pub target: String,
+}
+
+/// Entities, observations and relations to merge into the graph. Entities
+/// named only in a relation are created as needed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GraphUpdate {
+    #[serde(default)]
+    pub entities: Vec<Entity>,
+    #[serde(default)]
+    pub relations: Vec<Relation>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GraphUpsertReport {
+    pub entities_added: usize,
+    pub relations_added: usize,
+    pub observations_added: usize,
+}
+
+/// Neighborhood lookup. Seeds are the named entities plus any entity whose
+/// name appears in `mentioned_in`.
+#[derive(Debug, Clone)]
+pub struct GraphQuery {
+    /// Entity names; an exact (case-insensitive) match wins, else a partial one
+    pub entities: Vec<String>,
+    pub mentioned_in: Option<String>,
+    /// Only follow edges of this type
+    pub relation: Option<String>,
+    /// Hops from the seeds
+    pub depth: usize,
+    /// Maximum relations returned
+    pub limit: usize,
+    /// Visible scopes besides global (see `RecallQuery::scopes`)
+    pub scopes: Vec<MemoryScope>,
+}
+
+impl Default for GraphQuery {
+    fn default() -> Self {
+        Self {
+            entities: Vec::new(),
+            mentioned_in: None,
+            relation: None,
+            depth: 1,
+            limit: 20,
+            scopes: Vec::new(),
+        }
+    }
+}
+
+/// Result of a [`GraphQuery`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Subgraph {
+    pub entities: Vec<Entity>,
+    pub relations: Vec<Relation>,
+}
+
+impl Subgraph {
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty() && self.relations.is_empty()
+    }
+
+    /// One line per entity with observations, then one per relation.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for entity in self.entities.iter().filter(|e| !e.observations.is_empty()) {
+            let kind = if entity.kind.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", entity.kind)
+            };
+            let _ = writeln!(
+                out,
+                "- {}{kind}: {}",
+                entity.name,
+                entity.observations.join("; ")
+            );
+        }
+        for r in &self.relations {
+            let _ = writeln!(out, "- {} —{}→ {}", r.source, r.relation, r.target);
+        }
+        out
+    }
+}
+
+/// Normalized entity identity: trimmed, lowercased, single-spaced.
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Relation types are `snake_case`.
+fn normalize_relation(relation: &str) -> String {
+    let mut out = String::new();
+    for c in relation.trim().chars() {
+        if c.is_alphanumeric() {
+            out.extend(c.to_lowercase());
+        } else if !out.is_empty() && !out.ends_with('_') {
+            out.push('_');
+        }
+    }
+    out.trim_end_matches('_').to_string()
+}
+
+fn scope_label(scope: &MemoryScope) -> String {
+    match scope {
+        MemoryScope::Global => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// Condition on `{alias}.scope` ('' = global) for rows visible from `scopes`.
+fn visible(alias: &str, scopes: &[MemoryScope], params: &mut Vec<Value>) -> String {
+    let labels: Vec<String> = scopes
+        .iter()
+        .filter(|s| **s != MemoryScope::Global)
+        .map(ToString::to_string)
+        .collect();
+    if labels.is_empty() {
+        return format!("{alias}.scope = ''");
+    }
+    let placeholders: Vec<String> = labels
+        .into_iter()
+        .map(|label| {
+            params.push(Value::Text(label));
+            format!("?{}", params.len())
+        })
+        .collect();
+    format!(
+        "({alias}.scope = '' OR {alias}.scope IN ({}))",
+        placeholders.join(", ")
+    )
+}
+
+fn id_list(ids: &BTreeSet<i64>, params: &mut Vec<Value>) -> String {
+    ids.iter()
+        .map(|id| {
+            params.push(Value::Integer(*id));
+            format!("?{}", params.len())
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Observations follow the memory content's encryption setting.
+pub(super) struct Sealing<'a> {
+    pub cipher: Option<&'a ContentCipher>,
+    pub encrypt: bool,
+}
+
+impl Sealing<'_> {
+    fn seal(&self, text: &str) -> Result<String> {
+        match self.cipher {
+            Some(cipher) if self.encrypt => cipher.encrypt(text),
+            _ => Ok(text.to_string()),
+        }
+    }
+
+    fn open(&self, stored: String) -> Result<String> {
+        match self.cipher {
+            Some(cipher) => cipher.decrypt(&stored),
+            None if ContentCipher::is_encrypted(&stored) => {
+                anyhow::bail!("graph observation is encrypted but no key is available")
+            }
+            None => Ok(stored),
+        }
+    }
+}
+
+/// Id of the entity named `name`, created if missing. A non-empty `kind`
+/// replaces the stored one.
+fn upsert_entity(
+    conn: &Connection,
+    name: &str,
+    kind: &str,
+    report: &mut GraphUpsertReport,
+) -> Result<Option<i64>> {
+    let norm = normalize_name(name);
+    if norm.is_empty() {
+        return Ok(None);
+    }
+    let now = Local::now().to_rfc3339();
+    let kind = kind.trim().to_lowercase();
+    let existing: Option<(i64, String)> = conn
+        .query_row(
+            "SELECT id, kind FROM graph_entities WHERE norm_name = ?1",
+            params![norm],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    if let Some((id, stored_kind)) = existing {
+        if !kind.is_empty() && kind != stored_kind {
+            conn.execute(
+                "UPDATE graph_entities SET kind = ?2, updated_at = ?3 WHERE id = ?1",
+                params![id, kind, now],
+            )?;
+        }
+        return Ok(Some(id));
+    }
+    conn.execute(
+        "INSERT INTO graph_entities (name, norm_name, kind, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?4)",
+        params![
+            name.split_whitespace().collect::<Vec<_>>().join(" "),
+            norm,
+            kind,
+            now
+        ],
+    )?;
+    report.entities_added += 1;
+    Ok(Some(conn.last_insert_rowid()))
+}
+
+pub(super) fn upsert(
+    conn: &Connection,
+    sealing: &Sealing<'_>,
+    update: &GraphUpdate,
+    scope: &MemoryScope,
+) -> Result<GraphUpsertReport> {
+    let mut report = GraphUpsertReport::default();
+    let label = scope_label(scope);
+    let now = Local::now().to_rfc3339();
+    let tx = conn.unchecked_transaction()?;
+
+    for entity in &update.entities {
+        let Some(id) = upsert_entity(&tx, &entity.name, &entity.kind, &mut report)? else {
+            continue;
+        };
+        let mut known: BTreeSet<String> = {
+            let mut stmt = tx.prepare(
+                "SELECT content FROM graph_observations WHERE entity_id = ?1 AND scope = ?2",
+            )?;
+            let rows = stmt.query_map(params![id, label], |row| row.get::<_, String>(0))?;
+            rows.map(|r| Ok(sealing.open(r?)?.to_lowercase()))
+                .collect::<Result<_>>()?
+        };
+        for observation in &entity.observations {
+            let text = observation.trim();
+            if text.is_empty() || !known.insert(text.to_lowercase()) {
+                continue;
+            }
+            tx.execute(
+                "INSERT INTO graph_observations (entity_id, content, scope, created_at)
+                 VALUES (?1, ?2, ?3, ?4)",
+                params![id, sealing.seal(text)?, label, now],
+            )?;
+            report.observations_added += 1;
+        }
+    }
+
+    for relation in &update.relations {
+        let kind = normalize_relation(&relation.relation);
+        if kind.is_empty() {
+            continue;
+        }
+        let (Some(source), Some(target)) = (
+            upsert_entity(&tx, &relation.source, "", &mut report)?,
+            upsert_entity(&tx, &relation.target, "", &mut report)?,
+        ) else {
+            continue;
+        };
+        report.relations_added += tx.execute(
+            "INSERT OR IGNORE INTO graph_relations (source_id, relation, target_id, scope, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![source, kind, target, label, now],
+        )?;
+    }
+
+    tx.commit()?;
+    Ok(report)
+}
+
+/// Entities named in `text`, on word boundaries.
+fn mentioned_entities(conn: &Connection, text: &str) -> Result<BTreeSet<i64>> {
+    let haystack = text.to_lowercase();
+    let mut stmt = conn.prepare(
+        "SELECT id, norm_name FROM graph_entities
+         WHERE length(norm_name) >= ?2 AND instr(?1, norm_name) > 0",
+    )?;
+    let rows = stmt.query_map(params![haystack, i64::try_from(MIN_MENTION_LEN)?], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+    let boundary = |c: Option<char>| c.map_or(true, |c| !c.is_alphanumeric());
+    let mut ids = BTreeSet::new();
+    for row in rows {
+        let (id, name) = row?;
+        let found = haystack.match_indices(&name).any(|(at, _)| {
+            boundary(haystack[..at].chars().next_back())
+                && boundary(haystack[at + name.len()..].chars().next())
+        });
+        if found {
+            ids.insert(id);
+        }
+    }
+    Ok(ids)
+}
+
+fn named_entities(conn: &Connection, names: &[String]) -> Result<BTreeSet<i64>> {
+    let mut ids = BTreeSet::new();
+    for name in names {
+        let norm = normalize_name(name);
+        if norm.is_empty() {
+            continue;
+        }
+        let exact: Option<i64> = conn
+            .query_row(
+                "SELECT id FROM graph_entities WHERE norm_name = ?1",
+                params![norm],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if let Some(id) = exact {
+            ids.insert(id);
+            continue;
+        }
+        let mut stmt = conn.prepare(
+            "SELECT id FROM graph_entities WHERE instr(norm_name, ?1) > 0 ORDER BY length(norm_name) LIMIT 5",
+        )?;
+        for id in stmt.query_map(params![norm], |row| row.get(0))? {
+            ids.insert(id?);
+        }
+    }
+    Ok(ids)
+}
+
+pub(super) fn query(conn: &Connection, sealing: &Sealing<'_>, q: &GraphQuery) -> Result<Subgraph> {
+    let mut seeds = named_entities(conn, &q.entities)?;
+    if let Some(text) = &q.mentioned_in {
+        seeds.extend(mentioned_entities(conn, text)?);
+    }
+    if seeds.is_empty() || q.limit == 0 {
+        return Ok(Subgraph::default());
+    }
+
+    // Breadth-first over visible edges.
+    let relation = q.relation.as_deref().map(normalize_relation);
+    let mut edges: BTreeSet<(i64, String, i64)> = BTreeSet::new();
+    let mut reached = seeds.clone();
+    let mut frontier = seeds.clone();
+    for _ in 0..q.depth.max(1) {
+        if frontier.is_empty() || edges.len() >= q.limit {
+            break;
+        }
+        let mut values = Vec::new();
+        let ids = id_list(&frontier, &mut values);
+        let scope = visible("r", &q.scopes, &mut values);
+        let mut sql = format!(
+            "SELECT r.source_id, r.relation, r.target_id FROM graph_relations r
+             WHERE (r.source_id IN ({ids}) OR r.target_id IN ({ids})) AND {scope}"
+        );
+        if let Some(relation) = &relation {
+            values.push(Value::Text(relation.clone()));
+            let _ = write!(sql, " AND r.relation = ?{}", values.len());
+        }
+        sql.push_str(" ORDER BY r.created_at DESC");
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params_from_iter(values), |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?;
+
+        let mut next = BTreeSet::new();
+        for row in rows {
+            let (source, kind, target): (i64, String, i64) = row?;
+            if edges.len() >= q.limit {
+                break;
+            }
+            if edges.insert((source, kind, target)) {
+                for id in [source, target] {
+                    if reached.insert(id) {
+                        next.insert(id);
+                    }
+                }
+            }
+        }
+        frontier = next;
+    }
+
+    // Names, kinds and visible observations of everything reached.
+    let mut values = Vec::new();
+    let ids = id_list(&reached, &mut values);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, name, kind FROM graph_entities WHERE id IN ({ids})"
+    ))?;
+    let mut entities: BTreeMap<i64, Entity> = stmt
+        .query_map(params_from_iter(values), |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                Entity {
+                    name: row.get(1)?,
+                    kind: row.get(2)?,
+                    observations: Vec::new(),
+                },
+            ))
+        })?
+        .collect::<Result<_, _>>()?;
+
+    let mut values = Vec::new();
+    let ids = id_list(&reached, &mut values);
+    let scope = visible("o", &q.scopes, &mut values);
+    let mut stmt = conn.prepare(&format!(
+        "SELECT o.entity_id, o.content FROM graph_observations o
+         WHERE o.entity_id IN ({ids}) AND {scope}
+         ORDER BY o.created_at DESC, o.id DESC"
+    ))?;
+    let rows = stmt.query_map(params_from_iter(values), |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+    for row in rows {
+        let (id, stored) = row?;
+        if let Some(entity) = entities.get_mut(&id) {
+            if entity.observations.len() < MAX_OBSERVATIONS {
+                entity.observations.push(sealing.open(stored)?);
+            }
+        }
+    }
+
+    let linked: BTreeSet<i64> = edges.iter().flat_map(|(s, _, t)| [*s, *t]).collect();
+    let names: HashMap<i64, String> = entities
+        .iter()
+        .map(|(id, e)| (*id, e.name.clone()))
+        .collect();
+    let relations = edges
+        .iter()
+        .filter_map(|(s, kind, t)| {
+            Some(Relation {
+                source: names.get(s)?.clone(),
+                relation: kind.clone(),
+                target: names.get(t)?.clone(),
+            })
+        })
+        .collect();
+    // Nothing visible about an entity: don't reveal that it exists.
+    let entities = entities
+        .into_iter()
+        .filter(|(id, e)| linked.contains(id) || !e.observations.is_empty())
+        .map(|(_, e)| e)
+        .collect();
+    Ok(Subgraph {
+        entities,
+        relations,
+    })
+}
+
+/// `[Knowledge graph]` block for the prompt preamble: neighbors of entities
+/// named in `text` (the message plus recalled memories). Empty when the
+/// backend has no graph or nothing matches.
+pub async fn context_block(mem: &dyn Memory, text: &str, scopes: &[MemoryScope]) -> String {
+    let query = GraphQuery {
+        mentioned_in: Some(text.to_string()),
+        limit: CONTEXT_RELATIONS,
+        scopes: scopes.to_vec(),
+        ..GraphQuery::default()
+    };
+    match mem.graph_query(&query).await {
+        Ok(graph) if !graph.is_empty() => format!("[Knowledge graph]\n{}\n", graph.render()),
+        Ok(_) => String::new(),
+        Err(e) => {
+            tracing::debug!("knowledge graph lookup skipped: {e}");
+            String::new()
+        }
+    }
+}
+
+// ── Extraction ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ExtractionState {
+    last_run_at: Option<String>,
+    /// Content hash per memory key already read, so edits are re-read
+    processed: BTreeMap<String, String>,
+}
+
+/// Outcome of one extraction run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExtractionReport {
+    pub entries_considered: usize,
+    pub entities_added: usize,
+    pub relations_added: usize,
+    pub observations_added: usize,
+}
+
+pub struct GraphExtractor<'a> {
+    pub memory: &'a dyn Memory,
+    pub provider: &'a dyn Provider,
+    pub model: &'a str,
+    pub config: &'a GraphConfig,
+    pub workspace_dir: &'a Path,
+}
+
+impl GraphExtractor<'_> {
+    /// Read memories not extracted yet (or changed since) into the graph.
+    pub async fn run(&self) -> Result<ExtractionReport> {
+        let path = state_path(self.workspace_dir);
+        let mut state = load_state(&path)?;
+        let mut report = ExtractionReport::default();
+
+        let entries: Vec<MemoryEntry> = self
+            .memory
+            .list(None)
+            .await?
+            .into_iter()
+            .filter(|e| ingest::document_source(&e.key).is_none())
+            .collect();
+        let present: BTreeSet<&str> = entries.iter().map(|e| e.key.as_str()).collect();
+        state.processed.retain(|k, _| present.contains(k.as_str()));
+
+        let mut pending: Vec<MemoryEntry> = entries
+            .iter()
+            .filter(|e| state.processed.get(&e.key) != Some(&content_hash(&e.content)))
+            .cloned()
+            .collect();
+        pending.sort_by(|a, b| a.timestamp.cmp(&b.timestamp));
+        pending.truncate(self.config.max_entries);
+        report.entries_considered = pending.len();
+
+        // Never mix scopes: a private memory only yields facts in its scope.
+        let mut groups: BTreeMap<String, (MemoryScope, Vec<MemoryEntry>)> = BTreeMap::new();
+        for entry in pending {
+            let scope = MemoryScope::of(&entry);
+            groups
+                .entry(scope.to_string())
+                .or_insert_with(|| (scope, Vec::new()))
+                .1
+                .push(entry);
+        }
+
+        for (scope, entries) in groups.into_values() {
+            let update = self.propose(&scope, &entries).await?;
+            let added = self.memory.graph_upsert(&update, &scope).await?;
+            report.entities_added += added.entities_added;
+            report.relations_added += added.relations_added;
+            report.observations_added += added.observations_added;
+            for entry in entries {
+                state
+                    .processed
+                    .insert(entry.key.clone(), content_hash(&entry.content));
+            }
+        }
+
+        state.last_run_at = Some(Utc::now().to_rfc3339());
+        save_state(&path, &state)?;
+        Ok(report)
+    }
+
+    async fn propose(&self, scope: &MemoryScope, entries: &[MemoryEntry]) -> Result<GraphUpdate> {
+        let mut prompt = String::from("Entries:\n");
+        for entry in entries {
+            let _ = writeln!(
+                prompt,
+                "- [{}] ({}) {}",
+                scope.unqualify(&entry.key),
+                entry.timestamp,
+                entry.content
+            );
+        }
+        let raw = self
+            .provider
+            .chat_with_system(Some(SYSTEM_PROMPT), &prompt, self.model, 0.1)
+            .await?;
+        parse_update(&raw)
+    }
+}
+
+/// Run extraction from the heartbeat when enabled and the interval has elapsed.
+///
+/// Best-effort like consolidation: callers should log and continue on failure.
+pub async fn run_if_due(config: &Config) -> Result<Option<ExtractionReport>> {
+    let settings = &config.memory.graph;
+    if !settings.enabled || config.memory.backend != "sqlite" {
+        return Ok(None);
+    }
+    let state = load_state(&state_path(&config.workspace_dir))?;
+    let due = state
+        .last_run_at
+        .as_deref()
+        .and_then(|ts| DateTime::parse_from_rfc3339(ts).ok())
+        .map_or(true, |last| {
+            Utc::now().signed_duration_since(last.with_timezone(&Utc))
+                >= Duration::hours(i64::from(settings.interval_hours))
+        });
+    if !due {
+        return Ok(None);
+    }
+
+    let memory = super::create_memory(
+        &config.memory,
+        &config.workspace_dir,
+        config.api_key.as_deref(),
+    )?;
+    let provider = crate::providers::create_resilient_provider_with_options(
+        config.default_provider.as_deref().unwrap_or("openrouter"),
+        config.api_key.as_deref(),
+        &config.reliability,
+        &crate::providers::ProviderOptions::from_config(config),
+    )?;
+    let model = settings
+        .model
+        .clone()
+        .or_else(|| config.default_model.clone())
+        .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
+
+    let report = GraphExtractor {
+        memory: memory.as_ref(),
+        provider: provider.as_ref(),
+        model: &model,
+        config: settings,
+        workspace_dir: &config.workspace_dir,
+    }
+    .run()
+    .await?;
+    if report.entries_considered > 0 {
+        tracing::info!(
+            "knowledge graph extraction complete: considered={} entities={} relations={} observations={}",
+            report.entries_considered,
+            report.entities_added,
+            report.relations_added,
+            report.observations_added,
+        );
+    }
+    Ok(Some(report))
+}
+
+/// Extract the JSON object from a model reply (tolerates code fences and prose).
+fn parse_update(raw: &str) -> Result<GraphUpdate> {
+    let start = raw
+        .find('{')
+        .context("graph extraction reply contains no JSON")?;
+    let end = raw
+        .rfind('}')
+        .context("graph extraction reply contains no JSON")?;
+    serde_json::from_str(&raw[start..=end]).context("invalid graph extraction reply")
+}
+
+fn content_hash(text: &str) -> String {
+    use sha2::{Digest, Sha256};
+    hex::encode(&Sha256::digest(text.as_bytes())[..8])
+}
+
+fn state_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(STATE_FILE)
+}
+
+fn load_state(path: &Path) -> Result<ExtractionState> {
+    if !path.exists() {
+        return Ok(ExtractionState::default());
+    }
+    let raw = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&raw).unwrap_or_default())
+}
+
+fn save_state(path: &Path, state: &ExtractionState) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_vec_pretty(state)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MemoryCategory, SqliteMemory};
+    use async_trait::async_trait;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    fn billing_update() -> GraphUpdate {
+        GraphUpdate {
+            entities: vec![Entity {
+                name: "Alice".into(),
+                kind: "person".into(),
+                observations: vec!["Leads the payments team".into()],
+            }],
+            relations: vec![
+                Relation {
+                    source: "Alice".into(),
+                    relation: "Owns".into(),
+                    target: "Billing  Service".into(),
+                },
+                Relation {
+                    source: "Billing Service".into(),
+                    relation: "depends on".into(),
+                    target: "Postgres".into(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn names_and_relations_normalize() {
+        assert_eq!(normalize_name("  Billing   Service "), "billing service");
+        assert_eq!(normalize_relation("Depends On"), "depends_on");
+        assert_eq!(normalize_relation("--"), "");
+    }
+
+    #[tokio::test]
+    async fn upsert_is_idempotent_and_query_follows_edges() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let report = mem
+            .graph_upsert(&billing_update(), &MemoryScope::Global)
+            .await
+            .unwrap();
+        assert_eq!(
+            report,
+            GraphUpsertReport {
+                entities_added: 3,
+                relations_added: 2,
+                observations_added: 1,
+            }
+        );
+        let again = mem
+            .graph_upsert(&billing_update(), &MemoryScope::Global)
+            .await
+            .unwrap();
+        assert_eq!(again, GraphUpsertReport::default());
+
+        let owners = mem
+            .graph_query(&GraphQuery {
+                entities: vec!["billing service".into()],
+                relation: Some("owns".into()),
+                ..GraphQuery::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            owners.relations,
+            vec![Relation {
+                source: "Alice".into(),
+                relation: "owns".into(),
+                target: "Billing Service".into(),
+            }]
+        );
+        assert!(owners
+            .render()
+            .contains("Alice (person): Leads the payments team"));
+
+        let one_hop = mem
+            .graph_query(&GraphQuery {
+                entities: vec!["alice".into()],
+                ..GraphQuery::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(one_hop.relations.len(), 1);
+        let two_hops = mem
+            .graph_query(&GraphQuery {
+                entities: vec!["alice".into()],
+                depth: 2,
+                ..GraphQuery::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(two_hops.relations.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn scoped_facts_stay_in_scope() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let alice = MemoryScope::User("telegram:alice".into());
+        mem.graph_upsert(
+            &GraphUpdate {
+                entities: vec![Entity {
+                    name: "Locker".into(),
+                    kind: String::new(),
+                    observations: vec!["Code is 4411".into()],
+                }],
+                relations: vec![],
+            },
+            &alice,
+        )
+        .await
+        .unwrap();
+
+        let mentioned = |scopes: Vec<MemoryScope>| GraphQuery {
+            mentioned_in: Some("what's my locker code?".into()),
+            scopes,
+            ..GraphQuery::default()
+        };
+        assert!(mem
+            .graph_query(&mentioned(vec![]))
+            .await
+            .unwrap()
+            .is_empty());
+        let own = mem.graph_query(&mentioned(vec![alice])).await.unwrap();
+        assert_eq!(own.entities[0].observations, vec!["Code is 4411"]);
+    }
+
+    #[tokio::test]
+    async fn observations_are_sealed_when_encrypting() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path())
+            .unwrap()
+            .with_encryption(ContentCipher::new(&[7; 32], [9; 32]));
+        mem.graph_upsert(&billing_update(), &MemoryScope::Global)
+            .await
+            .unwrap();
+
+        let conn = Connection::open(tmp.path().join("memory").join("brain.db")).unwrap();
+        let stored: String = conn
+            .query_row("SELECT content FROM graph_observations", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert!(ContentCipher::is_encrypted(&stored));
+        let graph = mem
+            .graph_query(&GraphQuery {
+                entities: vec!["alice".into()],
+                ..GraphQuery::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            graph.entities[0].observations,
+            vec!["Leads the payments team"]
+        );
+    }
+
+    #[tokio::test]
+    async fn mentions_need_word_boundaries() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.graph_upsert(&billing_update(), &MemoryScope::Global)
+            .await
+            .unwrap();
+        let lookup = |text: &str| GraphQuery {
+            mentioned_in: Some(text.into()),
+            ..GraphQuery::default()
+        };
+        assert!(mem
+            .graph_query(&lookup("Alicent called"))
+            .await
+            .unwrap()
+            .is_empty());
+        let hit = mem
+            .graph_query(&lookup("Who owns the billing service?"))
+            .await
+            .unwrap();
+        assert_eq!(hit.relations.len(), 2);
+
+        let block = context_block(&mem, "ask Alice", &[]).await;
+        assert!(block.starts_with("[Knowledge graph]\n"));
+        assert!(block.contains("Alice —owns→ Billing Service"));
+    }
+
+    struct ScriptedProvider {
+        reply: String,
+        calls: Mutex<usize>,
+    }
+
+    #[async_trait]
+    impl Provider for ScriptedProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            _message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            *self.calls.lock().unwrap() += 1;
+            Ok(self.reply.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn extractor_reads_each_memory_once() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store(
+            "conv_1",
+            "Alice owns the billing service now",
+            MemoryCategory::Conversation,
+        )
+        .await
+        .unwrap();
+        let provider = ScriptedProvider {
+            reply: format!(
+                "```json\n{}\n```",
+                serde_json::to_string(&billing_update()).unwrap()
+            ),
+            calls: Mutex::new(0),
+        };
+        let config = GraphConfig::default();
+        let extractor = GraphExtractor {
+            memory: &mem,
+            provider: &provider,
+            model: "cheap",
+            config: &config,
+            workspace_dir: tmp.path(),
+        };
+
+        let report = extractor.run().await.unwrap();
+        assert_eq!(report.entries_considered, 1);
+        assert_eq!(report.relations_added, 2);
+        assert_eq!(extractor.run().await.unwrap().entries_considered, 0);
+        assert_eq!(*provider.calls.lock().unwrap(), 1);
+
+        mem.store(
+            "conv_1",
+            "Bob owns the billing service now",
+            MemoryCategory::Conversation,
+        )
+        .await
+        .unwrap();
+        assert_eq!(extractor.run().await.unwrap().entries_considered, 1);
+    }
+}
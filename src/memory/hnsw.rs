@@ -0,0 +1,750 @@
+// HNSW approximate nearest-neighbour index for vector recall.
+//
+// Hierarchical Navigable Small World graph (Malkov & Yashunin) over
+// L2-normalised embeddings, so distance is `1 - dot` and similarity matches
+// `vector::cosine_similarity`. The graph lives in memory and is persisted to
+// the `vector_index` / `vector_index_meta` tables in brain.db; inserts and
+// removals write back only the nodes they touched. Vectors are not
+// duplicated — they are read from `memories.embedding` on load.
+
+use super::vector;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// Max neighbours per node on upper layers (layer 0 keeps `2 * M`).
+const M: usize = 16;
+const EF_CONSTRUCTION: usize = 100;
+const EF_SEARCH: usize = 64;
+const MAX_LEVEL: usize = 16;
+/// Tombstones are compacted away once they outnumber live nodes.
+const COMPACT_MIN_DELETED: usize = 32;
+
+struct Node {
+    id: String,
+    vector: Vec<f32>,
+    neighbors: Vec<Vec<u32>>,
+    deleted: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    dist: f32,
+    slot: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.dist
+            .total_cmp(&other.dist)
+            .then(self.slot.cmp(&other.slot))
+    }
+}
+
+pub struct HnswIndex {
+    dims: usize,
+    nodes: Vec<Node>,
+    slots: HashMap<String, usize>,
+    entry: Option<usize>,
+    max_level: usize,
+    deleted: usize,
+    dirty: HashSet<usize>,
+    rewrite: bool,
+    rng: u64,
+}
+
+impl HnswIndex {
+    pub fn new() -> Self {
+        Self {
+            dims: 0,
+            nodes: Vec::new(),
+            slots: HashMap::new(),
+            entry: None,
+            max_level: 0,
+            deleted: 0,
+            dirty: HashSet::new(),
+            rewrite: true,
+            rng: 0x9E37_79B9_7F4A_7C15,
+        }
+    }
+
+    /// Number of live (searchable) vectors.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Insert or replace the vector for `id`. Zero vectors and vectors whose
+    /// dimensions differ from the index are dropped (and any old entry removed).
+    pub fn insert(&mut self, id: &str, vector: &[f32]) -> bool {
+        self.remove(id);
+        let Some(v) = normalize(vector) else {
+            return false;
+        };
+        if self.dims == 0 {
+            self.dims = v.len();
+        } else if v.len() != self.dims {
+            return false;
+        }
+
+        let level = self.random_level();
+        let slot = self.nodes.len();
+        self.nodes.push(Node {
+            id: id.to_string(),
+            vector: v,
+            neighbors: vec![Vec::new(); level + 1],
+            deleted: false,
+        });
+        self.slots.insert(id.to_string(), slot);
+        self.dirty.insert(slot);
+
+        let Some(mut ep) = self.entry else {
+            self.entry = Some(slot);
+            self.max_level = level;
+            return true;
+        };
+
+        let query = self.nodes[slot].vector.clone();
+        for layer in (level + 1..=self.max_level).rev() {
+            ep = self.search_layer(&query, &[ep], 1, layer)[0].slot;
+        }
+
+        let mut eps = vec![ep];
+        for layer in (0..=level.min(self.max_level)).rev() {
+            let found = self.search_layer(&query, &eps, EF_CONSTRUCTION, layer);
+            let selected: Vec<usize> = found
+                .iter()
+                .map(|c| c.slot)
+                .filter(|&s| s != slot)
+                .take(max_neighbors(layer))
+                .collect();
+            self.nodes[slot].neighbors[layer] = selected.iter().map(|&s| to_u32(s)).collect();
+            for &n in &selected {
+                self.link(n, slot, layer);
+            }
+            eps = found.into_iter().map(|c| c.slot).collect();
+        }
+
+        if level > self.max_level {
+            self.max_level = level;
+            self.entry = Some(slot);
+        }
+        true
+    }
+
+    /// Tombstone `id`. The node stays in the graph for navigation until the
+    /// next compaction.
+    pub fn remove(&mut self, id: &str) -> bool {
+        let Some(slot) = self.slots.remove(id) else {
+            return false;
+        };
+        self.nodes[slot].deleted = true;
+        self.deleted += 1;
+        self.dirty.insert(slot);
+        true
+    }
+
+    /// Top `limit` ids by cosine similarity (best first, similarity > 0).
+    pub fn search(&self, query: &[f32], limit: usize) -> Vec<(String, f32)> {
+        let Some(q) = normalize(query) else {
+            return Vec::new();
+        };
+        let Some(mut ep) = self.entry else {
+            return Vec::new();
+        };
+        if q.len() != self.dims || limit == 0 {
+            return Vec::new();
+        }
+
+        for layer in (1..=self.max_level).rev() {
+            ep = self.search_layer(&q, &[ep], 1, layer)[0].slot;
+        }
+        // Over-fetch so tombstones don't eat into the result count.
+        let ef = EF_SEARCH.max(limit * 2) + self.deleted.min(limit * 4);
+        self.search_layer(&q, &[ep], ef, 0)
+            .into_iter()
+            .filter(|c| !self.nodes[c.slot].deleted)
+            .map(|c| {
+                (
+                    self.nodes[c.slot].id.clone(),
+                    (1.0 - c.dist).clamp(0.0, 1.0),
+                )
+            })
+            .filter(|(_, sim)| *sim > 0.0)
+            .take(limit)
+            .collect()
+    }
+
+    fn distance(&self, query: &[f32], slot: usize) -> f32 {
+        let v = &self.nodes[slot].vector;
+        if v.len() != query.len() {
+            return 2.0;
+        }
+        1.0 - v.iter().zip(query).map(|(a, b)| a * b).sum::<f32>()
+    }
+
+    fn search_layer(
+        &self,
+        query: &[f32],
+        entry: &[usize],
+        ef: usize,
+        layer: usize,
+    ) -> Vec<Candidate> {
+        let mut visited = vec![false; self.nodes.len()];
+        for &slot in entry {
+            visited[slot] = true;
+        }
+        let mut candidates = BinaryHeap::new();
+        let mut results: BinaryHeap<Candidate> = BinaryHeap::new();
+
+        for &slot in entry {
+            let c = Candidate {
+                dist: self.distance(query, slot),
+                slot,
+            };
+            candidates.push(Reverse(c));
+            results.push(c);
+        }
+        while results.len() > ef {
+            results.pop();
+        }
+
+        while let Some(Reverse(current)) = candidates.pop() {
+            let worst = results.peek().map_or(f32::INFINITY, |w| w.dist);
+            if current.dist > worst && results.len() >= ef {
+                break;
+            }
+            let Some(neighbors) = self.nodes[current.slot].neighbors.get(layer) else {
+                continue;
+            };
+            for &n in neighbors {
+                let n = n as usize;
+                if std::mem::replace(&mut visited[n], true) {
+                    continue;
+                }
+                let dist = self.distance(query, n);
+                let worst = results.peek().map_or(f32::INFINITY, |w| w.dist);
+                if results.len() < ef || dist < worst {
+                    let c = Candidate { dist, slot: n };
+                    candidates.push(Reverse(c));
+                    results.push(c);
+                    if results.len() > ef {
+                        results.pop();
+                    }
+                }
+            }
+        }
+
+        results.into_sorted_vec()
+    }
+
+    /// Add `to` to `from`'s neighbour list, pruning to the closest if full.
+    fn link(&mut self, from: usize, to: usize, layer: usize) {
+        let max = max_neighbors(layer);
+        if self.nodes[from].neighbors.len() <= layer {
+            return;
+        }
+        self.nodes[from].neighbors[layer].push(to_u32(to));
+        if self.nodes[from].neighbors[layer].len() > max {
+            let base = &self.nodes[from].vector;
+            let mut scored: Vec<Candidate> = self.nodes[from].neighbors[layer]
+                .iter()
+                .map(|&n| Candidate {
+                    dist: self.distance(base, n as usize),
+                    slot: n as usize,
+                })
+                .collect();
+            scored.sort();
+            scored.truncate(max);
+            self.nodes[from].neighbors[layer] =
+                scored.into_iter().map(|c| to_u32(c.slot)).collect();
+        }
+        self.dirty.insert(from);
+    }
+
+    fn random_level(&mut self) -> usize {
+        // xorshift64* — level sampling only needs to be cheap, not secure.
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        let bits = self.rng.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11;
+        #[allow(clippy::cast_precision_loss)]
+        let uniform = (bits as f64 / (1_u64 << 53) as f64).max(f64::MIN_POSITIVE);
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let level = (-uniform.ln() / (M as f64).ln()) as usize;
+        level.min(MAX_LEVEL)
+    }
+
+    fn needs_compaction(&self) -> bool {
+        self.deleted >= COMPACT_MIN_DELETED && self.deleted > self.len()
+    }
+
+    /// Rebuild the graph from live nodes only.
+    fn compact(&mut self) {
+        let live: Vec<(String, Vec<f32>)> = std::mem::take(&mut self.nodes)
+            .into_iter()
+            .filter(|n| !n.deleted)
+            .map(|n| (n.id, n.vector))
+            .collect();
+        let rng = self.rng;
+        *self = Self::new();
+        self.rng = rng;
+        for (id, v) in live {
+            self.insert(&id, &v);
+        }
+    }
+
+    // ── Persistence ─────────────────────────────────────────────
+
+    pub fn init_schema(conn: &Connection) -> anyhow::Result<()> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS vector_index (
+                slot      INTEGER PRIMARY KEY,
+                memory_id TEXT NOT NULL,
+                deleted   INTEGER NOT NULL DEFAULT 0,
+                neighbors BLOB NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS vector_index_meta (
+                key   TEXT PRIMARY KEY,
+                value INTEGER NOT NULL
+            );",
+        )?;
+        Ok(())
+    }
+
+    /// Load the persisted graph and reconcile it with `memories`: nodes whose
+    /// row vanished are tombstoned, embedded rows missing from the graph are
+    /// inserted. A corrupt or missing graph is rebuilt from scratch.
+    pub fn load(conn: &Connection) -> anyhow::Result<Self> {
+        Self::init_schema(conn)?;
+        let vectors = load_vectors(conn)?;
+
+        let mut index = Self::read_graph(conn, &vectors).unwrap_or_else(|e| {
+            tracing::warn!("Rebuilding vector index: {e}");
+            Self::new()
+        });
+
+        let mut missing: Vec<(&String, &Vec<f32>)> = vectors
+            .iter()
+            .filter(|(id, _)| !index.slots.contains_key(*id))
+            .collect();
+        missing.sort_by(|a, b| a.0.cmp(b.0));
+        for (id, v) in missing {
+            index.insert(id, v);
+        }
+
+        index.save(conn)?;
+        Ok(index)
+    }
+
+    /// Discard the persisted graph and rebuild it from `memories`.
+    pub fn rebuild(conn: &Connection) -> anyhow::Result<Self> {
+        Self::init_schema(conn)?;
+        let mut vectors: Vec<(String, Vec<f32>)> = load_vectors(conn)?.into_iter().collect();
+        vectors.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut index = Self::new();
+        for (id, v) in &vectors {
+            index.insert(id, v);
+        }
+        index.save(conn)?;
+        Ok(index)
+    }
+
+    fn read_graph(conn: &Connection, vectors: &HashMap<String, Vec<f32>>) -> anyhow::Result<Self> {
+        let meta = |key: &str| -> anyhow::Result<Option<i64>> {
+            Ok(conn
+                .query_row(
+                    "SELECT value FROM vector_index_meta WHERE key = ?1",
+                    params![key],
+                    |row| row.get(0),
+                )
+                .optional()?)
+        };
+        let Some(dims) = meta("dims")? else {
+            return Ok(Self::new());
+        };
+
+        let mut index = Self::new();
+        index.rewrite = false;
+        #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+        {
+            index.dims = dims as usize;
+            index.max_level = meta("max_level")?.unwrap_or(0) as usize;
+            index.entry = meta("entry")?.filter(|e| *e >= 0).map(|e| e as usize);
+            index.rng ^= meta("rng")?.unwrap_or(0) as u64;
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT slot, memory_id, deleted, neighbors FROM vector_index ORDER BY slot",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, bool>(2)?,
+                row.get::<_, Vec<u8>>(3)?,
+            ))
+        })?;
+
+        for row in rows {
+            let (slot, id, mut deleted, blob) = row?;
+            let slot = usize::try_from(slot)?;
+            anyhow::ensure!(
+                slot == index.nodes.len(),
+                "vector index has a gap at slot {slot}"
+            );
+
+            let vector = vectors
+                .get(&id)
+                .and_then(|v| normalize(v))
+                .filter(|v| v.len() == index.dims);
+            if vector.is_none() && !deleted {
+                deleted = true;
+                index.dirty.insert(slot);
+            }
+            if deleted {
+                index.deleted += 1;
+            } else {
+                index.slots.insert(id.clone(), slot);
+            }
+            index.nodes.push(Node {
+                id,
+                vector: vector.unwrap_or_default(),
+                neighbors: decode_neighbors(&blob)?,
+                deleted,
+            });
+        }
+
+        let slots = index.nodes.len();
+        let in_bounds = index.nodes.iter().all(|n| {
+            n.neighbors
+                .iter()
+                .all(|layer| layer.iter().all(|&s| (s as usize) < slots))
+        });
+        anyhow::ensure!(in_bounds, "vector index references unknown slots");
+        anyhow::ensure!(
+            index.entry.map_or(slots == 0, |e| e < slots),
+            "vector index entry point out of range"
+        );
+        Ok(index)
+    }
+
+    /// Write nodes changed since the last save (or everything after a
+    /// rebuild/compaction) in a single transaction.
+    pub fn save(&mut self, conn: &Connection) -> anyhow::Result<()> {
+        if self.needs_compaction() {
+            self.compact();
+        }
+        if !self.rewrite && self.dirty.is_empty() {
+            return Ok(());
+        }
+
+        let tx = conn.unchecked_transaction()?;
+        let slots: Vec<usize> = if self.rewrite {
+            tx.execute("DELETE FROM vector_index", [])?;
+            (0..self.nodes.len()).collect()
+        } else {
+            self.dirty.iter().copied().collect()
+        };
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR REPLACE INTO vector_index (slot, memory_id, deleted, neighbors)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for slot in slots {
+                let node = &self.nodes[slot];
+                stmt.execute(params![
+                    i64::try_from(slot)?,
+                    node.id,
+                    node.deleted,
+                    encode_neighbors(&node.neighbors)
+                ])?;
+            }
+            let mut meta = tx
+                .prepare("INSERT OR REPLACE INTO vector_index_meta (key, value) VALUES (?1, ?2)")?;
+            meta.execute(params!["dims", i64::try_from(self.dims)?])?;
+            meta.execute(params!["max_level", i64::try_from(self.max_level)?])?;
+            meta.execute(params!["entry", self.entry.map_or(Ok(-1), i64::try_from)?])?;
+            #[allow(clippy::cast_possible_wrap)]
+            meta.execute(params!["rng", self.rng as i64])?;
+        }
+        tx.commit()?;
+
+        self.dirty.clear();
+        self.rewrite = false;
+        Ok(())
+    }
+}
+
+fn max_neighbors(layer: usize) -> usize {
+    if layer == 0 {
+        M * 2
+    } else {
+        M
+    }
+}
+
+fn to_u32(slot: usize) -> u32 {
+    u32::try_from(slot).unwrap_or(u32::MAX)
+}
+
+fn normalize(v: &[f32]) -> Option<Vec<f32>> {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if v.is_empty() || !norm.is_finite() || norm <= f32::EPSILON {
+        return None;
+    }
+    Some(v.iter().map(|x| x / norm).collect())
+}
+
+fn load_vectors(conn: &Connection) -> anyhow::Result<HashMap<String, Vec<f32>>> {
+    let mut stmt =
+        conn.prepare("SELECT id, embedding FROM memories WHERE embedding IS NOT NULL")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+    })?;
+    let mut vectors = HashMap::new();
+    for row in rows {
+        let (id, blob) = row?;
+        vectors.insert(id, vector::bytes_to_vec(&blob));
+    }
+    Ok(vectors)
+}
+
+/// `[layers u32][len u32, slot u32 * len]...`, little-endian.
+fn encode_neighbors(layers: &[Vec<u32>]) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(&to_u32(layers.len()).to_le_bytes());
+    for layer in layers {
+        out.extend_from_slice(&to_u32(layer.len()).to_le_bytes());
+        for slot in layer {
+            out.extend_from_slice(&slot.to_le_bytes());
+        }
+    }
+    out
+}
+
+fn decode_neighbors(bytes: &[u8]) -> anyhow::Result<Vec<Vec<u32>>> {
+    let mut words = bytes
+        .chunks_exact(4)
+        .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]));
+    let mut next = || {
+        words
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("truncated neighbour list"))
+    };
+
+    let layers = next()? as usize;
+    anyhow::ensure!(layers <= MAX_LEVEL + 1, "too many layers");
+    let mut out = Vec::with_capacity(layers);
+    for _ in 0..layers {
+        let len = next()? as usize;
+        anyhow::ensure!(len <= M * 2, "neighbour list too long");
+        out.push((0..len).map(|_| next()).collect::<anyhow::Result<_>>()?);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn random_vectors(n: usize, dims: usize, seed: u64) -> Vec<Vec<f32>> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..n)
+            .map(|_| (0..dims).map(|_| rng.gen_range(0.0..1.0)).collect())
+            .collect()
+    }
+
+    fn brute_force(vectors: &[Vec<f32>], query: &[f32], k: usize) -> Vec<String> {
+        let mut scored: Vec<(usize, f32)> = vectors
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (i, vector::cosine_similarity(query, v)))
+            .collect();
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        scored
+            .into_iter()
+            .take(k)
+            .map(|(i, _)| format!("m{i}"))
+            .collect()
+    }
+
+    fn memories_conn() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE memories (id TEXT PRIMARY KEY, embedding BLOB);")
+            .unwrap();
+        conn
+    }
+
+    fn put(conn: &Connection, id: &str, v: &[f32]) {
+        conn.execute(
+            "INSERT OR REPLACE INTO memories (id, embedding) VALUES (?1, ?2)",
+            params![id, vector::vec_to_bytes(v)],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn recall_matches_brute_force() {
+        let vectors = random_vectors(1000, 32, 7);
+        let mut index = HnswIndex::new();
+        for (i, v) in vectors.iter().enumerate() {
+            index.insert(&format!("m{i}"), v);
+        }
+        assert_eq!(index.len(), 1000);
+
+        let queries = random_vectors(20, 32, 99);
+        let mut hits = 0;
+        for q in &queries {
+            let exact = brute_force(&vectors, q, 10);
+            let approx: Vec<String> = index.search(q, 10).into_iter().map(|(id, _)| id).collect();
+            hits += approx.iter().filter(|id| exact.contains(id)).count();
+        }
+        let recall = f64::from(u32::try_from(hits).unwrap()) / 200.0;
+        assert!(recall >= 0.9, "recall@10 = {recall}");
+    }
+
+    #[test]
+    fn similarity_matches_cosine() {
+        let mut index = HnswIndex::new();
+        index.insert("a", &[1.0, 0.0]);
+        index.insert("b", &[1.0, 1.0]);
+        let results = index.search(&[2.0, 0.0], 2);
+        assert_eq!(results[0].0, "a");
+        assert!((results[0].1 - 1.0).abs() < 1e-5);
+        let expected = vector::cosine_similarity(&[2.0, 0.0], &[1.0, 1.0]);
+        assert!((results[1].1 - expected).abs() < 1e-5);
+    }
+
+    #[test]
+    fn removed_and_replaced_ids() {
+        let mut index = HnswIndex::new();
+        index.insert("a", &[1.0, 0.0]);
+        index.insert("b", &[0.0, 1.0]);
+        assert!(index.remove("a"));
+        assert!(!index.remove("a"));
+        assert!(index.search(&[1.0, 0.0], 5).iter().all(|(id, _)| id != "a"));
+
+        index.insert("b", &[1.0, 0.1]);
+        assert_eq!(index.len(), 1);
+        assert_eq!(index.search(&[1.0, 0.0], 1)[0].0, "b");
+    }
+
+    #[test]
+    fn rejects_zero_and_mismatched_vectors() {
+        let mut index = HnswIndex::new();
+        assert!(!index.insert("zero", &[0.0, 0.0]));
+        assert!(index.insert("a", &[1.0, 0.0]));
+        assert!(!index.insert("b", &[1.0, 0.0, 0.0]));
+        assert!(index.search(&[1.0, 0.0, 0.0], 5).is_empty());
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn compaction_drops_tombstones() {
+        let vectors = random_vectors(100, 8, 3);
+        let mut index = HnswIndex::new();
+        for (i, v) in vectors.iter().enumerate() {
+            index.insert(&format!("m{i}"), v);
+        }
+        for i in 0..80 {
+            index.remove(&format!("m{i}"));
+        }
+        assert!(index.needs_compaction());
+        index.compact();
+        assert_eq!(index.nodes.len(), 20);
+        assert_eq!(index.deleted, 0);
+        assert_eq!(index.search(&vectors[90], 1)[0].0, "m90");
+    }
+
+    #[test]
+    fn neighbor_encoding_roundtrip() {
+        let layers = vec![vec![1, 2, 3], vec![], vec![7]];
+        assert_eq!(
+            decode_neighbors(&encode_neighbors(&layers)).unwrap(),
+            layers
+        );
+        assert!(decode_neighbors(&[1, 0, 0, 0, 5, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn persisted_graph_reloads_and_reconciles() {
+        let conn = memories_conn();
+        let vectors = random_vectors(200, 16, 11);
+        for (i, v) in vectors.iter().enumerate() {
+            put(&conn, &format!("m{i}"), v);
+        }
+        let index = HnswIndex::load(&conn).unwrap();
+        assert_eq!(index.len(), 200);
+        let before = index.search(&vectors[5], 5);
+
+        // Row deleted behind the index's back, another added.
+        conn.execute("DELETE FROM memories WHERE id = 'm5'", [])
+            .unwrap();
+        put(&conn, "extra", &vectors[5]);
+
+        let reloaded = HnswIndex::load(&conn).unwrap();
+        assert_eq!(reloaded.len(), 200);
+        assert_eq!(reloaded.search(&vectors[5], 1)[0].0, "extra");
+        assert_eq!(
+            reloaded.search(&vectors[5], 5)[1..],
+            before[1..],
+            "other neighbours survive the reload"
+        );
+    }
+
+    #[test]
+    fn incremental_save_writes_only_dirty_nodes() {
+        let conn = memories_conn();
+        let mut index = HnswIndex::load(&conn).unwrap();
+        for (i, v) in random_vectors(50, 8, 5).iter().enumerate() {
+            index.insert(&format!("m{i}"), v);
+        }
+        index.save(&conn).unwrap();
+        assert!(index.dirty.is_empty());
+
+        index.remove("m3");
+        assert_eq!(index.dirty.len(), 1);
+        index.save(&conn).unwrap();
+        let deleted: i64 = conn
+            .query_row(
+                "SELECT deleted FROM vector_index WHERE memory_id = 'm3'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(deleted, 1);
+    }
+
+    #[test]
+    fn corrupt_graph_is_rebuilt() {
+        let conn = memories_conn();
+        put(&conn, "a", &[1.0, 0.0]);
+        HnswIndex::load(&conn).unwrap();
+        conn.execute("UPDATE vector_index SET neighbors = x'FF'", [])
+            .unwrap();
+
+        let index = HnswIndex::load(&conn).unwrap();
+        assert_eq!(index.search(&[1.0, 0.0], 1)[0].0, "a");
+    }
+}
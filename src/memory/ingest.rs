@@ -0,0 +1,524 @@
+// Document ingestion — load markdown/text files into memory as chunks.
+//
+// Each file is split with `chunker::chunk_markdown` and every chunk is stored
+// under a `doc:<source>#<index>` key in the `document` category, so the
+// backend embeds and indexes it like any other memory. A manifest of content
+// hashes (`state/memory_ingest_manifest.json`) lets re-runs skip unchanged
+// files; chunks of shrunk or deleted files are forgotten on every run.
+// Pruning only touches sources matched by the current patterns or recorded
+// in the manifest, so documents ingested from elsewhere are left alone.
+// Backends that don't keep custom categories (markdown files record only
+// core and daily entries) can't list the `document` category; for those the
+// manifest's chunk counts are the only record of what to forget.
+
+use super::chunker::chunk_markdown;
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MANIFEST_FILE: &str = "memory_ingest_manifest.json";
+const KEY_PREFIX: &str = "doc:";
+const DOCUMENT_EXTENSIONS: &[&str] = &["md", "markdown", "txt"];
+
+/// Category every ingested chunk is stored under.
+pub const DOCUMENT_CATEGORY: &str = "document";
+
+/// Instruction appended to memory context when it contains document chunks.
+pub const CITATION_HINT: &str =
+    "When you use information from a document excerpt, cite its source path.";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IngestReport {
+    pub files_ingested: usize,
+    pub files_unchanged: usize,
+    pub files_skipped: usize,
+    pub chunks_stored: usize,
+    pub chunks_removed: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ManifestEntry {
+    hash: String,
+    chunks: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct Manifest {
+    files: BTreeMap<String, ManifestEntry>,
+}
+
+/// Memory key for chunk `index` of `source`.
+pub fn chunk_key(source: &str, index: usize) -> String {
+    format!("{KEY_PREFIX}{source}#{index}")
+}
+
+/// Source path and chunk index if `key` belongs to an ingested document.
+pub fn document_source(key: &str) -> Option<(&str, usize)> {
+    let rest = key.strip_prefix(KEY_PREFIX)?;
+    let (source, index) = rest.rsplit_once('#')?;
+    Some((source, index.parse().ok()?))
+}
+
+/// Render a recalled entry for the `[Memory context]` block. Document chunks
+/// are labelled with their source so the model can cite them.
+pub fn context_line(entry: &MemoryEntry) -> String {
+    match document_source(&entry.key) {
+        Some((source, index)) => format!(
+            "- [source: {source}, chunk {index}] {}",
+            entry.content.replace('\n', " ")
+        ),
+        None => format!("- {}: {}", entry.key, entry.content),
+    }
+}
+
+/// Ingest every file matched by `patterns` (files, directories or globs).
+///
+/// Files whose content hash matches the manifest are skipped unless `force`
+/// is set. Chunks past a file's current chunk count, and every chunk of a
+/// manifest file that no longer exists, are forgotten.
+pub async fn ingest(
+    memory: &dyn Memory,
+    workspace_dir: &Path,
+    patterns: &[String],
+    max_tokens: usize,
+    force: bool,
+) -> Result<IngestReport> {
+    let files = resolve_files(patterns)?;
+    if files.is_empty() {
+        anyhow::bail!("No markdown or text files matched: {}", patterns.join(" "));
+    }
+
+    let mut manifest = read_manifest(workspace_dir);
+    let mut report = IngestReport::default();
+    let category = MemoryCategory::Custom(DOCUMENT_CATEGORY.into());
+    let mut known: BTreeSet<String> = manifest.files.keys().cloned().collect();
+    known.extend(files.iter().map(|path| source_name(path)));
+
+    for path in files {
+        let source = source_name(&path);
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                tracing::warn!("Skipping {}: {e}", path.display());
+                report.files_skipped += 1;
+                continue;
+            }
+        };
+
+        let hash = hex::encode(Sha256::digest(text.as_bytes()));
+        if !force && manifest.files.get(&source).is_some_and(|f| f.hash == hash) {
+            report.files_unchanged += 1;
+            continue;
+        }
+
+        let chunks = chunk_markdown(&text, max_tokens.max(1));
+        for chunk in &chunks {
+            memory
+                .store(
+                    &chunk_key(&source, chunk.index),
+                    &chunk.content,
+                    category.clone(),
+                )
+                .await
+                .with_context(|| format!("failed to store chunk {} of {source}", chunk.index))?;
+        }
+        report.files_ingested += 1;
+        report.chunks_stored += chunks.len();
+        let previous = manifest.files.insert(
+            source.clone(),
+            ManifestEntry {
+                hash,
+                chunks: chunks.len(),
+            },
+        );
+        if let Some(previous) = previous {
+            report.chunks_removed +=
+                forget_chunks(memory, &source, chunks.len()..previous.chunks).await?;
+        }
+    }
+
+    let deleted: Vec<(String, usize)> = manifest
+        .files
+        .iter()
+        .filter(|(source, _)| !Path::new(source).exists())
+        .map(|(source, file)| (source.clone(), file.chunks))
+        .collect();
+    for (source, chunks) in deleted {
+        report.chunks_removed += forget_chunks(memory, &source, 0..chunks).await?;
+        manifest.files.remove(&source);
+    }
+
+    // Re-sync against what the backend actually holds, so a lost or stale
+    // manifest can't leave orphaned chunks behind.
+    for entry in memory.list(Some(&category)).await? {
+        let Some((source, index)) = document_source(&entry.key) else {
+            continue;
+        };
+        if !known.contains(source) {
+            continue;
+        }
+        let stale = if Path::new(source).exists() {
+            manifest
+                .files
+                .get(source)
+                .is_some_and(|file| index >= file.chunks)
+        } else {
+            true
+        };
+        if stale && memory.forget(&entry.key).await? {
+            report.chunks_removed += 1;
+        }
+    }
+
+    write_manifest(workspace_dir, &manifest)?;
+    Ok(report)
+}
+
+async fn forget_chunks(
+    memory: &dyn Memory,
+    source: &str,
+    indices: std::ops::Range<usize>,
+) -> Result<usize> {
+    let mut removed = 0;
+    for index in indices {
+        if memory.forget(&chunk_key(source, index)).await? {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+fn source_name(path: &Path) -> String {
+    fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
+
+fn manifest_path(workspace_dir: &Path) -> PathBuf {
+    workspace_dir.join("state").join(MANIFEST_FILE)
+}
+
+fn read_manifest(workspace_dir: &Path) -> Manifest {
+    fs::read_to_string(manifest_path(workspace_dir))
+        .ok()
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn write_manifest(workspace_dir: &Path, manifest: &Manifest) -> Result<()> {
+    let path = manifest_path(workspace_dir);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&path, serde_json::to_vec_pretty(manifest)?)
+        .with_context(|| format!("failed to write {}", path.display()))
+}
+
+// ── Path resolution ─────────────────────────────────────────────
+
+pub(crate) fn is_document(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| DOCUMENT_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+fn has_wildcard(s: &str) -> bool {
+    s.contains(['*', '?', '['])
+}
+
+/// Expand files, directories (recursively) and `glob` patterns into a
+/// sorted, de-duplicated file list. Explicitly named files are always kept;
+/// directory and glob matches are limited to markdown/text extensions.
+fn resolve_files(patterns: &[String]) -> Result<Vec<PathBuf>> {
+    let mut files = BTreeSet::new();
+
+    for pattern in patterns {
+        if !has_wildcard(pattern) {
+            let path = PathBuf::from(pattern);
+            if path.is_dir() {
+                walk(&path, &mut |p| {
+                    if is_document(p) {
+                        files.insert(p.to_path_buf());
+                    }
+                })?;
+            } else if path.is_file() {
+                files.insert(path);
+            } else {
+                anyhow::bail!("Path not found: {pattern}");
+            }
+            continue;
+        }
+
+        let options = glob::MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: true,
+        };
+        let matches = glob::glob_with(pattern, options)
+            .with_context(|| format!("invalid glob pattern: {pattern}"))?;
+        for entry in matches {
+            match entry {
+                Ok(path) if path.is_file() && is_document(&path) => {
+                    files.insert(path);
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Skipping unreadable glob match: {e}"),
+            }
+        }
+    }
+
+    Ok(files.into_iter().collect())
+}
+
+fn walk(dir: &Path, visit: &mut dyn FnMut(&Path)) -> Result<()> {
+    let mut entries: Vec<_> = fs::read_dir(dir)
+        .with_context(|| format!("failed to read {}", dir.display()))?
+        .filter_map(std::result::Result::ok)
+        .collect();
+    entries.sort_by_key(fs::DirEntry::file_name);
+
+    for entry in entries {
+        let path = entry.path();
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if path.is_dir() {
+            if !hidden {
+                walk(&path, visit)?;
+            }
+        } else {
+            visit(&path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use tempfile::TempDir;
+
+    fn runbook(dir: &Path, name: &str, body: &str) -> PathBuf {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(&path, body).unwrap();
+        path
+    }
+
+    fn patterns(paths: &[&Path]) -> Vec<String> {
+        paths.iter().map(|p| p.display().to_string()).collect()
+    }
+
+    #[test]
+    fn chunk_key_roundtrip() {
+        let key = chunk_key("/docs/run#book.md", 3);
+        assert_eq!(document_source(&key), Some(("/docs/run#book.md", 3)));
+        assert_eq!(document_source("user_pref"), None);
+        assert_eq!(document_source("doc:missing-index"), None);
+    }
+
+    #[test]
+    fn context_line_labels_documents() {
+        let entry = MemoryEntry {
+            id: "1".into(),
+            key: chunk_key("/docs/deploy.md", 0),
+            content: "# Deploy\nRun make release".into(),
+            category: MemoryCategory::Custom(DOCUMENT_CATEGORY.into()),
+            timestamp: String::new(),
+            session_id: None,
+            score: None,
+            importance: None,
+            score_breakdown: None,
+        };
+        assert_eq!(
+            context_line(&entry),
+            "- [source: /docs/deploy.md, chunk 0] # Deploy Run make release"
+        );
+    }
+
+    #[test]
+    fn resolve_directories_and_globs() {
+        let tmp = TempDir::new().unwrap();
+        runbook(tmp.path(), "a.md", "a");
+        runbook(tmp.path(), "nested/b.txt", "b");
+        runbook(tmp.path(), "nested/c.rs", "c");
+        runbook(tmp.path(), ".git/d.md", "d");
+
+        let all = resolve_files(&patterns(&[tmp.path()])).unwrap();
+        assert_eq!(all.len(), 2);
+
+        let glob = format!("{}/**/*.txt", tmp.path().display());
+        let matched = resolve_files(&[glob]).unwrap();
+        assert_eq!(matched, vec![tmp.path().join("nested/b.txt")]);
+
+        let hidden = format!("{}/**/*.md", tmp.path().display());
+        assert_eq!(
+            resolve_files(&[hidden]).unwrap(),
+            vec![tmp.path().join("a.md")]
+        );
+
+        assert!(resolve_files(&["/nonexistent/file.md".into()]).is_err());
+    }
+
+    #[tokio::test]
+    async fn ingest_stores_chunks_and_skips_unchanged() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let doc = runbook(
+            tmp.path(),
+            "docs/restart.md",
+            "# Restart\nDrain the node first.\n\n## Rollback\nRevert the deploy tag.",
+        );
+        let paths = patterns(&[&doc]);
+
+        let report = ingest(&mem, tmp.path(), &paths, 512, false).await.unwrap();
+        assert_eq!(report.files_ingested, 1);
+        assert_eq!(report.chunks_stored, 2);
+
+        let source = source_name(&doc);
+        let chunk = mem.get(&chunk_key(&source, 1)).await.unwrap().unwrap();
+        assert!(chunk.content.contains("Revert the deploy tag"));
+        assert_eq!(
+            chunk.category,
+            MemoryCategory::Custom(DOCUMENT_CATEGORY.into())
+        );
+
+        let again = ingest(&mem, tmp.path(), &paths, 512, false).await.unwrap();
+        assert_eq!(again.files_unchanged, 1);
+        assert_eq!(again.chunks_stored, 0);
+
+        let forced = ingest(&mem, tmp.path(), &paths, 512, true).await.unwrap();
+        assert_eq!(forced.files_ingested, 1);
+    }
+
+    #[tokio::test]
+    async fn changed_file_drops_stale_chunks() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let doc = runbook(tmp.path(), "ops.md", "# One\nfirst\n\n# Two\nsecond");
+        let paths = patterns(&[&doc]);
+        ingest(&mem, tmp.path(), &paths, 512, false).await.unwrap();
+
+        fs::write(&doc, "# One\nrewritten").unwrap();
+        let report = ingest(&mem, tmp.path(), &paths, 512, false).await.unwrap();
+        assert_eq!(report.chunks_stored, 1);
+        assert_eq!(report.chunks_removed, 1);
+
+        let source = source_name(&doc);
+        assert!(mem.get(&chunk_key(&source, 1)).await.unwrap().is_none());
+        let first = mem.get(&chunk_key(&source, 0)).await.unwrap().unwrap();
+        assert!(first.content.contains("rewritten"));
+    }
+
+    #[tokio::test]
+    async fn resync_drops_deleted_files_and_orphaned_chunks() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let kept = runbook(tmp.path(), "docs/kept.md", "# Kept\nstays");
+        let gone = runbook(tmp.path(), "docs/gone.md", "# Gone\nremoved later");
+        let paths = vec![format!("{}/docs/*.md", tmp.path().display())];
+        ingest(&mem, tmp.path(), &paths, 512, false).await.unwrap();
+
+        // A chunk the manifest doesn't know about, e.g. from a lost manifest.
+        let kept_source = source_name(&kept);
+        let category = MemoryCategory::Custom(DOCUMENT_CATEGORY.into());
+        mem.store(&chunk_key(&kept_source, 7), "orphan", category)
+            .await
+            .unwrap();
+        let gone_source = source_name(&gone);
+        fs::remove_file(&gone).unwrap();
+
+        let report = ingest(&mem, tmp.path(), &paths, 512, false).await.unwrap();
+        assert_eq!(report.files_unchanged, 1);
+        assert_eq!(report.chunks_removed, 2);
+        assert!(mem
+            .get(&chunk_key(&gone_source, 0))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(mem
+            .get(&chunk_key(&kept_source, 7))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(mem
+            .get(&chunk_key(&kept_source, 0))
+            .await
+            .unwrap()
+            .is_some());
+        assert!(!read_manifest(tmp.path()).files.contains_key(&gone_source));
+    }
+
+    #[tokio::test]
+    async fn resync_leaves_documents_outside_patterns_and_manifest() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let doc = runbook(tmp.path(), "docs/kept.md", "# Kept\nstays");
+        let category = MemoryCategory::Custom(DOCUMENT_CATEGORY.into());
+        let foreign = chunk_key("/elsewhere/offline-share.md", 0);
+        mem.store(&foreign, "ingested from a mount that is offline", category)
+            .await
+            .unwrap();
+
+        let report = ingest(&mem, tmp.path(), &patterns(&[&doc]), 512, false)
+            .await
+            .unwrap();
+        assert_eq!(report.chunks_removed, 0);
+        assert!(mem.get(&foreign).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn markdown_backend_prunes_from_the_manifest() {
+        let tmp = TempDir::new().unwrap();
+        let mem = crate::memory::MarkdownMemory::new(tmp.path());
+        let doc = runbook(tmp.path(), "notes.md", "one line of notes");
+        let gone = runbook(tmp.path(), "gone.md", "removed later");
+        let paths = vec![format!("{}/*.md", tmp.path().display())];
+        ingest(&mem, tmp.path(), &paths, 512, false).await.unwrap();
+
+        let gone_source = source_name(&gone);
+        assert!(mem
+            .get(&chunk_key(&gone_source, 0))
+            .await
+            .unwrap()
+            .is_some());
+        fs::remove_file(&gone).unwrap();
+
+        let report = ingest(&mem, tmp.path(), &paths, 512, false).await.unwrap();
+        assert_eq!(report.chunks_removed, 1);
+        assert!(mem
+            .get(&chunk_key(&gone_source, 0))
+            .await
+            .unwrap()
+            .is_none());
+        assert!(mem
+            .get(&chunk_key(&source_name(&doc), 0))
+            .await
+            .unwrap()
+            .is_some());
+    }
+
+    #[tokio::test]
+    async fn recalled_chunks_carry_source() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let doc = runbook(
+            tmp.path(),
+            "failover.md",
+            "# Failover\nPromote the replica.",
+        );
+        ingest(&mem, tmp.path(), &patterns(&[&doc]), 512, false)
+            .await
+            .unwrap();
+
+        let hits = mem.recall("replica", 5).await.unwrap();
+        let line = context_line(&hits[0]);
+        assert!(line.contains(&format!("[source: {}", source_name(&doc))));
+    }
+}
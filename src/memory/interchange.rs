@@ -0,0 +1,456 @@
+//! Portable memory interchange format, used by `memory export` / `memory import`
+//! and for moving memories between machines or backends.
+//!
+//! JSON lines. The first line is a header, every following line one entry:
+//!
+//! ```text
+//! {"format":"zeroclaw-memory","version":1,"exported_at":"2025-06-01T09:00:00+00:00","backend":"sqlite","embedding_signature":"openai/text-embedding-3-small/1536"}
+//! {"key":"user_lang","content":"Prefers Rust","category":"core","created_at":"2025-05-02T10:11:12+00:00","updated_at":"2025-05-20T08:00:00+00:00","importance":0.8,"embedding":[0.0123,-0.0456]}
+//! {"key":"timezone","content":"Lives in CET","category":"core","scope":"user:telegram:alice","created_at":"2025-05-03T18:00:00+00:00"}
+//! ```
+//!
+//! - `key` is the key as stored by the caller; `scope` is the scope label
+//!   (`user:…`, `channel:…`, `session:…`) and is absent for global entries.
+//! - `embedding` is present only when the exporting backend stores vectors.
+//!   Importers reuse it only when the header's `embedding_signature`
+//!   (`provider/model/dimensions`) matches their own model, and re-embed otherwise.
+//! - Readers reject a `version` newer than [`FORMAT_VERSION`]. Files without a
+//!   header — older exports with one `MemoryEntry` per line, or a JSON array —
+//!   are still accepted.
+
+use super::traits::{Memory, MemoryCategory, MemoryEntry, MemoryScope};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+pub const FORMAT: &str = "zeroclaw-memory";
+pub const FORMAT_VERSION: u32 = 1;
+
+/// First line of an export.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Header {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: String,
+    /// Backend the snapshot was taken from
+    pub backend: String,
+    /// `provider/model/dimensions` of the model behind `Record::embedding`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding_signature: Option<String>,
+}
+
+/// One memory entry in interchange form.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub key: String,
+    pub content: String,
+    pub category: MemoryCategory,
+    /// Scope label; absent for global entries
+    #[serde(default, alias = "session_id", skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    #[serde(alias = "timestamp")]
+    pub created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub importance: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+impl Record {
+    pub fn from_entry(entry: &MemoryEntry) -> Self {
+        let scope = MemoryScope::of(entry);
+        Self {
+            key: scope.unqualify(&entry.key).to_string(),
+            content: entry.content.clone(),
+            category: entry.category.clone(),
+            scope: (scope != MemoryScope::Global).then(|| scope.to_string()),
+            created_at: entry.timestamp.clone(),
+            updated_at: None,
+            importance: entry.importance,
+            embedding: None,
+        }
+    }
+
+    pub fn scope(&self) -> Result<MemoryScope> {
+        match &self.scope {
+            Some(label) => label.parse(),
+            None => Ok(MemoryScope::Global),
+        }
+    }
+
+    /// Key as stored by backends (`<scope>/<key>` for scoped entries).
+    pub fn qualified_key(&self) -> Result<String> {
+        Ok(self.scope()?.qualify(&self.key))
+    }
+
+    /// Creation time as RFC 3339. Date-only values (Markdown daily logs)
+    /// become local midnight; anything unparseable becomes now.
+    pub fn created_at_rfc3339(&self) -> String {
+        normalize_time(&self.created_at).unwrap_or_else(|| Local::now().to_rfc3339())
+    }
+
+    /// Last write time as RFC 3339, falling back to the creation time.
+    pub fn updated_at_rfc3339(&self) -> String {
+        self.updated_at
+            .as_deref()
+            .and_then(normalize_time)
+            .unwrap_or_else(|| self.created_at_rfc3339())
+    }
+}
+
+fn normalize_time(raw: &str) -> Option<String> {
+    if DateTime::parse_from_rfc3339(raw).is_ok() {
+        return Some(raw.to_string());
+    }
+    let date = NaiveDate::parse_from_str(raw.get(..10)?, "%Y-%m-%d").ok()?;
+    super::traits::day_bound(date, false).map(|at| at.with_timezone(&Local).to_rfc3339())
+}
+
+/// All entries of a backend, plus the model their embeddings came from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snapshot {
+    pub embedding_signature: Option<String>,
+    pub records: Vec<Record>,
+}
+
+/// What to do when an imported key already exists with different content.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictStrategy {
+    /// Keep the existing entry
+    #[default]
+    Skip,
+    /// Replace the existing entry
+    Overwrite,
+    /// Import under a new key (`<key>_imported`, `<key>_imported_2`, …)
+    Rename,
+}
+
+impl std::str::FromStr for ConflictStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim() {
+            "skip" => Ok(Self::Skip),
+            "overwrite" => Ok(Self::Overwrite),
+            "rename" => Ok(Self::Rename),
+            other => {
+                bail!("unknown conflict strategy '{other}' (expected skip, overwrite or rename)")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// New keys
+    pub imported: usize,
+    pub overwritten: usize,
+    pub renamed: usize,
+    /// Conflicts kept as they were (`skip`)
+    pub skipped: usize,
+    /// Same key and content already present
+    pub unchanged: usize,
+}
+
+/// Serialize a snapshot: header line, then one record per line.
+pub fn write(snapshot: &Snapshot, backend: &str) -> Result<String> {
+    let header = Header {
+        format: FORMAT.into(),
+        version: FORMAT_VERSION,
+        exported_at: Local::now().to_rfc3339(),
+        backend: backend.into(),
+        embedding_signature: snapshot.embedding_signature.clone(),
+    };
+    let mut out = serde_json::to_string(&header)?;
+    out.push('\n');
+    for record in &snapshot.records {
+        out.push_str(&serde_json::to_string(record)?);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+/// Parse an export (any supported version, or a legacy headerless file).
+pub fn parse(raw: &str) -> Result<Snapshot> {
+    let mut records: Vec<Record> = Vec::new();
+    let mut embedding_signature = None;
+
+    if raw.trim_start().starts_with('[') {
+        records = serde_json::from_str(raw).context("invalid JSON array export")?;
+    } else {
+        for (i, line) in raw.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let value: serde_json::Value =
+                serde_json::from_str(line).with_context(|| format!("line {}", i + 1))?;
+            if value.get("format").is_some() {
+                let header: Header =
+                    serde_json::from_value(value).with_context(|| format!("line {}", i + 1))?;
+                if header.format != FORMAT {
+                    bail!("line {}: not a {FORMAT} export ({})", i + 1, header.format);
+                }
+                if header.version > FORMAT_VERSION {
+                    bail!(
+                        "export format v{} is newer than this build supports (v{FORMAT_VERSION}); upgrade zeroclaw",
+                        header.version
+                    );
+                }
+                embedding_signature = header.embedding_signature;
+                continue;
+            }
+            records.push(serde_json::from_value(value).with_context(|| format!("line {}", i + 1))?);
+        }
+    }
+
+    // Legacy exports carry qualified keys next to the scope label.
+    for record in &mut records {
+        let scope = record.scope()?;
+        record.key = scope.unqualify(&record.key).to_string();
+    }
+    Ok(Snapshot {
+        embedding_signature,
+        records,
+    })
+}
+
+/// Write `snapshot` into `mem`, resolving existing keys with `strategy`.
+pub async fn import(
+    mem: &dyn Memory,
+    snapshot: &Snapshot,
+    strategy: ConflictStrategy,
+) -> Result<ImportReport> {
+    let mut report = ImportReport::default();
+    let signature = snapshot.embedding_signature.as_deref();
+
+    for record in &snapshot.records {
+        let key = record.qualified_key()?;
+        let existing = mem.get(&key).await?.filter(|e| e.key == key);
+        let target = match existing {
+            None => {
+                report.imported += 1;
+                record.clone()
+            }
+            Some(existing) if existing.content == record.content => {
+                report.unchanged += 1;
+                continue;
+            }
+            Some(_) => match strategy {
+                ConflictStrategy::Skip => {
+                    report.skipped += 1;
+                    continue;
+                }
+                ConflictStrategy::Overwrite => {
+                    report.overwritten += 1;
+                    record.clone()
+                }
+                ConflictStrategy::Rename => {
+                    report.renamed += 1;
+                    Record {
+                        key: free_key(mem, &record.scope()?, &record.key).await?,
+                        ..record.clone()
+                    }
+                }
+            },
+        };
+        mem.restore(&target, signature).await?;
+    }
+    Ok(report)
+}
+
+async fn free_key(mem: &dyn Memory, scope: &MemoryScope, base: &str) -> Result<String> {
+    for i in 1..=10_000 {
+        let candidate = if i == 1 {
+            format!("{base}_imported")
+        } else {
+            format!("{base}_imported_{i}")
+        };
+        let qualified = scope.qualify(&candidate);
+        if mem
+            .get(&qualified)
+            .await?
+            .filter(|e| e.key == qualified)
+            .is_none()
+        {
+            return Ok(candidate);
+        }
+    }
+    bail!("Unable to allocate non-conflicting key for '{base}'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::{MarkdownMemory, SqliteMemory};
+    use tempfile::TempDir;
+
+    #[test]
+    fn parse_reads_header_and_records() {
+        let raw = r#"{"format":"zeroclaw-memory","version":1,"exported_at":"x","backend":"sqlite","embedding_signature":"local/hashed-ngram-v1/8"}
+{"key":"lang","content":"Rust","category":"core","created_at":"2025-01-01T00:00:00+00:00","embedding":[0.5,0.5]}
+"#;
+        let snapshot = parse(raw).unwrap();
+        assert_eq!(
+            snapshot.embedding_signature.as_deref(),
+            Some("local/hashed-ngram-v1/8")
+        );
+        assert_eq!(snapshot.records.len(), 1);
+        assert_eq!(snapshot.records[0].embedding, Some(vec![0.5, 0.5]));
+    }
+
+    #[test]
+    fn parse_accepts_legacy_entries() {
+        let raw = r#"{"id":"1","key":"user:alice/pin","content":"5512","category":"core","timestamp":"2025-01-01T00:00:00+00:00","session_id":"user:alice","score":null}"#;
+        let snapshot = parse(raw).unwrap();
+        let record = &snapshot.records[0];
+        assert_eq!(record.key, "pin");
+        assert_eq!(record.scope.as_deref(), Some("user:alice"));
+        assert_eq!(record.qualified_key().unwrap(), "user:alice/pin");
+        assert_eq!(snapshot.embedding_signature, None);
+    }
+
+    #[test]
+    fn parse_rejects_newer_versions_and_foreign_formats() {
+        let newer =
+            r#"{"format":"zeroclaw-memory","version":99,"exported_at":"x","backend":"sqlite"}"#;
+        assert!(parse(newer).unwrap_err().to_string().contains("newer"));
+        let foreign = r#"{"format":"other","version":1,"exported_at":"x","backend":"sqlite"}"#;
+        assert!(parse(foreign).is_err());
+    }
+
+    #[test]
+    fn date_only_created_at_is_normalized() {
+        let record = Record {
+            key: "k".into(),
+            content: "c".into(),
+            category: MemoryCategory::Daily,
+            scope: None,
+            created_at: "2025-03-01".into(),
+            updated_at: None,
+            importance: None,
+            embedding: None,
+        };
+        let at = DateTime::parse_from_rfc3339(&record.created_at_rfc3339()).unwrap();
+        assert_eq!(
+            at.with_timezone(&Local).date_naive().to_string(),
+            "2025-03-01"
+        );
+        assert_eq!(record.updated_at_rfc3339(), record.created_at_rfc3339());
+    }
+
+    #[tokio::test]
+    async fn conflict_strategies() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        mem.store("lang", "Prefers Go", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("tz", "UTC", MemoryCategory::Core).await.unwrap();
+
+        let record = |key: &str, content: &str| Record {
+            key: key.into(),
+            content: content.into(),
+            category: MemoryCategory::Core,
+            scope: None,
+            created_at: "2025-01-01T00:00:00+00:00".into(),
+            updated_at: None,
+            importance: Some(0.9),
+            embedding: None,
+        };
+        let snapshot = Snapshot {
+            embedding_signature: None,
+            records: vec![
+                record("lang", "Prefers Rust"),
+                record("tz", "UTC"),
+                record("editor", "helix"),
+            ],
+        };
+
+        let report = import(&mem, &snapshot, ConflictStrategy::Skip)
+            .await
+            .unwrap();
+        assert_eq!(
+            report,
+            ImportReport {
+                imported: 1,
+                skipped: 1,
+                unchanged: 1,
+                ..ImportReport::default()
+            }
+        );
+        assert_eq!(
+            mem.get("lang").await.unwrap().unwrap().content,
+            "Prefers Go"
+        );
+        assert_eq!(
+            mem.get("editor").await.unwrap().unwrap().importance,
+            Some(0.9)
+        );
+
+        let report = import(&mem, &snapshot, ConflictStrategy::Rename)
+            .await
+            .unwrap();
+        assert_eq!(report.renamed, 1);
+        assert_eq!(
+            mem.get("lang_imported").await.unwrap().unwrap().content,
+            "Prefers Rust"
+        );
+        assert_eq!(
+            mem.get("lang").await.unwrap().unwrap().content,
+            "Prefers Go"
+        );
+
+        let report = import(&mem, &snapshot, ConflictStrategy::Overwrite)
+            .await
+            .unwrap();
+        assert_eq!(report.overwritten, 1);
+        assert_eq!(
+            mem.get("lang").await.unwrap().unwrap().content,
+            "Prefers Rust"
+        );
+    }
+
+    #[tokio::test]
+    async fn sqlite_markdown_roundtrip_keeps_scopes_and_dates() {
+        let src_dir = TempDir::new().unwrap();
+        let src = SqliteMemory::new(src_dir.path()).unwrap();
+        let alice = MemoryScope::User("telegram:alice".into());
+        src.store("lang", "Prefers Rust", MemoryCategory::Core)
+            .await
+            .unwrap();
+        src.store_scoped("pin", "door pin 5512", MemoryCategory::Core, &alice)
+            .await
+            .unwrap();
+        src.store("standup", "standup moved to 10am", MemoryCategory::Daily)
+            .await
+            .unwrap();
+        let exported = write(&src.snapshot().await.unwrap(), "sqlite").unwrap();
+
+        let md_dir = TempDir::new().unwrap();
+        let md = MarkdownMemory::new(md_dir.path());
+        let report = import(&md, &parse(&exported).unwrap(), ConflictStrategy::Skip)
+            .await
+            .unwrap();
+        assert_eq!(report.imported, 3);
+        assert!(md.get("user:telegram:alice/pin").await.unwrap().is_some());
+
+        let back = write(&md.snapshot().await.unwrap(), "markdown").unwrap();
+        let dst_dir = TempDir::new().unwrap();
+        let dst = SqliteMemory::new(dst_dir.path()).unwrap();
+        import(&dst, &parse(&back).unwrap(), ConflictStrategy::Skip)
+            .await
+            .unwrap();
+        assert_eq!(dst.count().await.unwrap(), 3);
+        let pin = dst.get("user:telegram:alice/pin").await.unwrap().unwrap();
+        assert_eq!(pin.content, "door pin 5512");
+        assert_eq!(pin.category, MemoryCategory::Core);
+        let standup = dst.get("standup").await.unwrap().unwrap();
+        assert_eq!(standup.category, MemoryCategory::Daily);
+        assert_eq!(
+            standup.timestamp.get(..10),
+            Some(Local::now().format("%Y-%m-%d").to_string().as_str())
+        );
+    }
+}
@@ -1,18 +1,70 @@
-use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::interchange::{self, Snapshot};
+use super::traits::{Memory, MemoryCategory, MemoryEntry, MemoryScope};
 use async_trait::async_trait;
-use chrono::Local;
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
 use tokio::fs;
+use tokio::io::AsyncWriteExt;
+
+/// BM25 term-frequency saturation
+const BM25_K1: f64 = 1.2;
+/// BM25 document-length normalization
+const BM25_B: f64 = 0.75;
 
 /// Markdown-based memory — plain files as source of truth
 ///
 /// Layout:
 ///   workspace/MEMORY.md          — curated long-term memory (core)
 ///   workspace/memory/YYYY-MM-DD.md — daily logs (append-only)
+///   workspace/memory/scopes/<scope>/ — same layout for user/channel/session scopes,
+///                                        plus a `.scope` file holding the label
+///   workspace/memory/tombstones.jsonl — forgotten lines, hidden until `compact`
+///   workspace/memory/audit/compaction.jsonl — key and hash of lines removed by `compact`
 pub struct MarkdownMemory {
     workspace_dir: PathBuf,
 }
 
+/// One entry line of a memory file.
+struct Record {
+    entry: MemoryEntry,
+    /// File path relative to the workspace
+    file: String,
+    /// `{file stem}:{n}` — n-th entry line of the file
+    position: String,
+    /// Hash of the line, so a tombstone never hides a line that was edited
+    hash: String,
+}
+
+/// Marks one line as forgotten. Files stay append-only until `compact`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Tombstone {
+    file: String,
+    position: String,
+    hash: String,
+    key: String,
+    forgotten_at: String,
+}
+
+/// A line removed by `compact`, kept for auditing. Only the key and a hash of
+/// the line are recorded — forgotten content must not survive compaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CompactedLine {
+    file: String,
+    key: String,
+    hash: String,
+    forgotten_at: String,
+    compacted_at: String,
+}
+
+/// Outcome of [`MarkdownMemory::compact`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactReport {
+    pub entries_removed: usize,
+    pub files_rewritten: usize,
+}
+
 impl MarkdownMemory {
     pub fn new(workspace_dir: &Path) -> Self {
         Self {
@@ -29,17 +81,49 @@ impl MarkdownMemory {
     }
 
     fn daily_path(&self) -> PathBuf {
-        let date = Local::now().format("%Y-%m-%d").to_string();
-        self.memory_dir().join(format!("{date}.md"))
+        self.daily_path_for(Local::now().date_naive())
     }
 
-    async fn ensure_dirs(&self) -> anyhow::Result<()> {
-        fs::create_dir_all(self.memory_dir()).await?;
-        Ok(())
+    fn daily_path_for(&self, date: NaiveDate) -> PathBuf {
+        self.memory_dir()
+            .join(format!("{}.md", date.format("%Y-%m-%d")))
+    }
+
+    fn tombstones_path(&self) -> PathBuf {
+        self.memory_dir().join("tombstones.jsonl")
+    }
+
+    /// Where `compact` records the lines it removed.
+    pub fn audit_path(&self) -> PathBuf {
+        self.memory_dir().join("audit").join("compaction.jsonl")
+    }
+
+    /// Directory for a non-global scope: a readable slug plus a hash of the
+    /// exact label, so distinct senders never share a directory.
+    fn scope_dir(&self, scope: &MemoryScope) -> PathBuf {
+        use sha2::{Digest, Sha256};
+        let label = scope.to_string();
+        let slug: String = label
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let hash = Sha256::digest(label.as_bytes());
+        self.memory_dir().join("scopes").join(format!(
+            "{slug}-{:02x}{:02x}{:02x}{:02x}",
+            hash[0], hash[1], hash[2], hash[3]
+        ))
     }
 
     async fn append_to_file(&self, path: &Path, content: &str) -> anyhow::Result<()> {
-        self.ensure_dirs().await?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
 
         let existing = if path.exists() {
             fs::read_to_string(path).await.unwrap_or_default()
@@ -48,10 +132,13 @@ impl MarkdownMemory {
         };
 
         let updated = if existing.is_empty() {
-            let header = if path == self.core_path() {
+            let header = if path.file_name().and_then(|n| n.to_str()) == Some("MEMORY.md") {
                 "# Long-Term Memory\n\n"
             } else {
-                let date = Local::now().format("%Y-%m-%d").to_string();
+                let date = path
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or_default();
                 &format!("# Daily Log — {date}\n\n")
             };
             format!("{header}{content}\n")
@@ -63,72 +150,399 @@ impl MarkdownMemory {
         Ok(())
     }
 
-    fn parse_entries_from_file(
+    fn line_hash(line: &str) -> String {
+        use sha2::{Digest, Sha256};
+        let hash = Sha256::digest(line.as_bytes());
+        hex::encode(&hash[..8])
+    }
+
+    /// Entry lines are `- **key**: content`; other lines use their position as key.
+    fn split_key_markup(line: &str) -> Option<(&str, &str)> {
+        line.strip_prefix("**")?
+            .split_once("**: ")
+            .filter(|(key, _)| !key.is_empty())
+    }
+
+    fn parse_records(
+        &self,
         path: &Path,
         content: &str,
         category: &MemoryCategory,
-    ) -> Vec<MemoryEntry> {
+        scope: &MemoryScope,
+    ) -> Vec<Record> {
         let filename = path
             .file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("unknown");
+        let file = path
+            .strip_prefix(&self.workspace_dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let session_id = (*scope != MemoryScope::Global).then(|| scope.to_string());
 
         content
             .lines()
-            .filter(|line| {
-                let trimmed = line.trim();
-                !trimmed.is_empty() && !trimmed.starts_with('#')
-            })
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
             .enumerate()
             .map(|(i, line)| {
-                let trimmed = line.trim();
-                let clean = trimmed.strip_prefix("- ").unwrap_or(trimmed);
-                MemoryEntry {
-                    id: format!("{filename}:{i}"),
-                    key: format!("{filename}:{i}"),
-                    content: clean.to_string(),
-                    category: category.clone(),
-                    timestamp: filename.to_string(),
-                    session_id: None,
-                    score: None,
+                let clean = line.strip_prefix("- ").unwrap_or(line);
+                let position = format!("{filename}:{i}");
+                let (key, text) = Self::split_key_markup(clean).unwrap_or((&position, clean));
+                Record {
+                    entry: MemoryEntry {
+                        id: scope.qualify(&position),
+                        key: scope.qualify(key),
+                        content: text.to_string(),
+                        category: category.clone(),
+                        timestamp: filename.to_string(),
+                        session_id: session_id.clone(),
+                        score: None,
+                        importance: None,
+                        score_breakdown: None,
+                    },
+                    file: file.clone(),
+                    position,
+                    hash: Self::line_hash(line),
                 }
             })
             .collect()
     }
 
+    /// Memory files of a scope with their category: `MEMORY.md` is core,
+    /// everything else a daily log.
+    async fn scope_files(&self, scope: &MemoryScope) -> anyhow::Result<Vec<PathBuf>> {
+        let (core, dir) = match scope {
+            MemoryScope::Global => (self.core_path(), self.memory_dir()),
+            other => {
+                let dir = self.scope_dir(other);
+                (dir.join("MEMORY.md"), dir)
+            }
+        };
+
+        let mut files = Vec::new();
+        if core.exists() {
+            files.push(core.clone());
+        }
+        if dir.exists() {
+            let mut entries = fs::read_dir(&dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path != core && path.extension().and_then(|e| e.to_str()) == Some("md") {
+                    files.push(path);
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    /// Every entry line of a scope, including forgotten ones.
+    async fn read_records(&self, scope: &MemoryScope) -> anyhow::Result<Vec<Record>> {
+        let mut records = Vec::new();
+        for path in self.scope_files(scope).await? {
+            let category = if path.file_name().and_then(|n| n.to_str()) == Some("MEMORY.md") {
+                MemoryCategory::Core
+            } else {
+                MemoryCategory::Daily
+            };
+            let content = fs::read_to_string(&path).await?;
+            records.extend(self.parse_records(&path, &content, &category, scope));
+        }
+        records.sort_by(|a, b| b.entry.timestamp.cmp(&a.entry.timestamp));
+        Ok(records)
+    }
+
+    async fn read_tombstones(&self) -> anyhow::Result<Vec<Tombstone>> {
+        let path = self.tombstones_path();
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let raw = fs::read_to_string(&path).await?;
+        Ok(raw
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// Entry lines of a scope that have not been forgotten.
+    async fn live_records(&self, scope: &MemoryScope) -> anyhow::Result<Vec<Record>> {
+        let tombstones = self.read_tombstones().await?;
+        let dead: HashSet<(&str, &str, &str)> = tombstones
+            .iter()
+            .map(|t| (t.file.as_str(), t.position.as_str(), t.hash.as_str()))
+            .collect();
+        Ok(self
+            .read_records(scope)
+            .await?
+            .into_iter()
+            .filter(|r| !dead.contains(&(r.file.as_str(), r.position.as_str(), r.hash.as_str())))
+            .collect())
+    }
+
     async fn read_all_entries(&self) -> anyhow::Result<Vec<MemoryEntry>> {
-        let mut entries = Vec::new();
-
-        // Read MEMORY.md (core)
-        let core_path = self.core_path();
-        if core_path.exists() {
-            let content = fs::read_to_string(&core_path).await?;
-            entries.extend(Self::parse_entries_from_file(
-                &core_path,
-                &content,
-                &MemoryCategory::Core,
-            ));
+        Ok(self
+            .live_records(&MemoryScope::Global)
+            .await?
+            .into_iter()
+            .map(|r| r.entry)
+            .collect())
+    }
+
+    async fn write_tombstones(&self, records: &[&Record]) -> anyhow::Result<()> {
+        if records.is_empty() {
+            return Ok(());
         }
+        fs::create_dir_all(self.memory_dir()).await?;
+        let now = Local::now().to_rfc3339();
+        let mut out = String::new();
+        for r in records {
+            let tombstone = Tombstone {
+                file: r.file.clone(),
+                position: r.position.clone(),
+                hash: r.hash.clone(),
+                key: r.entry.key.clone(),
+                forgotten_at: now.clone(),
+            };
+            out.push_str(&serde_json::to_string(&tombstone)?);
+            out.push('\n');
+        }
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.tombstones_path())
+            .await?;
+        file.write_all(out.as_bytes()).await?;
+        // tokio hands the write to a blocking thread; flush so the next
+        // read sees the tombstone.
+        file.flush().await?;
+        Ok(())
+    }
 
-        // Read daily logs
-        let mem_dir = self.memory_dir();
-        if mem_dir.exists() {
-            let mut dir = fs::read_dir(&mem_dir).await?;
-            while let Some(entry) = dir.next_entry().await? {
-                let path = entry.path();
-                if path.extension().and_then(|e| e.to_str()) == Some("md") {
-                    let content = fs::read_to_string(&path).await?;
-                    entries.extend(Self::parse_entries_from_file(
-                        &path,
-                        &content,
-                        &MemoryCategory::Daily,
-                    ));
+    /// Non-global scopes with a directory, read back from their `.scope` labels.
+    async fn stored_scopes(&self) -> anyhow::Result<Vec<MemoryScope>> {
+        let root = self.memory_dir().join("scopes");
+        let mut scopes = Vec::new();
+        if !root.exists() {
+            return Ok(scopes);
+        }
+        let mut dirs = fs::read_dir(&root).await?;
+        while let Some(dir) = dirs.next_entry().await? {
+            let Ok(label) = fs::read_to_string(dir.path().join(".scope")).await else {
+                continue;
+            };
+            if let Ok(scope) = label.trim().parse::<MemoryScope>() {
+                scopes.push(scope);
+            }
+        }
+        scopes.sort_by_key(ToString::to_string);
+        Ok(scopes)
+    }
+
+    /// Append `key` to the file for `category` — core, or the daily log of
+    /// `date` — superseding earlier lines for the key in `scope`.
+    async fn write_entry(
+        &self,
+        key: &str,
+        content: &str,
+        category: &MemoryCategory,
+        scope: &MemoryScope,
+        date: NaiveDate,
+    ) -> anyhow::Result<()> {
+        let dir = match scope {
+            MemoryScope::Global => None,
+            other => {
+                let dir = self.scope_dir(other);
+                if !dir.join(".scope").exists() {
+                    fs::create_dir_all(&dir).await?;
+                    fs::write(dir.join(".scope"), other.to_string()).await?;
+                }
+                Some(dir)
+            }
+        };
+        let path = match (dir, category) {
+            (None, MemoryCategory::Core) => self.core_path(),
+            (None, _) => self.daily_path_for(date),
+            (Some(dir), MemoryCategory::Core) => dir.join("MEMORY.md"),
+            (Some(dir), _) => dir.join(self.daily_path_for(date).file_name().unwrap_or_default()),
+        };
+        // Like an upsert: earlier lines for this key are superseded.
+        self.forget_in_scope(scope, &scope.qualify(key)).await?;
+        self.append_to_file(&path, &format!("- **{key}**: {content}"))
+            .await
+    }
+
+    /// Tombstone the live lines of `scope` matching `key` (or their position id).
+    async fn forget_in_scope(&self, scope: &MemoryScope, key: &str) -> anyhow::Result<usize> {
+        let records = self.live_records(scope).await?;
+        let matches: Vec<&Record> = records
+            .iter()
+            .filter(|r| r.entry.key == key || r.entry.id == key)
+            .collect();
+        self.write_tombstones(&matches).await?;
+        Ok(matches.len())
+    }
+
+    /// Rewrite memory files without forgotten lines. Each removal is recorded
+    /// (key, file and line hash, not the content) in
+    /// [`audit_path`](Self::audit_path) and the tombstones are cleared.
+    pub async fn compact(&self) -> anyhow::Result<CompactReport> {
+        let tombstones = self.read_tombstones().await?;
+        let mut report = CompactReport::default();
+        if tombstones.is_empty() {
+            return Ok(report);
+        }
+
+        // Sorted so files are rewritten and audited in a stable order.
+        let mut by_file: BTreeMap<&str, Vec<&Tombstone>> = BTreeMap::new();
+        for t in &tombstones {
+            by_file.entry(t.file.as_str()).or_default().push(t);
+        }
+
+        let now = Local::now().to_rfc3339();
+        let mut audit = String::new();
+        for (file, dead) in by_file {
+            let path = self.workspace_dir.join(file);
+            if !path.exists() {
+                continue;
+            }
+            let content = fs::read_to_string(&path).await?;
+            let stem = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("unknown");
+
+            let mut kept = Vec::new();
+            let mut removed = 0;
+            let mut index = 0;
+            for line in content.lines() {
+                let trimmed = line.trim();
+                if trimmed.is_empty() || trimmed.starts_with('#') {
+                    kept.push(line);
+                    continue;
+                }
+                let position = format!("{stem}:{index}");
+                index += 1;
+                let hash = Self::line_hash(trimmed);
+                if let Some(t) = dead
+                    .iter()
+                    .find(|t| t.position == position && t.hash == hash)
+                {
+                    let record = CompactedLine {
+                        file: file.to_string(),
+                        key: t.key.clone(),
+                        hash,
+                        forgotten_at: t.forgotten_at.clone(),
+                        compacted_at: now.clone(),
+                    };
+                    audit.push_str(&serde_json::to_string(&record)?);
+                    audit.push('\n');
+                    removed += 1;
+                } else {
+                    kept.push(line);
                 }
             }
+
+            if removed > 0 {
+                // Collapse the blank line each removed entry leaves behind.
+                let mut rewritten = String::new();
+                for line in kept {
+                    if line.trim().is_empty() && rewritten.ends_with("\n\n") {
+                        continue;
+                    }
+                    rewritten.push_str(line);
+                    rewritten.push('\n');
+                }
+                let tmp = path.with_extension("md.tmp");
+                fs::write(&tmp, rewritten).await?;
+                fs::rename(&tmp, &path).await?;
+                report.entries_removed += removed;
+                report.files_rewritten += 1;
+            }
         }
 
-        entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-        Ok(entries)
+        if !audit.is_empty() {
+            let audit_path = self.audit_path();
+            if let Some(parent) = audit_path.parent() {
+                fs::create_dir_all(parent).await?;
+            }
+            let mut file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&audit_path)
+                .await?;
+            file.write_all(audit.as_bytes()).await?;
+            file.flush().await?;
+        }
+
+        // Positions shift after a rewrite, so every tombstone is now spent.
+        fs::remove_file(self.tombstones_path()).await?;
+        Ok(report)
+    }
+
+    fn tokenize(text: &str) -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|t| !t.is_empty())
+            .map(str::to_lowercase)
+            .collect()
+    }
+
+    /// BM25 over entry keys and content, matching how the sqlite backend
+    /// ranks with FTS5. Entries sharing no query token are dropped.
+    fn score_entries(entries: Vec<MemoryEntry>, query: &str) -> Vec<MemoryEntry> {
+        let mut terms = Self::tokenize(query);
+        terms.sort();
+        terms.dedup();
+        if terms.is_empty() || entries.is_empty() {
+            return Vec::new();
+        }
+
+        let docs: Vec<Vec<String>> = entries
+            .iter()
+            .map(|e| {
+                let mut tokens = Self::tokenize(&e.content);
+                if e.key != e.id {
+                    tokens.extend(Self::tokenize(&e.key));
+                }
+                tokens
+            })
+            .collect();
+
+        #[allow(clippy::cast_precision_loss)]
+        let n = docs.len() as f64;
+        #[allow(clippy::cast_precision_loss)]
+        let avg_len = docs.iter().map(Vec::len).sum::<usize>() as f64 / n;
+        let idf: Vec<f64> = terms
+            .iter()
+            .map(|term| {
+                #[allow(clippy::cast_precision_loss)]
+                let df = docs.iter().filter(|d| d.contains(term)).count() as f64;
+                (1.0 + (n - df + 0.5) / (df + 0.5)).ln()
+            })
+            .collect();
+
+        entries
+            .into_iter()
+            .zip(docs)
+            .filter_map(|(mut entry, doc)| {
+                #[allow(clippy::cast_precision_loss)]
+                let len = doc.len() as f64;
+                let score: f64 = terms
+                    .iter()
+                    .zip(&idf)
+                    .map(|(term, idf)| {
+                        #[allow(clippy::cast_precision_loss)]
+                        let tf = doc.iter().filter(|t| *t == term).count() as f64;
+                        idf * tf * (BM25_K1 + 1.0)
+                            / (tf + BM25_K1 * (1.0 - BM25_B + BM25_B * len / avg_len.max(1.0)))
+                    })
+                    .sum();
+                (score > 0.0).then(|| {
+                    entry.score = Some(score);
+                    entry
+                })
+            })
+            .collect()
     }
 }
 
@@ -144,38 +558,41 @@ impl Memory for MarkdownMemory {
         content: &str,
         category: MemoryCategory,
     ) -> anyhow::Result<()> {
-        let entry = format!("- **{key}**: {content}");
-        let path = match category {
-            MemoryCategory::Core => self.core_path(),
-            _ => self.daily_path(),
-        };
-        self.append_to_file(&path, &entry).await
+        self.store_scoped(key, content, category, &MemoryScope::Global)
+            .await
     }
 
     async fn recall(&self, query: &str, limit: usize) -> anyhow::Result<Vec<MemoryEntry>> {
-        let all = self.read_all_entries().await?;
-        let query_lower = query.to_lowercase();
-        let keywords: Vec<&str> = query_lower.split_whitespace().collect();
+        self.recall_scoped(query, limit, &[]).await
+    }
 
-        let mut scored: Vec<MemoryEntry> = all
-            .into_iter()
-            .filter_map(|mut entry| {
-                let content_lower = entry.content.to_lowercase();
-                let matched = keywords
-                    .iter()
-                    .filter(|kw| content_lower.contains(**kw))
-                    .count();
-                if matched > 0 {
-                    #[allow(clippy::cast_precision_loss)]
-                    let score = matched as f64 / keywords.len() as f64;
-                    entry.score = Some(score);
-                    Some(entry)
-                } else {
-                    None
-                }
-            })
-            .collect();
+    async fn store_scoped(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        scope: &MemoryScope,
+    ) -> anyhow::Result<()> {
+        self.write_entry(key, content, &category, scope, Local::now().date_naive())
+            .await
+    }
+
+    async fn recall_scoped(
+        &self,
+        query: &str,
+        limit: usize,
+        scopes: &[MemoryScope],
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        // Stores supersede per scope, and unkeyed lines are keyed by position,
+        // which repeats across scopes — so no cross-scope shadowing. The most
+        // specific scope is read first so it wins score ties.
+        let mut all = Vec::new();
+        for scope in scopes.iter().filter(|s| **s != MemoryScope::Global) {
+            all.extend(self.live_records(scope).await?.into_iter().map(|r| r.entry));
+        }
+        all.extend(self.read_all_entries().await?);
 
+        let mut scored = Self::score_entries(all, query);
         scored.sort_by(|a, b| {
             b.score
                 .partial_cmp(&a.score)
@@ -185,11 +602,54 @@ impl Memory for MarkdownMemory {
         Ok(scored)
     }
 
+    /// Global and scoped entries. Files only distinguish core from daily
+    /// logs, so other categories come back as daily; dates are the log's day.
+    async fn snapshot(&self) -> anyhow::Result<Snapshot> {
+        let mut scopes = vec![MemoryScope::Global];
+        scopes.extend(self.stored_scopes().await?);
+        let mut records = Vec::new();
+        for scope in &scopes {
+            let mut live = self.live_records(scope).await?;
+            live.reverse();
+            records.extend(live.iter().map(|r| {
+                let mut record = interchange::Record::from_entry(&r.entry);
+                record.created_at = record.created_at_rfc3339();
+                record
+            }));
+        }
+        Ok(Snapshot {
+            embedding_signature: None,
+            records,
+        })
+    }
+
+    /// Non-core records go to the daily log of their creation date.
+    async fn restore(
+        &self,
+        record: &interchange::Record,
+        _embedding_signature: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let date = DateTime::parse_from_rfc3339(&record.created_at_rfc3339()).map_or_else(
+            |_| Local::now().date_naive(),
+            |at| at.with_timezone(&Local).date_naive(),
+        );
+        self.write_entry(
+            &record.key,
+            &record.content,
+            &record.category,
+            &record.scope()?,
+            date,
+        )
+        .await
+    }
+
     async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
-        let all = self.read_all_entries().await?;
-        Ok(all
+        let (scope, _) = MemoryScope::split_key(key);
+        let live = self.live_records(&scope).await?;
+        Ok(live
             .into_iter()
-            .find(|e| e.key == key || e.content.contains(key)))
+            .map(|r| r.entry)
+            .find(|e| e.key == key || e.id == key || e.content.contains(key)))
     }
 
     async fn list(&self, category: Option<&MemoryCategory>) -> anyhow::Result<Vec<MemoryEntry>> {
@@ -200,10 +660,10 @@ impl Memory for MarkdownMemory {
         }
     }
 
-    async fn forget(&self, _key: &str) -> anyhow::Result<bool> {
-        // Markdown memory is append-only by design (audit trail)
-        // Return false to indicate the entry wasn't removed
-        Ok(false)
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        // Files stay append-only: a tombstone hides the lines until `compact`.
+        let (scope, _) = MemoryScope::split_key(key);
+        Ok(self.forget_in_scope(&scope, key).await? > 0)
     }
 
     async fn count(&self) -> anyhow::Result<usize> {
@@ -320,13 +780,176 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn markdown_forget_is_noop() {
+    async fn markdown_forget_hides_entry_but_keeps_file() {
+        let (_tmp, mem) = temp_workspace();
+        mem.store("scratch_note", "temporary", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("keeper", "permanent", MemoryCategory::Core)
+            .await
+            .unwrap();
+
+        assert!(mem.forget("scratch_note").await.unwrap());
+        assert!(!mem.forget("scratch_note").await.unwrap());
+        assert!(mem.get("scratch_note").await.unwrap().is_none());
+        assert_eq!(mem.count().await.unwrap(), 1);
+        assert!(mem.recall("temporary", 10).await.unwrap().is_empty());
+
+        // Still on disk until compaction.
+        let content = sync_fs::read_to_string(mem.core_path()).unwrap();
+        assert!(content.contains("temporary"));
+    }
+
+    #[tokio::test]
+    async fn markdown_store_same_key_supersedes() {
+        let (_tmp, mem) = temp_workspace();
+        mem.store("tz", "Timezone is UTC", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("tz", "Timezone is CET", MemoryCategory::Core)
+            .await
+            .unwrap();
+
+        let entry = mem.get("tz").await.unwrap().unwrap();
+        assert_eq!(entry.content, "Timezone is CET");
+        assert_eq!(mem.count().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn markdown_compact_rewrites_files_and_audits() {
+        let (_tmp, mem) = temp_workspace();
+        mem.store("keep", "stays forever", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("drop", "secret to remove", MemoryCategory::Core)
+            .await
+            .unwrap();
+        // A fixed date keeps the daily file stable if the test straddles midnight.
+        let day = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        mem.write_entry(
+            "note",
+            "daily scratch",
+            &MemoryCategory::Daily,
+            &MemoryScope::Global,
+            day,
+        )
+        .await
+        .unwrap();
+        mem.forget("drop").await.unwrap();
+        mem.forget("note").await.unwrap();
+
+        let report = mem.compact().await.unwrap();
+        assert_eq!(
+            report,
+            CompactReport {
+                entries_removed: 2,
+                files_rewritten: 2
+            }
+        );
+
+        let core = sync_fs::read_to_string(mem.core_path()).unwrap();
+        assert!(core.contains("stays forever"));
+        assert!(!core.contains("secret to remove"));
+        assert!(!mem.tombstones_path().exists());
+
+        assert!(!sync_fs::read_to_string(mem.daily_path_for(day))
+            .unwrap()
+            .contains("daily scratch"));
+
+        let audit = sync_fs::read_to_string(mem.audit_path()).unwrap();
+        let lines: Vec<&str> = audit.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(!audit.contains("secret to remove"));
+        assert!(!audit.contains("daily scratch"));
+        let first: CompactedLine = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first.key, "drop");
+        assert_eq!(first.file, "MEMORY.md");
+        assert_eq!(first.hash.len(), 16);
+        let second: CompactedLine = serde_json::from_str(lines[1]).unwrap();
+        assert_eq!(second.key, "note");
+        assert_eq!(second.file, "memory/2024-01-15.md");
+
+        // Positions shifted, yet the surviving entry is still live.
+        assert_eq!(mem.count().await.unwrap(), 1);
+        assert_eq!(
+            mem.get("keep").await.unwrap().unwrap().content,
+            "stays forever"
+        );
+        assert_eq!(mem.compact().await.unwrap(), CompactReport::default());
+    }
+
+    #[tokio::test]
+    async fn markdown_tombstone_ignores_edited_line() {
+        let (_tmp, mem) = temp_workspace();
+        mem.store("a", "original text", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.forget("a").await.unwrap();
+
+        // Hand-editing the file replaces the forgotten line with a new one.
+        let core = sync_fs::read_to_string(mem.core_path()).unwrap();
+        sync_fs::write(mem.core_path(), core.replace("original text", "rewritten")).unwrap();
+        assert_eq!(mem.get("a").await.unwrap().unwrap().content, "rewritten");
+    }
+
+    #[tokio::test]
+    async fn markdown_forget_scoped_entry() {
+        let (_tmp, mem) = temp_workspace();
+        let alice = MemoryScope::User("telegram:alice".into());
+        mem.store_scoped("pin", "pin is 1234", MemoryCategory::Core, &alice)
+            .await
+            .unwrap();
+        assert!(mem.forget("user:telegram:alice/pin").await.unwrap());
+        let hits = mem
+            .recall_scoped("pin", 10, std::slice::from_ref(&alice))
+            .await
+            .unwrap();
+        assert!(hits.is_empty());
+        assert_eq!(mem.compact().await.unwrap().entries_removed, 1);
+    }
+
+    #[tokio::test]
+    async fn markdown_recall_ranks_by_bm25() {
         let (_tmp, mem) = temp_workspace();
-        mem.store("a", "permanent", MemoryCategory::Core)
+        mem.store(
+            "a",
+            "deploy runbook: deploy with cargo, deploy twice",
+            MemoryCategory::Core,
+        )
+        .await
+        .unwrap();
+        mem.store("b", "the deploy window is Friday", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("c", "trusted contacts list", MemoryCategory::Core)
             .await
             .unwrap();
-        let removed = mem.forget("a").await.unwrap();
-        assert!(!removed, "Markdown memory is append-only");
+
+        let hits = mem.recall("deploy", 10).await.unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].key, "a");
+
+        // Whole tokens only: "rust" does not match "trusted".
+        assert!(mem.recall("rust", 10).await.unwrap().is_empty());
+        // Keys are searchable like the FTS5 key column.
+        assert_eq!(mem.recall("c", 10).await.unwrap()[0].key, "c");
+    }
+
+    #[test]
+    fn markdown_parses_key_markup() {
+        let mem = MarkdownMemory::new(Path::new("/ws"));
+        let records = mem.parse_records(
+            Path::new("/ws/MEMORY.md"),
+            "# Long-Term Memory\n\n- **lang**: Rust\n- plain line\n",
+            &MemoryCategory::Core,
+            &MemoryScope::Global,
+        );
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].entry.key, "lang");
+        assert_eq!(records[0].entry.content, "Rust");
+        assert_eq!(records[0].file, "MEMORY.md");
+        assert_eq!(records[1].entry.key, "MEMORY:1");
+        assert_eq!(records[1].entry.content, "plain line");
     }
 
     #[tokio::test]
@@ -341,4 +964,36 @@ mod tests {
         let (_tmp, mem) = temp_workspace();
         assert_eq!(mem.count().await.unwrap(), 0);
     }
+
+    #[tokio::test]
+    async fn markdown_scoped_entries_are_isolated() {
+        let (_tmp, mem) = temp_workspace();
+        let alice = MemoryScope::User("telegram:alice".into());
+        let bob = MemoryScope::User("telegram:bob".into());
+        mem.store_scoped("pet", "dog named Rex", MemoryCategory::Core, &alice)
+            .await
+            .unwrap();
+        mem.store("fact", "Rex is a common dog name", MemoryCategory::Core)
+            .await
+            .unwrap();
+
+        let for_bob = mem
+            .recall_scoped("Rex", 10, std::slice::from_ref(&bob))
+            .await
+            .unwrap();
+        assert_eq!(for_bob.len(), 1);
+        assert!(for_bob[0].session_id.is_none());
+
+        let for_alice = mem
+            .recall_scoped("Rex", 10, std::slice::from_ref(&alice))
+            .await
+            .unwrap();
+        assert_eq!(for_alice.len(), 2);
+        assert!(for_alice
+            .iter()
+            .any(|e| e.session_id.as_deref() == Some("user:telegram:alice")));
+
+        assert_eq!(mem.recall("Rex", 10).await.unwrap().len(), 1);
+        assert_ne!(mem.scope_dir(&alice), mem.scope_dir(&bob));
+    }
 }
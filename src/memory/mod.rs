@@ -1,21 +1,73 @@
 pub mod chunker;
+pub mod cli;
+pub mod consolidate;
+pub mod crypto;
 pub mod embeddings;
+pub mod extract;
+pub mod graph;
+pub mod hnsw;
 pub mod hygiene;
+pub mod ingest;
+pub mod interchange;
 pub mod markdown;
+pub mod ranking;
 pub mod sqlite;
 pub mod traits;
 pub mod vector;
 
+pub use cli::handle_command;
 pub use markdown::MarkdownMemory;
 pub use sqlite::SqliteMemory;
 pub use traits::Memory;
 #[allow(unused_imports)]
-pub use traits::{MemoryCategory, MemoryEntry};
+pub use traits::{MemoryCategory, MemoryEntry, MemoryScope, RecallQuery};
 
 use crate::config::MemoryConfig;
+use crate::security::SecretStore;
+use std::fmt::Write;
 use std::path::Path;
 use std::sync::Arc;
 
+/// Open the SQLite backend with the configured embedder and vector index.
+pub fn create_sqlite_memory(
+    config: &MemoryConfig,
+    workspace_dir: &Path,
+    api_key: Option<&str>,
+) -> anyhow::Result<SqliteMemory> {
+    let embedder: Arc<dyn embeddings::EmbeddingProvider> =
+        Arc::from(embeddings::create_embedding_provider(
+            &config.embedding_provider,
+            api_key,
+            &config.embedding_model,
+            config.embedding_dimensions,
+        ));
+
+    #[allow(clippy::cast_possible_truncation)]
+    let mem = SqliteMemory::with_embedder(
+        workspace_dir,
+        embedder,
+        config.vector_weight as f32,
+        config.keyword_weight as f32,
+        config.embedding_cache_size,
+    )?
+    .with_ranking(config.ranking.clone());
+
+    // The workspace is `~/.zeroclaw/workspace`; the key file sits beside it.
+    let secrets = SecretStore::new(workspace_dir.parent().unwrap_or(workspace_dir), true);
+    let mem = if config.encrypt {
+        mem.with_encryption(crypto::ContentCipher::from_secret_store(&secrets)?)
+    } else if secrets.has_key() {
+        // Rows sealed before encryption was turned off stay readable.
+        mem.with_decryption(crypto::ContentCipher::from_secret_store(&secrets)?)
+    } else {
+        mem
+    };
+    if config.vector_index == "none" {
+        return Ok(mem.without_vector_index());
+    }
+    Ok(mem)
+}
+
 /// Factory: create the right memory backend from config
 pub fn create_memory(
     config: &MemoryConfig,
@@ -29,22 +81,23 @@ pub fn create_memory(
 
     match config.backend.as_str() {
         "sqlite" => {
-            let embedder: Arc<dyn embeddings::EmbeddingProvider> =
-                Arc::from(embeddings::create_embedding_provider(
-                    &config.embedding_provider,
-                    api_key,
-                    &config.embedding_model,
-                    config.embedding_dimensions,
-                ));
-
-            #[allow(clippy::cast_possible_truncation)]
-            let mem = SqliteMemory::with_embedder(
-                workspace_dir,
-                embedder,
-                config.vector_weight as f32,
-                config.keyword_weight as f32,
-                config.embedding_cache_size,
-            )?;
+            let mem = create_sqlite_memory(config, workspace_dir, api_key)?;
+            if !mem.needs_reembed() {
+                return Ok(Box::new(mem));
+            }
+
+            // Vectors are missing or from another model: re-embed without
+            // blocking startup. Recall is keyword-only until it finishes.
+            let mem = Arc::new(mem);
+            match tokio::runtime::Handle::try_current() {
+                Ok(handle) => {
+                    let job = Arc::clone(&mem);
+                    handle.spawn(async move { job.reembed_in_background().await });
+                }
+                Err(_) => {
+                    tracing::warn!("Memories need re-embedding; run `zeroclaw memory reindex`");
+                }
+            }
             Ok(Box::new(mem))
         }
         "markdown" | "none" => Ok(Box::new(MarkdownMemory::new(workspace_dir))),
@@ -55,6 +108,35 @@ pub fn create_memory(
     }
 }
 
+/// Prompt preamble for `user_msg`: a `[Memory context]` block of recalled
+/// entries (with a citation hint when document chunks are present), followed
+/// by knowledge-graph neighbours of entities named in the message or recalls.
+pub async fn context_block(mem: &dyn Memory, user_msg: &str, scopes: &[MemoryScope]) -> String {
+    let mut context = String::new();
+
+    let mut mentions = user_msg.to_string();
+    if let Ok(entries) = mem.recall_scoped(user_msg, 5, scopes).await {
+        if !entries.is_empty() {
+            context.push_str("[Memory context]\n");
+            for entry in &entries {
+                let _ = writeln!(context, "{}", ingest::context_line(entry));
+                mentions.push('\n');
+                mentions.push_str(&entry.content);
+            }
+            if entries
+                .iter()
+                .any(|e| ingest::document_source(&e.key).is_some())
+            {
+                let _ = writeln!(context, "{}", ingest::CITATION_HINT);
+            }
+            context.push('\n');
+        }
+    }
+
+    context.push_str(&graph::context_block(mem, &mentions, scopes).await);
+    context
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
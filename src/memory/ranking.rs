@@ -0,0 +1,179 @@
+//! Recall re-ranking: blends search relevance with recency, importance,
+//! category and access-count signals (see `[memory.ranking]`).
+
+use super::traits::MemoryCategory;
+use crate::config::RankingConfig;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Importance assumed for entries that never had one set.
+pub const DEFAULT_IMPORTANCE: f64 = 0.5;
+
+/// How a recall score was computed. Signals are in [0, 1];
+/// `score = relevance × category_boost × (1 + Σ weight × signal)`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    /// Hybrid vector/keyword score normalized to [0, 1]
+    pub relevance: f64,
+    /// Exponential decay by age since the entry was last written
+    pub recency: f64,
+    pub importance: f64,
+    /// Diminishing-returns transform of the recall count
+    pub access: f64,
+    pub category_boost: f64,
+    pub score: f64,
+}
+
+/// Per-entry inputs to the ranker.
+#[derive(Debug, Clone, Copy)]
+pub struct Signals<'a> {
+    pub relevance: f64,
+    pub category: &'a MemoryCategory,
+    /// RFC 3339 time of the last write
+    pub updated_at: &'a str,
+    pub importance: Option<f64>,
+    pub access_count: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Ranker {
+    config: RankingConfig,
+}
+
+impl Ranker {
+    pub fn new(config: RankingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Score an entry as of `now`.
+    pub fn score(&self, signals: &Signals<'_>, now: DateTime<Utc>) -> ScoreBreakdown {
+        let relevance = signals.relevance.clamp(0.0, 1.0);
+        let recency = self.recency(signals.updated_at, now);
+        let importance = signals
+            .importance
+            .unwrap_or(DEFAULT_IMPORTANCE)
+            .clamp(0.0, 1.0);
+        let access = {
+            let reinforced = f64::from(signals.access_count).ln_1p();
+            reinforced / (1.0 + reinforced)
+        };
+        let category_boost = self
+            .config
+            .category_boosts
+            .get(&signals.category.to_string())
+            .copied()
+            .unwrap_or(1.0);
+
+        let bonus = self.config.recency_weight * recency
+            + self.config.importance_weight * importance
+            + self.config.access_weight * access;
+        ScoreBreakdown {
+            relevance,
+            recency,
+            importance,
+            access,
+            category_boost,
+            score: relevance * category_boost * (1.0 + bonus),
+        }
+    }
+
+    fn recency(&self, updated_at: &str, now: DateTime<Utc>) -> f64 {
+        let half_life = self.config.recency_half_life_days;
+        if half_life <= 0.0 {
+            return 1.0;
+        }
+        let Ok(at) = DateTime::parse_from_rfc3339(updated_at) else {
+            return 0.0;
+        };
+        #[allow(clippy::cast_precision_loss)]
+        let age_days = now
+            .signed_duration_since(at.with_timezone(&Utc))
+            .num_seconds()
+            .max(0) as f64
+            / 86_400.0;
+        0.5_f64.powf(age_days / half_life)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn signals<'a>(
+        relevance: f64,
+        category: &'a MemoryCategory,
+        updated_at: &'a str,
+    ) -> Signals<'a> {
+        Signals {
+            relevance,
+            category,
+            updated_at,
+            importance: None,
+            access_count: 0,
+        }
+    }
+
+    #[test]
+    fn recent_correction_outranks_stale_preference() {
+        let now = Utc::now();
+        let ranker = Ranker::default();
+        let last_year = (now - Duration::days(365)).to_rfc3339();
+        let yesterday = (now - Duration::days(1)).to_rfc3339();
+
+        let stale = ranker.score(&signals(1.0, &MemoryCategory::Core, &last_year), now);
+        let fresh = ranker.score(&signals(0.85, &MemoryCategory::Core, &yesterday), now);
+        assert!(fresh.score > stale.score, "{fresh:?} vs {stale:?}");
+        assert!(stale.recency < 0.01);
+        assert!(fresh.recency > 0.97);
+    }
+
+    #[test]
+    fn half_life_halves_recency() {
+        let now = Utc::now();
+        let ranker = Ranker::default();
+        let month_ago = (now - Duration::days(30)).to_rfc3339();
+        let b = ranker.score(&signals(1.0, &MemoryCategory::Core, &month_ago), now);
+        assert!((b.recency - 0.5).abs() < 1e-3);
+
+        let config = RankingConfig {
+            recency_half_life_days: 0.0,
+            ..RankingConfig::default()
+        };
+        let b = Ranker::new(config).score(&signals(1.0, &MemoryCategory::Core, "garbage"), now);
+        assert!((b.recency - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn category_importance_and_access_boost() {
+        let now = Utc::now();
+        let ranker = Ranker::default();
+        let ts = now.to_rfc3339();
+
+        let core = ranker.score(&signals(0.5, &MemoryCategory::Core, &ts), now);
+        let chat = ranker.score(&signals(0.5, &MemoryCategory::Conversation, &ts), now);
+        let custom = ranker.score(&signals(0.5, &MemoryCategory::Custom("x".into()), &ts), now);
+        assert!(core.score > chat.score);
+        assert!((custom.category_boost - 1.0).abs() < f64::EPSILON);
+
+        let important = ranker.score(
+            &Signals {
+                importance: Some(1.0),
+                ..signals(0.5, &MemoryCategory::Core, &ts)
+            },
+            now,
+        );
+        assert!(important.score > core.score);
+        assert!((core.importance - DEFAULT_IMPORTANCE).abs() < f64::EPSILON);
+
+        let used = ranker.score(
+            &Signals {
+                access_count: 10,
+                ..signals(0.5, &MemoryCategory::Core, &ts)
+            },
+            now,
+        );
+        assert!(used.score > core.score);
+        assert!(used.access > 0.0 && used.access < 1.0);
+    }
+}
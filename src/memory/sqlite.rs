@@ -1,10 +1,19 @@
+use super::crypto::ContentCipher;
 use super::embeddings::EmbeddingProvider;
-use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::graph::{self, GraphQuery, GraphUpdate, GraphUpsertReport, Sealing, Subgraph};
+use super::hnsw::HnswIndex;
+use super::interchange::{Record, Snapshot};
+use super::ranking::{Ranker, Signals};
+use super::traits::{self, Memory, MemoryCategory, MemoryEntry, MemoryScope, RecallQuery};
 use super::vector;
+use crate::config::RankingConfig;
+use anyhow::Context;
 use async_trait::async_trait;
-use chrono::Local;
-use rusqlite::{params, Connection};
+use chrono::{DateTime, Local, Utc};
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use uuid::Uuid;
 
@@ -13,8 +22,12 @@ use uuid::Uuid;
 /// Full-stack search engine:
 /// - **Vector DB**: embeddings stored as BLOB, cosine similarity search
 /// - **Keyword Search**: FTS5 virtual table with BM25 scoring
+/// - **ANN Index**: HNSW graph persisted in the same DB for large stores
 /// - **Hybrid Merge**: weighted fusion of vector + keyword results
+/// - **Re-ranking**: recency decay, importance, category boosts and access counts
 /// - **Embedding Cache**: LRU-evicted cache to avoid redundant API calls
+/// - **Model Tracking**: embedder signature recorded; a change triggers re-embedding
+/// - **Encryption at Rest**: optional sealed content with a blind keyword index
 /// - **Safe Reindex**: temp DB → seed → sync → atomic swap → rollback
 pub struct SqliteMemory {
     conn: Mutex<Connection>,
@@ -23,6 +36,203 @@ pub struct SqliteMemory {
     vector_weight: f32,
     keyword_weight: f32,
     cache_max: usize,
+    /// `None` when embeddings are disabled or the index is turned off.
+    index: Option<Mutex<HnswIndex>>,
+    /// Below this many vectors a full scan is exact and fast enough.
+    ann_min_rows: usize,
+    /// Set while rows are missing embeddings; recall is keyword-only meanwhile.
+    reembedding: AtomicBool,
+    ranker: Ranker,
+    /// Opens sealed content; `None` reads content as stored.
+    cipher: Option<ContentCipher>,
+    /// Seal content on write (otherwise the cipher only reads).
+    encrypt_writes: bool,
+}
+
+/// Progress of a re-embedding pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReembedProgress {
+    pub done: usize,
+    pub total: usize,
+}
+
+/// Rows embedded per provider call during re-embedding.
+pub const REEMBED_BATCH_SIZE: usize = 32;
+
+/// Vector count at which recall switches from a full scan to the HNSW index.
+const ANN_MIN_ROWS: usize = 1_000;
+
+/// `row_to_entry` columns plus the ranking inputs (updated time, access count).
+const RANKED_COLUMNS: &str = "m.id, m.key, m.content, m.category, m.created_at, m.scope, \
+     m.importance, m.updated_at, m.access_count";
+
+/// Current `brain.db` schema version; the last entry of [`MIGRATIONS`].
+pub const SCHEMA_VERSION: u32 = 5;
+
+/// Idempotent schema step; may run against tables it already describes.
+type Migration = fn(&Connection) -> anyhow::Result<()>;
+
+/// Forward migrations, oldest first. Never edit a released one; append.
+const MIGRATIONS: &[(u32, Migration)] = &[
+    (1, migrate_base_tables),
+    (2, migrate_scopes),
+    (3, migrate_ranking_signals),
+    (4, migrate_blind_index),
+    (5, migrate_knowledge_graph),
+];
+
+/// Memories, FTS5, `embedding_cache` and `memory_meta`.
+fn migrate_base_tables(conn: &Connection) -> anyhow::Result<()> {
+    conn.execute_batch(
+        "-- Core memories table
+        CREATE TABLE IF NOT EXISTS memories (
+            id          TEXT PRIMARY KEY,
+            key         TEXT NOT NULL UNIQUE,
+            content     TEXT NOT NULL,
+            category    TEXT NOT NULL DEFAULT 'core',
+            embedding   BLOB,
+            created_at  TEXT NOT NULL,
+            updated_at  TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_memories_category ON memories(category);
+        CREATE INDEX IF NOT EXISTS idx_memories_key ON memories(key);
+
+        -- FTS5 full-text search (BM25 scoring)
+        CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
+            key, content, content=memories, content_rowid=rowid
+        );
+
+        -- FTS5 triggers: keep in sync with memories table
+        CREATE TRIGGER IF NOT EXISTS memories_ai AFTER INSERT ON memories BEGIN
+            INSERT INTO memories_fts(rowid, key, content)
+            VALUES (new.rowid, new.key, new.content);
+        END;
+        CREATE TRIGGER IF NOT EXISTS memories_ad AFTER DELETE ON memories BEGIN
+            INSERT INTO memories_fts(memories_fts, rowid, key, content)
+            VALUES ('delete', old.rowid, old.key, old.content);
+        END;
+        CREATE TRIGGER IF NOT EXISTS memories_au AFTER UPDATE ON memories BEGIN
+            INSERT INTO memories_fts(memories_fts, rowid, key, content)
+            VALUES ('delete', old.rowid, old.key, old.content);
+            INSERT INTO memories_fts(rowid, key, content)
+            VALUES (new.rowid, new.key, new.content);
+        END;
+
+        -- Embedding cache with LRU eviction
+        CREATE TABLE IF NOT EXISTS embedding_cache (
+            content_hash TEXT PRIMARY KEY,
+            embedding    BLOB NOT NULL,
+            created_at   TEXT NOT NULL,
+            accessed_at  TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_cache_accessed ON embedding_cache(accessed_at);
+
+        -- Key/value metadata (embedding model signature)
+        CREATE TABLE IF NOT EXISTS memory_meta (
+            key   TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        );",
+    )?;
+    Ok(())
+}
+
+/// Per-user/channel/session scope label (NULL = global).
+fn migrate_scopes(conn: &Connection) -> anyhow::Result<()> {
+    add_column_if_missing(conn, "memories", "scope", "TEXT")?;
+    conn.execute_batch("CREATE INDEX IF NOT EXISTS idx_memories_scope ON memories(scope);")?;
+    Ok(())
+}
+
+/// Recall re-ranking inputs.
+fn migrate_ranking_signals(conn: &Connection) -> anyhow::Result<()> {
+    add_column_if_missing(conn, "memories", "importance", "REAL")?;
+    add_column_if_missing(
+        conn,
+        "memories",
+        "access_count",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    add_column_if_missing(conn, "memories", "last_accessed_at", "TEXT")
+}
+
+/// Encrypted rows are searched through `search_text` (blinded words) since
+/// FTS5 cannot tokenize ciphertext; plaintext rows leave it NULL.
+fn migrate_blind_index(conn: &Connection) -> anyhow::Result<()> {
+    add_column_if_missing(conn, "memories", "search_text", "TEXT")?;
+    conn.execute_batch(
+        "DROP TRIGGER IF EXISTS memories_ai;
+        DROP TRIGGER IF EXISTS memories_ad;
+        DROP TRIGGER IF EXISTS memories_au;
+        CREATE TRIGGER memories_ai AFTER INSERT ON memories BEGIN
+            INSERT INTO memories_fts(rowid, key, content)
+            VALUES (new.rowid, new.key, COALESCE(new.search_text, new.content));
+        END;
+        CREATE TRIGGER memories_ad AFTER DELETE ON memories BEGIN
+            INSERT INTO memories_fts(memories_fts, rowid, key, content)
+            VALUES ('delete', old.rowid, old.key, COALESCE(old.search_text, old.content));
+        END;
+        CREATE TRIGGER memories_au AFTER UPDATE OF key, content, search_text ON memories BEGIN
+            INSERT INTO memories_fts(memories_fts, rowid, key, content)
+            VALUES ('delete', old.rowid, old.key, COALESCE(old.search_text, old.content));
+            INSERT INTO memories_fts(rowid, key, content)
+            VALUES (new.rowid, new.key, COALESCE(new.search_text, new.content));
+        END;",
+    )?;
+    Ok(())
+}
+
+/// Knowledge graph (see `graph.rs`). Entities are shared; relations and
+/// observations carry a scope label ('' = global).
+fn migrate_knowledge_graph(conn: &Connection) -> anyhow::Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS graph_entities (
+            id          INTEGER PRIMARY KEY,
+            name        TEXT NOT NULL,
+            norm_name   TEXT NOT NULL UNIQUE,
+            kind        TEXT NOT NULL DEFAULT '',
+            created_at  TEXT NOT NULL,
+            updated_at  TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS graph_relations (
+            id          INTEGER PRIMARY KEY,
+            source_id   INTEGER NOT NULL REFERENCES graph_entities(id) ON DELETE CASCADE,
+            relation    TEXT NOT NULL,
+            target_id   INTEGER NOT NULL REFERENCES graph_entities(id) ON DELETE CASCADE,
+            scope       TEXT NOT NULL DEFAULT '',
+            created_at  TEXT NOT NULL,
+            UNIQUE(source_id, relation, target_id, scope)
+        );
+        CREATE INDEX IF NOT EXISTS idx_graph_relations_source ON graph_relations(source_id);
+        CREATE INDEX IF NOT EXISTS idx_graph_relations_target ON graph_relations(target_id);
+        CREATE TABLE IF NOT EXISTS graph_observations (
+            id          INTEGER PRIMARY KEY,
+            entity_id   INTEGER NOT NULL REFERENCES graph_entities(id) ON DELETE CASCADE,
+            content     TEXT NOT NULL,
+            scope       TEXT NOT NULL DEFAULT '',
+            created_at  TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS idx_graph_observations_entity ON graph_observations(entity_id);",
+    )?;
+    Ok(())
+}
+
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> anyhow::Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM pragma_table_info(?1) WHERE name = ?2)",
+        params![table, column],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {table} ADD COLUMN {column} {definition};"
+        ))?;
+    }
+    Ok(())
 }
 
 impl SqliteMemory {
@@ -52,6 +262,18 @@ impl SqliteMemory {
         let conn = Connection::open(&db_path)?;
         Self::init_schema(&conn)?;
 
+        let (index, pending) = if embedder.dimensions() > 0 {
+            Self::check_embedding_signature(&conn, embedder.as_ref())?;
+            let pending: bool = conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM memories WHERE embedding IS NULL)",
+                [],
+                |row| row.get(0),
+            )?;
+            (Some(Mutex::new(HnswIndex::load(&conn)?)), pending)
+        } else {
+            (None, false)
+        };
+
         Ok(Self {
             conn: Mutex::new(conn),
             db_path,
@@ -59,55 +281,500 @@ impl SqliteMemory {
             vector_weight,
             keyword_weight,
             cache_max,
+            index,
+            ann_min_rows: ANN_MIN_ROWS,
+            reembedding: AtomicBool::new(pending),
+            ranker: Ranker::default(),
+            cipher: None,
+            encrypt_writes: false,
         })
     }
 
-    /// Initialize all tables: memories, FTS5, `embedding_cache`
-    fn init_schema(conn: &Connection) -> anyhow::Result<()> {
-        conn.execute_batch(
-            "-- Core memories table
-            CREATE TABLE IF NOT EXISTS memories (
-                id          TEXT PRIMARY KEY,
-                key         TEXT NOT NULL UNIQUE,
-                content     TEXT NOT NULL,
-                category    TEXT NOT NULL DEFAULT 'core',
-                embedding   BLOB,
-                created_at  TEXT NOT NULL,
-                updated_at  TEXT NOT NULL
+    /// Compare the configured embedder with the one recorded in
+    /// `memory_meta`. Vectors from a different model or dimension are not
+    /// comparable, so on a change they are dropped (along with the embedding
+    /// cache and ANN graph) and left for `reembed` to fill back in.
+    fn check_embedding_signature(
+        conn: &Connection,
+        embedder: &dyn EmbeddingProvider,
+    ) -> anyhow::Result<()> {
+        let current = Self::signature_of(embedder);
+        let stored: Option<String> = conn
+            .query_row(
+                "SELECT value FROM memory_meta WHERE key = 'embedding_signature'",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        let changed = match &stored {
+            Some(stored) => stored != &current,
+            // Databases from before the signature was recorded: only a
+            // dimension mismatch is detectable.
+            None => conn.query_row(
+                "SELECT EXISTS(SELECT 1 FROM memories
+                 WHERE embedding IS NOT NULL AND length(embedding) != ?1)",
+                params![i64::try_from(embedder.dimensions() * 4)?],
+                |row| row.get(0),
+            )?,
+        };
+
+        if changed {
+            tracing::warn!(
+                from = stored.as_deref().unwrap_or("unknown"),
+                to = %current,
+                "Embedding model changed; memories will be re-embedded"
             );
-            CREATE INDEX IF NOT EXISTS idx_memories_category ON memories(category);
-            CREATE INDEX IF NOT EXISTS idx_memories_key ON memories(key);
+            HnswIndex::init_schema(conn)?;
+            conn.execute_batch(
+                "UPDATE memories SET embedding = NULL;
+                 DELETE FROM embedding_cache;
+                 DELETE FROM vector_index;
+                 DELETE FROM vector_index_meta;",
+            )?;
+        }
+
+        conn.execute(
+            "INSERT OR REPLACE INTO memory_meta (key, value) VALUES ('embedding_signature', ?1)",
+            params![current],
+        )?;
+        Ok(())
+    }
+
+    /// `provider/model/dimensions`: identifies which vectors are comparable.
+    fn signature_of(embedder: &dyn EmbeddingProvider) -> String {
+        format!(
+            "{}/{}/{}",
+            embedder.name(),
+            embedder.model(),
+            embedder.dimensions()
+        )
+    }
+
+    /// True while some rows still need embeddings from the current model.
+    pub fn needs_reembed(&self) -> bool {
+        self.reembedding.load(Ordering::Relaxed)
+    }
+
+    /// Drop every stored vector so the next `reembed` recomputes them all.
+    pub fn reset_embeddings(&self) -> anyhow::Result<()> {
+        if self.embedder.dimensions() == 0 {
+            return Ok(());
+        }
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+        conn.execute_batch(
+            "UPDATE memories SET embedding = NULL;
+             DELETE FROM embedding_cache;",
+        )?;
+        self.reembedding.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    /// Embed every row lacking a vector, `batch_size` rows per provider call.
+    /// Each batch is committed before the next, so an interrupted run resumes
+    /// where it stopped. Recall stays keyword-only until this completes.
+    pub async fn reembed(
+        &self,
+        batch_size: usize,
+        progress: &(dyn Fn(ReembedProgress) + Send + Sync),
+    ) -> anyhow::Result<usize> {
+        if self.embedder.dimensions() == 0 {
+            return Ok(0);
+        }
+
+        let total: usize = {
+            let conn = self
+                .conn
+                .lock()
+                .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+            let n: i64 = conn.query_row(
+                "SELECT COUNT(*) FROM memories WHERE embedding IS NULL",
+                [],
+                |row| row.get(0),
+            )?;
+            usize::try_from(n)?
+        };
+        if total > 0 {
+            self.reembedding.store(true, Ordering::Relaxed);
+        }
 
-            -- FTS5 full-text search (BM25 scoring)
-            CREATE VIRTUAL TABLE IF NOT EXISTS memories_fts USING fts5(
-                key, content, content=memories, content_rowid=rowid
+        let mut done = 0;
+        let mut last_rowid = 0_i64;
+        loop {
+            let batch: Vec<(i64, String, String)> = {
+                let conn = self
+                    .conn
+                    .lock()
+                    .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+                let mut stmt = conn.prepare(
+                    "SELECT rowid, id, content FROM memories
+                     WHERE embedding IS NULL AND rowid > ?1
+                     ORDER BY rowid LIMIT ?2",
+                )?;
+                let rows = stmt.query_map(
+                    params![last_rowid, i64::try_from(batch_size.max(1))?],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                )?;
+                rows.collect::<Result<_, _>>()?
+            };
+            let Some(&(rowid, _, _)) = batch.last() else {
+                break;
+            };
+            last_rowid = rowid;
+
+            let plaintexts = batch
+                .iter()
+                .map(|(_, _, c)| self.open(c.clone()))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let texts: Vec<&str> = plaintexts.iter().map(String::as_str).collect();
+            let vectors = self.embedder.embed(&texts).await?;
+            anyhow::ensure!(
+                vectors.len() == batch.len(),
+                "embedding provider returned {} vectors for {} texts",
+                vectors.len(),
+                batch.len()
             );
 
-            -- FTS5 triggers: keep in sync with memories table
-            CREATE TRIGGER IF NOT EXISTS memories_ai AFTER INSERT ON memories BEGIN
-                INSERT INTO memories_fts(rowid, key, content)
-                VALUES (new.rowid, new.key, new.content);
-            END;
-            CREATE TRIGGER IF NOT EXISTS memories_ad AFTER DELETE ON memories BEGIN
-                INSERT INTO memories_fts(memories_fts, rowid, key, content)
-                VALUES ('delete', old.rowid, old.key, old.content);
-            END;
-            CREATE TRIGGER IF NOT EXISTS memories_au AFTER UPDATE ON memories BEGIN
-                INSERT INTO memories_fts(memories_fts, rowid, key, content)
-                VALUES ('delete', old.rowid, old.key, old.content);
-                INSERT INTO memories_fts(rowid, key, content)
-                VALUES (new.rowid, new.key, new.content);
-            END;
-
-            -- Embedding cache with LRU eviction
-            CREATE TABLE IF NOT EXISTS embedding_cache (
-                content_hash TEXT PRIMARY KEY,
-                embedding    BLOB NOT NULL,
-                created_at   TEXT NOT NULL,
-                accessed_at  TEXT NOT NULL
+            {
+                let conn = self
+                    .conn
+                    .lock()
+                    .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+                let tx = conn.unchecked_transaction()?;
+                for ((_, id, _), v) in batch.iter().zip(&vectors) {
+                    // A concurrent store may already have embedded this row.
+                    tx.execute(
+                        "UPDATE memories SET embedding = ?1 WHERE id = ?2 AND embedding IS NULL",
+                        params![vector::vec_to_bytes(v), id],
+                    )?;
+                }
+                tx.commit()?;
+                let updates: Vec<(&str, Option<&[f32]>)> = batch
+                    .iter()
+                    .zip(&vectors)
+                    .map(|((_, id, _), v)| (id.as_str(), Some(v.as_slice())))
+                    .collect();
+                self.update_index(&conn, &updates)?;
+            }
+
+            done += batch.len();
+            progress(ReembedProgress {
+                done,
+                total: total.max(done),
+            });
+        }
+
+        self.reembedding.store(false, Ordering::Relaxed);
+        Ok(done)
+    }
+
+    /// Run `reembed` with progress logged, for use as a spawned task.
+    pub async fn reembed_in_background(&self) {
+        tracing::info!("Re-embedding memories in the background");
+        let result = self
+            .reembed(REEMBED_BATCH_SIZE, &|p| {
+                tracing::info!(done = p.done, total = p.total, "Re-embedding memories");
+            })
+            .await;
+        match result {
+            Ok(n) => tracing::info!("Re-embedded {n} memories"),
+            Err(e) => tracing::warn!(
+                "Re-embedding stopped, recall stays keyword-only until `zeroclaw memory reindex` succeeds: {e:#}"
+            ),
+        }
+    }
+
+    /// Always use the exact full-scan vector search (`vector_index = "none"`).
+    pub fn without_vector_index(mut self) -> Self {
+        self.index = None;
+        self
+    }
+
+    /// Re-rank recall results with these recency/importance/category weights.
+    pub fn with_ranking(mut self, config: RankingConfig) -> Self {
+        self.ranker = Ranker::new(config);
+        self
+    }
+
+    /// Seal content written from now on (and read sealed content back).
+    pub fn with_encryption(mut self, cipher: ContentCipher) -> Self {
+        self.cipher = Some(cipher);
+        self.encrypt_writes = true;
+        self
+    }
+
+    /// Read sealed content but write plaintext, e.g. after `encrypt = false`.
+    pub fn with_decryption(mut self, cipher: ContentCipher) -> Self {
+        self.cipher = Some(cipher);
+        self.encrypt_writes = false;
+        self
+    }
+
+    /// Stored form of `content`: `(content, search_text)`.
+    fn seal(&self, content: &str) -> anyhow::Result<(String, Option<String>)> {
+        match &self.cipher {
+            Some(cipher) if self.encrypt_writes => {
+                Ok((cipher.encrypt(content)?, Some(cipher.blind_index(content))))
+            }
+            _ => Ok((content.to_string(), None)),
+        }
+    }
+
+    fn sealing(&self) -> Sealing<'_> {
+        Sealing {
+            cipher: self.cipher.as_ref(),
+            encrypt: self.encrypt_writes,
+        }
+    }
+
+    fn open(&self, stored: String) -> anyhow::Result<String> {
+        if !ContentCipher::is_encrypted(&stored) {
+            return Ok(stored);
+        }
+        match &self.cipher {
+            Some(cipher) => cipher.decrypt(&stored),
+            None => anyhow::bail!(
+                "memory content is encrypted but no key is available; set [memory] encrypt = true"
+            ),
+        }
+    }
+
+    /// Seal (`encrypt`) or open every row in place, returning how many
+    /// changed. Each row's FTS entry follows through the update trigger.
+    pub fn migrate_encryption(&self, encrypt: bool) -> anyhow::Result<usize> {
+        let cipher = self
+            .cipher
+            .as_ref()
+            .context("no memory encryption key available")?;
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+        let tx = conn.unchecked_transaction()?;
+        let rows: Vec<(i64, String)> = {
+            let mut stmt = tx.prepare("SELECT rowid, content FROM memories")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<Result<_, _>>()?
+        };
+        let mut changed = 0;
+        for (rowid, stored) in rows {
+            if ContentCipher::is_encrypted(&stored) == encrypt {
+                continue;
+            }
+            let plaintext = cipher.decrypt(&stored)?;
+            let (content, search_text) = if encrypt {
+                (
+                    cipher.encrypt(&plaintext)?,
+                    Some(cipher.blind_index(&plaintext)),
+                )
+            } else {
+                (plaintext, None)
+            };
+            tx.execute(
+                "UPDATE memories SET content = ?2, search_text = ?3 WHERE rowid = ?1",
+                params![rowid, content, search_text],
+            )?;
+            changed += 1;
+        }
+        // Cache keys differ between modes; entries are cheap to recompute.
+        tx.execute("DELETE FROM embedding_cache", [])?;
+        tx.commit()?;
+        Ok(changed)
+    }
+
+    /// Keep the ANN index in step with rows' embeddings (`None` removes).
+    fn update_index(
+        &self,
+        conn: &Connection,
+        updates: &[(&str, Option<&[f32]>)],
+    ) -> anyhow::Result<()> {
+        let Some(index) = &self.index else {
+            return Ok(());
+        };
+        let mut index = index
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+        for (id, embedding) in updates {
+            match embedding {
+                Some(v) => {
+                    index.insert(id, v);
+                }
+                None => {
+                    index.remove(id);
+                }
+            }
+        }
+        index.save(conn)
+    }
+
+    /// Bring the schema up to [`SCHEMA_VERSION`], applying each pending
+    /// migration in its own transaction and recording it in `schema_version`.
+    /// Databases from before versioning run every migration; they are all
+    /// idempotent, so the ones already reflected in the tables are no-ops.
+    fn init_schema(conn: &Connection) -> anyhow::Result<()> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS schema_version (
+                version    INTEGER PRIMARY KEY,
+                applied_at TEXT NOT NULL
+            );",
+        )?;
+        let current = Self::schema_version(conn)?;
+        if current > SCHEMA_VERSION {
+            anyhow::bail!(
+                "brain.db schema v{current} is newer than this build supports (v{SCHEMA_VERSION}); upgrade zeroclaw"
             );
-            CREATE INDEX IF NOT EXISTS idx_cache_accessed ON embedding_cache(accessed_at);",
+        }
+        for (version, migrate) in MIGRATIONS.iter().copied() {
+            if version <= current {
+                continue;
+            }
+            let tx = conn.unchecked_transaction()?;
+            migrate(&tx)?;
+            tx.execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (?1, ?2)",
+                params![version, Local::now().to_rfc3339()],
+            )?;
+            tx.commit()?;
+        }
+        Ok(())
+    }
+
+    /// Highest applied migration (0 for a new or pre-versioning database).
+    pub fn schema_version(conn: &Connection) -> anyhow::Result<u32> {
+        Ok(conn.query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+            [],
+            |row| row.get(0),
+        )?)
+    }
+
+    /// SQL condition on alias `m` matching rows visible from `scopes`
+    /// (global rows plus the listed scopes), numbering parameters from `?{first}`.
+    fn scope_clause(scopes: &[MemoryScope], first: usize) -> (String, Vec<Value>) {
+        let labels: Vec<Value> = scopes
+            .iter()
+            .filter(|s| **s != MemoryScope::Global)
+            .map(|s| Value::Text(s.to_string()))
+            .collect();
+        if labels.is_empty() {
+            return ("m.scope IS NULL".into(), labels);
+        }
+        let placeholders: Vec<String> = (first..first + labels.len())
+            .map(|i| format!("?{i}"))
+            .collect();
+        (
+            format!(
+                "(m.scope IS NULL OR m.scope IN ({}))",
+                placeholders.join(", ")
+            ),
+            labels,
+        )
+    }
+
+    /// [`scope_clause`](Self::scope_clause) plus the query's category, time,
+    /// key-prefix and session filters.
+    fn filter_clause(filter: &RecallQuery, first: usize) -> (String, Vec<Value>) {
+        let (visible, mut params) = Self::scope_clause(&filter.scopes, first);
+        let mut conditions = vec![visible];
+        let bind = |value: Value, params: &mut Vec<Value>| {
+            params.push(value);
+            format!("?{}", first + params.len() - 1)
+        };
+
+        if let Some(category) = &filter.category {
+            let p = bind(Value::Text(Self::category_to_str(category)), &mut params);
+            conditions.push(format!("m.category = {p}"));
+        }
+        if let Some(since) = filter.since {
+            let p = bind(Value::Text(since.to_rfc3339()), &mut params);
+            conditions.push(format!("julianday(m.created_at) >= julianday({p})"));
+        }
+        if let Some(until) = filter.until {
+            let p = bind(Value::Text(until.to_rfc3339()), &mut params);
+            conditions.push(format!("julianday(m.created_at) <= julianday({p})"));
+        }
+        if let Some(prefix) = &filter.key_prefix {
+            // Scoped keys are `<scope>/<key>`; compare the caller's part.
+            let p = bind(Value::Text(prefix.clone()), &mut params);
+            conditions.push(format!(
+                "substr(CASE WHEN m.scope IS NULL THEN m.key \
+                 ELSE substr(m.key, length(m.scope) + 2) END, 1, length({p})) = {p}"
+            ));
+        }
+        if let Some(session) = &filter.session {
+            let label = bind(Value::Text(session.clone()), &mut params);
+            let prefix = bind(Value::Text(format!("{session}_")), &mut params);
+            conditions.push(format!(
+                "(m.scope = {label} OR (m.scope IS NULL \
+                 AND substr(m.key, 1, length({prefix})) = {prefix}))"
+            ));
+        }
+        (conditions.join(" AND "), params)
+    }
+
+    fn row_to_entry(
+        &self,
+        row: &rusqlite::Row,
+        score: Option<f64>,
+    ) -> rusqlite::Result<MemoryEntry> {
+        let content = self.open(row.get(2)?).map_err(|e| {
+            rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, e.into())
+        })?;
+        Ok(MemoryEntry {
+            id: row.get(0)?,
+            key: row.get(1)?,
+            content,
+            category: Self::str_to_category(&row.get::<_, String>(3)?),
+            timestamp: row.get(4)?,
+            session_id: row.get(5)?,
+            score,
+            importance: row.get(6)?,
+            score_breakdown: None,
+        })
+    }
+
+    /// Map a `RANKED_COLUMNS` row to an entry scored by the ranker.
+    fn ranked_entry(
+        &self,
+        row: &rusqlite::Row,
+        relevance: f64,
+        now: DateTime<Utc>,
+    ) -> rusqlite::Result<MemoryEntry> {
+        let mut entry = self.row_to_entry(row, None)?;
+        let updated_at: String = row.get(7)?;
+        let access_count: u32 = row.get(8)?;
+        let breakdown = self.ranker.score(
+            &Signals {
+                relevance,
+                category: &entry.category,
+                updated_at: &updated_at,
+                importance: entry.importance,
+                access_count,
+            },
+            now,
+        );
+        entry.score = Some(breakdown.score);
+        entry.score_breakdown = Some(breakdown);
+        Ok(entry)
+    }
+
+    /// Reinforce entries that were recalled.
+    fn record_access(
+        conn: &Connection,
+        entries: &[MemoryEntry],
+        now: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        let now = now.to_rfc3339();
+        let mut stmt = conn.prepare_cached(
+            "UPDATE memories SET access_count = access_count + 1, last_accessed_at = ?2
+             WHERE id = ?1",
         )?;
+        for entry in entries {
+            stmt.execute(params![entry.id, now])?;
+        }
         Ok(())
     }
 
@@ -152,7 +819,10 @@ impl SqliteMemory {
             return Ok(None); // Noop embedder
         }
 
-        let hash = Self::content_hash(text);
+        let hash = match &self.cipher {
+            Some(cipher) if self.encrypt_writes => cipher.cache_key(text),
+            _ => Self::content_hash(text),
+        };
         let now = Local::now().to_rfc3339();
 
         // Check cache
@@ -209,35 +879,46 @@ impl SqliteMemory {
         Ok(Some(embedding))
     }
 
-    /// FTS5 BM25 keyword search
+    /// FTS5 BM25 keyword search over rows visible from `scopes`
     fn fts5_search(
+        &self,
         conn: &Connection,
         query: &str,
         limit: usize,
+        filter: &RecallQuery,
     ) -> anyhow::Result<Vec<(String, f32)>> {
-        // Escape FTS5 special chars and build query
-        let fts_query: String = query
+        // Escape FTS5 special chars and build query. With a key, blinded
+        // words match sealed rows; plaintext rows may remain from before.
+        let mut terms: Vec<String> = query
             .split_whitespace()
             .map(|w| format!("\"{w}\""))
-            .collect::<Vec<_>>()
-            .join(" OR ");
+            .collect();
+        if let Some(cipher) = &self.cipher {
+            terms.extend(cipher.blind_words(query));
+        }
+        let fts_query = terms.join(" OR ");
 
         if fts_query.is_empty() {
             return Ok(Vec::new());
         }
 
-        let sql = "SELECT m.id, bm25(memories_fts) as score
-                   FROM memories_fts f
-                   JOIN memories m ON m.rowid = f.rowid
-                   WHERE memories_fts MATCH ?1
-                   ORDER BY score
-                   LIMIT ?2";
+        let (visible, scope_params) = Self::filter_clause(filter, 3);
+        let sql = format!(
+            "SELECT m.id, bm25(memories_fts) as score
+             FROM memories_fts f
+             JOIN memories m ON m.rowid = f.rowid
+             WHERE memories_fts MATCH ?1 AND {visible}
+             ORDER BY score
+             LIMIT ?2"
+        );
 
-        let mut stmt = conn.prepare(sql)?;
+        let mut stmt = conn.prepare(&sql)?;
         #[allow(clippy::cast_possible_wrap)]
         let limit_i64 = limit as i64;
+        let mut values = vec![Value::Text(fts_query), Value::Integer(limit_i64)];
+        values.extend(scope_params);
 
-        let rows = stmt.query_map(params![fts_query, limit_i64], |row| {
+        let rows = stmt.query_map(params_from_iter(values), |row| {
             let id: String = row.get(0)?;
             let score: f64 = row.get(1)?;
             // BM25 returns negative scores (lower = better), negate for ranking
@@ -252,16 +933,29 @@ impl SqliteMemory {
         Ok(results)
     }
 
-    /// Vector similarity search: scan embeddings and compute cosine similarity
+    /// Approximate vector search through the HNSW index, or `None` when the
+    /// index is disabled or too small to beat a full scan.
+    fn ann_search(&self, query_embedding: &[f32], limit: usize) -> Option<Vec<(String, f32)>> {
+        let index = self.index.as_ref()?.lock().ok()?;
+        if index.len() < self.ann_min_rows.max(1) {
+            return None;
+        }
+        Some(index.search(query_embedding, limit))
+    }
+
+    /// Vector similarity search: scan embeddings of rows visible from `scopes`
     fn vector_search(
         conn: &Connection,
         query_embedding: &[f32],
         limit: usize,
+        filter: &RecallQuery,
     ) -> anyhow::Result<Vec<(String, f32)>> {
-        let mut stmt =
-            conn.prepare("SELECT id, embedding FROM memories WHERE embedding IS NOT NULL")?;
+        let (visible, scope_params) = Self::filter_clause(filter, 1);
+        let mut stmt = conn.prepare(&format!(
+            "SELECT m.id, m.embedding FROM memories m WHERE m.embedding IS NOT NULL AND {visible}"
+        ))?;
 
-        let rows = stmt.query_map([], |row| {
+        let rows = stmt.query_map(params_from_iter(scope_params), |row| {
             let id: String = row.get(0)?;
             let blob: Vec<u8> = row.get(1)?;
             Ok((id, blob))
@@ -282,9 +976,17 @@ impl SqliteMemory {
         Ok(scored)
     }
 
-    /// Safe reindex: rebuild FTS5 + embeddings with rollback on failure
-    #[allow(dead_code)]
+    /// Rebuild FTS5, embed rows missing vectors, and rebuild the ANN index.
     pub async fn reindex(&self) -> anyhow::Result<usize> {
+        self.reindex_with_progress(REEMBED_BATCH_SIZE, &|_| {})
+            .await
+    }
+
+    pub async fn reindex_with_progress(
+        &self,
+        batch_size: usize,
+        progress: &(dyn Fn(ReembedProgress) + Send + Sync),
+    ) -> anyhow::Result<usize> {
         // Step 1: Rebuild FTS5
         {
             let conn = self
@@ -292,42 +994,30 @@ impl SqliteMemory {
                 .lock()
                 .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
 
-            conn.execute_batch("INSERT INTO memories_fts(memories_fts) VALUES('rebuild');")?;
+            // Not 'rebuild': that would index ciphertext instead of the blind index.
+            conn.execute_batch(
+                "INSERT INTO memories_fts(memories_fts) VALUES('delete-all');
+                 INSERT INTO memories_fts(rowid, key, content)
+                 SELECT rowid, key, COALESCE(search_text, content) FROM memories;",
+            )?;
         }
 
         // Step 2: Re-embed all memories that lack embeddings
         if self.embedder.dimensions() == 0 {
             return Ok(0);
         }
+        let count = self.reembed(batch_size, progress).await?;
 
-        let entries: Vec<(String, String)> = {
+        // Step 3: Rebuild the ANN index from the stored embeddings
+        if let Some(index) = &self.index {
             let conn = self
                 .conn
                 .lock()
                 .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
-
-            let mut stmt =
-                conn.prepare("SELECT id, content FROM memories WHERE embedding IS NULL")?;
-            let rows = stmt.query_map([], |row| {
-                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
-            })?;
-            rows.filter_map(std::result::Result::ok).collect()
-        };
-
-        let mut count = 0;
-        for (id, content) in &entries {
-            if let Ok(Some(emb)) = self.get_or_compute_embedding(content).await {
-                let bytes = vector::vec_to_bytes(&emb);
-                let conn = self
-                    .conn
-                    .lock()
-                    .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
-                conn.execute(
-                    "UPDATE memories SET embedding = ?1 WHERE id = ?2",
-                    params![bytes, id],
-                )?;
-                count += 1;
-            }
+            let rebuilt = HnswIndex::rebuild(&conn)?;
+            *index
+                .lock()
+                .map_err(|e| anyhow::anyhow!("Lock error: {e}"))? = rebuilt;
         }
 
         Ok(count)
@@ -345,6 +1035,21 @@ impl Memory for SqliteMemory {
         key: &str,
         content: &str,
         category: MemoryCategory,
+    ) -> anyhow::Result<()> {
+        self.store_scoped(key, content, category, &MemoryScope::Global)
+            .await
+    }
+
+    async fn recall(&self, query: &str, limit: usize) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.recall_scoped(query, limit, &[]).await
+    }
+
+    async fn store_scoped(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        scope: &MemoryScope,
     ) -> anyhow::Result<()> {
         // Compute embedding (async, before lock)
         let embedding_bytes = self
@@ -359,28 +1064,74 @@ impl Memory for SqliteMemory {
         let now = Local::now().to_rfc3339();
         let cat = Self::category_to_str(&category);
         let id = Uuid::new_v4().to_string();
+        let key = scope.qualify(key);
+        let scope_label = (*scope != MemoryScope::Global).then(|| scope.to_string());
+        let (content, search_text) = self.seal(content)?;
 
         conn.execute(
-            "INSERT INTO memories (id, key, content, category, embedding, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            "INSERT INTO memories
+                (id, key, content, category, embedding, created_at, updated_at, scope, search_text)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
              ON CONFLICT(key) DO UPDATE SET
                 content = excluded.content,
                 category = excluded.category,
                 embedding = excluded.embedding,
-                updated_at = excluded.updated_at",
-            params![id, key, content, cat, embedding_bytes, now, now],
+                updated_at = excluded.updated_at,
+                scope = excluded.scope,
+                search_text = excluded.search_text",
+            params![
+                id,
+                key,
+                content,
+                cat,
+                embedding_bytes,
+                now,
+                now,
+                scope_label,
+                search_text
+            ],
         )?;
 
+        if self.index.is_some() {
+            // On conflict the row keeps its original id.
+            let id: String = conn.query_row(
+                "SELECT id FROM memories WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )?;
+            let embedding = embedding_bytes.as_deref().map(vector::bytes_to_vec);
+            self.update_index(&conn, &[(&id, embedding.as_deref())])?;
+        }
+
         Ok(())
     }
 
-    async fn recall(&self, query: &str, limit: usize) -> anyhow::Result<Vec<MemoryEntry>> {
+    async fn recall_scoped(
+        &self,
+        query: &str,
+        limit: usize,
+        scopes: &[MemoryScope],
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        self.recall_query(&RecallQuery {
+            scopes: scopes.to_vec(),
+            ..RecallQuery::new(query, limit)
+        })
+        .await
+    }
+
+    async fn recall_query(&self, filter: &RecallQuery) -> anyhow::Result<Vec<MemoryEntry>> {
+        let (query, limit) = (filter.query.as_str(), filter.limit);
         if query.trim().is_empty() {
             return Ok(Vec::new());
         }
 
-        // Compute query embedding (async, before lock)
-        let query_embedding = self.get_or_compute_embedding(query).await?;
+        // Compute query embedding (async, before lock). While re-embedding,
+        // stored vectors are incomplete, so rank by keywords only.
+        let query_embedding = if self.needs_reembed() {
+            None
+        } else {
+            self.get_or_compute_embedding(query).await?
+        };
 
         let conn = self
             .conn
@@ -388,25 +1139,41 @@ impl Memory for SqliteMemory {
             .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
 
         // FTS5 BM25 keyword search
-        let keyword_results = Self::fts5_search(&conn, query, limit * 2).unwrap_or_default();
+        let keyword_results = self
+            .fts5_search(&conn, query, limit * 2, filter)
+            .unwrap_or_default();
 
-        // Vector similarity search (if embeddings available)
+        // Vector similarity search (if embeddings available). The ANN index
+        // spans every scope, so invisible hits are dropped when fetching below;
+        // filtered queries scan the matching rows exactly instead.
         let vector_results = if let Some(ref qe) = query_embedding {
-            Self::vector_search(&conn, qe, limit * 2).unwrap_or_default()
+            let ann = if filter.is_filtered() {
+                None
+            } else {
+                self.ann_search(qe, limit * 2)
+            };
+            ann.unwrap_or_else(|| {
+                Self::vector_search(&conn, qe, limit * 2, filter).unwrap_or_default()
+            })
         } else {
             Vec::new()
         };
 
-        // Hybrid merge
+        // Hybrid merge, keeping extra candidates so re-ranking can promote them
         let merged = if vector_results.is_empty() {
-            // No embeddings — use keyword results only
+            // No embeddings — use keyword results only, normalized to [0, 1]
+            let max_kw = keyword_results
+                .iter()
+                .map(|(_, s)| *s)
+                .fold(0.0_f32, f32::max);
+            let max_kw = if max_kw < f32::EPSILON { 1.0 } else { max_kw };
             keyword_results
                 .iter()
                 .map(|(id, score)| vector::ScoredResult {
                     id: id.clone(),
                     vector_score: None,
-                    keyword_score: Some(*score),
-                    final_score: *score,
+                    keyword_score: Some(score / max_kw),
+                    final_score: score / max_kw,
                 })
                 .collect::<Vec<_>>()
         } else {
@@ -415,26 +1182,22 @@ impl Memory for SqliteMemory {
                 &keyword_results,
                 self.vector_weight,
                 self.keyword_weight,
-                limit,
+                limit * 2,
             )
         };
 
         // Fetch full entries for merged results
+        let now = Utc::now();
+        let (visible, scope_params) = Self::filter_clause(filter, 2);
+        let mut stmt = conn.prepare(&format!(
+            "SELECT {RANKED_COLUMNS} FROM memories m WHERE m.id = ?1 AND {visible}"
+        ))?;
         let mut results = Vec::new();
         for scored in &merged {
-            let mut stmt = conn.prepare(
-                "SELECT id, key, content, category, created_at FROM memories WHERE id = ?1",
-            )?;
-            if let Ok(entry) = stmt.query_row(params![scored.id], |row| {
-                Ok(MemoryEntry {
-                    id: row.get(0)?,
-                    key: row.get(1)?,
-                    content: row.get(2)?,
-                    category: Self::str_to_category(&row.get::<_, String>(3)?),
-                    timestamp: row.get(4)?,
-                    session_id: None,
-                    score: Some(f64::from(scored.final_score)),
-                })
+            let mut values = vec![Value::Text(scored.id.clone())];
+            values.extend(scope_params.iter().cloned());
+            if let Ok(entry) = stmt.query_row(params_from_iter(values), |row| {
+                self.ranked_entry(row, f64::from(scored.final_score), now)
             }) {
                 results.push(entry);
             }
@@ -449,37 +1212,34 @@ impl Memory for SqliteMemory {
                     .iter()
                     .enumerate()
                     .map(|(i, _)| {
-                        format!("(content LIKE ?{} OR key LIKE ?{})", i * 2 + 1, i * 2 + 2)
+                        format!(
+                            "(m.content LIKE ?{} OR m.key LIKE ?{})",
+                            i * 2 + 1,
+                            i * 2 + 2
+                        )
                     })
                     .collect();
                 let where_clause = conditions.join(" OR ");
+                let (visible, scope_params) = Self::filter_clause(filter, keywords.len() * 2 + 2);
                 let sql = format!(
-                    "SELECT id, key, content, category, created_at FROM memories
-                     WHERE {where_clause}
-                     ORDER BY updated_at DESC
+                    "SELECT {RANKED_COLUMNS}
+                     FROM memories m
+                     WHERE ({where_clause}) AND {visible}
+                     ORDER BY m.updated_at DESC
                      LIMIT ?{}",
                     keywords.len() * 2 + 1
                 );
                 let mut stmt = conn.prepare(&sql)?;
-                let mut param_values: Vec<Box<dyn rusqlite::types::ToSql>> = Vec::new();
+                let mut values: Vec<Value> = Vec::new();
                 for kw in &keywords {
-                    param_values.push(Box::new(kw.clone()));
-                    param_values.push(Box::new(kw.clone()));
+                    values.push(Value::Text(kw.clone()));
+                    values.push(Value::Text(kw.clone()));
                 }
                 #[allow(clippy::cast_possible_wrap)]
-                param_values.push(Box::new(limit as i64));
-                let params_ref: Vec<&dyn rusqlite::types::ToSql> =
-                    param_values.iter().map(AsRef::as_ref).collect();
-                let rows = stmt.query_map(params_ref.as_slice(), |row| {
-                    Ok(MemoryEntry {
-                        id: row.get(0)?,
-                        key: row.get(1)?,
-                        content: row.get(2)?,
-                        category: Self::str_to_category(&row.get::<_, String>(3)?),
-                        timestamp: row.get(4)?,
-                        session_id: None,
-                        score: Some(1.0),
-                    })
+                values.push(Value::Integer(limit as i64));
+                values.extend(scope_params);
+                let rows = stmt.query_map(params_from_iter(values), |row| {
+                    self.ranked_entry(row, 1.0, now)
                 })?;
                 for row in rows {
                     results.push(row?);
@@ -487,62 +1247,182 @@ impl Memory for SqliteMemory {
             }
         }
 
-        results.truncate(limit);
+        let mut results = traits::resolve_scopes(results, &filter.scopes, limit);
+        if let Some(min) = filter.min_score {
+            results.retain(|e| e.score.is_some_and(|s| s >= min));
+        }
+        Self::record_access(&conn, &results, now)?;
         Ok(results)
     }
 
-    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+    async fn set_importance(&self, key: &str, importance: f64) -> anyhow::Result<bool> {
         let conn = self
             .conn
             .lock()
             .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
-
-        let mut stmt = conn.prepare(
-            "SELECT id, key, content, category, created_at FROM memories WHERE key = ?1",
+        let changed = conn.execute(
+            "UPDATE memories SET importance = ?2 WHERE key = ?1",
+            params![key, importance.clamp(0.0, 1.0)],
         )?;
-
-        let mut rows = stmt.query_map(params![key], |row| {
-            Ok(MemoryEntry {
-                id: row.get(0)?,
-                key: row.get(1)?,
-                content: row.get(2)?,
-                category: Self::str_to_category(&row.get::<_, String>(3)?),
-                timestamp: row.get(4)?,
-                session_id: None,
-                score: None,
-            })
-        })?;
-
-        match rows.next() {
-            Some(Ok(entry)) => Ok(Some(entry)),
-            _ => Ok(None),
-        }
+        Ok(changed > 0)
     }
 
-    async fn list(&self, category: Option<&MemoryCategory>) -> anyhow::Result<Vec<MemoryEntry>> {
+    async fn graph_upsert(
+        &self,
+        update: &GraphUpdate,
+        scope: &MemoryScope,
+    ) -> anyhow::Result<GraphUpsertReport> {
         let conn = self
             .conn
             .lock()
             .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+        graph::upsert(&conn, &self.sealing(), update, scope)
+    }
 
-        let mut results = Vec::new();
+    async fn graph_query(&self, query: &GraphQuery) -> anyhow::Result<Subgraph> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+        graph::query(&conn, &self.sealing(), query)
+    }
 
-        let row_mapper = |row: &rusqlite::Row| -> rusqlite::Result<MemoryEntry> {
-            Ok(MemoryEntry {
-                id: row.get(0)?,
-                key: row.get(1)?,
-                content: row.get(2)?,
-                category: Self::str_to_category(&row.get::<_, String>(3)?),
-                timestamp: row.get(4)?,
-                session_id: None,
-                score: None,
-            })
+    async fn snapshot(&self) -> anyhow::Result<Snapshot> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+        let mut stmt = conn.prepare(
+            "SELECT key, content, category, scope, created_at, updated_at, importance, embedding
+             FROM memories ORDER BY created_at, rowid",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let key: String = row.get(0)?;
+            let scope: Option<String> = row.get(3)?;
+            let key = match scope.as_deref().map(str::parse::<MemoryScope>) {
+                Some(Ok(parsed)) => parsed.unqualify(&key).to_string(),
+                _ => key,
+            };
+            Ok(Record {
+                key,
+                content: row.get(1)?,
+                category: Self::str_to_category(&row.get::<_, String>(2)?),
+                scope,
+                created_at: row.get(4)?,
+                updated_at: Some(row.get(5)?),
+                importance: row.get(6)?,
+                embedding: row
+                    .get::<_, Option<Vec<u8>>>(7)?
+                    .map(|bytes| vector::bytes_to_vec(&bytes)),
+            })
+        })?;
+        let mut records = rows.collect::<Result<Vec<_>, _>>()?;
+        for record in &mut records {
+            record.content = self.open(std::mem::take(&mut record.content))?;
+        }
+        let embedding_signature = (self.embedder.dimensions() > 0
+            && records.iter().any(|r| r.embedding.is_some()))
+        .then(|| Self::signature_of(self.embedder.as_ref()));
+        Ok(Snapshot {
+            embedding_signature,
+            records,
+        })
+    }
+
+    /// Upsert keeping the record's timestamps and importance. Its embedding
+    /// is reused when it came from the configured model, else recomputed.
+    async fn restore(
+        &self,
+        record: &Record,
+        embedding_signature: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let dims = self.embedder.dimensions();
+        let reusable = record.embedding.as_ref().filter(|v| {
+            dims > 0
+                && v.len() == dims
+                && embedding_signature == Some(Self::signature_of(self.embedder.as_ref()).as_str())
+        });
+        let embedding = match reusable {
+            Some(v) => Some(v.clone()),
+            None => self.get_or_compute_embedding(&record.content).await?,
         };
+        let embedding_bytes = embedding.as_deref().map(vector::vec_to_bytes);
+
+        let scope = record.scope()?;
+        let key = scope.qualify(&record.key);
+        let scope_label = (scope != MemoryScope::Global).then(|| scope.to_string());
+        let (content, search_text) = self.seal(&record.content)?;
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+        conn.execute(
+            "INSERT INTO memories
+                (id, key, content, category, embedding, created_at, updated_at, scope, importance,
+                 search_text)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT(key) DO UPDATE SET
+                content = excluded.content,
+                category = excluded.category,
+                embedding = excluded.embedding,
+                created_at = excluded.created_at,
+                updated_at = excluded.updated_at,
+                scope = excluded.scope,
+                importance = excluded.importance,
+                search_text = excluded.search_text",
+            params![
+                Uuid::new_v4().to_string(),
+                key,
+                content,
+                Self::category_to_str(&record.category),
+                embedding_bytes,
+                record.created_at_rfc3339(),
+                record.updated_at_rfc3339(),
+                scope_label,
+                record.importance.map(|i| i.clamp(0.0, 1.0)),
+                search_text,
+            ],
+        )?;
+
+        if self.index.is_some() {
+            let id: String = conn.query_row(
+                "SELECT id FROM memories WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )?;
+            self.update_index(&conn, &[(&id, embedding.as_deref())])?;
+        }
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+
+        let mut stmt = conn.prepare(
+            "SELECT id, key, content, category, created_at, scope, importance FROM memories WHERE key = ?1",
+        )?;
+
+        let mut rows = stmt.query_map(params![key], |row| self.row_to_entry(row, None))?;
+        Ok(rows.next().transpose()?)
+    }
+
+    async fn list(&self, category: Option<&MemoryCategory>) -> anyhow::Result<Vec<MemoryEntry>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+
+        let mut results = Vec::new();
+
+        let row_mapper = |row: &rusqlite::Row| self.row_to_entry(row, None);
 
         if let Some(cat) = category {
             let cat_str = Self::category_to_str(cat);
             let mut stmt = conn.prepare(
-                "SELECT id, key, content, category, created_at FROM memories
+                "SELECT id, key, content, category, created_at, scope, importance FROM memories
                  WHERE category = ?1 ORDER BY updated_at DESC",
             )?;
             let rows = stmt.query_map(params![cat_str], row_mapper)?;
@@ -551,7 +1431,7 @@ impl Memory for SqliteMemory {
             }
         } else {
             let mut stmt = conn.prepare(
-                "SELECT id, key, content, category, created_at FROM memories
+                "SELECT id, key, content, category, created_at, scope, importance FROM memories
                  ORDER BY updated_at DESC",
             )?;
             let rows = stmt.query_map([], row_mapper)?;
@@ -568,7 +1448,17 @@ impl Memory for SqliteMemory {
             .conn
             .lock()
             .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+        let id: Option<String> = conn
+            .query_row(
+                "SELECT id FROM memories WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?;
         let affected = conn.execute("DELETE FROM memories WHERE key = ?1", params![key])?;
+        if let Some(id) = id {
+            self.update_index(&conn, &[(&id, None)])?;
+        }
         Ok(affected > 0)
     }
 
@@ -593,6 +1483,7 @@ impl Memory for SqliteMemory {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::memory::interchange::{self, ConflictStrategy};
     use tempfile::TempDir;
 
     fn temp_sqlite() -> (TempDir, SqliteMemory) {
@@ -1370,4 +2261,795 @@ mod tests {
         let all = mem.list(None).await.unwrap();
         assert!(all.is_empty());
     }
+
+    // ── ANN index ────────────────────────────────────────────────
+
+    /// Bag-of-words embedder: each word bumps one of `dims` buckets.
+    struct WordHashEmbedding(usize);
+
+    #[async_trait]
+    impl EmbeddingProvider for WordHashEmbedding {
+        fn name(&self) -> &str {
+            "word-hash"
+        }
+
+        fn dimensions(&self) -> usize {
+            self.0
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|t| {
+                    let mut v = vec![0.0; self.0];
+                    for word in t.to_lowercase().split_whitespace() {
+                        let bucket =
+                            word.bytes().fold(7_usize, |h, b| h * 31 + b as usize) % self.0;
+                        v[bucket] += 1.0;
+                    }
+                    v
+                })
+                .collect())
+        }
+    }
+
+    fn ann_sqlite(dir: &Path) -> SqliteMemory {
+        let mut mem =
+            SqliteMemory::with_embedder(dir, Arc::new(WordHashEmbedding(64)), 1.0, 0.0, 100)
+                .unwrap();
+        mem.ann_min_rows = 0;
+        mem
+    }
+
+    fn index_len(mem: &SqliteMemory) -> usize {
+        mem.index.as_ref().unwrap().lock().unwrap().len()
+    }
+
+    #[tokio::test]
+    async fn ann_index_tracks_store_and_forget() {
+        let tmp = TempDir::new().unwrap();
+        let mem = ann_sqlite(tmp.path());
+        mem.store("rust", "rust borrow checker", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("tea", "green tea every morning", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("tea", "oolong tea every morning", MemoryCategory::Core)
+            .await
+            .unwrap();
+        assert_eq!(index_len(&mem), 2);
+
+        let hits = mem.recall("oolong tea", 1).await.unwrap();
+        assert_eq!(hits[0].key, "tea");
+
+        assert!(mem.forget("tea").await.unwrap());
+        assert_eq!(index_len(&mem), 1);
+        assert!(mem
+            .recall("oolong tea", 5)
+            .await
+            .unwrap()
+            .iter()
+            .all(|e| e.key != "tea"));
+    }
+
+    #[tokio::test]
+    async fn ann_index_persists_across_reopen() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let mem = ann_sqlite(tmp.path());
+            for i in 0..20 {
+                mem.store(
+                    &format!("k{i}"),
+                    &format!("note {i} topic{i}"),
+                    MemoryCategory::Core,
+                )
+                .await
+                .unwrap();
+            }
+        }
+        let mem = ann_sqlite(tmp.path());
+        assert_eq!(index_len(&mem), 20);
+        let hits = mem.recall("note 7 topic7", 1).await.unwrap();
+        assert_eq!(hits[0].key, "k7");
+    }
+
+    #[tokio::test]
+    async fn reindex_rebuilds_ann_index() {
+        let tmp = TempDir::new().unwrap();
+        let mem = ann_sqlite(tmp.path());
+        mem.store("a", "alpha beta", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("b", "gamma delta", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.conn
+            .lock()
+            .unwrap()
+            .execute_batch("DELETE FROM vector_index; DELETE FROM vector_index_meta;")
+            .unwrap();
+
+        mem.reindex().await.unwrap();
+        assert_eq!(index_len(&mem), 2);
+        let rows: i64 = mem
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM vector_index", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(rows, 2);
+    }
+
+    #[test]
+    fn noop_embedder_has_no_index() {
+        let (_tmp, mem) = temp_sqlite();
+        assert!(mem.index.is_none());
+    }
+
+    // ── Embedding model tracking ─────────────────────────────────
+
+    fn open_with(dir: &Path, dims: usize) -> SqliteMemory {
+        SqliteMemory::with_embedder(dir, Arc::new(WordHashEmbedding(dims)), 0.7, 0.3, 100).unwrap()
+    }
+
+    fn null_embeddings(mem: &SqliteMemory) -> i64 {
+        mem.conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT COUNT(*) FROM memories WHERE embedding IS NULL",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn model_change_drops_vectors_and_reembeds() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let mem = open_with(tmp.path(), 64);
+            assert!(!mem.needs_reembed());
+            mem.store("a", "rust ownership rules", MemoryCategory::Core)
+                .await
+                .unwrap();
+            mem.store("b", "tokio runtime notes", MemoryCategory::Core)
+                .await
+                .unwrap();
+        }
+
+        let mem = open_with(tmp.path(), 32);
+        assert!(mem.needs_reembed());
+        assert_eq!(null_embeddings(&mem), 2);
+        let cached: i64 = mem
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM embedding_cache", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(cached, 0);
+
+        // Keyword-only recall still works while re-embedding is pending.
+        let hits = mem.recall("ownership", 5).await.unwrap();
+        assert_eq!(hits[0].key, "a");
+
+        assert_eq!(mem.reembed(10, &|_| {}).await.unwrap(), 2);
+        assert!(!mem.needs_reembed());
+        assert_eq!(null_embeddings(&mem), 0);
+        let len: i64 = mem
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT length(embedding) FROM memories LIMIT 1", [], |r| {
+                r.get(0)
+            })
+            .unwrap();
+        assert_eq!(len, 32 * 4);
+
+        // Same model again: nothing to do.
+        drop(mem);
+        assert!(!open_with(tmp.path(), 32).needs_reembed());
+    }
+
+    #[tokio::test]
+    async fn legacy_db_dimension_mismatch_detected() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let mem = open_with(tmp.path(), 64);
+            mem.store("a", "hello world", MemoryCategory::Core)
+                .await
+                .unwrap();
+            mem.conn
+                .lock()
+                .unwrap()
+                .execute("DELETE FROM memory_meta", [])
+                .unwrap();
+        }
+        assert!(!open_with(tmp.path(), 64).needs_reembed());
+
+        let tmp2 = TempDir::new().unwrap();
+        {
+            let mem = open_with(tmp2.path(), 64);
+            mem.store("a", "hello world", MemoryCategory::Core)
+                .await
+                .unwrap();
+            mem.conn
+                .lock()
+                .unwrap()
+                .execute("DELETE FROM memory_meta", [])
+                .unwrap();
+        }
+        assert!(open_with(tmp2.path(), 16).needs_reembed());
+    }
+
+    #[tokio::test]
+    async fn reembed_reports_progress_per_batch() {
+        let tmp = TempDir::new().unwrap();
+        {
+            let plain = SqliteMemory::new(tmp.path()).unwrap();
+            for i in 0..5 {
+                plain
+                    .store(
+                        &format!("k{i}"),
+                        &format!("entry {i}"),
+                        MemoryCategory::Core,
+                    )
+                    .await
+                    .unwrap();
+            }
+        }
+
+        // Turning embeddings on for a keyword-only store needs a backfill.
+        let mem = open_with(tmp.path(), 64);
+        assert!(mem.needs_reembed());
+
+        let seen = Mutex::new(Vec::new());
+        let n = mem
+            .reembed(2, &|p| seen.lock().unwrap().push((p.done, p.total)))
+            .await
+            .unwrap();
+        assert_eq!(n, 5);
+        assert_eq!(*seen.lock().unwrap(), vec![(2, 5), (4, 5), (5, 5)]);
+        assert_eq!(index_len(&mem), 5);
+    }
+
+    #[tokio::test]
+    async fn reset_embeddings_forces_full_reembed() {
+        let tmp = TempDir::new().unwrap();
+        let mem = open_with(tmp.path(), 64);
+        mem.store("a", "alpha", MemoryCategory::Core).await.unwrap();
+        mem.reset_embeddings().unwrap();
+        assert!(mem.needs_reembed());
+        assert_eq!(mem.reindex().await.unwrap(), 1);
+        assert!(!mem.needs_reembed());
+    }
+
+    #[tokio::test]
+    async fn local_embedder_recalls_without_keyword_match() {
+        let tmp = TempDir::new().unwrap();
+        let embedder = Arc::new(crate::memory::embeddings::LocalEmbedding::new(256));
+        let mem = SqliteMemory::with_embedder(tmp.path(), embedder, 0.7, 0.3, 100).unwrap();
+        mem.store(
+            "a",
+            "deployment checklist for staging",
+            MemoryCategory::Core,
+        )
+        .await
+        .unwrap();
+        mem.store("b", "favourite pasta recipes", MemoryCategory::Core)
+            .await
+            .unwrap();
+
+        // "deploying" shares no FTS5 token with either entry.
+        let hits = mem.recall("deploying", 5).await.unwrap();
+        assert_eq!(hits[0].key, "a");
+    }
+
+    // ── Scopes ───────────────────────────────────────────────────
+
+    #[tokio::test]
+    async fn scoped_entries_are_isolated() {
+        let (_tmp, mem) = temp_sqlite();
+        let alice = MemoryScope::User("telegram:alice".into());
+        let bob = MemoryScope::User("telegram:bob".into());
+        mem.store_scoped("pin", "bank pin is 1234", MemoryCategory::Core, &alice)
+            .await
+            .unwrap();
+        mem.store("policy", "never share a bank pin", MemoryCategory::Core)
+            .await
+            .unwrap();
+
+        let for_bob = mem
+            .recall_scoped("bank pin", 10, std::slice::from_ref(&bob))
+            .await
+            .unwrap();
+        assert_eq!(for_bob.len(), 1);
+        assert_eq!(for_bob[0].key, "policy");
+
+        let for_alice = mem
+            .recall_scoped("bank pin", 10, std::slice::from_ref(&alice))
+            .await
+            .unwrap();
+        assert_eq!(for_alice.len(), 2);
+        let own = for_alice
+            .iter()
+            .find(|e| e.content.contains("1234"))
+            .unwrap();
+        assert_eq!(own.key, "user:telegram:alice/pin");
+        assert_eq!(own.session_id.as_deref(), Some("user:telegram:alice"));
+
+        // Global recall never sees scoped rows; key lookups still do.
+        assert_eq!(mem.recall("bank pin", 10).await.unwrap().len(), 1);
+        assert!(mem.get("user:telegram:alice/pin").await.unwrap().is_some());
+        assert!(mem.forget("user:telegram:alice/pin").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn scoped_key_shadows_global() {
+        let (_tmp, mem) = temp_sqlite();
+        let alice = MemoryScope::User("alice".into());
+        mem.store("timezone", "timezone is UTC", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store_scoped("timezone", "timezone is CET", MemoryCategory::Core, &alice)
+            .await
+            .unwrap();
+
+        let hits = mem
+            .recall_scoped("timezone", 10, std::slice::from_ref(&alice))
+            .await
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].content, "timezone is CET");
+        assert_eq!(mem.count().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn scoped_vector_search_filters_other_scopes() {
+        let tmp = TempDir::new().unwrap();
+        let mem = open_with(tmp.path(), 64);
+        let alice = MemoryScope::User("alice".into());
+        mem.store_scoped("a", "rust ownership notes", MemoryCategory::Core, &alice)
+            .await
+            .unwrap();
+
+        let bob = MemoryScope::User("bob".into());
+        let hits = mem
+            .recall_scoped("ownership", 5, std::slice::from_ref(&bob))
+            .await
+            .unwrap();
+        assert!(hits.is_empty());
+    }
+
+    #[tokio::test]
+    async fn legacy_table_gains_scope_column() {
+        let tmp = TempDir::new().unwrap();
+        let db_dir = tmp.path().join("memory");
+        std::fs::create_dir_all(&db_dir).unwrap();
+        {
+            let conn = Connection::open(db_dir.join("brain.db")).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE memories (
+                    id TEXT PRIMARY KEY, key TEXT NOT NULL UNIQUE, content TEXT NOT NULL,
+                    category TEXT NOT NULL DEFAULT 'core', embedding BLOB,
+                    created_at TEXT NOT NULL, updated_at TEXT NOT NULL
+                );
+                INSERT INTO memories VALUES ('1', 'old', 'legacy fact', 'core', NULL, 't', 't');",
+            )
+            .unwrap();
+        }
+
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        let entry = mem.get("old").await.unwrap().unwrap();
+        assert_eq!(entry.session_id, None);
+        mem.reindex().await.unwrap();
+        assert_eq!(mem.recall("legacy", 5).await.unwrap().len(), 1);
+    }
+
+    fn backdate(mem: &SqliteMemory, key: &str, days: i64) {
+        let at = (Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+        mem.conn
+            .lock()
+            .unwrap()
+            .execute(
+                "UPDATE memories SET created_at = ?2, updated_at = ?2 WHERE key = ?1",
+                params![key, at],
+            )
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn recent_entry_outranks_stale_one() {
+        let (_tmp, mem) = temp_sqlite();
+        mem.store(
+            "editor_2024",
+            "preferred editor is vim",
+            MemoryCategory::Core,
+        )
+        .await
+        .unwrap();
+        mem.store(
+            "editor_now",
+            "preferred editor is now helix",
+            MemoryCategory::Core,
+        )
+        .await
+        .unwrap();
+        backdate(&mem, "editor_2024", 365);
+
+        let results = mem.recall("preferred editor", 5).await.unwrap();
+        assert_eq!(results[0].key, "editor_now");
+        let stale = results[1].score_breakdown.unwrap();
+        let fresh = results[0].score_breakdown.unwrap();
+        assert!(stale.recency < 0.01);
+        assert!(fresh.recency > 0.99);
+        assert_eq!(results[0].score, Some(fresh.score));
+    }
+
+    #[tokio::test]
+    async fn importance_and_category_shift_ranking() {
+        let (_tmp, mem) = temp_sqlite();
+        mem.store(
+            "chat",
+            "deploys happen on friday",
+            MemoryCategory::Conversation,
+        )
+        .await
+        .unwrap();
+        mem.store("rule", "deploys happen on friday", MemoryCategory::Core)
+            .await
+            .unwrap();
+        let results = mem.recall("deploys friday", 5).await.unwrap();
+        assert_eq!(results[0].key, "rule");
+
+        assert!(mem.set_importance("chat", 1.0).await.unwrap());
+        assert!(!mem.set_importance("missing", 1.0).await.unwrap());
+        let config = RankingConfig {
+            category_boosts: std::collections::HashMap::new(),
+            ..RankingConfig::default()
+        };
+        let mem = mem.with_ranking(config);
+        let results = mem.recall("deploys friday", 5).await.unwrap();
+        assert_eq!(results[0].key, "chat");
+        assert_eq!(results[0].importance, Some(1.0));
+    }
+
+    #[tokio::test]
+    async fn recall_reinforces_access_count() {
+        let (_tmp, mem) = temp_sqlite();
+        mem.store("a", "rust ownership rules", MemoryCategory::Core)
+            .await
+            .unwrap();
+        let first = mem.recall("rust", 5).await.unwrap();
+        assert!(first[0].score_breakdown.unwrap().access.abs() < f64::EPSILON);
+        let second = mem.recall("rust", 5).await.unwrap();
+        assert!(second[0].score_breakdown.unwrap().access > 0.0);
+
+        let count: u32 = mem
+            .conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT access_count FROM memories WHERE key = 'a'",
+                [],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[tokio::test]
+    async fn recall_query_filters_in_sql() {
+        let (_tmp, mem) = temp_sqlite();
+        let alice = MemoryScope::Session("abc".into());
+        mem.store("project_x_db", "uses postgres", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("project_y_db", "uses postgres too", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("old_note", "postgres upgrade", MemoryCategory::Daily)
+            .await
+            .unwrap();
+        mem.store_scoped(
+            "project_x_notes",
+            "postgres tuning",
+            MemoryCategory::Core,
+            &alice,
+        )
+        .await
+        .unwrap();
+        backdate(&mem, "old_note", 30);
+
+        let keys = |entries: Vec<MemoryEntry>| {
+            let mut keys: Vec<String> = entries.into_iter().map(|e| e.key).collect();
+            keys.sort();
+            keys
+        };
+        let base = RecallQuery::new("postgres", 10);
+
+        let core = RecallQuery {
+            category: Some(MemoryCategory::Core),
+            ..base.clone()
+        };
+        assert_eq!(
+            keys(mem.recall_query(&core).await.unwrap()),
+            ["project_x_db", "project_y_db"]
+        );
+
+        let recent = RecallQuery {
+            since: Some(Utc::now() - chrono::Duration::days(7)),
+            category: Some(MemoryCategory::Daily),
+            ..base.clone()
+        };
+        assert!(mem.recall_query(&recent).await.unwrap().is_empty());
+        let older = RecallQuery {
+            until: Some(Utc::now() - chrono::Duration::days(7)),
+            ..base.clone()
+        };
+        assert_eq!(keys(mem.recall_query(&older).await.unwrap()), ["old_note"]);
+
+        // Prefixes match the caller's key inside a scope, not the qualifier.
+        let prefixed = RecallQuery {
+            key_prefix: Some("project_x".into()),
+            scopes: vec![alice.clone()],
+            ..base.clone()
+        };
+        assert_eq!(
+            keys(mem.recall_query(&prefixed).await.unwrap()),
+            ["project_x_db", "session:abc/project_x_notes"]
+        );
+
+        let session = RecallQuery {
+            session: Some("session:abc".into()),
+            scopes: vec![alice],
+            ..base.clone()
+        };
+        assert_eq!(
+            keys(mem.recall_query(&session).await.unwrap()),
+            ["session:abc/project_x_notes"]
+        );
+
+        let strict = RecallQuery {
+            min_score: Some(f64::MAX),
+            ..base
+        };
+        assert!(mem.recall_query(&strict).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn filtered_vector_recall_skips_ann() {
+        let tmp = TempDir::new().unwrap();
+        let mut mem = open_with(tmp.path(), 64);
+        mem.ann_min_rows = 0;
+        for i in 0..20 {
+            mem.store(
+                &format!("chat_{i}"),
+                "rust ownership",
+                MemoryCategory::Conversation,
+            )
+            .await
+            .unwrap();
+        }
+        mem.store("fact", "rust ownership", MemoryCategory::Core)
+            .await
+            .unwrap();
+
+        let query = RecallQuery {
+            category: Some(MemoryCategory::Core),
+            ..RecallQuery::new("ownership", 2)
+        };
+        let hits = mem.recall_query(&query).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].key, "fact");
+    }
+
+    // ── Schema versions & interchange ────────────────────────────
+
+    #[tokio::test]
+    async fn schema_version_recorded_once() {
+        let (tmp, mem) = temp_sqlite();
+        drop(mem);
+        let _reopened = SqliteMemory::new(tmp.path()).unwrap();
+        let conn = Connection::open(tmp.path().join("memory").join("brain.db")).unwrap();
+        assert_eq!(SqliteMemory::schema_version(&conn).unwrap(), SCHEMA_VERSION);
+        let rows: i64 = conn
+            .query_row("SELECT COUNT(*) FROM schema_version", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(rows, i64::from(SCHEMA_VERSION));
+    }
+
+    #[tokio::test]
+    async fn legacy_database_is_migrated_forward() {
+        let tmp = TempDir::new().unwrap();
+        let dir = tmp.path().join("memory");
+        std::fs::create_dir_all(&dir).unwrap();
+        let conn = Connection::open(dir.join("brain.db")).unwrap();
+        // A pre-versioning database: base tables, no scope or ranking columns.
+        migrate_base_tables(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO memories VALUES ('1', 'lang', 'Prefers Rust', 'core', NULL,
+                '2025-01-01T00:00:00+00:00', '2025-01-01T00:00:00+00:00')",
+            [],
+        )
+        .unwrap();
+        drop(conn);
+
+        let mem = SqliteMemory::new(tmp.path()).unwrap();
+        assert!(mem.set_importance("lang", 0.9).await.unwrap());
+        assert_eq!(
+            mem.get("lang").await.unwrap().unwrap().importance,
+            Some(0.9)
+        );
+        let conn = mem.conn.lock().unwrap();
+        assert_eq!(SqliteMemory::schema_version(&conn).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn newer_schema_is_refused() {
+        let (tmp, mem) = temp_sqlite();
+        mem.conn
+            .lock()
+            .unwrap()
+            .execute(
+                "INSERT INTO schema_version (version, applied_at) VALUES (?1, 'later')",
+                params![SCHEMA_VERSION + 1],
+            )
+            .unwrap();
+        drop(mem);
+        let err = SqliteMemory::new(tmp.path()).err().unwrap();
+        assert!(err.to_string().contains("newer than this build"));
+    }
+
+    #[tokio::test]
+    async fn restore_reuses_embeddings_only_from_the_same_model() {
+        let src_dir = TempDir::new().unwrap();
+        let src = open_with(src_dir.path(), 8);
+        src.store("tea", "green tea every morning", MemoryCategory::Core)
+            .await
+            .unwrap();
+        src.set_importance("tea", 0.7).await.unwrap();
+        let snapshot = src.snapshot().await.unwrap();
+        assert_eq!(
+            snapshot.embedding_signature.as_deref(),
+            Some("word-hash//8")
+        );
+        let record = &snapshot.records[0];
+        assert_eq!(record.embedding.as_ref().map(Vec::len), Some(8));
+
+        // A vector the embedder would never produce shows whether it was reused.
+        let marked = Record {
+            embedding: Some(vec![0.5; 8]),
+            ..record.clone()
+        };
+        let dst_dir = TempDir::new().unwrap();
+        let dst = open_with(dst_dir.path(), 8);
+        dst.restore(&marked, snapshot.embedding_signature.as_deref())
+            .await
+            .unwrap();
+        let restored = dst.snapshot().await.unwrap().records.remove(0);
+        assert_eq!(restored.embedding, Some(vec![0.5; 8]));
+        assert_eq!(restored.created_at, record.created_at);
+        assert_eq!(restored.importance, Some(0.7));
+
+        dst.restore(&marked, Some("openai/text-embedding-3-small/8"))
+            .await
+            .unwrap();
+        let restored = dst.snapshot().await.unwrap().records.remove(0);
+        assert_eq!(restored.embedding, record.embedding);
+    }
+
+    #[tokio::test]
+    async fn export_import_round_trips_scope_ids_with_slashes() {
+        let scope = MemoryScope::User("slack:T01/alice%".into());
+        let src_dir = TempDir::new().unwrap();
+        let src = open_with(src_dir.path(), 8);
+        src.store_scoped(
+            "tea",
+            "green tea every morning",
+            MemoryCategory::Core,
+            &scope,
+        )
+        .await
+        .unwrap();
+
+        let snapshot = src.snapshot().await.unwrap();
+        assert_eq!(snapshot.records[0].key, "tea");
+        let raw = interchange::write(&snapshot, "sqlite").unwrap();
+        let parsed = interchange::parse(&raw).unwrap();
+        assert_eq!(parsed.records[0].key, "tea");
+
+        let dst_dir = TempDir::new().unwrap();
+        let dst = open_with(dst_dir.path(), 8);
+        let report = interchange::import(&dst, &parsed, ConflictStrategy::Skip)
+            .await
+            .unwrap();
+        assert_eq!(report.imported, 1);
+        let restored = dst.get(&scope.qualify("tea")).await.unwrap().unwrap();
+        assert_eq!(restored.key, scope.qualify("tea"));
+        assert_eq!(restored.content, "green tea every morning");
+        assert_eq!(dst.count().await.unwrap(), 1);
+
+        // Importing again finds the same entry instead of a double-qualified copy.
+        let again = interchange::import(&dst, &parsed, ConflictStrategy::Skip)
+            .await
+            .unwrap();
+        assert_eq!(again.unchanged, 1);
+    }
+
+    // ── Encryption at rest ───────────────────────────────────────
+
+    fn test_cipher() -> ContentCipher {
+        ContentCipher::new(&[3; 32], [5; 32])
+    }
+
+    fn raw_content(mem: &SqliteMemory, key: &str) -> String {
+        mem.conn
+            .lock()
+            .unwrap()
+            .query_row(
+                "SELECT content FROM memories WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn encrypted_content_is_sealed_but_searchable() {
+        let tmp = TempDir::new().unwrap();
+        let mem = SqliteMemory::new(tmp.path())
+            .unwrap()
+            .with_encryption(test_cipher());
+        mem.store("door", "Door PIN is 5512", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("tea", "green tea every morning", MemoryCategory::Core)
+            .await
+            .unwrap();
+
+        let raw = raw_content(&mem, "door");
+        assert!(ContentCipher::is_encrypted(&raw));
+        assert!(!raw.contains("5512"));
+        assert_eq!(
+            mem.get("door").await.unwrap().unwrap().content,
+            "Door PIN is 5512"
+        );
+
+        let hits = mem.recall("pin", 5).await.unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].content, "Door PIN is 5512");
+
+        mem.reindex().await.unwrap();
+        assert_eq!(mem.recall("morning tea", 5).await.unwrap()[0].key, "tea");
+
+        drop(mem);
+        let keyless = SqliteMemory::new(tmp.path()).unwrap();
+        assert!(keyless.get("door").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn migrate_encryption_both_ways() {
+        let tmp = TempDir::new().unwrap();
+        let plain = SqliteMemory::new(tmp.path()).unwrap();
+        plain
+            .store("door", "Door PIN is 5512", MemoryCategory::Core)
+            .await
+            .unwrap();
+        assert!(plain.migrate_encryption(true).is_err());
+        drop(plain);
+
+        let mem = SqliteMemory::new(tmp.path())
+            .unwrap()
+            .with_encryption(test_cipher());
+        assert_eq!(mem.migrate_encryption(true).unwrap(), 1);
+        assert_eq!(mem.migrate_encryption(true).unwrap(), 0);
+        assert!(ContentCipher::is_encrypted(&raw_content(&mem, "door")));
+        assert_eq!(mem.recall("5512", 5).await.unwrap().len(), 1);
+
+        assert_eq!(mem.migrate_encryption(false).unwrap(), 1);
+        assert_eq!(raw_content(&mem, "door"), "Door PIN is 5512");
+        assert_eq!(mem.recall("5512", 5).await.unwrap().len(), 1);
+    }
 }
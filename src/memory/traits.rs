@@ -1,4 +1,8 @@
+use super::graph::{GraphQuery, GraphUpdate, GraphUpsertReport, Subgraph};
+use super::interchange::{Record, Snapshot};
+use super::ranking::ScoreBreakdown;
 use async_trait::async_trait;
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
 
 /// A single memory entry
@@ -11,6 +15,12 @@ pub struct MemoryEntry {
     pub timestamp: String,
     pub session_id: Option<String>,
     pub score: Option<f64>,
+    /// Caller-assigned importance in [0, 1]; `None` when never set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub importance: Option<f64>,
+    /// How `score` was computed (recall only), for tuning ranking weights
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_breakdown: Option<ScoreBreakdown>,
 }
 
 /// Memory categories for organization
@@ -38,6 +48,290 @@ impl std::fmt::Display for MemoryCategory {
     }
 }
 
+impl std::str::FromStr for MemoryCategory {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.trim() {
+            "core" => Self::Core,
+            "daily" => Self::Daily,
+            "conversation" => Self::Conversation,
+            other => Self::Custom(other.to_string()),
+        })
+    }
+}
+
+/// Who can see a memory entry. Scoped entries are only recalled for the same
+/// user, channel or session; global entries are visible everywhere.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryScope {
+    /// Shared by every sender (CLI, operator-curated facts)
+    Global,
+    /// One person on one channel, e.g. `telegram:12345`
+    User(String),
+    /// Everyone on a channel, e.g. `discord`
+    Channel(String),
+    /// A single conversation session
+    Session(String),
+}
+
+impl MemoryScope {
+    /// Storage key for `key` in this scope. Scoped keys are prefixed so
+    /// the same key can exist once per scope; `/` and `%` in the scope id
+    /// are percent-encoded so the first `/` always ends the prefix.
+    pub fn qualify(&self, key: &str) -> String {
+        match self {
+            Self::Global => key.to_string(),
+            _ => format!("{}/{key}", self.key_prefix()),
+        }
+    }
+
+    /// Inverse of [`qualify`](Self::qualify): the key as the caller stored it.
+    pub fn unqualify<'a>(&self, key: &'a str) -> &'a str {
+        match self {
+            Self::Global => key,
+            _ => key
+                .strip_prefix(&self.key_prefix())
+                .and_then(|rest| rest.strip_prefix('/'))
+                .unwrap_or(key),
+        }
+    }
+
+    /// Split a stored key into its scope and the caller's key.
+    pub fn split_key(key: &str) -> (Self, &str) {
+        key.split_once('/')
+            .and_then(|(label, rest)| {
+                label
+                    .replace("%2F", "/")
+                    .replace("%25", "%")
+                    .parse()
+                    .ok()
+                    .filter(|s| *s != Self::Global)
+                    .map(|s| (s, rest))
+            })
+            .unwrap_or((Self::Global, key))
+    }
+
+    /// Scope label with its id escaped for use in a qualified key.
+    fn key_prefix(&self) -> String {
+        self.to_string().replace('%', "%25").replace('/', "%2F")
+    }
+
+    /// Scope of a stored entry (`session_id` holds the scope label).
+    pub fn of(entry: &MemoryEntry) -> Self {
+        entry
+            .session_id
+            .as_deref()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(Self::Global)
+    }
+}
+
+impl std::fmt::Display for MemoryScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Global => write!(f, "global"),
+            Self::User(id) => write!(f, "user:{id}"),
+            Self::Channel(id) => write!(f, "channel:{id}"),
+            Self::Session(id) => write!(f, "session:{id}"),
+        }
+    }
+}
+
+impl std::str::FromStr for MemoryScope {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s == "global" {
+            return Ok(Self::Global);
+        }
+        let (kind, id) = s
+            .split_once(':')
+            .filter(|(_, id)| !id.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Invalid memory scope '{s}'"))?;
+        match kind {
+            "user" => Ok(Self::User(id.to_string())),
+            "channel" => Ok(Self::Channel(id.to_string())),
+            "session" => Ok(Self::Session(id.to_string())),
+            _ => anyhow::bail!("Invalid memory scope '{s}' (expected user:, channel: or session:)"),
+        }
+    }
+}
+
+/// Structured recall: a search plus filters that must all hold.
+///
+/// Visibility still follows `scopes` as in [`Memory::recall_scoped`]; the
+/// other fields only narrow what is visible.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RecallQuery {
+    pub query: String,
+    pub limit: usize,
+    /// Visible scopes besides global, most specific first
+    pub scopes: Vec<MemoryScope>,
+    pub category: Option<MemoryCategory>,
+    /// Earliest creation time (inclusive)
+    pub since: Option<DateTime<Utc>>,
+    /// Latest creation time (inclusive)
+    pub until: Option<DateTime<Utc>>,
+    /// Prefix of the key, without its scope qualifier
+    pub key_prefix: Option<String>,
+    /// Scope label (e.g. `session:abc`) the entry was stored under. Unscoped
+    /// entries match on key prefix, as conversation keys start with it.
+    pub session: Option<String>,
+    /// Minimum final (ranked) score
+    pub min_score: Option<f64>,
+}
+
+impl RecallQuery {
+    pub fn new(query: impl Into<String>, limit: usize) -> Self {
+        Self {
+            query: query.into(),
+            limit,
+            ..Self::default()
+        }
+    }
+
+    /// Whether any filter beyond visibility is set.
+    pub fn is_filtered(&self) -> bool {
+        self.category.is_some()
+            || self.since.is_some()
+            || self.until.is_some()
+            || self.key_prefix.is_some()
+            || self.session.is_some()
+            || self.min_score.is_some()
+    }
+
+    /// Client-side check of every filter except visibility.
+    pub fn matches(&self, entry: &MemoryEntry) -> bool {
+        if self.category.as_ref().is_some_and(|c| c != &entry.category) {
+            return false;
+        }
+        if let Some(prefix) = &self.key_prefix {
+            if !MemoryScope::of(entry)
+                .unqualify(&entry.key)
+                .starts_with(prefix.as_str())
+            {
+                return false;
+            }
+        }
+        if let Some(session) = &self.session {
+            let matched = match &entry.session_id {
+                Some(id) => id == session,
+                None => entry.key.starts_with(&format!("{session}_")),
+            };
+            if !matched {
+                return false;
+            }
+        }
+        if self.since.is_some() || self.until.is_some() {
+            let Some(created) = entry_time(entry) else {
+                return false;
+            };
+            if self.since.is_some_and(|since| created < since)
+                || self.until.is_some_and(|until| created > until)
+            {
+                return false;
+            }
+        }
+        self.min_score
+            .map_or(true, |min| entry.score.is_some_and(|s| s >= min))
+    }
+}
+
+/// Creation time of an entry: an RFC 3339 timestamp (SQLite) or the start of
+/// the day of a `YYYY-MM-DD` file stem (Markdown daily logs).
+fn entry_time(entry: &MemoryEntry) -> Option<DateTime<Utc>> {
+    if let Ok(at) = DateTime::parse_from_rfc3339(&entry.timestamp) {
+        return Some(at.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(entry.timestamp.get(..10)?, "%Y-%m-%d").ok()?;
+    day_bound(date, false)
+}
+
+/// Start (or last millisecond, with `end_of_day`) of a local calendar day.
+pub fn day_bound(date: NaiveDate, end_of_day: bool) -> Option<DateTime<Utc>> {
+    let time = if end_of_day {
+        NaiveTime::from_hms_milli_opt(23, 59, 59, 999)?
+    } else {
+        NaiveTime::MIN
+    };
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+/// Parse a time bound: RFC 3339, `YYYY-MM-DD` (local day; the end of it when
+/// `end_of_day`), or a relative age such as `7d` or `12h` before now.
+pub fn parse_time_bound(raw: &str, end_of_day: bool) -> anyhow::Result<DateTime<Utc>> {
+    let raw = raw.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+        return day_bound(date, end_of_day)
+            .ok_or_else(|| anyhow::anyhow!("invalid local date '{raw}'"));
+    }
+    let split = raw.len().saturating_sub(1);
+    let (amount, unit) = raw.split_at(split);
+    let amount: i64 = amount.parse().map_err(|_| {
+        anyhow::anyhow!("invalid time '{raw}', expected YYYY-MM-DD, RFC 3339 or e.g. 7d")
+    })?;
+    let age = match unit {
+        "d" => Duration::days(amount),
+        "h" => Duration::hours(amount),
+        "w" => Duration::weeks(amount),
+        _ => anyhow::bail!("invalid time '{raw}', expected YYYY-MM-DD, RFC 3339 or e.g. 7d"),
+    };
+    Ok(Utc::now() - age)
+}
+
+/// Apply scope precedence to recall candidates.
+///
+/// `scopes` lists the visible scopes, most specific first; global comes last.
+/// Entries from other scopes are dropped. When the same key exists in several
+/// visible scopes only the most specific copy is kept, so a user's own
+/// "timezone" overrides a global one. The rest is ordered by score, with
+/// ties going to the more specific scope.
+pub fn resolve_scopes(
+    entries: Vec<MemoryEntry>,
+    scopes: &[MemoryScope],
+    limit: usize,
+) -> Vec<MemoryEntry> {
+    let rank = |scope: &MemoryScope| match scope {
+        MemoryScope::Global => Some(scopes.len()),
+        other => scopes.iter().position(|s| s == other),
+    };
+
+    let mut best: Vec<(usize, MemoryEntry)> = Vec::new();
+    let mut by_key: std::collections::HashMap<String, usize> = std::collections::HashMap::new();
+    for entry in entries {
+        let scope = MemoryScope::of(&entry);
+        let Some(r) = rank(&scope) else {
+            continue;
+        };
+        let base = scope.unqualify(&entry.key).to_string();
+        match by_key.get(&base) {
+            Some(&i) if best[i].0 > r => best[i] = (r, entry),
+            Some(_) => {}
+            None => {
+                by_key.insert(base, best.len());
+                best.push((r, entry));
+            }
+        }
+    }
+
+    best.sort_by(|(ra, a), (rb, b)| {
+        b.score
+            .partial_cmp(&a.score)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(ra.cmp(rb))
+    });
+    best.into_iter().take(limit).map(|(_, e)| e).collect()
+}
+
 /// Core memory trait — implement for any persistence backend
 #[async_trait]
 pub trait Memory: Send + Sync {
@@ -51,6 +345,112 @@ pub trait Memory: Send + Sync {
     /// Recall memories matching a query (keyword search)
     async fn recall(&self, query: &str, limit: usize) -> anyhow::Result<Vec<MemoryEntry>>;
 
+    /// Store a memory entry visible only within `scope`.
+    /// Backends without scope support store it globally.
+    async fn store_scoped(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        scope: &MemoryScope,
+    ) -> anyhow::Result<()> {
+        let _ = scope;
+        self.store(key, content, category).await
+    }
+
+    /// Recall memories visible from `scopes` (most specific first) plus
+    /// global ones. A key stored in several scopes resolves to the most
+    /// specific copy (see [`resolve_scopes`]).
+    async fn recall_scoped(
+        &self,
+        query: &str,
+        limit: usize,
+        scopes: &[MemoryScope],
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        let _ = scopes;
+        self.recall(query, limit).await
+    }
+
+    /// Recall with structured filters. The default over-fetches through
+    /// `recall_scoped` and filters client-side; backends with an index
+    /// should push the filters down instead.
+    async fn recall_query(&self, query: &RecallQuery) -> anyhow::Result<Vec<MemoryEntry>> {
+        let fetch = if query.is_filtered() {
+            query.limit.saturating_mul(4)
+        } else {
+            query.limit
+        };
+        Ok(self
+            .recall_scoped(&query.query, fetch, &query.scopes)
+            .await?
+            .into_iter()
+            .filter(|e| query.matches(e))
+            .take(query.limit)
+            .collect())
+    }
+
+    /// Set how important an entry is (0–1) for recall ranking. Returns false
+    /// when the key doesn't exist or the backend doesn't track importance.
+    async fn set_importance(&self, key: &str, importance: f64) -> anyhow::Result<bool> {
+        let _ = (key, importance);
+        Ok(false)
+    }
+
+    /// Every entry, in interchange form, for `memory export`. The default
+    /// walks `list` and carries no embeddings.
+    async fn snapshot(&self) -> anyhow::Result<Snapshot> {
+        Ok(Snapshot {
+            embedding_signature: None,
+            records: self
+                .list(None)
+                .await?
+                .iter()
+                .map(Record::from_entry)
+                .collect(),
+        })
+    }
+
+    /// Write one exported record, replacing any entry under the same key.
+    /// Backends that keep timestamps or vectors should preserve the record's;
+    /// `embedding_signature` says which model produced `record.embedding`.
+    async fn restore(
+        &self,
+        record: &Record,
+        embedding_signature: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let _ = embedding_signature;
+        let scope = record.scope()?;
+        self.store_scoped(
+            &record.key,
+            &record.content,
+            record.category.clone(),
+            &scope,
+        )
+        .await?;
+        if let Some(importance) = record.importance {
+            self.set_importance(&scope.qualify(&record.key), importance)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Merge entities, relations and observations into the knowledge graph,
+    /// visible from `scope`. Backends without a graph refuse.
+    async fn graph_upsert(
+        &self,
+        update: &GraphUpdate,
+        scope: &MemoryScope,
+    ) -> anyhow::Result<GraphUpsertReport> {
+        let _ = (update, scope);
+        anyhow::bail!("the {} memory backend has no knowledge graph", self.name())
+    }
+
+    /// Neighborhood of the queried entities; empty without a graph.
+    async fn graph_query(&self, query: &GraphQuery) -> anyhow::Result<Subgraph> {
+        let _ = query;
+        Ok(Subgraph::default())
+    }
+
     /// Get a specific memory by key
     async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>>;
 
@@ -66,3 +466,224 @@ pub trait Memory: Send + Sync {
     /// Health check
     async fn health_check(&self) -> bool;
 }
+
+/// Shared backends (e.g. one also driven by a background task) are memories too.
+#[async_trait]
+impl<T: Memory + ?Sized> Memory for std::sync::Arc<T> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+    ) -> anyhow::Result<()> {
+        (**self).store(key, content, category).await
+    }
+
+    async fn recall(&self, query: &str, limit: usize) -> anyhow::Result<Vec<MemoryEntry>> {
+        (**self).recall(query, limit).await
+    }
+
+    async fn store_scoped(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        scope: &MemoryScope,
+    ) -> anyhow::Result<()> {
+        (**self).store_scoped(key, content, category, scope).await
+    }
+
+    async fn recall_scoped(
+        &self,
+        query: &str,
+        limit: usize,
+        scopes: &[MemoryScope],
+    ) -> anyhow::Result<Vec<MemoryEntry>> {
+        (**self).recall_scoped(query, limit, scopes).await
+    }
+
+    async fn recall_query(&self, query: &RecallQuery) -> anyhow::Result<Vec<MemoryEntry>> {
+        (**self).recall_query(query).await
+    }
+
+    async fn set_importance(&self, key: &str, importance: f64) -> anyhow::Result<bool> {
+        (**self).set_importance(key, importance).await
+    }
+
+    async fn snapshot(&self) -> anyhow::Result<Snapshot> {
+        (**self).snapshot().await
+    }
+
+    async fn restore(
+        &self,
+        record: &Record,
+        embedding_signature: Option<&str>,
+    ) -> anyhow::Result<()> {
+        (**self).restore(record, embedding_signature).await
+    }
+
+    async fn graph_upsert(
+        &self,
+        update: &GraphUpdate,
+        scope: &MemoryScope,
+    ) -> anyhow::Result<GraphUpsertReport> {
+        (**self).graph_upsert(update, scope).await
+    }
+
+    async fn graph_query(&self, query: &GraphQuery) -> anyhow::Result<Subgraph> {
+        (**self).graph_query(query).await
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<MemoryEntry>> {
+        (**self).get(key).await
+    }
+
+    async fn list(&self, category: Option<&MemoryCategory>) -> anyhow::Result<Vec<MemoryEntry>> {
+        (**self).list(category).await
+    }
+
+    async fn forget(&self, key: &str) -> anyhow::Result<bool> {
+        (**self).forget(key).await
+    }
+
+    async fn count(&self) -> anyhow::Result<usize> {
+        (**self).count().await
+    }
+
+    async fn health_check(&self) -> bool {
+        (**self).health_check().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, scope: &MemoryScope, score: f64) -> MemoryEntry {
+        MemoryEntry {
+            id: key.into(),
+            key: scope.qualify(key),
+            content: format!("{key} in {scope}"),
+            category: MemoryCategory::Core,
+            timestamp: String::new(),
+            session_id: (*scope != MemoryScope::Global).then(|| scope.to_string()),
+            score: Some(score),
+            importance: None,
+            score_breakdown: None,
+        }
+    }
+
+    #[test]
+    fn scope_label_roundtrip() {
+        for scope in [
+            MemoryScope::Global,
+            MemoryScope::User("telegram:42".into()),
+            MemoryScope::Channel("discord".into()),
+            MemoryScope::Session("abc".into()),
+        ] {
+            assert_eq!(scope.to_string().parse::<MemoryScope>().unwrap(), scope);
+        }
+        assert!("team:x".parse::<MemoryScope>().is_err());
+        assert!("user:".parse::<MemoryScope>().is_err());
+    }
+
+    #[test]
+    fn qualify_and_unqualify() {
+        let user = MemoryScope::User("telegram:42".into());
+        assert_eq!(user.qualify("tz"), "user:telegram:42/tz");
+        assert_eq!(user.unqualify("user:telegram:42/tz"), "tz");
+        assert_eq!(MemoryScope::Global.qualify("tz"), "tz");
+        assert_eq!(MemoryScope::split_key("user:telegram:42/tz"), (user, "tz"));
+        assert_eq!(
+            MemoryScope::split_key("doc:runbooks/a.md#0"),
+            (MemoryScope::Global, "doc:runbooks/a.md#0")
+        );
+    }
+
+    #[test]
+    fn qualify_escapes_slashes_in_scope_ids() {
+        let hook = MemoryScope::User("dingtalk:https://oapi.example/robot?s=1%2".into());
+        let stored = hook.qualify("notes/today");
+        assert_eq!(
+            stored,
+            "user:dingtalk:https:%2F%2Foapi.example%2Frobot?s=1%252/notes/today"
+        );
+        assert_eq!(hook.unqualify(&stored), "notes/today");
+        assert_eq!(MemoryScope::split_key(&stored), (hook, "notes/today"));
+    }
+
+    #[test]
+    fn resolve_scopes_prefers_specific_and_drops_invisible() {
+        let alice = MemoryScope::User("alice".into());
+        let bob = MemoryScope::User("bob".into());
+        let entries = vec![
+            entry("tz", &MemoryScope::Global, 0.9),
+            entry("tz", &alice, 0.5),
+            entry("secret", &bob, 1.0),
+            entry("lang", &MemoryScope::Global, 0.7),
+        ];
+
+        let resolved = resolve_scopes(entries, std::slice::from_ref(&alice), 10);
+        let keys: Vec<&str> = resolved.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["lang", "user:alice/tz"]);
+    }
+
+    #[test]
+    fn resolve_scopes_global_only_view() {
+        let alice = MemoryScope::User("alice".into());
+        let entries = vec![
+            entry("a", &alice, 1.0),
+            entry("b", &MemoryScope::Global, 0.1),
+        ];
+        let resolved = resolve_scopes(entries, &[], 10);
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].key, "b");
+    }
+
+    #[test]
+    fn parse_time_bound_formats() {
+        let at = parse_time_bound("2025-03-01T12:00:00Z", false).unwrap();
+        assert_eq!(at.to_rfc3339(), "2025-03-01T12:00:00+00:00");
+
+        let start = parse_time_bound("2025-03-01", false).unwrap();
+        let end = parse_time_bound("2025-03-01", true).unwrap();
+        assert_eq!(end - start, Duration::milliseconds(86_399_999));
+
+        let week = parse_time_bound("7d", false).unwrap();
+        let age = Utc::now() - week;
+        assert!(age >= Duration::days(7) && age < Duration::days(7) + Duration::minutes(1));
+        assert!(parse_time_bound("2w", false).is_ok());
+        assert!(parse_time_bound("soon", false).is_err());
+        assert!(parse_time_bound("5y", false).is_err());
+    }
+
+    #[test]
+    fn recall_query_matches_filters() {
+        let scope = MemoryScope::User("alice".into());
+        let mut e = entry("project_x", &scope, 0.5);
+        e.timestamp = "2025-03-01T12:00:00+00:00".into();
+
+        assert!(RecallQuery::new("q", 5).matches(&e));
+        let q = |f: fn(&mut RecallQuery)| {
+            let mut q = RecallQuery::new("q", 5);
+            f(&mut q);
+            q
+        };
+        assert!(q(|q| q.key_prefix = Some("project".into())).matches(&e));
+        assert!(!q(|q| q.key_prefix = Some("user:".into())).matches(&e));
+        assert!(q(|q| q.session = Some("user:alice".into())).matches(&e));
+        assert!(!q(|q| q.session = Some("user:bob".into())).matches(&e));
+        assert!(!q(|q| q.category = Some(MemoryCategory::Daily)).matches(&e));
+        assert!(!q(|q| q.min_score = Some(0.6)).matches(&e));
+        assert!(q(|q| q.until = parse_time_bound("2025-03-01", true).ok()).matches(&e));
+        assert!(!q(|q| q.since = parse_time_bound("2025-03-02", false).ok()).matches(&e));
+
+        // Markdown daily entries carry a date stem instead of a timestamp.
+        e.timestamp = "2025-03-01".into();
+        assert!(q(|q| q.since = parse_time_bound("2025-03-01", false).ok()).matches(&e));
+    }
+}
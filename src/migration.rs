@@ -1,33 +1,123 @@
 use crate::config::Config;
+use crate::memory::chunker::chunk_markdown;
+use crate::memory::consolidate::sanitize_key;
+use crate::memory::interchange::Record;
 use crate::memory::{MarkdownMemory, Memory, MemoryCategory, SqliteMemory};
+use crate::providers::Provider;
 use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
 use directories::UserDirs;
 use rusqlite::{Connection, OpenFlags, OptionalExtension};
-use std::collections::HashSet;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+/// Category of notes imported by `migrate markdown-dir`.
+const NOTES_CATEGORY: &str = "notes";
+
+/// Transcript characters sent to the model per conversation when distilling.
+const MAX_DISTILL_CHARS: usize = 24_000;
+
+const DISTILL_PROMPT: &str = "You are importing someone's chat history into their \
+assistant's long-term memory. From the conversation, extract durable facts about the \
+user: preferences, background, projects, decisions, people and recurring needs. Skip \
+general knowledge the assistant explained, one-off tasks and small talk.\n\n\
+Reply with JSON only:\n\
+{\"facts\": [{\"key\": \"snake_case_topic\", \"fact\": \"one concise sentence\"}]}\n\n\
+Return {\"facts\": []} when nothing is worth keeping.";
+
 #[derive(Debug, Clone)]
 struct SourceEntry {
     key: String,
     content: String,
     category: MemoryCategory,
+    /// Original creation time (RFC 3339), kept on import when known
+    created_at: Option<String>,
 }
 
 #[derive(Debug, Default)]
 struct MigrationStats {
     from_sqlite: usize,
     from_markdown: usize,
+    conversations: usize,
+    messages: usize,
+    distill_failures: usize,
     imported: usize,
     skipped_unchanged: usize,
     renamed_conflicts: usize,
 }
 
+/// A chat from an exported history, oldest message first.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Conversation {
+    id: String,
+    title: String,
+    created_at: Option<String>,
+    /// (speaker, text)
+    messages: Vec<(String, String)>,
+}
+
+/// Chat history export formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ChatExport {
+    ChatGpt,
+    Claude,
+    Jsonl,
+}
+
+impl ChatExport {
+    /// Key prefix and backup label.
+    fn label(self) -> &'static str {
+        match self {
+            Self::ChatGpt => "chatgpt",
+            Self::Claude => "claude",
+            Self::Jsonl => "jsonl",
+        }
+    }
+
+    fn display_name(self) -> &'static str {
+        match self {
+            Self::ChatGpt => "ChatGPT",
+            Self::Claude => "Claude",
+            Self::Jsonl => "JSONL",
+        }
+    }
+
+    fn read(self, source: &Path) -> Result<Vec<Conversation>> {
+        match self {
+            Self::ChatGpt => read_chatgpt_export(&export_file(source)?),
+            Self::Claude => read_claude_export(&export_file(source)?),
+            Self::Jsonl => read_jsonl_export(source),
+        }
+    }
+}
+
 pub async fn handle_command(command: crate::MigrateCommands, config: &Config) -> Result<()> {
     match command {
         crate::MigrateCommands::Openclaw { source, dry_run } => {
             migrate_openclaw_memory(config, source, dry_run).await
         }
+        crate::MigrateCommands::Chatgpt {
+            source,
+            dry_run,
+            distill,
+        } => migrate_chat_export(config, ChatExport::ChatGpt, &source, dry_run, distill).await,
+        crate::MigrateCommands::Claude {
+            source,
+            dry_run,
+            distill,
+        } => migrate_chat_export(config, ChatExport::Claude, &source, dry_run, distill).await,
+        crate::MigrateCommands::Jsonl {
+            source,
+            dry_run,
+            distill,
+        } => migrate_chat_export(config, ChatExport::Jsonl, &source, dry_run, distill).await,
+        crate::MigrateCommands::MarkdownDir { source, dry_run } => {
+            migrate_markdown_dir(config, &source, dry_run).await
+        }
     }
 }
 
@@ -72,16 +162,37 @@ async fn migrate_openclaw_memory(
         return Ok(());
     }
 
-    if let Some(backup_dir) = backup_target_memory(&config.workspace_dir)? {
+    if let Some(backup_dir) = backup_target_memory(&config.workspace_dir, "openclaw")? {
         println!("🛟 Backup created: {}", backup_dir.display());
     }
 
     let memory = target_memory_backend(config)?;
+    import_entries(memory.as_ref(), entries, "openclaw", &mut stats).await?;
 
+    println!("✅ OpenClaw memory migration complete");
+    println!("  Source: {}", source_workspace.display());
+    println!("  Target: {}", config.workspace_dir.display());
+    println!("  Imported:         {}", stats.imported);
+    println!("  Skipped unchanged:{}", stats.skipped_unchanged);
+    println!("  Renamed conflicts:{}", stats.renamed_conflicts);
+    println!("  Source sqlite rows:{}", stats.from_sqlite);
+    println!("  Source markdown:   {}", stats.from_markdown);
+
+    Ok(())
+}
+
+/// Write entries, skipping unchanged keys and renaming conflicting ones
+/// (`<key>__<label>_<n>`).
+async fn import_entries(
+    memory: &dyn Memory,
+    entries: Vec<SourceEntry>,
+    label: &str,
+    stats: &mut MigrationStats,
+) -> Result<()> {
     for (idx, entry) in entries.into_iter().enumerate() {
         let mut key = entry.key.trim().to_string();
         if key.is_empty() {
-            key = format!("openclaw_{idx}");
+            key = format!("{label}_{idx}");
         }
 
         if let Some(existing) = memory.get(&key).await? {
@@ -90,23 +201,169 @@ async fn migrate_openclaw_memory(
                 continue;
             }
 
-            let renamed = next_available_key(memory.as_ref(), &key).await?;
+            let renamed = next_available_key(memory, &key, label).await?;
             key = renamed;
             stats.renamed_conflicts += 1;
         }
 
-        memory.store(&key, &entry.content, entry.category).await?;
+        match entry.created_at {
+            // Keep the original date so recency ranking treats old chats as old.
+            Some(created_at) => {
+                let record = Record {
+                    key,
+                    content: entry.content,
+                    category: entry.category,
+                    scope: None,
+                    created_at,
+                    updated_at: None,
+                    importance: None,
+                    embedding: None,
+                };
+                memory.restore(&record, None).await?;
+            }
+            None => memory.store(&key, &entry.content, entry.category).await?,
+        }
         stats.imported += 1;
     }
+    Ok(())
+}
 
-    println!("✅ OpenClaw memory migration complete");
-    println!("  Source: {}", source_workspace.display());
+async fn migrate_chat_export(
+    config: &Config,
+    export: ChatExport,
+    source: &Path,
+    dry_run: bool,
+    distill: bool,
+) -> Result<()> {
+    if !source.exists() {
+        bail!(
+            "{} export not found at {}",
+            export.display_name(),
+            source.display()
+        );
+    }
+
+    let conversations = export.read(source)?;
+    let mut stats = MigrationStats {
+        conversations: conversations.len(),
+        messages: conversations.iter().map(|c| c.messages.len()).sum(),
+        ..MigrationStats::default()
+    };
+    if conversations.is_empty() {
+        println!("No conversations found in {}", source.display());
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("🔎 Dry run: {} import preview", export.display_name());
+        println!("  Source: {}", source.display());
+        println!("  Target: {}", config.workspace_dir.display());
+        println!("  Conversations: {}", stats.conversations);
+        println!("  Messages:      {}", stats.messages);
+        if distill {
+            println!("  Each conversation would be distilled into core facts by the model.");
+        } else {
+            let entries = conversation_entries(
+                export.label(),
+                &conversations,
+                config.memory.chunk_max_tokens,
+            );
+            println!("  Transcript entries: {}", entries.len());
+        }
+        println!();
+        println!("Run without --dry-run to import.");
+        return Ok(());
+    }
+
+    let entries = if distill {
+        let provider = crate::providers::create_resilient_provider_with_options(
+            config.default_provider.as_deref().unwrap_or("openrouter"),
+            config.api_key.as_deref(),
+            &config.reliability,
+            &crate::providers::ProviderOptions::from_config(config),
+        )?;
+        let model = config
+            .memory
+            .consolidation
+            .model
+            .clone()
+            .or_else(|| config.default_model.clone())
+            .unwrap_or_else(|| "anthropic/claude-sonnet-4-20250514".into());
+        distill_conversations(
+            provider.as_ref(),
+            &model,
+            export.label(),
+            &conversations,
+            &mut stats,
+        )
+        .await?
+    } else {
+        conversation_entries(
+            export.label(),
+            &conversations,
+            config.memory.chunk_max_tokens,
+        )
+    };
+
+    if let Some(backup_dir) = backup_target_memory(&config.workspace_dir, export.label())? {
+        println!("🛟 Backup created: {}", backup_dir.display());
+    }
+    let memory = target_memory_backend(config)?;
+    import_entries(memory.as_ref(), entries, export.label(), &mut stats).await?;
+
+    println!("✅ {} import complete", export.display_name());
+    println!("  Source: {}", source.display());
     println!("  Target: {}", config.workspace_dir.display());
+    println!("  Conversations:    {}", stats.conversations);
+    println!("  Imported:         {}", stats.imported);
+    println!("  Skipped unchanged:{}", stats.skipped_unchanged);
+    println!("  Renamed conflicts:{}", stats.renamed_conflicts);
+    if distill && stats.distill_failures > 0 {
+        println!("  Distill failures: {}", stats.distill_failures);
+    }
+
+    Ok(())
+}
+
+async fn migrate_markdown_dir(config: &Config, source: &Path, dry_run: bool) -> Result<()> {
+    if !source.is_dir() {
+        bail!("Notes folder not found at {}", source.display());
+    }
+    if paths_equal(source, &config.workspace_dir) {
+        bail!("Source folder matches current ZeroClaw workspace; refusing self-migration");
+    }
+
+    let mut stats = MigrationStats::default();
+    let entries = read_markdown_dir(source, config.memory.chunk_max_tokens, &mut stats)?;
+    if entries.is_empty() {
+        println!("No markdown notes found in {}", source.display());
+        return Ok(());
+    }
+
+    if dry_run {
+        println!("🔎 Dry run: markdown notes import preview");
+        println!("  Source: {}", source.display());
+        println!("  Target: {}", config.workspace_dir.display());
+        println!("  Notes:   {}", stats.from_markdown);
+        println!("  Entries: {}", entries.len());
+        println!();
+        println!("Run without --dry-run to import these entries.");
+        return Ok(());
+    }
+
+    if let Some(backup_dir) = backup_target_memory(&config.workspace_dir, "notes")? {
+        println!("🛟 Backup created: {}", backup_dir.display());
+    }
+    let memory = target_memory_backend(config)?;
+    import_entries(memory.as_ref(), entries, "notes", &mut stats).await?;
+
+    println!("✅ Markdown notes import complete");
+    println!("  Source: {}", source.display());
+    println!("  Target: {}", config.workspace_dir.display());
+    println!("  Notes:            {}", stats.from_markdown);
     println!("  Imported:         {}", stats.imported);
     println!("  Skipped unchanged:{}", stats.skipped_unchanged);
     println!("  Renamed conflicts:{}", stats.renamed_conflicts);
-    println!("  Source sqlite rows:{}", stats.from_sqlite);
-    println!("  Source markdown:   {}", stats.from_markdown);
 
     Ok(())
 }
@@ -203,6 +460,7 @@ fn read_openclaw_sqlite_entries(db_path: &Path) -> Result<Vec<SourceEntry>> {
             key: normalize_key(&key, idx),
             content: content.trim().to_string(),
             category: parse_category(&category_raw),
+            created_at: None,
         });
 
         idx += 1;
@@ -282,6 +540,7 @@ fn parse_markdown_file(
             key,
             content: text,
             category: default_category.clone(),
+            created_at: None,
         });
     }
 
@@ -305,6 +564,473 @@ fn parse_structured_memory_line(line: &str) -> Option<(&str, &str)> {
     Some((key, value))
 }
 
+/// `conversations.json`, given the file or the unzipped export folder.
+fn export_file(source: &Path) -> Result<PathBuf> {
+    if source.is_dir() {
+        let file = source.join("conversations.json");
+        if !file.exists() {
+            bail!("No conversations.json in {}", source.display());
+        }
+        return Ok(file);
+    }
+    Ok(source.to_path_buf())
+}
+
+fn read_json_array(path: &Path) -> Result<Vec<Value>> {
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    match serde_json::from_str(&raw)
+        .with_context(|| format!("{} is not valid JSON", path.display()))?
+    {
+        Value::Array(items) => Ok(items),
+        _ => bail!(
+            "{} should contain a JSON array of conversations",
+            path.display()
+        ),
+    }
+}
+
+/// RFC 3339 from an ISO string or Unix seconds.
+fn timestamp_of(value: Option<&Value>) -> Option<String> {
+    match value? {
+        Value::String(s) => DateTime::parse_from_rfc3339(s)
+            .ok()
+            .map(|t| t.with_timezone(&Local).to_rfc3339()),
+        Value::Number(n) => {
+            #[allow(clippy::cast_possible_truncation)]
+            let secs = n.as_f64()? as i64;
+            DateTime::from_timestamp(secs, 0).map(|t| t.with_timezone(&Local).to_rfc3339())
+        }
+        _ => None,
+    }
+}
+
+fn str_field<'a>(value: &'a Value, names: &[&str]) -> Option<&'a str> {
+    names
+        .iter()
+        .find_map(|name| value.get(*name).and_then(Value::as_str))
+}
+
+/// "User" / "Assistant" for chat roles; `None` for system and tool turns.
+fn speaker(role: &str) -> Option<String> {
+    match role.trim().to_ascii_lowercase().as_str() {
+        "user" | "human" => Some("User".into()),
+        "assistant" | "ai" | "bot" | "model" => Some("Assistant".into()),
+        "system" | "tool" | "function" | "" => None,
+        other => Some(other.to_string()),
+    }
+}
+
+/// ChatGPT `conversations.json`: each conversation is a tree of messages in
+/// `mapping`; the visible thread runs from `current_node` up through parents.
+fn read_chatgpt_export(path: &Path) -> Result<Vec<Conversation>> {
+    let mut conversations = Vec::new();
+    for (idx, item) in read_json_array(path)?.iter().enumerate() {
+        let Some(mapping) = item.get("mapping").and_then(Value::as_object) else {
+            continue;
+        };
+
+        let mut thread = Vec::new();
+        let mut node = item.get("current_node").and_then(Value::as_str);
+        while let Some(id) = node {
+            let Some(entry) = mapping.get(id) else { break };
+            thread.push(entry);
+            node = entry.get("parent").and_then(Value::as_str);
+        }
+        thread.reverse();
+        if thread.is_empty() {
+            // No current_node: fall back to message time order.
+            thread = mapping.values().collect();
+            thread.sort_by(|a, b| {
+                let at = |n: &Value| {
+                    n.pointer("/message/create_time")
+                        .and_then(Value::as_f64)
+                        .unwrap_or(0.0)
+                };
+                at(a).total_cmp(&at(b))
+            });
+        }
+
+        let messages = thread
+            .iter()
+            .filter_map(|entry| {
+                let message = entry.get("message")?;
+                let speaker = speaker(message.pointer("/author/role")?.as_str()?)?;
+                let content = message.get("content")?;
+                let text = match content.get("parts").and_then(Value::as_array) {
+                    Some(parts) => parts
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                    None => str_field(content, &["text"])?.to_string(),
+                };
+                let text = text.trim();
+                (!text.is_empty()).then(|| (speaker, text.to_string()))
+            })
+            .collect::<Vec<_>>();
+        if messages.is_empty() {
+            continue;
+        }
+
+        conversations.push(Conversation {
+            id: str_field(item, &["conversation_id", "id"])
+                .map_or_else(|| idx.to_string(), str::to_string),
+            title: str_field(item, &["title"]).unwrap_or_default().to_string(),
+            created_at: timestamp_of(item.get("create_time")),
+            messages,
+        });
+    }
+    Ok(conversations)
+}
+
+/// Claude `conversations.json`: conversations with a flat `chat_messages` list.
+fn read_claude_export(path: &Path) -> Result<Vec<Conversation>> {
+    let mut conversations = Vec::new();
+    for (idx, item) in read_json_array(path)?.iter().enumerate() {
+        let Some(chat) = item.get("chat_messages").and_then(Value::as_array) else {
+            continue;
+        };
+        let messages = chat
+            .iter()
+            .filter_map(|message| {
+                let speaker = speaker(str_field(message, &["sender", "role"])?)?;
+                let mut text = str_field(message, &["text"])
+                    .unwrap_or_default()
+                    .to_string();
+                if text.trim().is_empty() {
+                    // Newer exports keep the text in typed content blocks.
+                    text = message
+                        .get("content")
+                        .and_then(Value::as_array)
+                        .map(|blocks| {
+                            blocks
+                                .iter()
+                                .filter(|b| b.get("type").and_then(Value::as_str) == Some("text"))
+                                .filter_map(|b| str_field(b, &["text"]))
+                                .collect::<Vec<_>>()
+                                .join("\n")
+                        })
+                        .unwrap_or_default();
+                }
+                let text = text.trim();
+                (!text.is_empty()).then(|| (speaker, text.to_string()))
+            })
+            .collect::<Vec<_>>();
+        if messages.is_empty() {
+            continue;
+        }
+
+        conversations.push(Conversation {
+            id: str_field(item, &["uuid", "id"]).map_or_else(|| idx.to_string(), str::to_string),
+            title: str_field(item, &["name", "title"])
+                .unwrap_or_default()
+                .to_string(),
+            created_at: timestamp_of(item.get("created_at")),
+            messages,
+        });
+    }
+    Ok(conversations)
+}
+
+#[derive(Deserialize)]
+struct JsonlMessage {
+    #[serde(alias = "sender", alias = "author")]
+    role: String,
+    #[serde(alias = "text")]
+    content: String,
+}
+
+/// Generic JSONL: each line is either one conversation (`messages` array,
+/// optional `id`/`title`/`created_at`) or one message (`role`, `content`,
+/// optional `conversation`/`conversation_id`/`session_id` and `timestamp`).
+/// Messages without a conversation id belong to one default conversation.
+fn read_jsonl_export(path: &Path) -> Result<Vec<Conversation>> {
+    let raw =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut conversations: Vec<Conversation> = Vec::new();
+    let mut by_id: HashMap<String, usize> = HashMap::new();
+
+    for (line_no, line) in raw.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let value: Value = serde_json::from_str(line)
+            .with_context(|| format!("{}:{}: invalid JSON", path.display(), line_no + 1))?;
+
+        if let Some(items) = value.get("messages") {
+            let messages: Vec<JsonlMessage> = serde_json::from_value(items.clone())
+                .with_context(|| format!("{}:{}: invalid messages", path.display(), line_no + 1))?;
+            conversations.push(Conversation {
+                id: str_field(&value, &["id", "conversation_id"])
+                    .map_or_else(|| format!("line{}", line_no + 1), str::to_string),
+                title: str_field(&value, &["title", "name"])
+                    .unwrap_or_default()
+                    .to_string(),
+                created_at: timestamp_of(value.get("created_at").or(value.get("timestamp"))),
+                messages: messages
+                    .into_iter()
+                    .filter_map(|m| Some((speaker(&m.role)?, m.content.trim().to_string())))
+                    .filter(|(_, text)| !text.is_empty())
+                    .collect(),
+            });
+            continue;
+        }
+
+        let message: JsonlMessage = serde_json::from_value(value.clone()).with_context(|| {
+            format!(
+                "{}:{}: expected `role` and `content` (or a `messages` array)",
+                path.display(),
+                line_no + 1
+            )
+        })?;
+        let Some(speaker) = speaker(&message.role) else {
+            continue;
+        };
+        let text = message.content.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let id = str_field(&value, &["conversation", "conversation_id", "session_id"])
+            .unwrap_or("default")
+            .to_string();
+        let slot = *by_id.entry(id.clone()).or_insert_with(|| {
+            conversations.push(Conversation {
+                id,
+                created_at: timestamp_of(value.get("timestamp").or(value.get("created_at"))),
+                ..Conversation::default()
+            });
+            conversations.len() - 1
+        });
+        conversations[slot]
+            .messages
+            .push((speaker, text.to_string()));
+    }
+
+    conversations.retain(|c| !c.messages.is_empty());
+    Ok(conversations)
+}
+
+/// Stable key stem for a conversation: title slug plus a short id.
+fn conversation_key(label: &str, conversation: &Conversation) -> String {
+    let title = sanitize_key(&conversation.title);
+    let id: String = sanitize_key(&conversation.id)
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .take(8)
+        .collect();
+    match (title.is_empty(), id.is_empty()) {
+        (false, false) => format!("{label}_{title}_{id}"),
+        (false, true) => format!("{label}_{title}"),
+        _ => format!("{label}_{id}"),
+    }
+}
+
+/// Markdown transcript, titled when the conversation has one.
+fn transcript(conversation: &Conversation) -> String {
+    let mut out = String::new();
+    if !conversation.title.trim().is_empty() {
+        let _ = writeln!(out, "# {}\n", conversation.title.trim());
+    }
+    for (speaker, text) in &conversation.messages {
+        let _ = writeln!(out, "**{speaker}:** {text}\n");
+    }
+    out
+}
+
+/// Raw transcripts as conversation entries, chunked like ingested documents.
+fn conversation_entries(
+    label: &str,
+    conversations: &[Conversation],
+    max_tokens: usize,
+) -> Vec<SourceEntry> {
+    let mut entries = Vec::new();
+    for conversation in conversations {
+        let base = conversation_key(label, conversation);
+        let chunks = chunk_markdown(&transcript(conversation), max_tokens);
+        let single = chunks.len() == 1;
+        for chunk in chunks {
+            entries.push(SourceEntry {
+                key: if single {
+                    base.clone()
+                } else {
+                    format!("{base}_{}", chunk.index + 1)
+                },
+                content: chunk.content,
+                category: MemoryCategory::Conversation,
+                created_at: conversation.created_at.clone(),
+            });
+        }
+    }
+    entries
+}
+
+#[derive(Debug, Deserialize)]
+struct DistilledFacts {
+    #[serde(default)]
+    facts: Vec<DistilledFact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DistilledFact {
+    key: String,
+    fact: String,
+}
+
+/// Ask the model for durable facts per conversation and return them as
+/// core entries. A conversation whose reply can't be used is skipped.
+async fn distill_conversations(
+    provider: &dyn Provider,
+    model: &str,
+    label: &str,
+    conversations: &[Conversation],
+    stats: &mut MigrationStats,
+) -> Result<Vec<SourceEntry>> {
+    let mut entries = Vec::new();
+    for conversation in conversations {
+        let mut text = transcript(conversation);
+        if text.len() > MAX_DISTILL_CHARS {
+            let mut end = MAX_DISTILL_CHARS;
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            text.truncate(end);
+        }
+
+        let reply = match provider
+            .chat_with_system(Some(DISTILL_PROMPT), &text, model, 0.2)
+            .await
+        {
+            Ok(reply) => reply,
+            Err(e) => {
+                tracing::warn!("Distilling conversation {} failed: {e}", conversation.id);
+                stats.distill_failures += 1;
+                continue;
+            }
+        };
+        let parsed = reply
+            .find('{')
+            .zip(reply.rfind('}'))
+            .and_then(|(start, end)| {
+                serde_json::from_str::<DistilledFacts>(reply.get(start..=end)?).ok()
+            });
+        let Some(parsed) = parsed else {
+            tracing::warn!(
+                "Distilling conversation {}: reply was not valid JSON",
+                conversation.id
+            );
+            stats.distill_failures += 1;
+            continue;
+        };
+
+        for fact in parsed.facts {
+            let content = fact.fact.trim();
+            let key = sanitize_key(&fact.key);
+            if content.is_empty() || key.is_empty() {
+                continue;
+            }
+            entries.push(SourceEntry {
+                key: format!("{label}_{key}"),
+                content: content.to_string(),
+                category: MemoryCategory::Core,
+                created_at: conversation.created_at.clone(),
+            });
+        }
+    }
+
+    if entries.is_empty() && stats.distill_failures == conversations.len() {
+        bail!("Distilling failed for every conversation; nothing imported");
+    }
+    Ok(entries)
+}
+
+/// Markdown notes under `root` (hidden folders skipped), one entry per
+/// chunk keyed by the note's relative path.
+fn read_markdown_dir(
+    root: &Path,
+    max_tokens: usize,
+    stats: &mut MigrationStats,
+) -> Result<Vec<SourceEntry>> {
+    let mut files = Vec::new();
+    let mut pending = vec![root.to_path_buf()];
+    while let Some(dir) = pending.pop() {
+        for item in fs::read_dir(&dir)? {
+            let path = item?.path();
+            let hidden = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with('.'));
+            if hidden {
+                continue;
+            }
+            if path.is_dir() {
+                pending.push(path);
+            } else if matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("md" | "markdown")
+            ) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+
+    let mut entries = Vec::new();
+    for path in files {
+        let raw = fs::read_to_string(&path)?;
+        let body = strip_front_matter(&raw);
+        if body.trim().is_empty() {
+            continue;
+        }
+        stats.from_markdown += 1;
+
+        let relative = path.strip_prefix(root).unwrap_or(&path).with_extension("");
+        let base = format!("note_{}", sanitize_key(&relative.to_string_lossy()));
+        let title = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        // Keep the note's title with its first section.
+        let text = if body.trim_start().starts_with('#') {
+            body.to_string()
+        } else {
+            format!("# {title}\n\n{body}")
+        };
+        let created_at = fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .ok()
+            .map(|t| DateTime::<Local>::from(t).to_rfc3339());
+
+        let chunks = chunk_markdown(&text, max_tokens);
+        let single = chunks.len() == 1;
+        for chunk in chunks {
+            entries.push(SourceEntry {
+                key: if single {
+                    base.clone()
+                } else {
+                    format!("{base}_{}", chunk.index + 1)
+                },
+                content: chunk.content,
+                category: MemoryCategory::Custom(NOTES_CATEGORY.into()),
+                created_at: created_at.clone(),
+            });
+        }
+    }
+    Ok(entries)
+}
+
+/// Drop a leading YAML front matter block (`---` … `---`).
+fn strip_front_matter(raw: &str) -> &str {
+    let Some(rest) = raw.strip_prefix("---\n") else {
+        return raw;
+    };
+    match rest.find("\n---") {
+        Some(end) => rest[end + 4..].trim_start_matches(['\r', '\n']),
+        None => raw,
+    }
+}
+
 fn parse_category(raw: &str) -> MemoryCategory {
     match raw.trim().to_ascii_lowercase().as_str() {
         "core" | "" => MemoryCategory::Core,
@@ -322,9 +1048,9 @@ fn normalize_key(key: &str, fallback_idx: usize) -> String {
     trimmed.to_string()
 }
 
-async fn next_available_key(memory: &dyn Memory, base: &str) -> Result<String> {
+async fn next_available_key(memory: &dyn Memory, base: &str, label: &str) -> Result<String> {
     for i in 1..=10_000 {
-        let candidate = format!("{base}__openclaw_{i}");
+        let candidate = format!("{base}__{label}_{i}");
         if memory.get(&candidate).await?.is_none() {
             return Ok(candidate);
         }
@@ -376,12 +1102,12 @@ fn paths_equal(a: &Path, b: &Path) -> bool {
     }
 }
 
-fn backup_target_memory(workspace_dir: &Path) -> Result<Option<PathBuf>> {
+fn backup_target_memory(workspace_dir: &Path, label: &str) -> Result<Option<PathBuf>> {
     let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S").to_string();
     let backup_root = workspace_dir
         .join("memory")
         .join("migrations")
-        .join(format!("openclaw-{timestamp}"));
+        .join(format!("{label}-{timestamp}"));
 
     let mut copied_any = false;
     fs::create_dir_all(&backup_root)?;
@@ -550,4 +1276,174 @@ mod tests {
         let target_mem = SqliteMemory::new(target.path()).unwrap();
         assert_eq!(target_mem.count().await.unwrap(), 0);
     }
+
+    #[test]
+    fn chatgpt_export_follows_current_thread() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("conversations.json");
+        let export = serde_json::json!([{
+            "id": "abc123",
+            "title": "Trip planning",
+            "create_time": 1_700_000_000.0,
+            "current_node": "n3",
+            "mapping": {
+                "root": { "message": null, "parent": null },
+                "n1": {
+                    "parent": "root",
+                    "message": { "author": { "role": "user" },
+                                 "content": { "parts": ["I live in Lisbon"] } }
+                },
+                "n2": {
+                    "parent": "n1",
+                    "message": { "author": { "role": "assistant" },
+                                 "content": { "parts": ["Nice city!"] } }
+                },
+                "n2b": {
+                    "parent": "n1",
+                    "message": { "author": { "role": "assistant" },
+                                 "content": { "parts": ["Regenerated branch"] } }
+                },
+                "n3": {
+                    "parent": "n2",
+                    "message": { "author": { "role": "user" },
+                                 "content": { "parts": ["Plan a weekend"] } }
+                }
+            }
+        }]);
+        fs::write(&path, export.to_string()).unwrap();
+
+        let conversations = read_chatgpt_export(&path).unwrap();
+        assert_eq!(conversations.len(), 1);
+        let c = &conversations[0];
+        assert_eq!(c.title, "Trip planning");
+        assert!(c.created_at.is_some());
+        let texts: Vec<&str> = c.messages.iter().map(|(_, t)| t.as_str()).collect();
+        assert_eq!(texts, ["I live in Lisbon", "Nice city!", "Plan a weekend"]);
+        assert_eq!(c.messages[0].0, "User");
+    }
+
+    #[test]
+    fn claude_export_reads_text_and_content_blocks() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("conversations.json");
+        let export = serde_json::json!([{
+            "uuid": "c-1",
+            "name": "Rust help",
+            "created_at": "2024-03-01T10:00:00Z",
+            "chat_messages": [
+                { "sender": "human", "text": "I prefer tokio" },
+                { "sender": "assistant", "text": "",
+                  "content": [{ "type": "text", "text": "Noted." }] }
+            ]
+        }, {
+            "uuid": "c-2",
+            "name": "Empty",
+            "chat_messages": []
+        }]);
+        fs::write(&path, export.to_string()).unwrap();
+
+        let conversations = read_claude_export(&path).unwrap();
+        assert_eq!(conversations.len(), 1);
+        assert_eq!(
+            conversations[0].messages,
+            vec![
+                ("User".to_string(), "I prefer tokio".to_string()),
+                ("Assistant".to_string(), "Noted.".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn jsonl_groups_messages_by_conversation() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("chat.jsonl");
+        fs::write(
+            &path,
+            concat!(
+                r#"{"role":"user","content":"hi","conversation":"a"}"#,
+                "\n",
+                r#"{"role":"system","content":"ignored","conversation":"a"}"#,
+                "\n",
+                r#"{"role":"assistant","content":"hello","conversation":"a"}"#,
+                "\n",
+                r#"{"title":"Whole","messages":[{"role":"user","content":"one line"}]}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        let conversations = read_jsonl_export(&path).unwrap();
+        assert_eq!(conversations.len(), 2);
+        assert_eq!(conversations[0].id, "a");
+        assert_eq!(conversations[0].messages.len(), 2);
+        assert_eq!(conversations[1].title, "Whole");
+    }
+
+    #[test]
+    fn markdown_dir_skips_hidden_folders_and_front_matter() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(".obsidian")).unwrap();
+        fs::create_dir_all(dir.path().join("projects")).unwrap();
+        fs::write(dir.path().join(".obsidian/workspace.md"), "ignored").unwrap();
+        fs::write(
+            dir.path().join("projects/Garden Plan.md"),
+            "---\ntags: [home]\n---\nTomatoes go in the south bed.",
+        )
+        .unwrap();
+
+        let mut stats = MigrationStats::default();
+        let entries = read_markdown_dir(dir.path(), 512, &mut stats).unwrap();
+        assert_eq!(stats.from_markdown, 1);
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].key.starts_with("note_projects"));
+        assert!(entries[0].content.contains("Tomatoes"));
+        assert!(!entries[0].content.contains("tags:"));
+        assert_eq!(
+            entries[0].category,
+            MemoryCategory::Custom(NOTES_CATEGORY.into())
+        );
+    }
+
+    #[tokio::test]
+    async fn chat_import_keeps_original_dates_and_is_idempotent() {
+        let source = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let path = source.path().join("chat.jsonl");
+        fs::write(
+            &path,
+            r#"{"id":"x1","title":"Old chat","created_at":"2021-05-01T12:00:00Z","messages":[{"role":"user","content":"I use NixOS"}]}"#,
+        )
+        .unwrap();
+
+        let config = test_config(target.path());
+        migrate_chat_export(&config, ChatExport::Jsonl, &path, false, false)
+            .await
+            .unwrap();
+        migrate_chat_export(&config, ChatExport::Jsonl, &path, false, false)
+            .await
+            .unwrap();
+
+        let target_mem = SqliteMemory::new(target.path()).unwrap();
+        let all = target_mem.list(None).await.unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].category, MemoryCategory::Conversation);
+        assert!(all[0].timestamp.starts_with("2021-05-01"));
+        assert!(all[0].content.contains("I use NixOS"));
+    }
+
+    #[tokio::test]
+    async fn chat_import_dry_run_does_not_write() {
+        let source = TempDir::new().unwrap();
+        let target = TempDir::new().unwrap();
+        let path = source.path().join("chat.jsonl");
+        fs::write(&path, r#"{"role":"user","content":"hello"}"#).unwrap();
+
+        let config = test_config(target.path());
+        migrate_chat_export(&config, ChatExport::Jsonl, &path, true, false)
+            .await
+            .unwrap();
+
+        let target_mem = SqliteMemory::new(target.path()).unwrap();
+        assert_eq!(target_mem.count().await.unwrap(), 0);
+    }
 }
@@ -37,6 +37,9 @@ impl Observer for LogObserver {
             ObserverEvent::HeartbeatTick => {
                 info!("heartbeat.tick");
             }
+            ObserverEvent::CacheLookup { hit, mode } => {
+                info!(hit = hit, mode = %mode, "cache.lookup");
+            }
             ObserverEvent::Error { component, message } => {
                 info!(component = %component, error = %message, "error");
             }
@@ -58,6 +61,9 @@ impl Observer for LogObserver {
             ObserverMetric::QueueDepth(d) => {
                 info!(depth = d, "metric.queue_depth");
             }
+            ObserverMetric::CacheHitRate(rate) => {
+                info!(rate = rate, "metric.cache_hit_rate");
+            }
         }
     }
 
@@ -101,6 +107,10 @@ mod tests {
             direction: "outbound".into(),
         });
         obs.record_event(&ObserverEvent::HeartbeatTick);
+        obs.record_event(&ObserverEvent::CacheLookup {
+            hit: true,
+            mode: "exact".into(),
+        });
         obs.record_event(&ObserverEvent::Error {
             component: "provider".into(),
             message: "timeout".into(),
@@ -115,5 +125,6 @@ mod tests {
         obs.record_metric(&ObserverMetric::TokensUsed(u64::MAX));
         obs.record_metric(&ObserverMetric::ActiveSessions(1));
         obs.record_metric(&ObserverMetric::QueueDepth(999));
+        obs.record_metric(&ObserverMetric::CacheHitRate(0.5));
     }
 }
@@ -24,6 +24,8 @@ pub struct OtelObserver {
     tokens_used: Counter<u64>,
     active_sessions: Gauge<u64>,
     queue_depth: Gauge<u64>,
+    cache_lookups: Counter<u64>,
+    cache_hit_rate: Gauge<f64>,
 }
 
 impl OtelObserver {
@@ -136,6 +138,16 @@ impl OtelObserver {
             .with_description("Current message queue depth")
             .build();
 
+        let cache_lookups = meter
+            .u64_counter("zeroclaw.cache.lookups")
+            .with_description("Provider response cache lookups, by hit/miss")
+            .build();
+
+        let cache_hit_rate = meter
+            .f64_gauge("zeroclaw.cache.hit_rate")
+            .with_description("Provider response cache hit rate since startup")
+            .build();
+
         Ok(Self {
             tracer_provider,
             meter_provider: meter_provider_clone,
@@ -150,6 +162,8 @@ impl OtelObserver {
             tokens_used,
             active_sessions,
             queue_depth,
+            cache_lookups,
+            cache_hit_rate,
         })
     }
 }
@@ -242,6 +256,15 @@ impl Observer for OtelObserver {
             ObserverEvent::HeartbeatTick => {
                 self.heartbeat_ticks.add(1, &[]);
             }
+            ObserverEvent::CacheLookup { hit, mode } => {
+                self.cache_lookups.add(
+                    1,
+                    &[
+                        KeyValue::new("hit", *hit),
+                        KeyValue::new("mode", mode.clone()),
+                    ],
+                );
+            }
             ObserverEvent::Error { component, message } => {
                 // Create an error span for visibility in trace backends
                 let mut span = tracer.build(
@@ -275,6 +298,9 @@ impl Observer for OtelObserver {
             ObserverMetric::QueueDepth(d) => {
                 self.queue_depth.record(*d as u64, &[]);
             }
+            ObserverMetric::CacheHitRate(rate) => {
+                self.cache_hit_rate.record(*rate, &[]);
+            }
         }
     }
 
@@ -21,6 +21,11 @@ pub enum ObserverEvent {
         direction: String,
     },
     HeartbeatTick,
+    /// Provider response cache lookup (`mode` is "exact" or "similar" on a hit).
+    CacheLookup {
+        hit: bool,
+        mode: String,
+    },
     Error {
         component: String,
         message: String,
@@ -34,6 +39,8 @@ pub enum ObserverMetric {
     TokensUsed(u64),
     ActiveSessions(u64),
     QueueDepth(u64),
+    /// Provider response cache hit rate since startup (0.0–1.0)
+    CacheHitRate(f64),
 }
 
 /// Core observability trait — implement for any backend
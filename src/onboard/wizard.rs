@@ -59,7 +59,7 @@ pub fn run_wizard() -> Result<Config> {
     let (workspace_dir, config_path) = setup_workspace()?;
 
     print_step(2, 8, "AI Provider & API Key");
-    let (provider, api_key, model) = setup_provider()?;
+    let (provider, api_key, model, ollama_config) = setup_provider()?;
 
     print_step(3, 8, "Channels (How You Talk to ZeroClaw)");
     let channels_config = setup_channels()?;
@@ -97,8 +97,14 @@ pub fn run_wizard() -> Result<Config> {
         runtime: RuntimeConfig::default(),
         reliability: crate::config::ReliabilityConfig::default(),
         model_routes: Vec::new(),
+        reasoning: crate::config::ReasoningConfig::default(),
+        azure_openai: crate::config::AzureOpenAiConfig::default(),
+        ollama: ollama_config,
+        response_cache: crate::config::ResponseCacheConfig::default(),
+        http: crate::config::HttpConfig::default(),
         heartbeat: HeartbeatConfig::default(),
         channels_config,
+        media: crate::config::MediaConfig::default(),
         memory: memory_config, // User-selected memory backend
         tunnel: tunnel_config,
         gateway: crate::config::GatewayConfig::default(),
@@ -171,6 +177,8 @@ pub fn run_channels_repair_wizard() -> Result<Config> {
     println!();
 
     let mut config = Config::load_or_init()?;
+    // Connection checks below go through the configured proxy and CA bundle.
+    crate::http::init(&config.http)?;
 
     print_step(1, 1, "Channels (How You Talk to ZeroClaw)");
     config.channels_config = setup_channels()?;
@@ -275,6 +283,13 @@ pub fn run_quick_setup(
             0
         },
         chunk_max_tokens: 512,
+        vector_index: "hnsw".into(),
+        channel_scope: "user".into(),
+        consolidation: crate::config::ConsolidationConfig::default(),
+        graph: crate::config::GraphConfig::default(),
+        extraction: crate::config::ExtractionConfig::default(),
+        ranking: crate::config::RankingConfig::default(),
+        encrypt: false,
     };
 
     let config = Config {
@@ -289,8 +304,14 @@ pub fn run_quick_setup(
         runtime: RuntimeConfig::default(),
         reliability: crate::config::ReliabilityConfig::default(),
         model_routes: Vec::new(),
+        reasoning: crate::config::ReasoningConfig::default(),
+        azure_openai: crate::config::AzureOpenAiConfig::default(),
+        ollama: crate::config::OllamaConfig::default(),
+        response_cache: crate::config::ResponseCacheConfig::default(),
+        http: crate::config::HttpConfig::default(),
         heartbeat: HeartbeatConfig::default(),
         channels_config: ChannelsConfig::default(),
+        media: crate::config::MediaConfig::default(),
         memory: memory_config,
         tunnel: crate::config::TunnelConfig::default(),
         gateway: crate::config::GatewayConfig::default(),
@@ -471,14 +492,14 @@ fn setup_workspace() -> Result<(PathBuf, PathBuf)> {
 // ── Step 2: Provider & API Key ───────────────────────────────────
 
 #[allow(clippy::too_many_lines)]
-fn setup_provider() -> Result<(String, String, String)> {
+fn setup_provider() -> Result<(String, String, String, crate::config::OllamaConfig)> {
     // ── Tier selection ──
     let tiers = vec![
         "⭐ Recommended (OpenRouter, Venice, Anthropic, OpenAI, Gemini)",
         "⚡ Fast inference (Groq, Fireworks, Together AI)",
-        "🌐 Gateway / proxy (Vercel AI, Cloudflare AI, Amazon Bedrock)",
+        "🌐 Gateway / proxy (Vercel AI, Cloudflare AI, Amazon Bedrock, Azure OpenAI)",
         "🔬 Specialized (Moonshot/Kimi, GLM/Zhipu, MiniMax, Qianfan, Z.AI, Synthetic, OpenCode Zen, Cohere)",
-        "🏠 Local / private (Ollama — no API key needed)",
+        "🏠 Local / private (Ollama, LM Studio, llama.cpp, vLLM, TGI — auto-detected)",
         "🔧 Custom — bring your own OpenAI-compatible API",
     ];
 
@@ -515,6 +536,7 @@ fn setup_provider() -> Result<(String, String, String)> {
             ("vercel", "Vercel AI Gateway"),
             ("cloudflare", "Cloudflare AI Gateway"),
             ("bedrock", "Amazon Bedrock — AWS managed models"),
+            ("azure", "Azure OpenAI — GPT models on your Azure resource"),
         ],
         3 => vec![
             ("moonshot", "Moonshot — Kimi & Kimi Coding"),
@@ -526,7 +548,12 @@ fn setup_provider() -> Result<(String, String, String)> {
             ("opencode", "OpenCode Zen — code-focused AI"),
             ("cohere", "Cohere — Command R+ & embeddings"),
         ],
-        4 => vec![("ollama", "Ollama — local models (Llama, Mistral, Phi)")],
+        4 => {
+            if let Some(local) = setup_local_server()? {
+                return Ok(local);
+            }
+            vec![("ollama", "Ollama — local models (Llama, Mistral, Phi)")]
+        }
         _ => vec![], // Custom — handled below
     };
 
@@ -570,7 +597,12 @@ fn setup_provider() -> Result<(String, String, String)> {
             style(&model).green()
         );
 
-        return Ok((provider_name, api_key, model));
+        return Ok((
+            provider_name,
+            api_key,
+            model,
+            crate::config::OllamaConfig::default(),
+        ));
     }
 
     let provider_labels: Vec<&str> = providers.iter().map(|(_, label)| *label).collect();
@@ -634,6 +666,15 @@ fn setup_provider() -> Result<(String, String, String)> {
                 .allow_empty(true)
                 .interact_text()?
         }
+    } else if matches!(provider_name, "bedrock" | "aws-bedrock") {
+        // Bedrock never reads the config api_key; it authenticates from the environment.
+        print_bullet(&format!(
+            "Bedrock uses AWS credentials ({} / {} or ~/.aws/credentials) or {}.",
+            style("AWS_ACCESS_KEY_ID").yellow(),
+            style("AWS_SECRET_ACCESS_KEY").yellow(),
+            style("AWS_BEARER_TOKEN_BEDROCK").yellow()
+        ));
+        String::new()
     } else {
         let key_url = match provider_name {
             "openrouter" => "https://openrouter.ai/keys",
@@ -654,7 +695,7 @@ fn setup_provider() -> Result<(String, String, String)> {
             "minimax" => "https://www.minimaxi.com/user-center/basic-information",
             "vercel" => "https://vercel.com/account/tokens",
             "cloudflare" => "https://dash.cloudflare.com/profile/api-tokens",
-            "bedrock" => "https://console.aws.amazon.com/iam",
+            "azure" => "https://portal.azure.com",
             "gemini" | "google" | "google-gemini" => "https://aistudio.google.com/app/apikey",
             _ => "",
         };
@@ -807,6 +848,13 @@ fn setup_provider() -> Result<(String, String, String)> {
             ("gemini-1.5-pro", "Gemini 1.5 Pro (best quality)"),
             ("gemini-1.5-flash", "Gemini 1.5 Flash (balanced)"),
         ],
+        // Azure deployments are assumed to be named after the model; map
+        // custom names under [azure_openai.deployments].
+        "azure" => vec![
+            ("gpt-4o", "GPT-4o (deployment \"gpt-4o\")"),
+            ("gpt-4o-mini", "GPT-4o Mini (deployment \"gpt-4o-mini\")"),
+            ("o3-mini", "o3-mini (deployment \"o3-mini\")"),
+        ],
         _ => vec![("default", "Default model")],
     };
 
@@ -820,14 +868,125 @@ fn setup_provider() -> Result<(String, String, String)> {
 
     let model = models[model_idx].0.to_string();
 
+    // Azure endpoints are per-resource: encode the resource in the provider name.
+    let provider_name = if provider_name == "azure" {
+        let resource: String = Input::new()
+            .with_prompt("  Azure OpenAI resource name (https://<resource>.openai.azure.com)")
+            .allow_empty(true)
+            .interact_text()?;
+        let resource = resource.trim();
+        if resource.is_empty() {
+            print_bullet("Skipped. Set AZURE_OPENAI_ENDPOINT or [azure_openai].resource later.");
+            "azure".to_string()
+        } else {
+            format!("azure:{resource}")
+        }
+    } else {
+        provider_name.to_string()
+    };
+
+    println!(
+        "  {} Provider: {} | Model: {}",
+        style("✓").green().bold(),
+        style(&provider_name).green(),
+        style(&model).green()
+    );
+
+    Ok((
+        provider_name,
+        api_key,
+        model,
+        crate::config::OllamaConfig::default(),
+    ))
+}
+
+/// Probe well-known local ports and offer any LLM server found. Returns `None`
+/// when nothing answers or the user prefers to configure Ollama manually.
+fn setup_local_server() -> Result<Option<(String, String, String, crate::config::OllamaConfig)>> {
+    use crate::providers::discovery;
+
+    print_bullet("Scanning for local LLM servers...");
+    let servers = discovery::discover_blocking(&discovery::default_candidates(None));
+
+    if servers.is_empty() {
+        print_bullet(&format!(
+            "No local server found on ports {}.",
+            discovery::DEFAULT_PORTS.map(|p| p.to_string()).join(", ")
+        ));
+        println!();
+        return Ok(None);
+    }
+
+    let mut labels: Vec<String> = servers
+        .iter()
+        .map(|s| {
+            format!(
+                "{} at {} ({} model{})",
+                s.kind.label(),
+                s.base_url,
+                s.models.len(),
+                if s.models.len() == 1 { "" } else { "s" }
+            )
+        })
+        .collect();
+    labels.push("Configure Ollama manually".into());
+
+    let idx = Select::new()
+        .with_prompt("  Found local LLM servers — select one")
+        .items(&labels)
+        .default(0)
+        .interact()?;
+
+    let Some(server) = servers.get(idx) else {
+        return Ok(None);
+    };
+
+    let model = if server.models.is_empty() {
+        print_bullet("The server reported no models — enter the model name it serves.");
+        Input::new()
+            .with_prompt("  Model name")
+            .default(if server.kind == discovery::LocalServerKind::Ollama {
+                default_model_for_provider("ollama")
+            } else {
+                "default".into()
+            })
+            .interact_text()?
+    } else {
+        let model_idx = Select::new()
+            .with_prompt("  Select your default model")
+            .items(&server.models)
+            .default(0)
+            .interact()?;
+        server.models[model_idx].clone()
+    };
+
+    let provider_name = server.provider_name();
     println!(
         "  {} Provider: {} | Model: {}",
         style("✓").green().bold(),
-        style(provider_name).green(),
+        style(&provider_name).green(),
         style(&model).green()
     );
 
-    Ok((provider_name.to_string(), api_key, model))
+    Ok(Some((
+        provider_name,
+        String::new(),
+        model,
+        ollama_config_for(server),
+    )))
+}
+
+/// `[ollama]` section for a discovered server: Ollama found anywhere but the
+/// default address keeps its URL, everything else uses the defaults.
+fn ollama_config_for(
+    server: &crate::providers::discovery::DiscoveredServer,
+) -> crate::config::OllamaConfig {
+    use crate::providers::discovery;
+
+    let base_url = (server.kind == discovery::LocalServerKind::Ollama
+        && server.base_url.trim_end_matches('/') != discovery::DEFAULT_OLLAMA_URL)
+        .then(|| server.base_url.clone());
+    crate::config::OllamaConfig { base_url }
 }
 
 /// Map provider name to its conventional env var
@@ -855,6 +1014,7 @@ fn provider_env_var(name: &str) -> &'static str {
         "vercel" | "vercel-ai" => "VERCEL_API_KEY",
         "cloudflare" | "cloudflare-ai" => "CLOUDFLARE_API_KEY",
         "bedrock" | "aws-bedrock" => "AWS_ACCESS_KEY_ID",
+        "azure" | "azure-openai" => "AZURE_OPENAI_API_KEY",
         "gemini" | "google" | "google-gemini" => "GEMINI_API_KEY",
         _ => "API_KEY",
     }
@@ -1104,6 +1264,13 @@ fn setup_memory() -> Result<MemoryConfig> {
         keyword_weight: 0.3,
         embedding_cache_size: if backend == "sqlite" { 10000 } else { 0 },
         chunk_max_tokens: 512,
+        vector_index: "hnsw".into(),
+        channel_scope: "user".into(),
+        consolidation: crate::config::ConsolidationConfig::default(),
+        graph: crate::config::GraphConfig::default(),
+        extraction: crate::config::ExtractionConfig::default(),
+        ranking: crate::config::RankingConfig::default(),
+        encrypt: false,
     })
 }
 
@@ -1231,7 +1398,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
 
                 // Test connection
                 print!("  {} Testing connection... ", style("⏳").dim());
-                let client = reqwest::blocking::Client::new();
+                let client =
+                    crate::http::blocking_client("telegram", crate::http::Defaults::PROVIDER);
                 let url = format!("https://api.telegram.org/bot{token}/getMe");
                 match client.get(&url).send() {
                     Ok(resp) if resp.status().is_success() => {
@@ -1290,6 +1458,9 @@ fn setup_channels() -> Result<ChannelsConfig> {
                 config.telegram = Some(TelegramConfig {
                     bot_token: token,
                     allowed_users,
+                    mode: "polling".into(),
+                    webhook_url: None,
+                    webhook_secret: None,
                 });
             }
             1 => {
@@ -1315,7 +1486,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
 
                 // Test connection
                 print!("  {} Testing connection... ", style("⏳").dim());
-                let client = reqwest::blocking::Client::new();
+                let client =
+                    crate::http::blocking_client("discord", crate::http::Defaults::PROVIDER);
                 match client
                     .get("https://discord.com/api/v10/users/@me")
                     .header("Authorization", format!("Bot {token}"))
@@ -1406,7 +1578,7 @@ fn setup_channels() -> Result<ChannelsConfig> {
 
                 // Test connection
                 print!("  {} Testing connection... ", style("⏳").dim());
-                let client = reqwest::blocking::Client::new();
+                let client = crate::http::blocking_client("slack", crate::http::Defaults::PROVIDER);
                 match client
                     .get("https://slack.com/api/auth.test")
                     .bearer_auth(&token)
@@ -1450,6 +1622,18 @@ fn setup_channels() -> Result<ChannelsConfig> {
                     .allow_empty(true)
                     .interact_text()?;
 
+                // Without Socket Mode, events can come in through the gateway instead
+                let signing_secret: String = if app_token.is_empty() {
+                    Input::new()
+                        .with_prompt(
+                            "  Signing secret (Events API via gateway /slack/events, optional, Enter to skip)",
+                        )
+                        .allow_empty(true)
+                        .interact_text()?
+                } else {
+                    String::new()
+                };
+
                 let channel: String = Input::new()
                     .with_prompt("  Default channel ID (optional, Enter to skip)")
                     .allow_empty(true)
@@ -1498,6 +1682,11 @@ fn setup_channels() -> Result<ChannelsConfig> {
                         Some(channel)
                     },
                     allowed_users,
+                    signing_secret: if signing_secret.is_empty() {
+                        None
+                    } else {
+                        Some(signing_secret)
+                    },
                 });
             }
             3 => {
@@ -1576,7 +1765,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
                 // Test connection
                 let hs = homeserver.trim_end_matches('/');
                 print!("  {} Testing connection... ", style("⏳").dim());
-                let client = reqwest::blocking::Client::new();
+                let client =
+                    crate::http::blocking_client("matrix", crate::http::Defaults::PROVIDER);
                 match client
                     .get(format!("{hs}/_matrix/client/v3/account/whoami"))
                     .header("Authorization", format!("Bearer {access_token}"))
@@ -1663,7 +1853,8 @@ fn setup_channels() -> Result<ChannelsConfig> {
 
                 // Test connection
                 print!("  {} Testing connection... ", style("⏳").dim());
-                let client = reqwest::blocking::Client::new();
+                let client =
+                    crate::http::blocking_client("whatsapp", crate::http::Defaults::PROVIDER);
                 let url = format!(
                     "https://graph.facebook.com/v18.0/{}",
                     phone_number_id.trim()
@@ -2253,7 +2444,13 @@ fn scaffold_workspace(workspace_dir: &Path, ctx: &ProjectContext) -> Result<()>
            - Don't use when: the answer is already in current files/conversation.\n\
          - **memory_forget** — Delete a memory entry\n\
            - Use when: memory is incorrect, stale, or explicitly requested to be removed.\n\
-           - Don't use when: uncertain about impact; verify before deleting.\n\n\
+           - Don't use when: uncertain about impact; verify before deleting.\n\
+         - **memory_graph_query** — Look up an entity and its relations\n\
+           - Use when: asking who owns, works on, or depends on something.\n\
+           - Don't use when: a plain memory search answers it.\n\
+         - **memory_graph_upsert** — Record entities and relations\n\
+           - Use when: learning durable facts about how people, projects, and services relate.\n\
+           - Don't use when: the fact is transient or already recorded.\n\n\
          ---\n\
          *Add whatever helps you do your job. This is your cheat sheet.*\n";
 
@@ -2886,6 +3083,8 @@ mod tests {
             "memory_store",
             "memory_recall",
             "memory_forget",
+            "memory_graph_query",
+            "memory_graph_upsert",
         ] {
             assert!(
                 tools.contains(tool),
@@ -2981,6 +3180,28 @@ mod tests {
 
     // ── provider_env_var ────────────────────────────────────────
 
+    #[test]
+    fn discovered_ollama_keeps_non_default_url() {
+        use crate::providers::discovery::{DiscoveredServer, LocalServerKind, DEFAULT_OLLAMA_URL};
+
+        let mut server = DiscoveredServer {
+            kind: LocalServerKind::Ollama,
+            base_url: "http://192.168.1.20:11434".into(),
+            models: vec![],
+        };
+        assert_eq!(
+            ollama_config_for(&server).base_url.as_deref(),
+            Some("http://192.168.1.20:11434")
+        );
+
+        server.base_url = DEFAULT_OLLAMA_URL.into();
+        assert!(ollama_config_for(&server).base_url.is_none());
+
+        server.kind = LocalServerKind::LmStudio;
+        server.base_url = "http://localhost:1234".into();
+        assert!(ollama_config_for(&server).base_url.is_none());
+    }
+
     #[test]
     fn provider_env_var_known_providers() {
         assert_eq!(provider_env_var("openrouter"), "OPENROUTER_API_KEY");
@@ -2991,6 +3212,7 @@ mod tests {
         assert_eq!(provider_env_var("grok"), "XAI_API_KEY"); // alias
         assert_eq!(provider_env_var("together"), "TOGETHER_API_KEY");
         assert_eq!(provider_env_var("together-ai"), "TOGETHER_API_KEY"); // alias
+        assert_eq!(provider_env_var("azure"), "AZURE_OPENAI_API_KEY");
     }
 
     #[test]
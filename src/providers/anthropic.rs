@@ -1,4 +1,5 @@
-use crate::providers::traits::Provider;
+use crate::providers::reasoning::wrap_reasoning;
+use crate::providers::traits::{ImageInput, Provider};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
@@ -10,12 +11,12 @@ pub struct AnthropicProvider {
 }
 
 #[derive(Debug, Serialize)]
-struct ChatRequest {
+struct ChatRequest<M = Message> {
     model: String,
     max_tokens: u32,
     #[serde(skip_serializing_if = "Option::is_none")]
     system: Option<String>,
-    messages: Vec<Message>,
+    messages: Vec<M>,
     temperature: f64,
 }
 
@@ -32,7 +33,33 @@ struct ChatResponse {
 
 #[derive(Debug, Deserialize)]
 struct ContentBlock {
+    #[serde(rename = "type", default)]
+    kind: Option<String>,
+    #[serde(default)]
     text: String,
+    /// Present on `thinking` blocks when extended thinking is enabled.
+    #[serde(default)]
+    thinking: Option<String>,
+}
+
+impl ChatResponse {
+    /// Return the first text block, with any thinking blocks prepended as a
+    /// `<think>` block.
+    fn into_text(self) -> Option<String> {
+        let mut thinking = Vec::new();
+        let mut text = None;
+        for block in self.content {
+            if block.kind.as_deref() == Some("thinking") {
+                if let Some(t) = block.thinking {
+                    thinking.push(t);
+                }
+            } else if text.is_none() {
+                text = Some(block.text);
+            }
+        }
+        let reasoning = thinking.join("\n\n");
+        text.map(|t| wrap_reasoning(Some(&reasoning), &t))
+    }
 }
 
 impl AnthropicProvider {
@@ -51,51 +78,27 @@ impl AnthropicProvider {
                 .filter(|k| !k.is_empty())
                 .map(ToString::to_string),
             base_url,
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: crate::http::client("anthropic", crate::http::Defaults::PROVIDER),
         }
     }
 
     fn is_setup_token(token: &str) -> bool {
         token.starts_with("sk-ant-oat01-")
     }
-}
 
-#[async_trait]
-impl Provider for AnthropicProvider {
-    async fn chat_with_system(
-        &self,
-        system_prompt: Option<&str>,
-        message: &str,
-        model: &str,
-        temperature: f64,
-    ) -> anyhow::Result<String> {
+    async fn send<M: Serialize + Sync>(&self, request: &ChatRequest<M>) -> anyhow::Result<String> {
         let credential = self.credential.as_ref().ok_or_else(|| {
             anyhow::anyhow!(
                 "Anthropic credentials not set. Set ANTHROPIC_API_KEY or ANTHROPIC_OAUTH_TOKEN (setup-token)."
             )
         })?;
 
-        let request = ChatRequest {
-            model: model.to_string(),
-            max_tokens: 4096,
-            system: system_prompt.map(ToString::to_string),
-            messages: vec![Message {
-                role: "user".to_string(),
-                content: message.to_string(),
-            }],
-            temperature,
-        };
-
         let mut request = self
             .client
             .post(format!("{}/v1/messages", self.base_url))
             .header("anthropic-version", "2023-06-01")
             .header("content-type", "application/json")
-            .json(&request);
+            .json(request);
 
         if Self::is_setup_token(credential) {
             request = request.header("Authorization", format!("Bearer {credential}"));
@@ -112,14 +115,64 @@ impl Provider for AnthropicProvider {
         let chat_response: ChatResponse = response.json().await?;
 
         chat_response
-            .content
-            .into_iter()
-            .next()
-            .map(|c| c.text)
+            .into_text()
             .ok_or_else(|| anyhow::anyhow!("No response from Anthropic"))
     }
 }
 
+#[async_trait]
+impl Provider for AnthropicProvider {
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let request = ChatRequest {
+            model: model.to_string(),
+            max_tokens: 4096,
+            system: system_prompt.map(ToString::to_string),
+            messages: vec![Message {
+                role: "user".to_string(),
+                content: message.to_string(),
+            }],
+            temperature,
+        };
+
+        self.send(&request).await
+    }
+
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageInput],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        if images.is_empty() {
+            return self
+                .chat_with_system(system_prompt, message, model, temperature)
+                .await;
+        }
+
+        let mut content: Vec<serde_json::Value> =
+            images.iter().map(ImageInput::anthropic_block).collect();
+        content.push(serde_json::json!({ "type": "text", "text": message }));
+
+        let request = ChatRequest {
+            model: model.to_string(),
+            max_tokens: 4096,
+            system: system_prompt.map(ToString::to_string),
+            messages: vec![serde_json::json!({ "role": "user", "content": content })],
+            temperature,
+        };
+
+        self.send(&request).await
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -263,10 +316,20 @@ mod tests {
         assert_eq!(resp.content[1].text, "Second");
     }
 
+    #[test]
+    fn chat_response_thinking_block_becomes_reasoning() {
+        let json = r#"{"content":[{"type":"thinking","thinking":"Consider the options"},{"type":"text","text":"Pick B"}]}"#;
+        let resp: ChatResponse = serde_json::from_str(json).unwrap();
+        let text = resp.into_text().unwrap();
+        let (reasoning, answer) = crate::providers::reasoning::split_reasoning(&text);
+        assert_eq!(reasoning.as_deref(), Some("Consider the options"));
+        assert_eq!(answer, "Pick B");
+    }
+
     #[test]
     fn temperature_range_serializes() {
         for temp in [0.0, 0.5, 1.0, 2.0] {
-            let req = ChatRequest {
+            let req: ChatRequest = ChatRequest {
                 model: "claude-3-opus".to_string(),
                 max_tokens: 4096,
                 system: None,
@@ -0,0 +1,351 @@
+//! Azure OpenAI provider.
+//!
+//! Azure serves OpenAI models from per-resource endpoints where each model is
+//! exposed as a named *deployment*:
+//! `https://{resource}.openai.azure.com/openai/deployments/{deployment}/chat/completions?api-version=...`
+//! and authenticates with an `api-key` header instead of a bearer token.
+
+use crate::config::AzureOpenAiConfig;
+use crate::providers::compatible::{ApiChatResponse, Message};
+use crate::providers::traits::{ChatMessage, Provider};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Serialize;
+use std::collections::HashMap;
+
+pub struct AzureOpenAiProvider {
+    endpoint: Option<String>,
+    api_key: Option<String>,
+    api_version: String,
+    deployments: HashMap<String, String>,
+    reasoning_effort: Option<String>,
+    client: Client,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatRequest {
+    messages: Vec<Message>,
+    temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning_effort: Option<String>,
+}
+
+impl AzureOpenAiProvider {
+    /// Create an Azure OpenAI provider.
+    ///
+    /// `[azure_openai].api_key` wins over `api_key`, which the factory
+    /// resolves from the top-level key or `AZURE_OPENAI_API_KEY`.
+    ///
+    /// Endpoint resolution order:
+    /// 1. `resource` from the provider name (`azure:<resource>`)
+    /// 2. `[azure_openai].endpoint`, then `[azure_openai].resource`
+    /// 3. `AZURE_OPENAI_ENDPOINT` environment variable
+    pub fn new(resource: Option<&str>, api_key: Option<&str>, config: &AzureOpenAiConfig) -> Self {
+        let endpoint = resource
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .map(resource_endpoint)
+            .or_else(|| config.endpoint.clone())
+            .or_else(|| config.resource.as_deref().map(resource_endpoint))
+            .or_else(|| {
+                std::env::var("AZURE_OPENAI_ENDPOINT")
+                    .ok()
+                    .filter(|v| !v.trim().is_empty())
+            })
+            .map(|e| e.trim().trim_end_matches('/').to_string());
+
+        Self {
+            endpoint,
+            api_key: config
+                .api_key
+                .as_deref()
+                .or(api_key)
+                .map(str::trim)
+                .filter(|k| !k.is_empty())
+                .map(ToString::to_string),
+            api_version: config.api_version.clone(),
+            deployments: config.deployments.clone(),
+            reasoning_effort: None,
+            client: crate::http::client("azure", crate::http::Defaults::PROVIDER),
+        }
+    }
+
+    /// Set the `reasoning_effort` request parameter for o-series deployments.
+    pub fn with_reasoning_effort(mut self, effort: Option<&str>) -> Self {
+        self.reasoning_effort = effort.map(ToString::to_string);
+        self
+    }
+
+    /// Map a model name to its deployment. Unmapped models are assumed to be
+    /// deployed under their own name, which is Azure's default.
+    fn deployment_for(&self, model: &str) -> String {
+        self.deployments
+            .get(model)
+            .cloned()
+            .unwrap_or_else(|| model.to_string())
+    }
+
+    fn chat_completions_url(&self, endpoint: &str, model: &str) -> String {
+        format!(
+            "{endpoint}/openai/deployments/{}/chat/completions?api-version={}",
+            self.deployment_for(model),
+            self.api_version
+        )
+    }
+
+    async fn send(
+        &self,
+        messages: Vec<Message>,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let endpoint = self.endpoint.as_deref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Azure OpenAI endpoint not set. Use `azure:<resource>`, set [azure_openai].resource \
+                 in config.toml, or set AZURE_OPENAI_ENDPOINT."
+            )
+        })?;
+        let api_key = self.api_key.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Azure OpenAI API key not set. Set AZURE_OPENAI_API_KEY or edit config.toml."
+            )
+        })?;
+
+        let request = ChatRequest {
+            messages,
+            temperature,
+            reasoning_effort: self.reasoning_effort.clone(),
+        };
+
+        let response = self
+            .client
+            .post(self.chat_completions_url(endpoint, model))
+            .header("api-key", api_key)
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(super::api_error("Azure OpenAI", response).await);
+        }
+
+        let chat_response: ApiChatResponse = response.json().await?;
+
+        chat_response
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message.into_text())
+            .ok_or_else(|| anyhow::anyhow!("No response from Azure OpenAI"))
+    }
+}
+
+fn resource_endpoint(resource: &str) -> String {
+    format!("https://{resource}.openai.azure.com")
+}
+
+#[async_trait]
+impl Provider for AzureOpenAiProvider {
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut messages = Vec::new();
+
+        if let Some(sys) = system_prompt {
+            messages.push(Message {
+                role: "system".to_string(),
+                content: sys.to_string(),
+            });
+        }
+
+        messages.push(Message {
+            role: "user".to_string(),
+            content: message.to_string(),
+        });
+
+        self.send(messages, model, temperature).await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let api_messages = messages
+            .iter()
+            .map(|m| Message {
+                role: m.role.clone(),
+                content: m.content.clone(),
+            })
+            .collect();
+
+        self.send(api_messages, model, temperature).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> AzureOpenAiConfig {
+        let mut config = AzureOpenAiConfig::default();
+        config
+            .deployments
+            .insert("gpt-4o".into(), "prod-gpt4o".into());
+        config
+    }
+
+    #[test]
+    fn resource_from_name_builds_endpoint() {
+        let p = AzureOpenAiProvider::new(Some("contoso"), Some("key"), &config());
+        assert_eq!(
+            p.endpoint.as_deref(),
+            Some("https://contoso.openai.azure.com")
+        );
+    }
+
+    #[test]
+    fn config_endpoint_overrides_resource() {
+        let mut cfg = config();
+        cfg.resource = Some("ignored".into());
+        cfg.endpoint = Some("https://gateway.example.com/".into());
+        let p = AzureOpenAiProvider::new(None, Some("key"), &cfg);
+        assert_eq!(p.endpoint.as_deref(), Some("https://gateway.example.com"));
+    }
+
+    #[test]
+    fn deployment_mapping_falls_back_to_model_name() {
+        let p = AzureOpenAiProvider::new(Some("contoso"), Some("key"), &config());
+        assert_eq!(p.deployment_for("gpt-4o"), "prod-gpt4o");
+        assert_eq!(p.deployment_for("gpt-4o-mini"), "gpt-4o-mini");
+    }
+
+    #[test]
+    fn url_includes_deployment_and_api_version() {
+        let p = AzureOpenAiProvider::new(Some("contoso"), Some("key"), &config());
+        assert_eq!(
+            p.chat_completions_url("https://contoso.openai.azure.com", "gpt-4o"),
+            format!(
+                "https://contoso.openai.azure.com/openai/deployments/prod-gpt4o/chat/completions?api-version={}",
+                AzureOpenAiConfig::default().api_version
+            )
+        );
+    }
+
+    #[test]
+    fn config_api_key_used_when_none_passed() {
+        let mut cfg = config();
+        cfg.api_key = Some("cfg-key".into());
+        let p = AzureOpenAiProvider::new(Some("contoso"), None, &cfg);
+        assert_eq!(p.api_key.as_deref(), Some("cfg-key"));
+    }
+
+    #[test]
+    fn config_api_key_takes_precedence_over_resolved_key() {
+        let mut cfg = config();
+        cfg.api_key = Some("cfg-key".into());
+        let p = AzureOpenAiProvider::new(Some("contoso"), Some("top-level-key"), &cfg);
+        assert_eq!(p.api_key.as_deref(), Some("cfg-key"));
+
+        cfg.api_key = Some("  ".into());
+        let p = AzureOpenAiProvider::new(Some("contoso"), Some("top-level-key"), &cfg);
+        assert_eq!(p.api_key.as_deref(), None);
+
+        cfg.api_key = None;
+        let p = AzureOpenAiProvider::new(Some("contoso"), Some("top-level-key"), &cfg);
+        assert_eq!(p.api_key.as_deref(), Some("top-level-key"));
+    }
+
+    #[tokio::test]
+    async fn chat_fails_without_key() {
+        let p = AzureOpenAiProvider::new(Some("contoso"), None, &config());
+        let err = p.chat("hello", "gpt-4o", 0.7).await.unwrap_err();
+        assert!(err.to_string().contains("API key not set"));
+    }
+
+    #[test]
+    fn request_omits_model_field() {
+        let req = ChatRequest {
+            messages: vec![],
+            temperature: 0.5,
+            reasoning_effort: None,
+        };
+        let json = serde_json::to_string(&req).unwrap();
+        assert!(!json.contains("model"));
+        assert!(!json.contains("reasoning_effort"));
+    }
+
+    #[test]
+    fn response_tool_calls_serialize_for_agent_loop() {
+        let json = r#"{"choices":[{"message":{"content":null,"tool_calls":[{"id":"c1","type":"function","function":{"name":"shell","arguments":"{}"}}]}}]}"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        let text = resp.choices.into_iter().next().unwrap().message.into_text();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["tool_calls"][0]["function"]["name"], "shell");
+    }
+
+    #[tokio::test]
+    async fn chat_with_system_against_stand_in() {
+        use axum::{
+            extract::State,
+            http::{HeaderMap, Uri},
+            routing::post,
+            Json, Router,
+        };
+        use std::sync::{Arc, Mutex};
+
+        type Seen = Arc<Mutex<Option<(Uri, HeaderMap, serde_json::Value)>>>;
+        let seen: Seen = Arc::default();
+        let app = Router::new()
+            .route(
+                "/openai/deployments/:deployment/chat/completions",
+                post(
+                    |State(seen): State<Seen>,
+                     uri: Uri,
+                     headers: HeaderMap,
+                     Json(body): Json<serde_json::Value>| async move {
+                        *seen.lock().unwrap() = Some((uri, headers, body));
+                        Json(serde_json::json!({
+                            "choices": [{"message": {"content": "pong"}}]
+                        }))
+                    },
+                ),
+            )
+            .with_state(seen.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+
+        let mut cfg = config();
+        cfg.endpoint = Some(format!("http://{addr}"));
+        cfg.api_key = Some("azure-key".into());
+        let p = AzureOpenAiProvider::new(None, None, &cfg);
+        let reply = p
+            .chat_with_system(Some("sys"), "ping", "gpt-4o", 0.2)
+            .await
+            .unwrap();
+        assert_eq!(reply, "pong");
+
+        let (uri, headers, body) = seen.lock().unwrap().take().unwrap();
+        assert_eq!(
+            uri.path(),
+            "/openai/deployments/prod-gpt4o/chat/completions"
+        );
+        assert_eq!(
+            uri.query(),
+            Some(format!("api-version={}", cfg.api_version).as_str())
+        );
+        assert_eq!(headers.get("api-key").unwrap(), "azure-key");
+        assert!(headers.get("authorization").is_none());
+        assert_eq!(body["messages"][0]["role"], "system");
+        assert_eq!(body["messages"][1]["content"], "ping");
+        assert!(body.get("model").is_none());
+    }
+}
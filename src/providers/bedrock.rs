@@ -0,0 +1,981 @@
+//! Native Amazon Bedrock provider.
+//!
+//! Calls the Bedrock Runtime Converse API (`POST /model/{modelId}/converse`)
+//! and signs every request with AWS Signature Version 4 using standard AWS
+//! credentials. Bedrock API keys (bearer tokens) are also accepted.
+
+use crate::providers::reasoning::wrap_reasoning;
+use crate::providers::traits::{ChatMessage, Provider};
+use crate::tools::ToolSpec;
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+const SERVICE: &str = "bedrock";
+const DEFAULT_REGION: &str = "us-east-1";
+const DEFAULT_MAX_TOKENS: u32 = 4096;
+/// Stand-in first turn when history would otherwise open with the assistant.
+const LEADING_USER_TURN: &str = "(conversation continues)";
+
+/// Static AWS credentials used for SigV4 signing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AwsCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    /// Read `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY` / `AWS_SESSION_TOKEN`.
+    pub fn from_env() -> Option<Self> {
+        let access_key_id = non_empty_env("AWS_ACCESS_KEY_ID")?;
+        let secret_access_key = non_empty_env("AWS_SECRET_ACCESS_KEY")?;
+        Some(Self {
+            access_key_id,
+            secret_access_key,
+            session_token: non_empty_env("AWS_SESSION_TOKEN"),
+        })
+    }
+
+    /// Read a profile from an AWS shared credentials file.
+    pub fn from_profile_file(path: &Path, profile: &str) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let sections = parse_ini(&content);
+        let section = sections.get(profile)?;
+        Some(Self {
+            access_key_id: section.get("aws_access_key_id")?.clone(),
+            secret_access_key: section.get("aws_secret_access_key")?.clone(),
+            session_token: section.get("aws_session_token").cloned(),
+        })
+    }
+
+    /// Resolve credentials: environment first, then the shared credentials
+    /// file (`AWS_SHARED_CREDENTIALS_FILE` or `~/.aws/credentials`) for
+    /// `AWS_PROFILE` (default: `default`).
+    pub fn resolve() -> Option<Self> {
+        Self::from_env().or_else(|| {
+            let path = non_empty_env("AWS_SHARED_CREDENTIALS_FILE")
+                .map(PathBuf::from)
+                .or_else(|| aws_dir().map(|d| d.join("credentials")))?;
+            Self::from_profile_file(&path, &aws_profile())
+        })
+    }
+}
+
+/// How requests are authenticated.
+#[derive(Debug, Clone)]
+enum BedrockAuth {
+    /// SigV4 with access key / secret key (+ optional session token).
+    SigV4(AwsCredentials),
+    /// Bedrock API key sent as `Authorization: Bearer`.
+    Bearer(String),
+}
+
+pub struct BedrockProvider {
+    auth: Option<BedrockAuth>,
+    region: String,
+    endpoint: String,
+    client: Client,
+}
+
+impl BedrockProvider {
+    /// Create a provider from the environment.
+    ///
+    /// Authentication priority:
+    /// 1. Explicit API key passed in (Bedrock API key, bearer auth)
+    /// 2. `AWS_BEARER_TOKEN_BEDROCK` environment variable
+    /// 3. `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY` environment variables
+    /// 4. Shared credentials file profile (`AWS_PROFILE`, default `default`)
+    ///
+    /// Region comes from `AWS_REGION`, `AWS_DEFAULT_REGION`, the profile in
+    /// `~/.aws/config`, or falls back to `us-east-1`.
+    pub fn new(api_key: Option<&str>) -> Self {
+        let auth = api_key
+            .map(str::trim)
+            .filter(|k| !k.is_empty())
+            .map(ToString::to_string)
+            .or_else(|| non_empty_env("AWS_BEARER_TOKEN_BEDROCK"))
+            .map(BedrockAuth::Bearer)
+            .or_else(|| AwsCredentials::resolve().map(BedrockAuth::SigV4));
+        let region = resolve_region();
+        let endpoint = format!("https://bedrock-runtime.{region}.amazonaws.com");
+        Self::build(auth, region, endpoint)
+    }
+
+    /// Create a provider with explicit SigV4 credentials and endpoint
+    /// (e.g. a VPC endpoint or a local stand-in).
+    pub fn with_credentials(credentials: AwsCredentials, region: &str, endpoint: &str) -> Self {
+        Self::build(
+            Some(BedrockAuth::SigV4(credentials)),
+            region.to_string(),
+            endpoint.trim_end_matches('/').to_string(),
+        )
+    }
+
+    fn build(auth: Option<BedrockAuth>, region: String, endpoint: String) -> Self {
+        Self {
+            auth,
+            region,
+            endpoint,
+            client: crate::http::client("bedrock", crate::http::Defaults::PROVIDER),
+        }
+    }
+
+    fn converse_path(model: &str) -> String {
+        format!("/model/{}/converse", uri_encode(model, true))
+    }
+
+    async fn converse(&self, request: &ConverseRequest, model: &str) -> anyhow::Result<String> {
+        let auth = self.auth.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "Amazon Bedrock credentials not set. Set AWS_ACCESS_KEY_ID/AWS_SECRET_ACCESS_KEY, \
+                 configure an AWS profile (AWS_PROFILE), or set AWS_BEARER_TOKEN_BEDROCK."
+            )
+        })?;
+
+        let path = Self::converse_path(model);
+        let url = format!("{}{path}", self.endpoint);
+        let body = serde_json::to_vec(request)?;
+
+        let mut builder = self
+            .client
+            .post(&url)
+            .header("content-type", "application/json");
+
+        match auth {
+            BedrockAuth::Bearer(token) => {
+                builder = builder.header("Authorization", format!("Bearer {token}"));
+            }
+            BedrockAuth::SigV4(credentials) => {
+                let parsed = reqwest::Url::parse(&url)?;
+                let host = match (parsed.host_str(), parsed.port()) {
+                    (Some(host), Some(port)) => format!("{host}:{port}"),
+                    (Some(host), None) => host.to_string(),
+                    (None, _) => anyhow::bail!("Invalid Bedrock endpoint: {url}"),
+                };
+                let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+                let signed = sign_v4(&SigningInput {
+                    method: "POST",
+                    host: &host,
+                    path: &path,
+                    query: "",
+                    content_type: Some("application/json"),
+                    body: &body,
+                    amz_date: &amz_date,
+                    region: &self.region,
+                    service: SERVICE,
+                    credentials,
+                });
+                builder = builder
+                    .header("x-amz-date", amz_date)
+                    .header("Authorization", signed);
+                if let Some(token) = &credentials.session_token {
+                    builder = builder.header("x-amz-security-token", token);
+                }
+            }
+        }
+
+        let response = builder.body(body).send().await?;
+
+        if !response.status().is_success() {
+            return Err(super::api_error("Amazon Bedrock", response).await);
+        }
+
+        let converse: ConverseResponse = response.json().await?;
+        converse
+            .into_text()
+            .ok_or_else(|| anyhow::anyhow!("No response from Amazon Bedrock"))
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// CONVERSE API TYPES
+// ══════════════════════════════════════════════════════════════════════════════
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConverseRequest {
+    messages: Vec<ConverseMessage>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    system: Vec<TextBlock>,
+    inference_config: InferenceConfig,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_config: Option<ToolConfig>,
+}
+
+#[derive(Debug, Serialize)]
+struct ConverseMessage {
+    role: String,
+    content: Vec<TextBlock>,
+}
+
+#[derive(Debug, Serialize)]
+struct TextBlock {
+    text: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InferenceConfig {
+    temperature: f64,
+    max_tokens: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct ToolConfig {
+    tools: Vec<ToolEntry>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolEntry {
+    tool_spec: BedrockToolSpec,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BedrockToolSpec {
+    name: String,
+    description: String,
+    input_schema: InputSchema,
+}
+
+#[derive(Debug, Serialize)]
+struct InputSchema {
+    json: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConverseResponse {
+    output: Option<ConverseOutput>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConverseOutput {
+    message: Option<OutputMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OutputMessage {
+    #[serde(default)]
+    content: Vec<OutputBlock>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OutputBlock {
+    text: Option<String>,
+    tool_use: Option<ToolUse>,
+    reasoning_content: Option<ReasoningContent>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ToolUse {
+    tool_use_id: String,
+    name: String,
+    #[serde(default)]
+    input: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReasoningContent {
+    reasoning_text: Option<ReasoningText>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReasoningText {
+    text: String,
+}
+
+impl ConverseResponse {
+    /// Flatten the output message into the string form used by the agent loop:
+    /// plain text, or OpenAI-style `tool_calls` JSON when the model requested
+    /// tools, with any reasoning prepended as a `<think>` block.
+    fn into_text(self) -> Option<String> {
+        let message = self.output?.message?;
+        let mut text = String::new();
+        let mut reasoning = Vec::new();
+        let mut tool_calls = Vec::new();
+
+        for block in message.content {
+            if let Some(t) = block.text {
+                text.push_str(&t);
+            }
+            if let Some(r) = block.reasoning_content.and_then(|r| r.reasoning_text) {
+                reasoning.push(r.text);
+            }
+            if let Some(tool) = block.tool_use {
+                tool_calls.push(serde_json::json!({
+                    "id": tool.tool_use_id,
+                    "type": "function",
+                    "function": {
+                        "name": tool.name,
+                        "arguments": tool.input.to_string(),
+                    }
+                }));
+            }
+        }
+
+        let body = if tool_calls.is_empty() {
+            text
+        } else {
+            serde_json::json!({
+                "content": if text.is_empty() { None } else { Some(text) },
+                "tool_calls": tool_calls,
+            })
+            .to_string()
+        };
+
+        Some(wrap_reasoning(Some(&reasoning.join("\n\n")), &body))
+    }
+}
+
+/// Convert chat history into Converse messages. System prompts are lifted
+/// into the top-level `system` field and consecutive same-role messages are
+/// merged, since Converse requires alternating user/assistant turns.
+fn build_request(
+    messages: &[ChatMessage],
+    tools: &[ToolSpec],
+    temperature: f64,
+) -> ConverseRequest {
+    let mut system = Vec::new();
+    let mut converse: Vec<ConverseMessage> = Vec::new();
+
+    for msg in messages {
+        if msg.role == "system" {
+            system.push(TextBlock {
+                text: msg.content.clone(),
+            });
+            continue;
+        }
+        let role = if msg.role == "assistant" {
+            "assistant"
+        } else {
+            "user"
+        };
+        match converse.last_mut() {
+            Some(last) if last.role == role => last.content.push(TextBlock {
+                text: msg.content.clone(),
+            }),
+            _ => converse.push(ConverseMessage {
+                role: role.to_string(),
+                content: vec![TextBlock {
+                    text: msg.content.clone(),
+                }],
+            }),
+        }
+    }
+
+    // Converse rejects conversations that don't open with a user turn.
+    if converse.first().map_or(true, |m| m.role != "user") {
+        converse.insert(
+            0,
+            ConverseMessage {
+                role: "user".to_string(),
+                content: vec![TextBlock {
+                    text: LEADING_USER_TURN.to_string(),
+                }],
+            },
+        );
+    }
+
+    let tool_config = if tools.is_empty() {
+        None
+    } else {
+        Some(ToolConfig {
+            tools: tools
+                .iter()
+                .map(|t| ToolEntry {
+                    tool_spec: BedrockToolSpec {
+                        name: t.name.clone(),
+                        description: t.description.clone(),
+                        input_schema: InputSchema {
+                            json: t.parameters.clone(),
+                        },
+                    },
+                })
+                .collect(),
+        })
+    };
+
+    ConverseRequest {
+        messages: converse,
+        system,
+        inference_config: InferenceConfig {
+            temperature,
+            max_tokens: DEFAULT_MAX_TOKENS,
+        },
+        tool_config,
+    }
+}
+
+#[async_trait]
+impl Provider for BedrockProvider {
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut messages = Vec::new();
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+        self.chat_with_tools(&messages, &[], model, temperature)
+            .await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.chat_with_tools(messages, &[], model, temperature)
+            .await
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[ToolSpec],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let request = build_request(messages, tools, temperature);
+        self.converse(&request, model).await
+    }
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// SIGV4 SIGNING
+// ══════════════════════════════════════════════════════════════════════════════
+
+struct SigningInput<'a> {
+    method: &'a str,
+    host: &'a str,
+    /// Request path exactly as sent on the wire (already URI-encoded).
+    path: &'a str,
+    /// Canonical (sorted, encoded) query string.
+    query: &'a str,
+    content_type: Option<&'a str>,
+    body: &'a [u8],
+    amz_date: &'a str,
+    region: &'a str,
+    service: &'a str,
+    credentials: &'a AwsCredentials,
+}
+
+/// Compute the SigV4 `Authorization` header value for a request.
+///
+/// Signed headers are `content-type` (when present), `host`, `x-amz-date` and
+/// `x-amz-security-token` (when using temporary credentials).
+fn sign_v4(input: &SigningInput<'_>) -> String {
+    let date = &input.amz_date[..8];
+
+    let mut headers: Vec<(&str, &str)> = Vec::new();
+    if let Some(ct) = input.content_type {
+        headers.push(("content-type", ct));
+    }
+    headers.push(("host", input.host));
+    headers.push(("x-amz-date", input.amz_date));
+    if let Some(token) = &input.credentials.session_token {
+        headers.push(("x-amz-security-token", token));
+    }
+
+    let mut canonical_headers = String::new();
+    for (k, v) in &headers {
+        let _ = writeln!(canonical_headers, "{k}:{}", v.trim());
+    }
+    let signed_headers = headers
+        .iter()
+        .map(|(k, _)| *k)
+        .collect::<Vec<_>>()
+        .join(";");
+
+    // Non-S3 services double-encode the path: each segment as sent is encoded again.
+    let canonical_uri = input
+        .path
+        .split('/')
+        .map(|segment| uri_encode(segment, true))
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let canonical_request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        input.method,
+        canonical_uri,
+        input.query,
+        canonical_headers,
+        signed_headers,
+        hex::encode(Sha256::digest(input.body))
+    );
+
+    let scope = format!("{date}/{}/{}/aws4_request", input.region, input.service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{scope}\n{}",
+        input.amz_date,
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let k_date = hmac_sha256(
+        format!("AWS4{}", input.credentials.secret_access_key).as_bytes(),
+        date.as_bytes(),
+    );
+    let k_region = hmac_sha256(&k_date, input.region.as_bytes());
+    let k_service = hmac_sha256(&k_region, input.service.as_bytes());
+    let k_signing = hmac_sha256(&k_service, b"aws4_request");
+    let signature = hex::encode(hmac_sha256(&k_signing, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+        input.credentials.access_key_id
+    )
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    // HMAC accepts keys of any length, so this cannot fail.
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// RFC 3986 percent-encoding as required by SigV4 (unreserved characters are
+/// left as-is). When `encode_slash` is false, `/` is preserved.
+fn uri_encode(input: &str, encode_slash: bool) -> String {
+    let mut out = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(byte as char);
+            }
+            b'/' if !encode_slash => out.push('/'),
+            _ => {
+                let _ = write!(out, "%{byte:02X}");
+            }
+        }
+    }
+    out
+}
+
+// ══════════════════════════════════════════════════════════════════════════════
+// AWS CONFIG HELPERS
+// ══════════════════════════════════════════════════════════════════════════════
+
+fn non_empty_env(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn aws_dir() -> Option<PathBuf> {
+    directories::UserDirs::new().map(|u| u.home_dir().join(".aws"))
+}
+
+fn aws_profile() -> String {
+    non_empty_env("AWS_PROFILE").unwrap_or_else(|| "default".to_string())
+}
+
+fn resolve_region() -> String {
+    non_empty_env("AWS_REGION")
+        .or_else(|| non_empty_env("AWS_DEFAULT_REGION"))
+        .or_else(|| {
+            let path = non_empty_env("AWS_CONFIG_FILE")
+                .map(PathBuf::from)
+                .or_else(|| aws_dir().map(|d| d.join("config")))?;
+            region_from_config_file(&path, &aws_profile())
+        })
+        .unwrap_or_else(|| DEFAULT_REGION.to_string())
+}
+
+/// Read `region` for a profile from `~/.aws/config`, where non-default
+/// profiles are written as `[profile name]`.
+fn region_from_config_file(path: &Path, profile: &str) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let sections = parse_ini(&content);
+    let section = if profile == "default" {
+        sections.get("default")
+    } else {
+        sections
+            .get(&format!("profile {profile}"))
+            .or_else(|| sections.get(profile))
+    }?;
+    section.get("region").cloned()
+}
+
+/// Minimal INI parser for AWS shared config/credentials files.
+fn parse_ini(content: &str) -> HashMap<String, HashMap<String, String>> {
+    let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut current: Option<String> = None;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let name = name.trim().to_string();
+            sections.entry(name.clone()).or_default();
+            current = Some(name);
+            continue;
+        }
+        if let (Some(section), Some((key, value))) = (&current, line.split_once('=')) {
+            sections
+                .entry(section.clone())
+                .or_default()
+                .insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        extract::State,
+        http::{HeaderMap, StatusCode},
+        routing::post,
+        Json, Router,
+    };
+    use std::sync::{Arc, Mutex};
+
+    fn example_credentials() -> AwsCredentials {
+        AwsCredentials {
+            access_key_id: "AKIDEXAMPLE".into(),
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".into(),
+            session_token: None,
+        }
+    }
+
+    #[test]
+    fn sigv4_matches_aws_get_vanilla_vector() {
+        // AWS SigV4 test suite: get-vanilla
+        let creds = example_credentials();
+        let auth = sign_v4(&SigningInput {
+            method: "GET",
+            host: "example.amazonaws.com",
+            path: "/",
+            query: "",
+            content_type: None,
+            body: b"",
+            amz_date: "20150830T123600Z",
+            region: "us-east-1",
+            service: "service",
+            credentials: &creds,
+        });
+        assert_eq!(
+            auth,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn sigv4_includes_session_token_header() {
+        let mut creds = example_credentials();
+        creds.session_token = Some("session".into());
+        let auth = sign_v4(&SigningInput {
+            method: "POST",
+            host: "bedrock-runtime.us-east-1.amazonaws.com",
+            path: "/model/m/converse",
+            query: "",
+            content_type: Some("application/json"),
+            body: b"{}",
+            amz_date: "20240101T000000Z",
+            region: "us-east-1",
+            service: SERVICE,
+            credentials: &creds,
+        });
+        assert!(auth.contains("SignedHeaders=content-type;host;x-amz-date;x-amz-security-token"));
+        assert!(auth.contains("/20240101/us-east-1/bedrock/aws4_request"));
+    }
+
+    #[test]
+    fn converse_path_encodes_model_id() {
+        assert_eq!(
+            BedrockProvider::converse_path("anthropic.claude-3-5-sonnet-20240620-v1:0"),
+            "/model/anthropic.claude-3-5-sonnet-20240620-v1%3A0/converse"
+        );
+    }
+
+    #[test]
+    fn uri_encode_reserved_characters() {
+        assert_eq!(uri_encode("a b:c/d", true), "a%20b%3Ac%2Fd");
+        assert_eq!(uri_encode("a/b", false), "a/b");
+        assert_eq!(uri_encode("%3A", true), "%253A");
+    }
+
+    #[test]
+    fn build_request_lifts_system_and_merges_roles() {
+        let messages = vec![
+            ChatMessage::system("Be brief"),
+            ChatMessage::user("hi"),
+            ChatMessage::user("[Tool results]\nok"),
+            ChatMessage::assistant("hello"),
+        ];
+        let req = build_request(&messages, &[], 0.5);
+        assert_eq!(req.system.len(), 1);
+        assert_eq!(req.messages.len(), 2);
+        assert_eq!(req.messages[0].role, "user");
+        assert_eq!(req.messages[0].content.len(), 2);
+        assert_eq!(req.messages[1].role, "assistant");
+        assert!(req.tool_config.is_none());
+
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["inferenceConfig"]["maxTokens"], DEFAULT_MAX_TOKENS);
+        assert_eq!(json["system"][0]["text"], "Be brief");
+    }
+
+    #[test]
+    fn build_request_always_opens_with_user_turn() {
+        let messages = vec![
+            ChatMessage::system("Be brief"),
+            ChatMessage::assistant("Earlier answer"),
+            ChatMessage::user("follow-up"),
+        ];
+        let req = build_request(&messages, &[], 0.5);
+        let roles: Vec<&str> = req.messages.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["user", "assistant", "user"]);
+        assert_eq!(req.messages[0].content[0].text, LEADING_USER_TURN);
+
+        let only_system = build_request(&[ChatMessage::system("Be brief")], &[], 0.5);
+        assert_eq!(only_system.messages.len(), 1);
+        assert_eq!(only_system.messages[0].role, "user");
+
+        let normal = build_request(&[ChatMessage::user("hi")], &[], 0.5);
+        assert_eq!(normal.messages.len(), 1);
+        assert_eq!(normal.messages[0].content[0].text, "hi");
+    }
+
+    #[test]
+    fn build_request_includes_tool_config() {
+        let tools = vec![ToolSpec {
+            name: "shell".into(),
+            description: "Run a command".into(),
+            parameters: serde_json::json!({"type": "object"}),
+        }];
+        let req = build_request(&[ChatMessage::user("ls")], &tools, 0.0);
+        let json = serde_json::to_value(&req).unwrap();
+        assert_eq!(json["toolConfig"]["tools"][0]["toolSpec"]["name"], "shell");
+        assert_eq!(
+            json["toolConfig"]["tools"][0]["toolSpec"]["inputSchema"]["json"]["type"],
+            "object"
+        );
+    }
+
+    #[test]
+    fn response_text_only() {
+        let json = r#"{"output":{"message":{"role":"assistant","content":[{"text":"Hello"}]}},"stopReason":"end_turn"}"#;
+        let resp: ConverseResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.into_text().as_deref(), Some("Hello"));
+    }
+
+    #[test]
+    fn response_tool_use_becomes_tool_calls_json() {
+        let json = r#"{"output":{"message":{"role":"assistant","content":[
+            {"text":"Checking."},
+            {"toolUse":{"toolUseId":"t1","name":"shell","input":{"command":"ls"}}}
+        ]}},"stopReason":"tool_use"}"#;
+        let resp: ConverseResponse = serde_json::from_str(json).unwrap();
+        let text = resp.into_text().unwrap();
+        let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(value["content"], "Checking.");
+        assert_eq!(value["tool_calls"][0]["function"]["name"], "shell");
+        let args: serde_json::Value = serde_json::from_str(
+            value["tool_calls"][0]["function"]["arguments"]
+                .as_str()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(args["command"], "ls");
+    }
+
+    #[test]
+    fn response_reasoning_becomes_think_block() {
+        let json = r#"{"output":{"message":{"role":"assistant","content":[
+            {"reasoningContent":{"reasoningText":{"text":"hmm"}}},
+            {"text":"Done"}
+        ]}}}"#;
+        let resp: ConverseResponse = serde_json::from_str(json).unwrap();
+        let (reasoning, answer) =
+            crate::providers::reasoning::split_reasoning(&resp.into_text().unwrap());
+        assert_eq!(reasoning.as_deref(), Some("hmm"));
+        assert_eq!(answer, "Done");
+    }
+
+    #[test]
+    fn parse_ini_reads_profiles() {
+        let ini = "[default]\naws_access_key_id = AKID\naws_secret_access_key=SECRET\n\n# comment\n[profile work]\nregion = eu-west-1\n";
+        let sections = parse_ini(ini);
+        assert_eq!(sections["default"]["aws_access_key_id"], "AKID");
+        assert_eq!(sections["default"]["aws_secret_access_key"], "SECRET");
+        assert_eq!(sections["profile work"]["region"], "eu-west-1");
+    }
+
+    #[test]
+    fn credentials_and_region_from_files() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let creds_path = tmp.path().join("credentials");
+        std::fs::write(
+            &creds_path,
+            "[work]\naws_access_key_id=AKIDWORK\naws_secret_access_key=S\naws_session_token=T\n",
+        )
+        .unwrap();
+        let creds = AwsCredentials::from_profile_file(&creds_path, "work").unwrap();
+        assert_eq!(creds.access_key_id, "AKIDWORK");
+        assert_eq!(creds.session_token.as_deref(), Some("T"));
+        assert!(AwsCredentials::from_profile_file(&creds_path, "missing").is_none());
+
+        let config_path = tmp.path().join("config");
+        std::fs::write(
+            &config_path,
+            "[default]\nregion=us-west-2\n[profile work]\nregion=eu-central-1\n",
+        )
+        .unwrap();
+        assert_eq!(
+            region_from_config_file(&config_path, "work").as_deref(),
+            Some("eu-central-1")
+        );
+        assert_eq!(
+            region_from_config_file(&config_path, "default").as_deref(),
+            Some("us-west-2")
+        );
+    }
+
+    #[tokio::test]
+    async fn chat_fails_without_credentials() {
+        let p = BedrockProvider::build(None, "us-east-1".into(), "http://127.0.0.1:1".into());
+        let err = p
+            .chat_with_system(None, "hello", "model", 0.7)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("credentials not set"));
+    }
+
+    // ── Local stand-in endpoint ─────────────────────────────────
+
+    #[derive(Clone, Default)]
+    struct Captured {
+        headers: Arc<Mutex<Option<HeaderMap>>>,
+        body: Arc<Mutex<Option<serde_json::Value>>>,
+    }
+
+    async fn spawn_stand_in(reply: serde_json::Value, status: StatusCode) -> (String, Captured) {
+        let captured = Captured::default();
+        let app = Router::new()
+            .route(
+                "/model/:model/converse",
+                post(
+                    move |State(c): State<Captured>,
+                          headers: HeaderMap,
+                          Json(body): Json<serde_json::Value>| {
+                        let reply = reply.clone();
+                        async move {
+                            *c.headers.lock().unwrap() = Some(headers);
+                            *c.body.lock().unwrap() = Some(body);
+                            (status, Json(reply))
+                        }
+                    },
+                ),
+            )
+            .with_state(captured.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+        (format!("http://{addr}"), captured)
+    }
+
+    #[tokio::test]
+    async fn converse_round_trip_against_stand_in() {
+        let (endpoint, captured) = spawn_stand_in(
+            serde_json::json!({
+                "output": {"message": {"role": "assistant", "content": [{"text": "pong"}]}},
+                "stopReason": "end_turn"
+            }),
+            StatusCode::OK,
+        )
+        .await;
+
+        let mut creds = example_credentials();
+        creds.session_token = Some("tok".into());
+        let p = BedrockProvider::with_credentials(creds, "us-west-2", &endpoint);
+        let reply = p
+            .chat_with_system(Some("sys"), "ping", "anthropic.claude-v2:1", 0.2)
+            .await
+            .unwrap();
+        assert_eq!(reply, "pong");
+
+        let headers = captured.headers.lock().unwrap().clone().unwrap();
+        let auth = headers.get("authorization").unwrap().to_str().unwrap();
+        assert!(auth.starts_with("AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/"));
+        assert!(auth.contains("/us-west-2/bedrock/aws4_request"));
+        assert!(headers.get("x-amz-date").is_some());
+        assert_eq!(headers.get("x-amz-security-token").unwrap(), "tok");
+
+        let body = captured.body.lock().unwrap().clone().unwrap();
+        assert_eq!(body["system"][0]["text"], "sys");
+        assert_eq!(body["messages"][0]["content"][0]["text"], "ping");
+    }
+
+    #[tokio::test]
+    async fn converse_tool_use_against_stand_in() {
+        let (endpoint, captured) = spawn_stand_in(
+            serde_json::json!({
+                "output": {"message": {"role": "assistant", "content": [
+                    {"toolUse": {"toolUseId": "abc", "name": "file_read", "input": {"path": "a.txt"}}}
+                ]}},
+                "stopReason": "tool_use"
+            }),
+            StatusCode::OK,
+        )
+        .await;
+
+        let p = BedrockProvider::with_credentials(example_credentials(), "us-east-1", &endpoint);
+        let tools = vec![ToolSpec {
+            name: "file_read".into(),
+            description: "Read a file".into(),
+            parameters: serde_json::json!({"type": "object", "properties": {"path": {"type": "string"}}}),
+        }];
+        let reply = p
+            .chat_with_tools(&[ChatMessage::user("read a.txt")], &tools, "m", 0.0)
+            .await
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_str(&reply).unwrap();
+        assert_eq!(value["tool_calls"][0]["function"]["name"], "file_read");
+
+        let body = captured.body.lock().unwrap().clone().unwrap();
+        assert_eq!(
+            body["toolConfig"]["tools"][0]["toolSpec"]["name"],
+            "file_read"
+        );
+    }
+
+    #[tokio::test]
+    async fn converse_error_is_sanitized() {
+        let (endpoint, _) = spawn_stand_in(
+            serde_json::json!({"message": "The security token included in the request is invalid."}),
+            StatusCode::FORBIDDEN,
+        )
+        .await;
+
+        let p = BedrockProvider::with_credentials(example_credentials(), "us-east-1", &endpoint);
+        let err = p.chat("hi", "m", 0.0).await.unwrap_err().to_string();
+        assert!(err.contains("Amazon Bedrock API error (403 Forbidden)"));
+        assert!(err.contains("security token"));
+    }
+}
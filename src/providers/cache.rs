@@ -0,0 +1,782 @@
+//! SQLite-backed provider response cache.
+//!
+//! Wraps any [`Provider`] and serves repeated `(system prompt, history, model,
+//! temperature)` requests from `{workspace}/cache/responses.db`:
+//! - **Exact mode**: SHA-256 of the full request, valid for `ttl_secs`
+//! - **Similarity mode**: when `similarity_threshold` is set, a request whose
+//!   earlier context matches exactly and whose final user message is close
+//!   enough (cosine similarity over `memory::embeddings`) reuses the answer
+//!
+//! Turns that carry tool definitions bypass the cache unless
+//! `cache_tool_turns` is set, in which case the tool names and schemas are part
+//! of the key. Channel traffic with per-user or per-channel memory scopes uses
+//! exact mode only (see [`wrap_channel_provider`]). Only requests sent at
+//! temperature 0 are cached unless `cache_nonzero_temperature` is set; with
+//! the default `default_temperature = 0.7` that flag is what lets repeated
+//! heartbeat and cron prompts hit. Every lookup is reported to the observer
+//! together with the running hit rate.
+
+use super::traits::{ChatMessage, ImageInput, Provider};
+use crate::config::ResponseCacheConfig;
+use crate::memory::embeddings::EmbeddingProvider;
+use crate::memory::vector;
+use crate::observability::traits::ObserverMetric;
+use crate::observability::{Observer, ObserverEvent};
+use crate::tools::ToolSpec;
+use async_trait::async_trait;
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// The cache store: SQLite table, lookup policy and hit-rate counters.
+pub struct ResponseCache {
+    conn: Mutex<Connection>,
+    config: ResponseCacheConfig,
+    embedder: Option<Arc<dyn EmbeddingProvider>>,
+    observer: Arc<dyn Observer>,
+    lookups: AtomicU64,
+    hits: AtomicU64,
+}
+
+/// Cache keys for one request.
+struct CacheKeys {
+    /// Hash of the whole request.
+    exact: String,
+    /// Hash of everything except the final user message (similarity scope).
+    context: String,
+    /// Final user message, embedded in similarity mode.
+    query: Option<String>,
+}
+
+impl ResponseCache {
+    pub fn open(
+        workspace_dir: &Path,
+        config: &ResponseCacheConfig,
+        embedder: Option<Arc<dyn EmbeddingProvider>>,
+        observer: Arc<dyn Observer>,
+    ) -> anyhow::Result<Self> {
+        let db_path = workspace_dir.join("cache").join("responses.db");
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let conn = Connection::open(&db_path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS response_cache (
+                key         TEXT PRIMARY KEY,
+                context_key TEXT NOT NULL,
+                response    TEXT NOT NULL,
+                embedding   BLOB,
+                created_at  INTEGER NOT NULL,
+                hit_count   INTEGER NOT NULL DEFAULT 0
+            );
+            CREATE INDEX IF NOT EXISTS idx_response_cache_context ON response_cache(context_key);
+            CREATE INDEX IF NOT EXISTS idx_response_cache_created ON response_cache(created_at);",
+        )?;
+
+        // Similarity mode needs a real embedder; "none" silently degrades to exact-only.
+        let embedder =
+            embedder.filter(|e| e.dimensions() > 0 && config.similarity_threshold.is_some());
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            config: config.clone(),
+            embedder,
+            observer,
+            lookups: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+        })
+    }
+
+    fn should_cache(&self, tools: &[ToolSpec], temperature: f64) -> bool {
+        (temperature == 0.0 || self.config.cache_nonzero_temperature)
+            && (tools.is_empty() || self.config.cache_tool_turns)
+    }
+
+    fn keys(
+        messages: &[ChatMessage],
+        tools: &[ToolSpec],
+        model: &str,
+        temperature: f64,
+    ) -> CacheKeys {
+        // Names, descriptions and schemas: a changed tool invalidates its turns.
+        let tools_hash = hex::encode(Sha256::digest(
+            serde_json::to_string(tools).unwrap_or_default().as_bytes(),
+        ));
+        let hash = |messages: &[ChatMessage]| {
+            let payload = serde_json::json!({
+                "model": model,
+                "temperature": temperature,
+                "tools": tools_hash,
+                "messages": messages,
+            });
+            hex::encode(Sha256::digest(payload.to_string().as_bytes()))
+        };
+
+        let (context, query) = match messages.split_last() {
+            Some((last, rest)) if last.role == "user" => (hash(rest), Some(last.content.clone())),
+            _ => (hash(messages), None),
+        };
+
+        CacheKeys {
+            exact: hash(messages),
+            context,
+            query,
+        }
+    }
+
+    fn min_created_at(&self) -> i64 {
+        if self.config.ttl_secs == 0 {
+            return i64::MIN;
+        }
+        let ttl = i64::try_from(self.config.ttl_secs).unwrap_or(i64::MAX);
+        chrono::Utc::now().timestamp().saturating_sub(ttl)
+    }
+
+    fn lookup_exact(&self, key: &str) -> anyhow::Result<Option<String>> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+        let response: Option<String> = conn
+            .query_row(
+                "SELECT response FROM response_cache WHERE key = ?1 AND created_at >= ?2",
+                params![key, self.min_created_at()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        if response.is_some() {
+            conn.execute(
+                "UPDATE response_cache SET hit_count = hit_count + 1 WHERE key = ?1",
+                params![key],
+            )?;
+        }
+        Ok(response)
+    }
+
+    fn lookup_similar(&self, context: &str, embedding: &[f32]) -> anyhow::Result<Option<String>> {
+        let threshold = self.config.similarity_threshold.unwrap_or(1.0);
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+        let mut stmt = conn.prepare(
+            "SELECT key, response, embedding FROM response_cache
+             WHERE context_key = ?1 AND embedding IS NOT NULL AND created_at >= ?2",
+        )?;
+        let rows = stmt.query_map(params![context, self.min_created_at()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Vec<u8>>(2)?,
+            ))
+        })?;
+
+        let mut best: Option<(f32, String, String)> = None;
+        for row in rows {
+            let (key, response, blob) = row?;
+            let score = vector::cosine_similarity(embedding, &vector::bytes_to_vec(&blob));
+            if f64::from(score) >= threshold && best.as_ref().map_or(true, |(b, _, _)| score > *b) {
+                best = Some((score, key, response));
+            }
+        }
+        drop(stmt);
+
+        Ok(best.map(|(_, key, response)| {
+            let _ = conn.execute(
+                "UPDATE response_cache SET hit_count = hit_count + 1 WHERE key = ?1",
+                params![key],
+            );
+            response
+        }))
+    }
+
+    fn store(
+        &self,
+        keys: &CacheKeys,
+        response: &str,
+        embedding: Option<&[f32]>,
+    ) -> anyhow::Result<()> {
+        let conn = self
+            .conn
+            .lock()
+            .map_err(|e| anyhow::anyhow!("Lock error: {e}"))?;
+        conn.execute(
+            "INSERT OR REPLACE INTO response_cache (key, context_key, response, embedding, created_at, hit_count)
+             VALUES (?1, ?2, ?3, ?4, ?5, 0)",
+            params![
+                keys.exact,
+                keys.context,
+                response,
+                embedding.map(vector::vec_to_bytes),
+                chrono::Utc::now().timestamp(),
+            ],
+        )?;
+
+        // Drop expired rows, then evict the oldest beyond `max_entries`.
+        conn.execute(
+            "DELETE FROM response_cache WHERE created_at < ?1",
+            params![self.min_created_at()],
+        )?;
+        conn.execute(
+            "DELETE FROM response_cache WHERE key IN (
+                SELECT key FROM response_cache ORDER BY created_at DESC LIMIT -1 OFFSET ?1
+            )",
+            params![i64::try_from(self.config.max_entries).unwrap_or(i64::MAX)],
+        )?;
+        Ok(())
+    }
+
+    fn record_lookup(&self, hit: Option<&str>) {
+        let lookups = self.lookups.fetch_add(1, Ordering::Relaxed) + 1;
+        let hits = if hit.is_some() {
+            self.hits.fetch_add(1, Ordering::Relaxed) + 1
+        } else {
+            self.hits.load(Ordering::Relaxed)
+        };
+
+        self.observer.record_event(&ObserverEvent::CacheLookup {
+            hit: hit.is_some(),
+            mode: hit.unwrap_or("miss").to_string(),
+        });
+        #[allow(clippy::cast_precision_loss)]
+        self.observer
+            .record_metric(&ObserverMetric::CacheHitRate(hits as f64 / lookups as f64));
+    }
+
+    /// Serve from cache or call `fetch` and store its response.
+    pub async fn get_or_fetch<F, Fut>(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[ToolSpec],
+        model: &str,
+        temperature: f64,
+        fetch: F,
+    ) -> anyhow::Result<String>
+    where
+        F: FnOnce() -> Fut + Send,
+        Fut: Future<Output = anyhow::Result<String>> + Send,
+    {
+        if !self.should_cache(tools, temperature) {
+            return fetch().await;
+        }
+
+        let keys = Self::keys(messages, tools, model, temperature);
+
+        match self.lookup_exact(&keys.exact) {
+            Ok(Some(response)) => {
+                self.record_lookup(Some("exact"));
+                return Ok(response);
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Response cache lookup failed: {e}"),
+        }
+
+        let embedding = match (&self.embedder, &keys.query) {
+            (Some(embedder), Some(query)) => match embedder.embed_one(query).await {
+                Ok(v) if !v.is_empty() => Some(v),
+                Ok(_) => None,
+                Err(e) => {
+                    tracing::warn!("Response cache embedding failed: {e}");
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        if let Some(embedding) = embedding.as_deref() {
+            match self.lookup_similar(&keys.context, embedding) {
+                Ok(Some(response)) => {
+                    self.record_lookup(Some("similar"));
+                    return Ok(response);
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Response cache similarity lookup failed: {e}"),
+            }
+        }
+
+        self.record_lookup(None);
+        let response = fetch().await?;
+        if !response.trim().is_empty() {
+            if let Err(e) = self.store(&keys, &response, embedding.as_deref()) {
+                tracing::warn!("Response cache store failed: {e}");
+            }
+        }
+        Ok(response)
+    }
+}
+
+/// [`Provider`] wrapper that consults a [`ResponseCache`] before the inner provider.
+pub struct CachedProvider {
+    inner: Box<dyn Provider>,
+    cache: ResponseCache,
+}
+
+impl CachedProvider {
+    pub fn new(inner: Box<dyn Provider>, cache: ResponseCache) -> Self {
+        Self { inner, cache }
+    }
+}
+
+#[async_trait]
+impl Provider for CachedProvider {
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let mut messages = Vec::with_capacity(2);
+        if let Some(sys) = system_prompt {
+            messages.push(ChatMessage::system(sys));
+        }
+        messages.push(ChatMessage::user(message));
+
+        self.cache
+            .get_or_fetch(&messages, &[], model, temperature, || {
+                self.inner
+                    .chat_with_system(system_prompt, message, model, temperature)
+            })
+            .await
+    }
+
+    async fn chat_with_history(
+        &self,
+        messages: &[ChatMessage],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.cache
+            .get_or_fetch(messages, &[], model, temperature, || {
+                self.inner.chat_with_history(messages, model, temperature)
+            })
+            .await
+    }
+
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[ToolSpec],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.cache
+            .get_or_fetch(messages, tools, model, temperature, || {
+                self.inner
+                    .chat_with_tools(messages, tools, model, temperature)
+            })
+            .await
+    }
+
+    /// Not cached: the key would have to cover the image bytes, and repeated
+    /// images are rare.
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageInput],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        if images.is_empty() {
+            return self
+                .chat_with_system(system_prompt, message, model, temperature)
+                .await;
+        }
+        self.inner
+            .chat_with_images(system_prompt, message, images, model, temperature)
+            .await
+    }
+
+    async fn warmup(&self) -> anyhow::Result<()> {
+        self.inner.warmup().await
+    }
+}
+
+/// Wrap `provider` in a [`CachedProvider`] when `[response_cache]` is enabled.
+/// Falls back to the bare provider (with a warning) if the cache DB cannot be opened.
+pub fn wrap_provider(
+    provider: Box<dyn Provider>,
+    config: &crate::config::Config,
+    observer: Arc<dyn Observer>,
+) -> Box<dyn Provider> {
+    wrap_with(provider, config, &config.response_cache, observer)
+}
+
+/// Wrap the provider used for channel messages.
+///
+/// With a scoped `[memory] channel_scope` the final user message starts with
+/// the sender's own recalled memories, and similarity mode only compares that
+/// message by embedding — a paraphrase from another sender could then be
+/// answered from the first sender's private context. Exact mode keys on the
+/// full message, so it stays on.
+pub fn wrap_channel_provider(
+    provider: Box<dyn Provider>,
+    config: &crate::config::Config,
+    observer: Arc<dyn Observer>,
+) -> Box<dyn Provider> {
+    let cache_config = channel_cache_config(&config.response_cache, &config.memory.channel_scope);
+    wrap_with(provider, config, &cache_config, observer)
+}
+
+fn channel_cache_config(cache: &ResponseCacheConfig, channel_scope: &str) -> ResponseCacheConfig {
+    let mut cache = cache.clone();
+    if channel_scope != "global" && cache.similarity_threshold.take().is_some() {
+        tracing::info!(
+            "Response cache similarity mode disabled for channels (memory scope: {channel_scope})"
+        );
+    }
+    cache
+}
+
+fn wrap_with(
+    provider: Box<dyn Provider>,
+    config: &crate::config::Config,
+    cache_config: &ResponseCacheConfig,
+    observer: Arc<dyn Observer>,
+) -> Box<dyn Provider> {
+    if !cache_config.enabled {
+        return provider;
+    }
+
+    let embedder: Option<Arc<dyn EmbeddingProvider>> =
+        cache_config.similarity_threshold.map(|_| {
+            Arc::from(crate::memory::embeddings::create_embedding_provider(
+                &config.memory.embedding_provider,
+                config.api_key.as_deref(),
+                &config.memory.embedding_model,
+                config.memory.embedding_dimensions,
+            ))
+        });
+
+    match ResponseCache::open(&config.workspace_dir, cache_config, embedder, observer) {
+        Ok(cache) => Box::new(CachedProvider::new(provider, cache)),
+        Err(e) => {
+            tracing::warn!("Response cache disabled, failed to open: {e}");
+            provider
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::NoopObserver;
+    use std::sync::atomic::AtomicUsize;
+    use tempfile::TempDir;
+
+    struct CountingProvider {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Provider for CountingProvider {
+        async fn chat_with_system(
+            &self,
+            _system_prompt: Option<&str>,
+            message: &str,
+            _model: &str,
+            _temperature: f64,
+        ) -> anyhow::Result<String> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(format!("answer {n} to {message}"))
+        }
+    }
+
+    /// Embeds texts by keyword so paraphrases land on the same vector.
+    struct KeywordEmbedding;
+
+    #[async_trait]
+    impl EmbeddingProvider for KeywordEmbedding {
+        fn name(&self) -> &str {
+            "keyword"
+        }
+
+        fn dimensions(&self) -> usize {
+            2
+        }
+
+        async fn embed(&self, texts: &[&str]) -> anyhow::Result<Vec<Vec<f32>>> {
+            Ok(texts
+                .iter()
+                .map(|t| {
+                    if t.contains("weather") {
+                        vec![1.0, 0.0]
+                    } else {
+                        vec![0.0, 1.0]
+                    }
+                })
+                .collect())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingObserver {
+        events: Mutex<Vec<(bool, String)>>,
+        rates: Mutex<Vec<f64>>,
+    }
+
+    impl Observer for RecordingObserver {
+        fn record_event(&self, event: &ObserverEvent) {
+            if let ObserverEvent::CacheLookup { hit, mode } = event {
+                self.events.lock().unwrap().push((*hit, mode.clone()));
+            }
+        }
+
+        fn record_metric(&self, metric: &ObserverMetric) {
+            if let ObserverMetric::CacheHitRate(rate) = metric {
+                self.rates.lock().unwrap().push(*rate);
+            }
+        }
+
+        fn name(&self) -> &str {
+            "recording"
+        }
+    }
+
+    fn cached(
+        tmp: &TempDir,
+        config: &ResponseCacheConfig,
+        embedder: Option<Arc<dyn EmbeddingProvider>>,
+        observer: Arc<dyn Observer>,
+    ) -> (CachedProvider, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let inner = Box::new(CountingProvider {
+            calls: calls.clone(),
+        });
+        let cache = ResponseCache::open(tmp.path(), config, embedder, observer).unwrap();
+        (CachedProvider::new(inner, cache), calls)
+    }
+
+    fn enabled() -> ResponseCacheConfig {
+        ResponseCacheConfig {
+            enabled: true,
+            ..ResponseCacheConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn identical_request_is_served_from_cache() {
+        let tmp = TempDir::new().unwrap();
+        let (p, calls) = cached(&tmp, &enabled(), None, Arc::new(NoopObserver));
+
+        let first = p
+            .chat_with_system(Some("sys"), "hello", "m", 0.0)
+            .await
+            .unwrap();
+        let second = p
+            .chat_with_system(Some("sys"), "hello", "m", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Different model or system prompt is a different key.
+        p.chat_with_system(Some("sys"), "hello", "other", 0.0)
+            .await
+            .unwrap();
+        p.chat_with_system(Some("other"), "hello", "m", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn cache_persists_across_instances() {
+        let tmp = TempDir::new().unwrap();
+        let (p, _) = cached(&tmp, &enabled(), None, Arc::new(NoopObserver));
+        p.chat("hello", "m", 0.0).await.unwrap();
+
+        let (p2, calls) = cached(&tmp, &enabled(), None, Arc::new(NoopObserver));
+        p2.chat("hello", "m", 0.0).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn nonzero_temperature_bypasses_unless_configured() {
+        let tmp = TempDir::new().unwrap();
+        let (p, calls) = cached(&tmp, &enabled(), None, Arc::new(NoopObserver));
+        p.chat("hello", "m", 0.7).await.unwrap();
+        p.chat("hello", "m", 0.7).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let config = ResponseCacheConfig {
+            cache_nonzero_temperature: true,
+            ..enabled()
+        };
+        let (p, calls) = cached(&tmp, &config, None, Arc::new(NoopObserver));
+        p.chat("hello", "m", 0.7).await.unwrap();
+        p.chat("hello", "m", 0.7).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn tool_turns_cached_only_when_enabled_and_keyed_on_schemas() {
+        let tmp = TempDir::new().unwrap();
+        let mut tools = vec![ToolSpec {
+            name: "shell".into(),
+            description: "run".into(),
+            parameters: serde_json::json!({}),
+        }];
+        let history = vec![ChatMessage::user("list files")];
+
+        let (p, calls) = cached(&tmp, &enabled(), None, Arc::new(NoopObserver));
+        p.chat_with_tools(&history, &tools, "m", 0.0).await.unwrap();
+        p.chat_with_tools(&history, &tools, "m", 0.0).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let config = ResponseCacheConfig {
+            cache_tool_turns: true,
+            ..enabled()
+        };
+        let (p, calls) = cached(&tmp, &config, None, Arc::new(NoopObserver));
+        p.chat_with_tools(&history, &tools, "m", 0.0).await.unwrap();
+        p.chat_with_tools(&history, &tools, "m", 0.0).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Same tool name, different schema: a different request
+        tools[0].parameters = serde_json::json!({"type": "object", "required": ["command"]});
+        p.chat_with_tools(&history, &tools, "m", 0.0).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn expired_entries_are_refetched() {
+        let tmp = TempDir::new().unwrap();
+        let (p, calls) = cached(&tmp, &enabled(), None, Arc::new(NoopObserver));
+        p.chat("hello", "m", 0.0).await.unwrap();
+
+        p.cache
+            .conn
+            .lock()
+            .unwrap()
+            .execute(
+                "UPDATE response_cache SET created_at = created_at - 7200",
+                [],
+            )
+            .unwrap();
+
+        p.chat("hello", "m", 0.0).await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn oldest_entries_evicted_beyond_max() {
+        let tmp = TempDir::new().unwrap();
+        let config = ResponseCacheConfig {
+            max_entries: 2,
+            ..enabled()
+        };
+        let (p, _) = cached(&tmp, &config, None, Arc::new(NoopObserver));
+        for msg in ["a", "b", "c"] {
+            p.chat(msg, "m", 0.0).await.unwrap();
+        }
+        let count: i64 = p
+            .cache
+            .conn
+            .lock()
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM response_cache", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[tokio::test]
+    async fn similar_question_reuses_answer() {
+        let tmp = TempDir::new().unwrap();
+        let config = ResponseCacheConfig {
+            similarity_threshold: Some(0.95),
+            ..enabled()
+        };
+        let observer = Arc::new(RecordingObserver::default());
+        let (p, calls) = cached(
+            &tmp,
+            &config,
+            Some(Arc::new(KeywordEmbedding)),
+            observer.clone(),
+        );
+
+        let first = p
+            .chat_with_system(Some("sys"), "what's the weather?", "m", 0.0)
+            .await
+            .unwrap();
+        let second = p
+            .chat_with_system(Some("sys"), "how is the weather today", "m", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Same question under a different system prompt is not reused.
+        p.chat_with_system(Some("other"), "how is the weather today", "m", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let events = observer.events.lock().unwrap().clone();
+        assert_eq!(
+            events,
+            vec![
+                (false, "miss".to_string()),
+                (true, "similar".to_string()),
+                (false, "miss".to_string()),
+            ]
+        );
+        let rates = observer.rates.lock().unwrap().clone();
+        assert!((rates[1] - 0.5).abs() < f64::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn scoped_channels_never_share_answers_across_senders() {
+        let tmp = TempDir::new().unwrap();
+        let config = ResponseCacheConfig {
+            similarity_threshold: Some(0.95),
+            ..enabled()
+        };
+        let alice = "[Memory context]\n- alice lives in Oslo\n\nwhat's the weather?";
+        let bob = "[Memory context]\n- bob lives in Lima\n\nhow is the weather today";
+
+        let scoped = channel_cache_config(&config, "user");
+        assert_eq!(scoped.similarity_threshold, None);
+        let (p, calls) = cached(
+            &tmp,
+            &scoped,
+            Some(Arc::new(KeywordEmbedding)),
+            Arc::new(NoopObserver),
+        );
+        let first = p
+            .chat_with_system(Some("sys"), alice, "m", 0.0)
+            .await
+            .unwrap();
+        let second = p
+            .chat_with_system(Some("sys"), bob, "m", 0.0)
+            .await
+            .unwrap();
+        assert_ne!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // The same sender repeating the exact message is still served.
+        p.chat_with_system(Some("sys"), alice, "m", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let global = channel_cache_config(&config, "global");
+        assert_eq!(global.similarity_threshold, Some(0.95));
+    }
+
+    #[test]
+    fn disabled_config_returns_inner_provider() {
+        let tmp = TempDir::new().unwrap();
+        let mut config = crate::config::Config::default();
+        config.workspace_dir = tmp.path().to_path_buf();
+        let inner = Box::new(CountingProvider {
+            calls: Arc::new(AtomicUsize::new(0)),
+        });
+        let _ = wrap_provider(inner, &config, Arc::new(NoopObserver));
+        assert!(!tmp.path().join("cache").exists());
+    }
+}
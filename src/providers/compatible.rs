@@ -2,19 +2,22 @@
 //! Most LLM APIs follow the same `/v1/chat/completions` format.
 //! This module provides a single implementation that works for all of them.
 
-use crate::providers::traits::{ChatMessage, Provider};
+use crate::providers::reasoning::wrap_reasoning;
+use crate::providers::traits::{openai_vision_messages, ChatMessage, ImageInput, Provider};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
 /// A provider that speaks the OpenAI-compatible chat completions API.
 /// Used by: Venice, Vercel AI Gateway, Cloudflare AI Gateway, Moonshot,
-/// Synthetic, `OpenCode` Zen, `Z.AI`, `GLM`, `MiniMax`, Bedrock, Qianfan, Groq, Mistral, `xAI`, etc.
+/// Synthetic, `OpenCode` Zen, `Z.AI`, `GLM`, `MiniMax`, Qianfan, Groq, Mistral, `xAI`, etc.
 pub struct OpenAiCompatibleProvider {
     pub(crate) name: String,
     pub(crate) base_url: String,
     pub(crate) api_key: Option<String>,
     pub(crate) auth_header: AuthStyle,
+    /// `reasoning_effort` sent to reasoning models (o-series, DeepSeek, etc.)
+    pub(crate) reasoning_effort: Option<String>,
     client: Client,
 }
 
@@ -31,19 +34,34 @@ pub enum AuthStyle {
 
 impl OpenAiCompatibleProvider {
     pub fn new(name: &str, base_url: &str, api_key: Option<&str>, auth_style: AuthStyle) -> Self {
+        Self::with_http_component(name, base_url, api_key, auth_style, "compatible")
+    }
+
+    /// Like [`Self::new`], but builds the HTTP client with the
+    /// `[http.overrides.<http_component>]` settings (e.g. `groq`, `custom`).
+    pub fn with_http_component(
+        name: &str,
+        base_url: &str,
+        api_key: Option<&str>,
+        auth_style: AuthStyle,
+        http_component: &str,
+    ) -> Self {
         Self {
             name: name.to_string(),
             base_url: base_url.trim_end_matches('/').to_string(),
             api_key: api_key.map(ToString::to_string),
             auth_header: auth_style,
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            reasoning_effort: None,
+            client: crate::http::client(http_component, crate::http::Defaults::PROVIDER),
         }
     }
 
+    /// Set the `reasoning_effort` request parameter (`low`, `medium`, `high`).
+    pub fn with_reasoning_effort(mut self, effort: Option<&str>) -> Self {
+        self.reasoning_effort = effort.map(ToString::to_string);
+        self
+    }
+
     /// Build the full URL for chat completions, detecting if base_url already includes the path.
     /// This allows custom providers with non-standard endpoints (e.g., VolcEngine ARK uses
     /// `/api/coding/v3/chat/completions` instead of `/v1/chat/completions`).
@@ -70,36 +88,61 @@ impl OpenAiCompatibleProvider {
 }
 
 #[derive(Debug, Serialize)]
-struct ChatRequest {
+struct ChatRequest<M = Message> {
     model: String,
-    messages: Vec<Message>,
+    messages: Vec<M>,
     temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning_effort: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
-struct Message {
-    role: String,
-    content: String,
+pub(super) struct Message {
+    pub(super) role: String,
+    pub(super) content: String,
 }
 
+/// Chat Completions response; shared with other OpenAI-shaped APIs (Azure).
 #[derive(Debug, Deserialize)]
-struct ApiChatResponse {
-    choices: Vec<Choice>,
+pub(super) struct ApiChatResponse {
+    pub(super) choices: Vec<Choice>,
 }
 
 #[derive(Debug, Deserialize)]
-struct Choice {
-    message: ResponseMessage,
+pub(super) struct Choice {
+    pub(super) message: ResponseMessage,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
-struct ResponseMessage {
+pub(super) struct ResponseMessage {
     #[serde(default)]
     content: Option<String>,
+    /// Chain of thought returned by reasoning models (DeepSeek-R1, QwQ, MiniMax).
+    /// Never serialized back into the tool-call JSON handed to `parse_tool_calls`.
+    #[serde(default, alias = "reasoning", skip_serializing)]
+    reasoning_content: Option<String>,
     #[serde(default)]
     tool_calls: Option<Vec<ToolCall>>,
 }
 
+impl ResponseMessage {
+    /// Flatten the message into the string form expected by the agent loop.
+    ///
+    /// Tool calls are serialized as OpenAI-style JSON so `parse_tool_calls` can
+    /// handle them; a separate `reasoning_content` field is prepended as a
+    /// `<think>` block so callers can split it off uniformly.
+    pub(super) fn into_text(self) -> String {
+        let has_tool_calls = self.tool_calls.as_ref().map_or(false, |t| !t.is_empty());
+        let body = if has_tool_calls {
+            serde_json::to_string(&self)
+                .unwrap_or_else(|_| self.content.clone().unwrap_or_default())
+        } else {
+            self.content.clone().unwrap_or_default()
+        };
+        wrap_reasoning(self.reasoning_content.as_deref(), &body)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 struct ToolCall {
     #[serde(rename = "type")]
@@ -270,6 +313,7 @@ impl Provider for OpenAiCompatibleProvider {
             model: model.to_string(),
             messages,
             temperature,
+            reasoning_effort: self.reasoning_effort.clone(),
         };
 
         let url = self.chat_completions_url();
@@ -307,22 +351,7 @@ impl Provider for OpenAiCompatibleProvider {
             .choices
             .into_iter()
             .next()
-            .map(|c| {
-                // If tool_calls are present, serialize the full message as JSON
-                // so parse_tool_calls can handle the OpenAI-style format
-                if c.message.tool_calls.is_some()
-                    && c.message
-                        .tool_calls
-                        .as_ref()
-                        .map_or(false, |t| !t.is_empty())
-                {
-                    serde_json::to_string(&c.message)
-                        .unwrap_or_else(|_| c.message.content.unwrap_or_default())
-                } else {
-                    // No tool calls, return content as-is
-                    c.message.content.unwrap_or_default()
-                }
-            })
+            .map(|c| c.message.into_text())
             .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))
     }
 
@@ -351,6 +380,7 @@ impl Provider for OpenAiCompatibleProvider {
             model: model.to_string(),
             messages: api_messages,
             temperature,
+            reasoning_effort: self.reasoning_effort.clone(),
         };
 
         let url = self.chat_completions_url();
@@ -394,22 +424,54 @@ impl Provider for OpenAiCompatibleProvider {
             .choices
             .into_iter()
             .next()
-            .map(|c| {
-                // If tool_calls are present, serialize the full message as JSON
-                // so parse_tool_calls can handle the OpenAI-style format
-                if c.message.tool_calls.is_some()
-                    && c.message
-                        .tool_calls
-                        .as_ref()
-                        .map_or(false, |t| !t.is_empty())
-                {
-                    serde_json::to_string(&c.message)
-                        .unwrap_or_else(|_| c.message.content.unwrap_or_default())
-                } else {
-                    // No tool calls, return content as-is
-                    c.message.content.unwrap_or_default()
-                }
-            })
+            .map(|c| c.message.into_text())
+            .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))
+    }
+
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageInput],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        if images.is_empty() {
+            return self
+                .chat_with_system(system_prompt, message, model, temperature)
+                .await;
+        }
+        let api_key = self.api_key.as_ref().ok_or_else(|| {
+            anyhow::anyhow!(
+                "{} API key not set. Run `zeroclaw onboard` or set the appropriate env var.",
+                self.name
+            )
+        })?;
+
+        let request = ChatRequest {
+            model: model.to_string(),
+            messages: openai_vision_messages(system_prompt, message, images),
+            temperature,
+            reasoning_effort: self.reasoning_effort.clone(),
+        };
+
+        let url = self.chat_completions_url();
+        let response = self
+            .apply_auth_header(self.client.post(&url).json(&request), api_key)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(super::api_error(&self.name, response).await);
+        }
+
+        let chat_response: ApiChatResponse = response.json().await?;
+
+        chat_response
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message.into_text())
             .ok_or_else(|| anyhow::anyhow!("No response from {}", self.name))
     }
 }
@@ -470,9 +532,11 @@ mod tests {
                 },
             ],
             temperature: 0.7,
+            reasoning_effort: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("llama-3.3-70b"));
+        assert!(!json.contains("reasoning_effort"));
         assert!(json.contains("system"));
         assert!(json.contains("user"));
     }
@@ -487,6 +551,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn request_serializes_reasoning_effort() {
+        let req: ChatRequest = ChatRequest {
+            model: "o3-mini".to_string(),
+            messages: vec![],
+            temperature: 1.0,
+            reasoning_effort: Some("high".to_string()),
+        };
+        let json = serde_json::to_string(&req).unwrap();
+        assert!(json.contains(r#""reasoning_effort":"high""#));
+    }
+
+    #[test]
+    fn with_reasoning_effort_sets_field() {
+        let p = make_provider("deepseek", "https://api.deepseek.com", None)
+            .with_reasoning_effort(Some("low"));
+        assert_eq!(p.reasoning_effort.as_deref(), Some("low"));
+    }
+
+    #[test]
+    fn response_reasoning_content_becomes_think_block() {
+        let json = r#"{"choices":[{"message":{"content":"42","reasoning_content":"6 times 7"}}]}"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        let text = resp.choices.into_iter().next().unwrap().message.into_text();
+        let (reasoning, answer) = crate::providers::reasoning::split_reasoning(&text);
+        assert_eq!(reasoning.as_deref(), Some("6 times 7"));
+        assert_eq!(answer, "42");
+    }
+
+    #[test]
+    fn response_reasoning_alias_is_accepted() {
+        let json = r#"{"choices":[{"message":{"content":"ok","reasoning":"thinking"}}]}"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            resp.choices[0].message.reasoning_content.as_deref(),
+            Some("thinking")
+        );
+    }
+
+    #[test]
+    fn tool_call_json_excludes_reasoning_content() {
+        let json = r#"{"choices":[{"message":{"content":null,"reasoning_content":"plan","tool_calls":[{"type":"function","function":{"name":"shell","arguments":"{}"}}]}}]}"#;
+        let resp: ApiChatResponse = serde_json::from_str(json).unwrap();
+        let text = resp.choices.into_iter().next().unwrap().message.into_text();
+        let answer = crate::providers::reasoning::strip_reasoning(&text);
+        assert!(answer.contains("tool_calls"));
+        assert!(!answer.contains("plan"));
+    }
+
     #[test]
     fn response_empty_choices() {
         let json = r#"{"choices":[]}"#;
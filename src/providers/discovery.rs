@@ -0,0 +1,374 @@
+//! Local LLM server auto-discovery.
+//!
+//! Probes well-known localhost ports, identifies which server is answering
+//! (Ollama, LM Studio, llama.cpp, vLLM, Hugging Face TGI or a generic
+//! OpenAI-compatible server) and lists its models. Used by `zeroclaw onboard`
+//! and `zeroclaw doctor`.
+
+use serde_json::Value;
+use std::time::Duration;
+
+/// Default Ollama API address.
+pub const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
+
+/// Well-known local ports: Ollama, LM Studio, llama.cpp / TGI, vLLM, TGI (docker).
+pub const DEFAULT_PORTS: [u16; 5] = [11434, 1234, 8080, 8000, 3000];
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+const PROBE_CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LocalServerKind {
+    Ollama,
+    LmStudio,
+    LlamaCpp,
+    Vllm,
+    Tgi,
+    /// Answers `/v1/models` but could not be identified further.
+    OpenAiCompatible,
+}
+
+impl LocalServerKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Ollama => "Ollama",
+            Self::LmStudio => "LM Studio",
+            Self::LlamaCpp => "llama.cpp server",
+            Self::Vllm => "vLLM",
+            Self::Tgi => "Hugging Face TGI",
+            Self::OpenAiCompatible => "OpenAI-compatible server",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredServer {
+    pub kind: LocalServerKind,
+    pub base_url: String,
+    pub models: Vec<String>,
+}
+
+impl DiscoveredServer {
+    /// Provider name to put in `default_provider` for this server.
+    pub fn provider_name(&self) -> String {
+        match self.kind {
+            LocalServerKind::Ollama => "ollama".into(),
+            _ => format!("custom:{}/v1", self.base_url),
+        }
+    }
+}
+
+/// Normalize an Ollama address such as `OLLAMA_HOST=0.0.0.0:11434` into a base URL.
+pub fn normalize_ollama_url(raw: &str) -> Option<String> {
+    let raw = raw.trim().trim_end_matches('/');
+    if raw.is_empty() {
+        return None;
+    }
+    let url = if raw.contains("://") {
+        raw.to_string()
+    } else {
+        format!("http://{raw}")
+    };
+    // A bind-all address is not connectable as a client.
+    Some(url.replace("://0.0.0.0", "://localhost"))
+}
+
+/// Ollama base URL from the `OLLAMA_HOST` environment variable, if set.
+pub fn ollama_host_from_env() -> Option<String> {
+    std::env::var("OLLAMA_HOST")
+        .ok()
+        .and_then(|v| normalize_ollama_url(&v))
+}
+
+/// Base URLs probed by default: `localhost` on [`DEFAULT_PORTS`], plus `OLLAMA_HOST`
+/// and the configured Ollama URL when they point elsewhere.
+pub fn default_candidates(ollama_base_url: Option<&str>) -> Vec<String> {
+    let mut urls: Vec<String> = DEFAULT_PORTS
+        .iter()
+        .map(|port| format!("http://localhost:{port}"))
+        .collect();
+    for extra in [
+        ollama_base_url.and_then(normalize_ollama_url),
+        ollama_host_from_env(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if !urls.contains(&extra) {
+            urls.insert(0, extra);
+        }
+    }
+    urls
+}
+
+/// Probe all `candidates` concurrently and return the servers that answered,
+/// in candidate order.
+pub async fn discover(candidates: &[String]) -> Vec<DiscoveredServer> {
+    // Loopback probes never go through the `[http]` proxy.
+    let client = reqwest::Client::builder()
+        .no_proxy()
+        .timeout(PROBE_TIMEOUT)
+        .connect_timeout(PROBE_CONNECT_TIMEOUT)
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+
+    let mut set = tokio::task::JoinSet::new();
+    for (idx, base_url) in candidates.iter().enumerate() {
+        let client = client.clone();
+        let base_url = base_url.trim_end_matches('/').to_string();
+        set.spawn(async move { (idx, probe(&client, &base_url).await) });
+    }
+
+    let mut found = Vec::new();
+    while let Some(joined) = set.join_next().await {
+        if let Ok((idx, Some(server))) = joined {
+            found.push((idx, server));
+        }
+    }
+    found.sort_by_key(|(idx, _)| *idx);
+    found.into_iter().map(|(_, server)| server).collect()
+}
+
+/// Blocking wrapper for synchronous callers (the onboarding wizard, `doctor`).
+/// Runs on its own thread so it is safe to call from inside a Tokio runtime.
+pub fn discover_blocking(candidates: &[String]) -> Vec<DiscoveredServer> {
+    let candidates = candidates.to_vec();
+    std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map(|rt| rt.block_on(discover(&candidates)))
+            .unwrap_or_default()
+    })
+    .join()
+    .unwrap_or_default()
+}
+
+/// Identify the server at `base_url`. Server-specific endpoints are tried
+/// before the generic `/v1/models` so the most precise match wins.
+async fn probe(client: &reqwest::Client, base_url: &str) -> Option<DiscoveredServer> {
+    let server = |kind, models| DiscoveredServer {
+        kind,
+        base_url: base_url.to_string(),
+        models,
+    };
+
+    if let Some(models) = get_json(client, base_url, "/api/tags")
+        .await
+        .and_then(|v| parse_ollama_tags(&v))
+    {
+        return Some(server(LocalServerKind::Ollama, models));
+    }
+    if let Some(models) = get_json(client, base_url, "/info")
+        .await
+        .and_then(|v| parse_tgi_info(&v))
+    {
+        return Some(server(LocalServerKind::Tgi, models));
+    }
+    if let Some(models) = get_json(client, base_url, "/api/v0/models")
+        .await
+        .and_then(|v| parse_lmstudio_models(&v))
+    {
+        return Some(server(LocalServerKind::LmStudio, models));
+    }
+    let (kind, models) = parse_openai_models(&get_json(client, base_url, "/v1/models").await?)?;
+    Some(server(kind, models))
+}
+
+async fn get_json(client: &reqwest::Client, base_url: &str, path: &str) -> Option<Value> {
+    let response = client.get(format!("{base_url}{path}")).send().await.ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    response.json().await.ok()
+}
+
+/// Ollama `GET /api/tags`: `{"models":[{"name":"llama3.2:latest",...}]}`
+fn parse_ollama_tags(v: &Value) -> Option<Vec<String>> {
+    let models = v.get("models")?.as_array()?;
+    Some(
+        models
+            .iter()
+            .filter_map(|m| m.get("name").and_then(Value::as_str))
+            .map(ToString::to_string)
+            .collect(),
+    )
+}
+
+/// TGI `GET /info`: `{"model_id":"mistralai/Mistral-7B",...}`
+fn parse_tgi_info(v: &Value) -> Option<Vec<String>> {
+    let model_id = v.get("model_id")?.as_str()?;
+    Some(vec![model_id.to_string()])
+}
+
+/// LM Studio `GET /api/v0/models`: `{"data":[{"id":..,"type":"llm","state":"loaded"}]}`.
+/// Prefers loaded chat models; falls back to every LLM when none is loaded.
+fn parse_lmstudio_models(v: &Value) -> Option<Vec<String>> {
+    let data = v.get("data")?.as_array()?;
+    // The `state` field distinguishes LM Studio from a plain OpenAI model list.
+    if data.is_empty() || !data.iter().all(|m| m.get("state").is_some()) {
+        return None;
+    }
+    let llms: Vec<&Value> = data
+        .iter()
+        .filter(|m| m.get("type").and_then(Value::as_str) != Some("embeddings"))
+        .collect();
+    let loaded: Vec<String> = llms
+        .iter()
+        .filter(|m| m.get("state").and_then(Value::as_str) == Some("loaded"))
+        .filter_map(|m| m.get("id").and_then(Value::as_str))
+        .map(ToString::to_string)
+        .collect();
+    if !loaded.is_empty() {
+        return Some(loaded);
+    }
+    Some(
+        llms.iter()
+            .filter_map(|m| m.get("id").and_then(Value::as_str))
+            .map(ToString::to_string)
+            .collect(),
+    )
+}
+
+/// OpenAI-style `GET /v1/models`; `owned_by` tells vLLM and llama.cpp apart.
+fn parse_openai_models(v: &Value) -> Option<(LocalServerKind, Vec<String>)> {
+    let data = v.get("data")?.as_array()?;
+    let owner = data
+        .iter()
+        .find_map(|m| m.get("owned_by").and_then(Value::as_str))
+        .unwrap_or_default();
+    let kind = match owner {
+        "vllm" => LocalServerKind::Vllm,
+        "llamacpp" => LocalServerKind::LlamaCpp,
+        _ => LocalServerKind::OpenAiCompatible,
+    };
+    let models = data
+        .iter()
+        .filter_map(|m| m.get("id").and_then(Value::as_str))
+        .map(ToString::to_string)
+        .collect();
+    Some((kind, models))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{routing::get, Json, Router};
+    use serde_json::json;
+
+    async fn serve(app: Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{addr}")
+    }
+
+    #[test]
+    fn parses_ollama_tags() {
+        let v = json!({"models": [{"name": "llama3.2:latest"}, {"name": "phi3"}]});
+        assert_eq!(
+            parse_ollama_tags(&v).unwrap(),
+            vec!["llama3.2:latest", "phi3"]
+        );
+    }
+
+    #[test]
+    fn lmstudio_prefers_loaded_llms() {
+        let v = json!({"data": [
+            {"id": "qwen2.5-7b", "type": "llm", "state": "not-loaded"},
+            {"id": "llama-3.2-3b", "type": "llm", "state": "loaded"},
+            {"id": "nomic-embed", "type": "embeddings", "state": "loaded"}
+        ]});
+        assert_eq!(parse_lmstudio_models(&v).unwrap(), vec!["llama-3.2-3b"]);
+    }
+
+    #[test]
+    fn plain_openai_list_is_not_lmstudio() {
+        let v = json!({"data": [{"id": "m", "owned_by": "vllm"}]});
+        assert!(parse_lmstudio_models(&v).is_none());
+        let (kind, models) = parse_openai_models(&v).unwrap();
+        assert_eq!(kind, LocalServerKind::Vllm);
+        assert_eq!(models, vec!["m"]);
+    }
+
+    #[test]
+    fn openai_owner_identifies_llamacpp() {
+        let v = json!({"data": [{"id": "model.gguf", "owned_by": "llamacpp"}]});
+        assert_eq!(
+            parse_openai_models(&v).unwrap().0,
+            LocalServerKind::LlamaCpp
+        );
+    }
+
+    #[test]
+    fn provider_name_for_each_kind() {
+        let mut server = DiscoveredServer {
+            kind: LocalServerKind::Ollama,
+            base_url: DEFAULT_OLLAMA_URL.into(),
+            models: vec![],
+        };
+        assert_eq!(server.provider_name(), "ollama");
+
+        server.kind = LocalServerKind::LmStudio;
+        server.base_url = "http://localhost:1234".into();
+        assert_eq!(server.provider_name(), "custom:http://localhost:1234/v1");
+    }
+
+    #[test]
+    fn normalizes_ollama_host_values() {
+        assert_eq!(
+            normalize_ollama_url("0.0.0.0:11434").as_deref(),
+            Some("http://localhost:11434")
+        );
+        assert_eq!(
+            normalize_ollama_url("https://ollama.lan/").as_deref(),
+            Some("https://ollama.lan")
+        );
+        assert_eq!(normalize_ollama_url("  "), None);
+    }
+
+    #[test]
+    fn configured_ollama_url_is_probed_first() {
+        let urls = default_candidates(Some("http://gpu-box:11434"));
+        assert_eq!(urls[0], "http://gpu-box:11434");
+        assert!(urls.contains(&"http://localhost:1234".to_string()));
+    }
+
+    #[tokio::test]
+    async fn discovers_ollama_and_tgi_servers() {
+        let ollama = serve(Router::new().route(
+            "/api/tags",
+            get(|| async { Json(json!({"models": [{"name": "llama3.2"}]})) }),
+        ))
+        .await;
+        let tgi = serve(Router::new().route(
+            "/info",
+            get(|| async { Json(json!({"model_id": "HuggingFaceH4/zephyr-7b-beta"})) }),
+        ))
+        .await;
+        let nothing = "http://127.0.0.1:1".to_string();
+
+        let found = discover(&[nothing, ollama.clone(), tgi.clone()]).await;
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[0].kind, LocalServerKind::Ollama);
+        assert_eq!(found[0].base_url, ollama);
+        assert_eq!(found[0].models, vec!["llama3.2"]);
+        assert_eq!(found[1].kind, LocalServerKind::Tgi);
+        assert_eq!(found[1].models, vec!["HuggingFaceH4/zephyr-7b-beta"]);
+    }
+
+    #[tokio::test]
+    async fn discovers_generic_openai_server() {
+        let server = serve(Router::new().route(
+            "/v1/models",
+            get(|| async { Json(json!({"data": [{"id": "local-model"}]})) }),
+        ))
+        .await;
+        let found = discover(&[server]).await;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, LocalServerKind::OpenAiCompatible);
+        assert_eq!(found[0].models, vec!["local-model"]);
+    }
+}
@@ -101,6 +101,27 @@ struct CandidateContent {
 #[derive(Debug, Deserialize)]
 struct ResponsePart {
     text: Option<String>,
+    /// Set on thought-summary parts from Gemini thinking models.
+    #[serde(default)]
+    thought: Option<bool>,
+}
+
+impl CandidateContent {
+    /// Join answer parts, prepending thought parts as a `<think>` block.
+    fn into_text(self) -> Option<String> {
+        let mut thoughts = Vec::new();
+        let mut answer: Option<String> = None;
+        for part in self.parts {
+            let Some(text) = part.text else { continue };
+            if part.thought.unwrap_or(false) {
+                thoughts.push(text);
+            } else {
+                answer.get_or_insert_with(String::new).push_str(&text);
+            }
+        }
+        let reasoning = thoughts.join("\n\n");
+        answer.map(|a| super::reasoning::wrap_reasoning(Some(&reasoning), &a))
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -137,11 +158,7 @@ impl GeminiProvider {
 
         Self {
             auth: resolved_auth,
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: crate::http::client("gemini", crate::http::Defaults::PROVIDER),
         }
     }
 
@@ -317,8 +334,7 @@ impl Provider for GeminiProvider {
         result
             .candidates
             .and_then(|c| c.into_iter().next())
-            .and_then(|c| c.content.parts.into_iter().next())
-            .and_then(|p| p.text)
+            .and_then(|c| c.content.into_text())
             .ok_or_else(|| anyhow::anyhow!("No response from Gemini"))
     }
 }
@@ -545,6 +561,34 @@ mod tests {
         assert_eq!(text, Some("Hello there!".to_string()));
     }
 
+    #[test]
+    fn thought_parts_become_reasoning() {
+        let json = r#"{
+            "candidates": [{
+                "content": {
+                    "parts": [
+                        {"text": "Weighing both answers", "thought": true},
+                        {"text": "The answer is yes."}
+                    ]
+                }
+            }]
+        }"#;
+
+        let response: GenerateContentResponse = serde_json::from_str(json).unwrap();
+        let text = response
+            .candidates
+            .unwrap()
+            .into_iter()
+            .next()
+            .unwrap()
+            .content
+            .into_text();
+        let (reasoning, answer) =
+            crate::providers::reasoning::split_reasoning(text.as_deref().unwrap());
+        assert_eq!(reasoning.as_deref(), Some("Weighing both answers"));
+        assert_eq!(answer, "The answer is yes.");
+    }
+
     #[test]
     fn error_response_deserialization() {
         let json = r#"{
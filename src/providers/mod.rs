@@ -1,14 +1,19 @@
 pub mod anthropic;
+pub mod azure;
+pub mod bedrock;
+pub mod cache;
 pub mod compatible;
+pub mod discovery;
 pub mod gemini;
 pub mod ollama;
 pub mod openai;
 pub mod openrouter;
+pub mod reasoning;
 pub mod reliable;
 pub mod router;
 pub mod traits;
 
-pub use traits::{ChatMessage, Provider};
+pub use traits::{ChatMessage, ImageInput, Provider};
 
 use compatible::{AuthStyle, OpenAiCompatibleProvider};
 use reliable::ReliableProvider;
@@ -109,6 +114,7 @@ fn resolve_api_key(name: &str, api_key: Option<&str>) -> Option<String> {
         "anthropic" => vec!["ANTHROPIC_OAUTH_TOKEN", "ANTHROPIC_API_KEY"],
         "openrouter" => vec!["OPENROUTER_API_KEY"],
         "openai" => vec!["OPENAI_API_KEY"],
+        "azure" | "azure-openai" => vec!["AZURE_OPENAI_API_KEY"],
         "venice" => vec!["VENICE_API_KEY"],
         "groq" => vec!["GROQ_API_KEY"],
         "mistral" => vec!["MISTRAL_API_KEY"],
@@ -151,93 +157,163 @@ fn resolve_api_key(name: &str, api_key: Option<&str>) -> Option<String> {
     None
 }
 
+/// Per-instance provider settings that are not encoded in the provider name.
+#[derive(Debug, Clone, Default)]
+pub struct ProviderOptions {
+    /// Reasoning effort for thinking models (`minimal`, `low`, `medium`, `high`).
+    /// Forwarded by OpenAI, OpenRouter and OpenAI-compatible providers.
+    pub reasoning_effort: Option<String>,
+    /// Resource, API version and deployment mapping for the `azure` provider.
+    pub azure: crate::config::AzureOpenAiConfig,
+    /// Ollama base URL (`[ollama].base_url`); `None` uses `OLLAMA_HOST` or localhost.
+    pub ollama_base_url: Option<String>,
+}
+
+impl ProviderOptions {
+    /// Options derived from the top-level config (no route-specific settings).
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        Self {
+            reasoning_effort: None,
+            azure: config.azure_openai.clone(),
+            ollama_base_url: config.ollama.base_url.clone(),
+        }
+    }
+}
+
 /// Factory: create the right provider from config
-#[allow(clippy::too_many_lines)]
 pub fn create_provider(name: &str, api_key: Option<&str>) -> anyhow::Result<Box<dyn Provider>> {
-    let resolved_key = resolve_api_key(name, api_key);
+    create_provider_with_options(name, api_key, &ProviderOptions::default())
+}
+
+/// Factory with per-instance options (e.g. `reasoning_effort` from a model route).
+#[allow(clippy::too_many_lines)]
+pub fn create_provider_with_options(
+    name: &str,
+    api_key: Option<&str>,
+    options: &ProviderOptions,
+) -> anyhow::Result<Box<dyn Provider>> {
+    let env_name = if name.starts_with("azure:") {
+        "azure"
+    } else {
+        name
+    };
+    let resolved_key = resolve_api_key(env_name, api_key);
     let key = resolved_key.as_deref();
+    let effort = options.reasoning_effort.as_deref();
+    // `[http.overrides.<name>]` key: the provider name without its `:<url>` suffix.
+    let http_component = name.split(':').next().unwrap_or(name);
+    let compatible = |name: &str, base_url: &str, key: Option<&str>, auth: AuthStyle| {
+        OpenAiCompatibleProvider::with_http_component(name, base_url, key, auth, http_component)
+            .with_reasoning_effort(effort)
+    };
     match name {
         // ── Primary providers (custom implementations) ───────
-        "openrouter" => Ok(Box::new(openrouter::OpenRouterProvider::new(key))),
+        "openrouter" => Ok(Box::new(
+            openrouter::OpenRouterProvider::new(key).with_reasoning_effort(effort),
+        )),
         "anthropic" => Ok(Box::new(anthropic::AnthropicProvider::new(key))),
-        "openai" => Ok(Box::new(openai::OpenAiProvider::new(key))),
+        "openai" => Ok(Box::new(
+            openai::OpenAiProvider::new(key).with_reasoning_effort(effort),
+        )),
         // Ollama is a local service that doesn't use API keys.
         // The api_key parameter is ignored to avoid it being misinterpreted as a base_url.
-        "ollama" => Ok(Box::new(ollama::OllamaProvider::new(None))),
+        "ollama" => {
+            let base_url = options
+                .ollama_base_url
+                .as_deref()
+                .and_then(discovery::normalize_ollama_url)
+                .or_else(discovery::ollama_host_from_env);
+            Ok(Box::new(ollama::OllamaProvider::new(base_url.as_deref())))
+        }
+        // Bedrock signs requests with AWS credentials (env or profile), or uses
+        // `AWS_BEARER_TOKEN_BEDROCK`. The generic api_key usually belongs to another
+        // provider, so it must never become a Bedrock bearer token.
+        "bedrock" | "aws-bedrock" => Ok(Box::new(bedrock::BedrockProvider::new(None))),
         "gemini" | "google" | "google-gemini" => {
             Ok(Box::new(gemini::GeminiProvider::new(key)))
         }
 
         // ── OpenAI-compatible providers ──────────────────────
-        "venice" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "venice" => Ok(Box::new(compatible(
             "Venice", "https://api.venice.ai", key, AuthStyle::Bearer,
         ))),
-        "vercel" | "vercel-ai" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "vercel" | "vercel-ai" => Ok(Box::new(compatible(
             "Vercel AI Gateway", "https://api.vercel.ai", key, AuthStyle::Bearer,
         ))),
-        "cloudflare" | "cloudflare-ai" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "cloudflare" | "cloudflare-ai" => Ok(Box::new(compatible(
             "Cloudflare AI Gateway",
             "https://gateway.ai.cloudflare.com/v1",
             api_key,
             AuthStyle::Bearer,
         ))),
-        "moonshot" | "kimi" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "moonshot" | "kimi" => Ok(Box::new(compatible(
             "Moonshot", "https://api.moonshot.cn", key, AuthStyle::Bearer,
         ))),
-        "synthetic" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "synthetic" => Ok(Box::new(compatible(
             "Synthetic", "https://api.synthetic.com", key, AuthStyle::Bearer,
         ))),
-        "opencode" | "opencode-zen" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "opencode" | "opencode-zen" => Ok(Box::new(compatible(
             "OpenCode Zen", "https://api.opencode.ai", key, AuthStyle::Bearer,
         ))),
-        "zai" | "z.ai" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "zai" | "z.ai" => Ok(Box::new(compatible(
             "Z.AI", "https://api.z.ai/api/coding/paas/v4", key, AuthStyle::Bearer,
         ))),
-        "glm" | "zhipu" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "glm" | "zhipu" => Ok(Box::new(compatible(
             "GLM", "https://open.bigmodel.cn/api/coding/paas/v4", key, AuthStyle::Bearer,
         ))),
-        "minimax" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "minimax" => Ok(Box::new(compatible(
             "MiniMax", "https://api.minimax.chat", key, AuthStyle::Bearer,
         ))),
-        "bedrock" | "aws-bedrock" => Ok(Box::new(OpenAiCompatibleProvider::new(
-            "Amazon Bedrock",
-            "https://bedrock-runtime.us-east-1.amazonaws.com",
-            api_key,
-            AuthStyle::Bearer,
-        ))),
-        "qianfan" | "baidu" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "qianfan" | "baidu" => Ok(Box::new(compatible(
             "Qianfan", "https://aip.baidubce.com", key, AuthStyle::Bearer,
         ))),
 
         // ── Extended ecosystem (community favorites) ─────────
-        "groq" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "groq" => Ok(Box::new(compatible(
             "Groq", "https://api.groq.com/openai", key, AuthStyle::Bearer,
         ))),
-        "mistral" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "mistral" => Ok(Box::new(compatible(
             "Mistral", "https://api.mistral.ai", key, AuthStyle::Bearer,
         ))),
-        "xai" | "grok" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "xai" | "grok" => Ok(Box::new(compatible(
             "xAI", "https://api.x.ai", key, AuthStyle::Bearer,
         ))),
-        "deepseek" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "deepseek" => Ok(Box::new(compatible(
             "DeepSeek", "https://api.deepseek.com", key, AuthStyle::Bearer,
         ))),
-        "together" | "together-ai" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "together" | "together-ai" => Ok(Box::new(compatible(
             "Together AI", "https://api.together.xyz", key, AuthStyle::Bearer,
         ))),
-        "fireworks" | "fireworks-ai" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "fireworks" | "fireworks-ai" => Ok(Box::new(compatible(
             "Fireworks AI", "https://api.fireworks.ai/inference", key, AuthStyle::Bearer,
         ))),
-        "perplexity" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "perplexity" => Ok(Box::new(compatible(
             "Perplexity", "https://api.perplexity.ai", key, AuthStyle::Bearer,
         ))),
-        "cohere" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "cohere" => Ok(Box::new(compatible(
             "Cohere", "https://api.cohere.com/compatibility", key, AuthStyle::Bearer,
         ))),
-        "copilot" | "github-copilot" => Ok(Box::new(OpenAiCompatibleProvider::new(
+        "copilot" | "github-copilot" => Ok(Box::new(compatible(
             "GitHub Copilot", "https://api.githubcopilot.com", key, AuthStyle::Bearer,
         ))),
 
+        // ── Azure OpenAI (deployment-based URLs, api-key header) ──
+        // Format: "azure" (resource from [azure_openai] / env) or "azure:<resource>"
+        "azure" | "azure-openai" => Ok(Box::new(
+            azure::AzureOpenAiProvider::new(None, key, &options.azure)
+                .with_reasoning_effort(effort),
+        )),
+        name if name.starts_with("azure:") => {
+            let resource = name.strip_prefix("azure:").unwrap_or("");
+            if resource.is_empty() {
+                anyhow::bail!("Azure provider requires a resource name. Format: azure:<resource>");
+            }
+            Ok(Box::new(
+                azure::AzureOpenAiProvider::new(Some(resource), key, &options.azure)
+                    .with_reasoning_effort(effort),
+            ))
+        }
+
         // ── Bring Your Own Provider (custom URL) ───────────
         // Format: "custom:https://your-api.com" or "custom:http://localhost:1234"
         name if name.starts_with("custom:") => {
@@ -245,7 +321,7 @@ pub fn create_provider(name: &str, api_key: Option<&str>) -> anyhow::Result<Box<
             if base_url.is_empty() {
                 anyhow::bail!("Custom provider requires a URL. Format: custom:https://your-api.com");
             }
-            Ok(Box::new(OpenAiCompatibleProvider::new(
+            Ok(Box::new(compatible(
                 "Custom",
                 base_url,
                 key,
@@ -268,7 +344,8 @@ pub fn create_provider(name: &str, api_key: Option<&str>) -> anyhow::Result<Box<
         _ => anyhow::bail!(
             "Unknown provider: {name}. Check README for supported providers or run `zeroclaw onboard --interactive` to reconfigure.\n\
              Tip: Use \"custom:https://your-api.com\" for OpenAI-compatible endpoints.\n\
-             Tip: Use \"anthropic-custom:https://your-api.com\" for Anthropic-compatible endpoints."
+             Tip: Use \"anthropic-custom:https://your-api.com\" for Anthropic-compatible endpoints.\n\
+             Tip: Use \"azure:<resource>\" for Azure OpenAI deployments."
         ),
     }
 }
@@ -278,12 +355,28 @@ pub fn create_resilient_provider(
     primary_name: &str,
     api_key: Option<&str>,
     reliability: &crate::config::ReliabilityConfig,
+) -> anyhow::Result<Box<dyn Provider>> {
+    create_resilient_provider_with_options(
+        primary_name,
+        api_key,
+        reliability,
+        &ProviderOptions::default(),
+    )
+}
+
+/// Create provider chain with retry and fallback behavior, applying `options`
+/// to the primary and every fallback provider.
+pub fn create_resilient_provider_with_options(
+    primary_name: &str,
+    api_key: Option<&str>,
+    reliability: &crate::config::ReliabilityConfig,
+    options: &ProviderOptions,
 ) -> anyhow::Result<Box<dyn Provider>> {
     let mut providers: Vec<(String, Box<dyn Provider>)> = Vec::new();
 
     providers.push((
         primary_name.to_string(),
-        create_provider(primary_name, api_key)?,
+        create_provider_with_options(primary_name, api_key, options)?,
     ));
 
     for fallback in &reliability.fallback_providers {
@@ -300,7 +393,7 @@ pub fn create_resilient_provider(
             );
         }
 
-        match create_provider(fallback, api_key) {
+        match create_provider_with_options(fallback, api_key, options) {
             Ok(provider) => providers.push((fallback.clone(), provider)),
             Err(e) => {
                 tracing::warn!(
@@ -318,6 +411,15 @@ pub fn create_resilient_provider(
     )))
 }
 
+/// Router lookup key for a route's provider instance: the provider name, plus
+/// the reasoning effort when the route sets one.
+fn route_provider_key(provider: &str, reasoning_effort: Option<&str>) -> String {
+    match reasoning_effort {
+        Some(effort) => format!("{provider}#reasoning={effort}"),
+        None => provider.to_string(),
+    }
+}
+
 /// Create a RouterProvider if model routes are configured, otherwise return a
 /// standard resilient provider. The router wraps individual providers per route,
 /// each with its own retry/fallback chain.
@@ -327,29 +429,52 @@ pub fn create_routed_provider(
     reliability: &crate::config::ReliabilityConfig,
     model_routes: &[crate::config::ModelRouteConfig],
     default_model: &str,
+    base_options: &ProviderOptions,
 ) -> anyhow::Result<Box<dyn Provider>> {
     if model_routes.is_empty() {
-        return create_resilient_provider(primary_name, api_key, reliability);
+        return create_resilient_provider_with_options(
+            primary_name,
+            api_key,
+            reliability,
+            base_options,
+        );
     }
 
-    // Collect unique provider names needed
-    let mut needed: Vec<String> = vec![primary_name.to_string()];
+    // Collect unique (provider, reasoning effort) pairs needed. Routes that set
+    // `reasoning_effort` get their own provider instance so the effort only
+    // applies to that route.
+    let mut needed: Vec<(String, String, Option<String>)> =
+        vec![(primary_name.to_string(), primary_name.to_string(), None)];
     for route in model_routes {
-        if !needed.iter().any(|n| n == &route.provider) {
-            needed.push(route.provider.clone());
+        if let Some(effort) = route.reasoning_effort.as_deref() {
+            if !reasoning::is_valid_reasoning_effort(effort) {
+                tracing::warn!(
+                    hint = route.hint.as_str(),
+                    effort,
+                    "Unrecognized reasoning_effort; forwarding to provider as-is"
+                );
+            }
+        }
+        let key = route_provider_key(&route.provider, route.reasoning_effort.as_deref());
+        if !needed.iter().any(|(k, _, _)| k == &key) {
+            needed.push((key, route.provider.clone(), route.reasoning_effort.clone()));
         }
     }
 
     // Create each provider (with its own resilience wrapper)
     let mut providers: Vec<(String, Box<dyn Provider>)> = Vec::new();
-    for name in &needed {
+    for (provider_key, name, reasoning_effort) in &needed {
         let key = model_routes
             .iter()
             .find(|r| &r.provider == name)
             .and_then(|r| r.api_key.as_deref())
             .or(api_key);
-        match create_resilient_provider(name, key, reliability) {
-            Ok(provider) => providers.push((name.clone(), provider)),
+        let options = ProviderOptions {
+            reasoning_effort: reasoning_effort.clone(),
+            ..base_options.clone()
+        };
+        match create_resilient_provider_with_options(name, key, reliability, &options) {
+            Ok(provider) => providers.push((provider_key.clone(), provider)),
             Err(e) => {
                 if name == primary_name {
                     return Err(e);
@@ -369,7 +494,7 @@ pub fn create_routed_provider(
             (
                 r.hint.clone(),
                 router::Route {
-                    provider_name: r.provider.clone(),
+                    provider_name: route_provider_key(&r.provider, r.reasoning_effort.as_deref()),
                     model: r.model.clone(),
                 },
             )
@@ -569,6 +694,26 @@ mod tests {
         }
     }
 
+    // ── Azure OpenAI ─────────────────────────────────────────
+
+    #[test]
+    fn factory_azure() {
+        assert!(create_provider("azure", Some("key")).is_ok());
+        assert!(create_provider("azure-openai", Some("key")).is_ok());
+        assert!(create_provider("azure:contoso", Some("key")).is_ok());
+    }
+
+    #[test]
+    fn factory_azure_empty_resource_errors() {
+        match create_provider("azure:", None) {
+            Err(e) => assert!(
+                e.to_string().contains("requires a resource"),
+                "Expected 'requires a resource', got: {e}"
+            ),
+            Ok(_) => panic!("Expected error for empty azure resource"),
+        }
+    }
+
     // ── Anthropic-compatible custom endpoints ─────────────────
 
     #[test]
@@ -662,6 +807,7 @@ mod tests {
             "glm",
             "minimax",
             "bedrock",
+            "azure",
             "qianfan",
             "groq",
             "mistral",
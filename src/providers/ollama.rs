@@ -35,6 +35,9 @@ struct ChatResponse {
 #[derive(Debug, Deserialize)]
 struct ResponseMessage {
     content: String,
+    /// Separate reasoning returned when `think` is enabled on thinking models.
+    #[serde(default)]
+    thinking: Option<String>,
 }
 
 impl OllamaProvider {
@@ -44,11 +47,15 @@ impl OllamaProvider {
                 .unwrap_or("http://localhost:11434")
                 .trim_end_matches('/')
                 .to_string(),
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(300)) // Ollama runs locally, may be slow
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: crate::http::client(
+                "ollama",
+                crate::http::Defaults {
+                    // Ollama runs locally, may be slow
+                    timeout: Some(std::time::Duration::from_secs(300)),
+                    connect_timeout: Some(std::time::Duration::from_secs(10)),
+                    poll: None,
+                },
+            ),
         }
     }
 }
@@ -93,7 +100,10 @@ impl Provider for OllamaProvider {
         }
 
         let chat_response: ChatResponse = response.json().await?;
-        Ok(chat_response.message.content)
+        Ok(super::reasoning::wrap_reasoning(
+            chat_response.message.thinking.as_deref(),
+            &chat_response.message.content,
+        ))
     }
 }
 
@@ -185,4 +195,11 @@ mod tests {
         let resp: ChatResponse = serde_json::from_str(json).unwrap();
         assert!(resp.message.content.contains("line1"));
     }
+
+    #[test]
+    fn response_with_thinking_field() {
+        let json = r#"{"message":{"role":"assistant","content":"4","thinking":"2 plus 2"}}"#;
+        let resp: ChatResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(resp.message.thinking.as_deref(), Some("2 plus 2"));
+    }
 }
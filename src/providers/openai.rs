@@ -1,18 +1,21 @@
-use crate::providers::traits::Provider;
+use crate::providers::traits::{openai_vision_messages, ImageInput, Provider};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
 pub struct OpenAiProvider {
     api_key: Option<String>,
+    reasoning_effort: Option<String>,
     client: Client,
 }
 
 #[derive(Debug, Serialize)]
-struct ChatRequest {
+struct ChatRequest<M = Message> {
     model: String,
-    messages: Vec<Message>,
+    messages: Vec<M>,
     temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning_effort: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -40,13 +43,49 @@ impl OpenAiProvider {
     pub fn new(api_key: Option<&str>) -> Self {
         Self {
             api_key: api_key.map(ToString::to_string),
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            reasoning_effort: None,
+            client: crate::http::client("openai", crate::http::Defaults::PROVIDER),
         }
     }
+
+    /// Set the `reasoning_effort` request parameter for o-series models.
+    pub fn with_reasoning_effort(mut self, effort: Option<&str>) -> Self {
+        self.reasoning_effort = effort.map(ToString::to_string);
+        self
+    }
+
+    fn api_key(&self) -> anyhow::Result<&str> {
+        self.api_key.as_deref().ok_or_else(|| {
+            anyhow::anyhow!("OpenAI API key not set. Set OPENAI_API_KEY or edit config.toml.")
+        })
+    }
+
+    async fn complete<M: Serialize + Sync>(
+        &self,
+        api_key: &str,
+        request: &ChatRequest<M>,
+    ) -> anyhow::Result<String> {
+        let response = self
+            .client
+            .post("https://api.openai.com/v1/chat/completions")
+            .header("Authorization", format!("Bearer {api_key}"))
+            .json(request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenAI", response).await);
+        }
+
+        let chat_response: ChatResponse = response.json().await?;
+
+        chat_response
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message.content)
+            .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))
+    }
 }
 
 #[async_trait]
@@ -58,9 +97,7 @@ impl Provider for OpenAiProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let api_key = self.api_key.as_ref().ok_or_else(|| {
-            anyhow::anyhow!("OpenAI API key not set. Set OPENAI_API_KEY or edit config.toml.")
-        })?;
+        let api_key = self.api_key()?;
 
         let mut messages = Vec::new();
 
@@ -80,28 +117,35 @@ impl Provider for OpenAiProvider {
             model: model.to_string(),
             messages,
             temperature,
+            reasoning_effort: self.reasoning_effort.clone(),
         };
 
-        let response = self
-            .client
-            .post("https://api.openai.com/v1/chat/completions")
-            .header("Authorization", format!("Bearer {api_key}"))
-            .json(&request)
-            .send()
-            .await?;
+        self.complete(api_key, &request).await
+    }
 
-        if !response.status().is_success() {
-            return Err(super::api_error("OpenAI", response).await);
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageInput],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        if images.is_empty() {
+            return self
+                .chat_with_system(system_prompt, message, model, temperature)
+                .await;
         }
+        let api_key = self.api_key()?;
 
-        let chat_response: ChatResponse = response.json().await?;
+        let request = ChatRequest {
+            model: model.to_string(),
+            messages: openai_vision_messages(system_prompt, message, images),
+            temperature,
+            reasoning_effort: self.reasoning_effort.clone(),
+        };
 
-        chat_response
-            .choices
-            .into_iter()
-            .next()
-            .map(|c| c.message.content)
-            .ok_or_else(|| anyhow::anyhow!("No response from OpenAI"))
+        self.complete(api_key, &request).await
     }
 }
 
@@ -159,6 +203,7 @@ mod tests {
                 },
             ],
             temperature: 0.7,
+            reasoning_effort: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("\"role\":\"system\""));
@@ -175,9 +220,11 @@ mod tests {
                 content: "hello".to_string(),
             }],
             temperature: 0.0,
+            reasoning_effort: None,
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(!json.contains("system"));
+        assert!(!json.contains("reasoning_effort"));
         assert!(json.contains("\"temperature\":0.0"));
     }
 
@@ -189,6 +236,19 @@ mod tests {
         assert_eq!(resp.choices[0].message.content, "Hi!");
     }
 
+    #[test]
+    fn with_reasoning_effort_serializes() {
+        let p = OpenAiProvider::new(Some("sk-test")).with_reasoning_effort(Some("medium"));
+        let req: ChatRequest = ChatRequest {
+            model: "o3-mini".to_string(),
+            messages: vec![],
+            temperature: 1.0,
+            reasoning_effort: p.reasoning_effort.clone(),
+        };
+        let json = serde_json::to_string(&req).unwrap();
+        assert!(json.contains(r#""reasoning_effort":"medium""#));
+    }
+
     #[test]
     fn response_deserializes_empty_choices() {
         let json = r#"{"choices":[]}"#;
@@ -1,18 +1,28 @@
-use crate::providers::traits::{ChatMessage, Provider};
+use crate::providers::reasoning::wrap_reasoning;
+use crate::providers::traits::{openai_vision_messages, ChatMessage, ImageInput, Provider};
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
 pub struct OpenRouterProvider {
     api_key: Option<String>,
+    reasoning_effort: Option<String>,
     client: Client,
 }
 
 #[derive(Debug, Serialize)]
-struct ChatRequest {
+struct ChatRequest<M = Message> {
     model: String,
-    messages: Vec<Message>,
+    messages: Vec<M>,
     temperature: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning: Option<ReasoningOptions>,
+}
+
+/// OpenRouter's unified reasoning parameter.
+#[derive(Debug, Serialize)]
+struct ReasoningOptions {
+    effort: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -33,20 +43,44 @@ struct Choice {
 
 #[derive(Debug, Deserialize)]
 struct ResponseMessage {
-    content: String,
+    #[serde(default)]
+    content: Option<String>,
+    /// Reasoning tokens returned for thinking models.
+    #[serde(default)]
+    reasoning: Option<String>,
+}
+
+impl ResponseMessage {
+    fn into_text(self) -> String {
+        wrap_reasoning(
+            self.reasoning.as_deref(),
+            self.content.as_deref().unwrap_or_default(),
+        )
+    }
 }
 
 impl OpenRouterProvider {
     pub fn new(api_key: Option<&str>) -> Self {
         Self {
             api_key: api_key.map(ToString::to_string),
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(120))
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            reasoning_effort: None,
+            client: crate::http::client("openrouter", crate::http::Defaults::PROVIDER),
         }
     }
+
+    /// Set the reasoning effort forwarded to thinking models.
+    pub fn with_reasoning_effort(mut self, effort: Option<&str>) -> Self {
+        self.reasoning_effort = effort.map(ToString::to_string);
+        self
+    }
+
+    fn reasoning_options(&self) -> Option<ReasoningOptions> {
+        self.reasoning_effort
+            .as_ref()
+            .map(|effort| ReasoningOptions {
+                effort: effort.clone(),
+            })
+    }
 }
 
 #[async_trait]
@@ -93,6 +127,7 @@ impl Provider for OpenRouterProvider {
             model: model.to_string(),
             messages,
             temperature,
+            reasoning: self.reasoning_options(),
         };
 
         let response = self
@@ -118,7 +153,7 @@ impl Provider for OpenRouterProvider {
             .choices
             .into_iter()
             .next()
-            .map(|c| c.message.content)
+            .map(|c| c.message.into_text())
             .ok_or_else(|| anyhow::anyhow!("No response from OpenRouter"))
     }
 
@@ -143,6 +178,57 @@ impl Provider for OpenRouterProvider {
             model: model.to_string(),
             messages: api_messages,
             temperature,
+            reasoning: self.reasoning_options(),
+        };
+
+        let response = self
+            .client
+            .post("https://openrouter.ai/api/v1/chat/completions")
+            .header("Authorization", format!("Bearer {api_key}"))
+            .header(
+                "HTTP-Referer",
+                "https://github.com/theonlyhennygod/zeroclaw",
+            )
+            .header("X-Title", "ZeroClaw")
+            .json(&request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(super::api_error("OpenRouter", response).await);
+        }
+
+        let chat_response: ApiChatResponse = response.json().await?;
+
+        chat_response
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.message.into_text())
+            .ok_or_else(|| anyhow::anyhow!("No response from OpenRouter"))
+    }
+
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageInput],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        if images.is_empty() {
+            return self
+                .chat_with_system(system_prompt, message, model, temperature)
+                .await;
+        }
+        let api_key = self.api_key.as_ref()
+            .ok_or_else(|| anyhow::anyhow!("OpenRouter API key not set. Run `zeroclaw onboard` or set OPENROUTER_API_KEY env var."))?;
+
+        let request = ChatRequest {
+            model: model.to_string(),
+            messages: openai_vision_messages(system_prompt, message, images),
+            temperature,
+            reasoning: self.reasoning_options(),
         };
 
         let response = self
@@ -168,7 +254,7 @@ impl Provider for OpenRouterProvider {
             .choices
             .into_iter()
             .next()
-            .map(|c| c.message.content)
+            .map(|c| c.message.into_text())
             .ok_or_else(|| anyhow::anyhow!("No response from OpenRouter"))
     }
 }
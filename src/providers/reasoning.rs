@@ -0,0 +1,158 @@
+//! Reasoning-model output handling.
+//!
+//! Models such as DeepSeek-R1, QwQ and MiniMax either embed their chain of
+//! thought inline as `<think>...</think>` or return it in a separate field
+//! (`reasoning_content`, `reasoning`, Anthropic `thinking` blocks, Gemini
+//! `thought` parts, Ollama `thinking`). Providers normalize all of these into a
+//! single leading `<think>` block via [`wrap_reasoning`], so callers only ever
+//! need [`split_reasoning`] or [`strip_reasoning`] to separate it from the answer.
+
+const THINK_OPEN: &str = "<think>";
+const THINK_CLOSE: &str = "</think>";
+
+/// Prefix `content` with a `<think>` block when the provider returned reasoning
+/// in a dedicated field. Empty reasoning is dropped.
+pub fn wrap_reasoning(reasoning: Option<&str>, content: &str) -> String {
+    match reasoning.map(str::trim).filter(|r| !r.is_empty()) {
+        Some(reasoning) => format!("{THINK_OPEN}{reasoning}{THINK_CLOSE}\n{content}"),
+        None => content.to_string(),
+    }
+}
+
+/// Split a raw model response into `(reasoning, answer)`.
+///
+/// Handles multiple `<think>` blocks, an unterminated `<think>` (everything
+/// after it is reasoning), and a dangling `</think>` without an opening tag,
+/// which some hosted DeepSeek-R1 deployments emit because the opening tag is
+/// part of the prompt template.
+pub fn split_reasoning(response: &str) -> (Option<String>, String) {
+    let mut reasoning_parts: Vec<&str> = Vec::new();
+    let mut answer = String::new();
+    let mut remaining = response;
+
+    // Dangling close tag: everything before it is reasoning.
+    if let Some(close) = remaining.find(THINK_CLOSE) {
+        let opens_before = remaining[..close].contains(THINK_OPEN);
+        if !opens_before {
+            reasoning_parts.push(&remaining[..close]);
+            remaining = &remaining[close + THINK_CLOSE.len()..];
+        }
+    }
+
+    while let Some(start) = remaining.find(THINK_OPEN) {
+        answer.push_str(&remaining[..start]);
+        let after_open = &remaining[start + THINK_OPEN.len()..];
+        if let Some(end) = after_open.find(THINK_CLOSE) {
+            reasoning_parts.push(&after_open[..end]);
+            remaining = &after_open[end + THINK_CLOSE.len()..];
+        } else {
+            reasoning_parts.push(after_open);
+            remaining = "";
+        }
+    }
+    answer.push_str(remaining);
+
+    let reasoning = reasoning_parts
+        .iter()
+        .map(|part| part.trim())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    let reasoning = if reasoning.is_empty() {
+        None
+    } else {
+        Some(reasoning)
+    };
+
+    (reasoning, answer.trim().to_string())
+}
+
+/// Remove any reasoning from a response, returning only the answer.
+/// Used before delivering replies to channels and webhook callers.
+pub fn strip_reasoning(response: &str) -> String {
+    split_reasoning(response).1
+}
+
+/// Returns true when `effort` is a value accepted by OpenAI-style APIs.
+pub fn is_valid_reasoning_effort(effort: &str) -> bool {
+    matches!(effort, "minimal" | "low" | "medium" | "high")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_plain_response_has_no_reasoning() {
+        let (reasoning, answer) = split_reasoning("Hello there");
+        assert!(reasoning.is_none());
+        assert_eq!(answer, "Hello there");
+    }
+
+    #[test]
+    fn split_inline_think_block() {
+        let (reasoning, answer) =
+            split_reasoning("<think>\nThe user greets me.\n</think>\n\nHello!");
+        assert_eq!(reasoning.as_deref(), Some("The user greets me."));
+        assert_eq!(answer, "Hello!");
+    }
+
+    #[test]
+    fn split_multiple_think_blocks() {
+        let (reasoning, answer) =
+            split_reasoning("<think>first</think>Part one. <think>second</think>Part two.");
+        assert_eq!(reasoning.as_deref(), Some("first\n\nsecond"));
+        assert_eq!(answer, "Part one. Part two.");
+    }
+
+    #[test]
+    fn split_unterminated_think_block() {
+        let (reasoning, answer) = split_reasoning("Answer first<think>still thinking");
+        assert_eq!(reasoning.as_deref(), Some("still thinking"));
+        assert_eq!(answer, "Answer first");
+    }
+
+    #[test]
+    fn split_dangling_close_tag() {
+        let (reasoning, answer) = split_reasoning("Let me think about it.</think>The answer is 4.");
+        assert_eq!(reasoning.as_deref(), Some("Let me think about it."));
+        assert_eq!(answer, "The answer is 4.");
+    }
+
+    #[test]
+    fn split_empty_think_block() {
+        let (reasoning, answer) = split_reasoning("<think>\n\n</think>Done.");
+        assert!(reasoning.is_none());
+        assert_eq!(answer, "Done.");
+    }
+
+    #[test]
+    fn wrap_then_split_round_trips() {
+        let wrapped = wrap_reasoning(Some("step 1, step 2"), "Result");
+        let (reasoning, answer) = split_reasoning(&wrapped);
+        assert_eq!(reasoning.as_deref(), Some("step 1, step 2"));
+        assert_eq!(answer, "Result");
+    }
+
+    #[test]
+    fn wrap_without_reasoning_is_identity() {
+        assert_eq!(wrap_reasoning(None, "Result"), "Result");
+        assert_eq!(wrap_reasoning(Some("   "), "Result"), "Result");
+    }
+
+    #[test]
+    fn wrapped_tool_call_json_is_parseable_after_split() {
+        let json = r#"{"content":null,"tool_calls":[{"type":"function","function":{"name":"shell","arguments":"{}"}}]}"#;
+        let wrapped = wrap_reasoning(Some("I should run a command"), json);
+        let answer = strip_reasoning(&wrapped);
+        assert!(serde_json::from_str::<serde_json::Value>(&answer).is_ok());
+    }
+
+    #[test]
+    fn reasoning_effort_validation() {
+        assert!(is_valid_reasoning_effort("low"));
+        assert!(is_valid_reasoning_effort("high"));
+        assert!(!is_valid_reasoning_effort("extreme"));
+    }
+}
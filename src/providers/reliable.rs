@@ -1,5 +1,6 @@
-use super::traits::ChatMessage;
+use super::traits::{ChatMessage, ImageInput};
 use super::Provider;
+use crate::tools::ToolSpec;
 use async_trait::async_trait;
 use std::time::Duration;
 
@@ -48,35 +49,21 @@ impl ReliableProvider {
     }
 }
 
-#[async_trait]
-impl Provider for ReliableProvider {
-    async fn warmup(&self) -> anyhow::Result<()> {
-        for (name, provider) in &self.providers {
-            tracing::info!(provider = name, "Warming up provider connection pool");
-            if let Err(e) = provider.warmup().await {
-                tracing::warn!(provider = name, "Warmup failed (non-fatal): {e}");
-            }
-        }
-        Ok(())
-    }
-
-    async fn chat_with_system(
-        &self,
-        system_prompt: Option<&str>,
-        message: &str,
-        model: &str,
-        temperature: f64,
-    ) -> anyhow::Result<String> {
+impl ReliableProvider {
+    /// Run `call` against each provider in order, retrying transient errors
+    /// with exponential backoff before falling back to the next one.
+    async fn with_retries<'a, F, Fut>(&'a self, mut call: F) -> anyhow::Result<String>
+    where
+        F: FnMut(&'a dyn Provider) -> Fut + Send,
+        Fut: std::future::Future<Output = anyhow::Result<String>> + Send,
+    {
         let mut failures = Vec::new();
 
         for (provider_name, provider) in &self.providers {
             let mut backoff_ms = self.base_backoff_ms;
 
             for attempt in 0..=self.max_retries {
-                match provider
-                    .chat_with_system(system_prompt, message, model, temperature)
-                    .await
-                {
+                match call(provider.as_ref()).await {
                     Ok(resp) => {
                         if attempt > 0 {
                             tracing::info!(
@@ -122,6 +109,32 @@ impl Provider for ReliableProvider {
 
         anyhow::bail!("All providers failed. Attempts:\n{}", failures.join("\n"))
     }
+}
+
+#[async_trait]
+impl Provider for ReliableProvider {
+    async fn warmup(&self) -> anyhow::Result<()> {
+        for (name, provider) in &self.providers {
+            tracing::info!(provider = name, "Warming up provider connection pool");
+            if let Err(e) = provider.warmup().await {
+                tracing::warn!(provider = name, "Warmup failed (non-fatal): {e}");
+            }
+        }
+        Ok(())
+    }
+
+    async fn chat_with_system(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.with_retries(|provider| {
+            provider.chat_with_system(system_prompt, message, model, temperature)
+        })
+        .await
+    }
 
     async fn chat_with_history(
         &self,
@@ -129,60 +142,33 @@ impl Provider for ReliableProvider {
         model: &str,
         temperature: f64,
     ) -> anyhow::Result<String> {
-        let mut failures = Vec::new();
-
-        for (provider_name, provider) in &self.providers {
-            let mut backoff_ms = self.base_backoff_ms;
-
-            for attempt in 0..=self.max_retries {
-                match provider
-                    .chat_with_history(messages, model, temperature)
-                    .await
-                {
-                    Ok(resp) => {
-                        if attempt > 0 {
-                            tracing::info!(
-                                provider = provider_name,
-                                attempt,
-                                "Provider recovered after retries"
-                            );
-                        }
-                        return Ok(resp);
-                    }
-                    Err(e) => {
-                        let non_retryable = is_non_retryable(&e);
-                        failures.push(format!(
-                            "{provider_name} attempt {}/{}: {e}",
-                            attempt + 1,
-                            self.max_retries + 1
-                        ));
-
-                        if non_retryable {
-                            tracing::warn!(
-                                provider = provider_name,
-                                "Non-retryable error, switching provider"
-                            );
-                            break;
-                        }
-
-                        if attempt < self.max_retries {
-                            tracing::warn!(
-                                provider = provider_name,
-                                attempt = attempt + 1,
-                                max_retries = self.max_retries,
-                                "Provider call failed, retrying"
-                            );
-                            tokio::time::sleep(Duration::from_millis(backoff_ms)).await;
-                            backoff_ms = (backoff_ms.saturating_mul(2)).min(10_000);
-                        }
-                    }
-                }
-            }
+        self.with_retries(|provider| provider.chat_with_history(messages, model, temperature))
+            .await
+    }
 
-            tracing::warn!(provider = provider_name, "Switching to fallback provider");
-        }
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[ToolSpec],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.with_retries(|provider| provider.chat_with_tools(messages, tools, model, temperature))
+            .await
+    }
 
-        anyhow::bail!("All providers failed. Attempts:\n{}", failures.join("\n"))
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageInput],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        self.with_retries(|provider| {
+            provider.chat_with_images(system_prompt, message, images, model, temperature)
+        })
+        .await
     }
 }
 
@@ -472,4 +458,44 @@ mod tests {
         assert_eq!(primary_calls.load(Ordering::SeqCst), 2);
         assert_eq!(fallback_calls.load(Ordering::SeqCst), 1);
     }
+
+    #[tokio::test]
+    async fn chat_with_tools_shares_retry_and_fallback() {
+        let primary_calls = Arc::new(AtomicUsize::new(0));
+        let fallback_calls = Arc::new(AtomicUsize::new(0));
+
+        let provider = ReliableProvider::new(
+            vec![
+                (
+                    "primary".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&primary_calls),
+                        fail_until_attempt: usize::MAX,
+                        response: "never",
+                        error: "503 overloaded",
+                    }),
+                ),
+                (
+                    "fallback".into(),
+                    Box::new(MockProvider {
+                        calls: Arc::clone(&fallback_calls),
+                        fail_until_attempt: 1,
+                        response: "tools ok",
+                        error: "temporary",
+                    }),
+                ),
+            ],
+            1,
+            1,
+        );
+
+        let messages = vec![ChatMessage::user("hello")];
+        let result = provider
+            .chat_with_tools(&messages, &[], "test", 0.0)
+            .await
+            .unwrap();
+        assert_eq!(result, "tools ok");
+        assert_eq!(primary_calls.load(Ordering::SeqCst), 2);
+        assert_eq!(fallback_calls.load(Ordering::SeqCst), 2);
+    }
 }
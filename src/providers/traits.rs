@@ -1,3 +1,4 @@
+use crate::tools::ToolSpec;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
@@ -31,6 +32,51 @@ impl ChatMessage {
     }
 }
 
+/// An image sent with a user message to a vision-capable model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageInput {
+    /// e.g. `image/jpeg`
+    pub mime_type: String,
+    /// Base64-encoded image bytes
+    pub data: String,
+}
+
+impl ImageInput {
+    /// OpenAI-style `image_url` content part carrying a data URL.
+    pub fn openai_part(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "image_url",
+            "image_url": { "url": format!("data:{};base64,{}", self.mime_type, self.data) }
+        })
+    }
+
+    /// Anthropic `image` content block.
+    pub fn anthropic_block(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "image",
+            "source": { "type": "base64", "media_type": self.mime_type, "data": self.data }
+        })
+    }
+}
+
+/// OpenAI-style chat `messages` for a system prompt and a user turn with
+/// images as content parts.
+pub fn openai_vision_messages(
+    system_prompt: Option<&str>,
+    message: &str,
+    images: &[ImageInput],
+) -> Vec<serde_json::Value> {
+    let mut parts = vec![serde_json::json!({ "type": "text", "text": message })];
+    parts.extend(images.iter().map(ImageInput::openai_part));
+
+    let mut messages = Vec::new();
+    if let Some(sys) = system_prompt {
+        messages.push(serde_json::json!({ "role": "system", "content": sys }));
+    }
+    messages.push(serde_json::json!({ "role": "user", "content": parts }));
+    messages
+}
+
 /// A tool call requested by the LLM.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ToolCall {
@@ -118,6 +164,42 @@ pub trait Provider: Send + Sync {
             .await
     }
 
+    /// Multi-turn conversation with native tool definitions.
+    ///
+    /// Providers with a function-calling API (e.g. Bedrock Converse) send `tools`
+    /// natively and return requested calls as OpenAI-style `tool_calls` JSON so
+    /// the agent loop's `parse_tool_calls` handles them. The default ignores
+    /// `tools` (they are already described in the system prompt) and delegates
+    /// to `chat_with_history`.
+    async fn chat_with_tools(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[ToolSpec],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let _ = tools;
+        self.chat_with_history(messages, model, temperature).await
+    }
+
+    /// Single turn with images attached to the user message.
+    ///
+    /// Providers whose API accepts image input send them natively. The
+    /// default drops the images (the message is expected to mention them)
+    /// and delegates to `chat_with_system`.
+    async fn chat_with_images(
+        &self,
+        system_prompt: Option<&str>,
+        message: &str,
+        images: &[ImageInput],
+        model: &str,
+        temperature: f64,
+    ) -> anyhow::Result<String> {
+        let _ = images;
+        self.chat_with_system(system_prompt, message, model, temperature)
+            .await
+    }
+
     /// Warm up the HTTP connection pool (TLS handshake, DNS, HTTP/2 setup).
     /// Default implementation is a no-op; providers with HTTP clients should override.
     async fn warmup(&self) -> anyhow::Result<()> {
@@ -163,6 +245,25 @@ mod tests {
         assert_eq!(with_tools.text_or_empty(), "Let me check");
     }
 
+    #[test]
+    fn vision_messages_put_images_after_text() {
+        let image = ImageInput {
+            mime_type: "image/png".into(),
+            data: "aGk=".into(),
+        };
+        let messages =
+            openai_vision_messages(Some("sys"), "what is this?", std::slice::from_ref(&image));
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0]["content"], "sys");
+        let parts = messages[1]["content"].as_array().unwrap();
+        assert_eq!(parts[0]["text"], "what is this?");
+        assert_eq!(parts[1]["image_url"]["url"], "data:image/png;base64,aGk=");
+
+        let block = image.anthropic_block();
+        assert_eq!(block["source"]["media_type"], "image/png");
+        assert_eq!(block["source"]["data"], "aGk=");
+    }
+
     #[test]
     fn tool_call_serialization() {
         let tc = ToolCall {
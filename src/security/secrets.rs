@@ -167,6 +167,25 @@ impl SecretStore {
         value.starts_with("enc2:")
     }
 
+    /// Whether the key file exists yet (nothing has been encrypted without it).
+    pub fn has_key(&self) -> bool {
+        self.key_path.exists()
+    }
+
+    /// Derive a 256-bit key for another subsystem (e.g. memory encryption)
+    /// as HMAC-SHA256(secret key, `purpose`), creating the key file if needed.
+    /// Distinct purposes yield unrelated keys.
+    pub fn derive_key(&self, purpose: &str) -> Result<[u8; KEY_LEN]> {
+        use hmac::{Hmac, Mac};
+        use sha2::Sha256;
+
+        let master = self.load_or_create_key()?;
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&master)
+            .map_err(|e| anyhow::anyhow!("Invalid secret key: {e}"))?;
+        mac.update(purpose.as_bytes());
+        Ok(mac.finalize().into_bytes().into())
+    }
+
     /// Load the encryption key from disk, or create one if it doesn't exist.
     fn load_or_create_key(&self) -> Result<Vec<u8>> {
         if self.key_path.exists() {
@@ -301,6 +320,17 @@ mod tests {
         assert_eq!(decrypted, secret, "Roundtrip must preserve original");
     }
 
+    #[test]
+    fn derived_keys_are_stable_and_purpose_bound() {
+        let tmp = TempDir::new().unwrap();
+        let store = SecretStore::new(tmp.path(), true);
+        assert!(!store.has_key());
+        let a = store.derive_key("memory-content").unwrap();
+        assert!(store.has_key());
+        assert_eq!(a, store.derive_key("memory-content").unwrap());
+        assert_ne!(a, store.derive_key("memory-index").unwrap());
+    }
+
     #[test]
     fn encrypt_empty_returns_empty() {
         let tmp = TempDir::new().unwrap();
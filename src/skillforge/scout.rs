@@ -69,6 +69,7 @@ pub trait Scout: Send + Sync {
 /// Searches GitHub for repos matching skill-related queries.
 pub struct GitHubScout {
     client: reqwest::Client,
+    headers: reqwest::header::HeaderMap,
     queries: Vec<String>,
 }
 
@@ -91,14 +92,18 @@ impl GitHubScout {
             }
         }
 
-        let client = reqwest::Client::builder()
-            .default_headers(headers)
-            .timeout(Duration::from_secs(30))
-            .build()
-            .expect("failed to build reqwest client");
+        let client = crate::http::client(
+            "skillforge",
+            crate::http::Defaults {
+                timeout: Some(Duration::from_secs(30)),
+                connect_timeout: None,
+                poll: None,
+            },
+        );
 
         Self {
             client,
+            headers,
             queries: vec!["zeroclaw skill".into(), "ai agent skill".into()],
         }
     }
@@ -168,7 +173,13 @@ impl Scout for GitHubScout {
             );
             debug!(query = query.as_str(), "Searching GitHub");
 
-            let resp = match self.client.get(&url).send().await {
+            let resp = match self
+                .client
+                .get(&url)
+                .headers(self.headers.clone())
+                .send()
+                .await
+            {
                 Ok(r) => r,
                 Err(e) => {
                     warn!(
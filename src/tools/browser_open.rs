@@ -109,7 +109,7 @@ impl Tool for BrowserOpenTool {
             }
         };
 
-        match open_in_brave(&url).await {
+        match open_in_brave(&url, &brave_args()).await {
             Ok(()) => Ok(ToolResult {
                 success: true,
                 output: format!("Opened in Brave: {url}"),
@@ -124,7 +124,21 @@ impl Tool for BrowserOpenTool {
     }
 }
 
-async fn open_in_brave(url: &str) -> anyhow::Result<()> {
+/// Extra Brave flags from `[http]` / `[http.overrides.browser_open]`. Chromium only
+/// honours `--proxy-server` when it starts a new browser process.
+fn brave_args() -> Vec<String> {
+    proxy_server_args(crate::http::settings("browser_open").proxy.as_deref())
+}
+
+fn proxy_server_args(proxy: Option<&str>) -> Vec<String> {
+    match proxy.map(str::trim) {
+        Some("") => vec!["--no-proxy-server".into()],
+        Some(url) => vec![format!("--proxy-server={url}")],
+        None => Vec::new(),
+    }
+}
+
+async fn open_in_brave(url: &str, extra_args: &[String]) -> anyhow::Result<()> {
     #[cfg(target_os = "macos")]
     {
         for app in ["Brave Browser", "Brave"] {
@@ -132,6 +146,12 @@ async fn open_in_brave(url: &str) -> anyhow::Result<()> {
                 .arg("-a")
                 .arg(app)
                 .arg(url)
+                .args(if extra_args.is_empty() {
+                    &[][..]
+                } else {
+                    &["--args"][..]
+                })
+                .args(extra_args)
                 .status()
                 .await;
 
@@ -150,7 +170,12 @@ async fn open_in_brave(url: &str) -> anyhow::Result<()> {
     {
         let mut last_error = String::new();
         for cmd in ["brave-browser", "brave"] {
-            match tokio::process::Command::new(cmd).arg(url).status().await {
+            match tokio::process::Command::new(cmd)
+                .args(extra_args)
+                .arg(url)
+                .status()
+                .await
+            {
                 Ok(status) if status.success() => return Ok(()),
                 Ok(status) => {
                     last_error = format!("{cmd} exited with status {status}");
@@ -166,7 +191,9 @@ async fn open_in_brave(url: &str) -> anyhow::Result<()> {
     #[cfg(target_os = "windows")]
     {
         let status = tokio::process::Command::new("cmd")
-            .args(["/C", "start", "", "brave", url])
+            .args(["/C", "start", "", "brave"])
+            .args(extra_args)
+            .arg(url)
             .status()
             .await?;
 
@@ -179,7 +206,7 @@ async fn open_in_brave(url: &str) -> anyhow::Result<()> {
 
     #[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
     {
-        let _ = url;
+        let _ = (url, extra_args);
         anyhow::bail!("browser_open is not supported on this OS");
     }
 }
@@ -321,6 +348,19 @@ mod tests {
         )
     }
 
+    #[test]
+    fn proxy_server_args_follow_http_config() {
+        assert!(proxy_server_args(None).is_empty());
+        assert_eq!(
+            proxy_server_args(Some("socks5://127.0.0.1:1080")),
+            vec!["--proxy-server=socks5://127.0.0.1:1080".to_string()]
+        );
+        assert_eq!(
+            proxy_server_args(Some("")),
+            vec!["--no-proxy-server".to_string()]
+        );
+    }
+
     #[test]
     fn normalize_domain_strips_scheme_path_and_case() {
         let got = normalize_domain("  HTTPS://Docs.Example.com/path ").unwrap();
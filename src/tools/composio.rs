@@ -24,11 +24,14 @@ impl ComposioTool {
     pub fn new(api_key: &str) -> Self {
         Self {
             api_key: api_key.to_string(),
-            client: Client::builder()
-                .timeout(std::time::Duration::from_secs(60))
-                .connect_timeout(std::time::Duration::from_secs(10))
-                .build()
-                .unwrap_or_else(|_| Client::new()),
+            client: crate::http::client(
+                "composio",
+                crate::http::Defaults {
+                    timeout: Some(std::time::Duration::from_secs(60)),
+                    connect_timeout: Some(std::time::Duration::from_secs(10)),
+                    poll: None,
+                },
+            ),
         }
     }
 
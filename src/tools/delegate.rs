@@ -1,6 +1,6 @@
 use super::traits::{Tool, ToolResult};
 use crate::config::DelegateAgentConfig;
-use crate::providers::{self, Provider};
+use crate::providers::{self, Provider, ProviderOptions};
 use async_trait::async_trait;
 use serde_json::json;
 use std::collections::HashMap;
@@ -20,6 +20,8 @@ pub struct DelegateTool {
     fallback_api_key: Option<String>,
     /// Depth at which this tool instance lives in the delegation chain.
     depth: u32,
+    /// `[ollama]` / `[azure_openai]` settings for sub-agent providers
+    provider_options: ProviderOptions,
 }
 
 impl DelegateTool {
@@ -31,9 +33,16 @@ impl DelegateTool {
             agents: Arc::new(agents),
             fallback_api_key,
             depth: 0,
+            provider_options: ProviderOptions::default(),
         }
     }
 
+    /// Create sub-agent providers with the same options as the main agent.
+    pub fn with_provider_options(mut self, options: ProviderOptions) -> Self {
+        self.provider_options = options;
+        self
+    }
+
     /// Create a DelegateTool for a sub-agent (with incremented depth).
     /// When sub-agents eventually get their own tool registry, construct
     /// their DelegateTool via this method with `depth: parent.depth + 1`.
@@ -46,6 +55,7 @@ impl DelegateTool {
             agents: Arc::new(agents),
             fallback_api_key,
             depth,
+            provider_options: ProviderOptions::default(),
         }
     }
 }
@@ -170,20 +180,23 @@ impl Tool for DelegateTool {
             .as_deref()
             .or(self.fallback_api_key.as_deref());
 
-        let provider: Box<dyn Provider> =
-            match providers::create_provider(&agent_config.provider, api_key) {
-                Ok(p) => p,
-                Err(e) => {
-                    return Ok(ToolResult {
-                        success: false,
-                        output: String::new(),
-                        error: Some(format!(
-                            "Failed to create provider '{}' for agent '{agent_name}': {e}",
-                            agent_config.provider
-                        )),
-                    });
-                }
-            };
+        let provider: Box<dyn Provider> = match providers::create_provider_with_options(
+            &agent_config.provider,
+            api_key,
+            &self.provider_options,
+        ) {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!(
+                        "Failed to create provider '{}' for agent '{agent_name}': {e}",
+                        agent_config.provider
+                    )),
+                });
+            }
+        };
 
         // Build the message
         let full_prompt = if context.is_empty() {
@@ -221,11 +234,13 @@ impl Tool for DelegateTool {
 
         match result {
             Ok(response) => Ok(ToolResult {
+                // Sub-agent reasoning stays private; only the answer is returned.
                 success: true,
                 output: format!(
                     "[Agent '{agent_name}' ({provider}/{model})]\n{response}",
                     provider = agent_config.provider,
-                    model = agent_config.model
+                    model = agent_config.model,
+                    response = providers::reasoning::strip_reasoning(&response)
                 ),
                 error: None,
             }),
@@ -423,4 +438,33 @@ mod tests {
                     .contains("Unknown agent")
         );
     }
+
+    #[tokio::test]
+    async fn sub_agents_use_configured_ollama_base_url() {
+        use axum::{routing::post, Json, Router};
+
+        let app = Router::new().route(
+            "/api/chat",
+            post(|| async {
+                Json(json!({"message": {"role": "assistant", "content": "from stub"}}))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+
+        let tool =
+            DelegateTool::new(sample_agents(), None).with_provider_options(ProviderOptions {
+                ollama_base_url: Some(format!("http://{addr}")),
+                ..ProviderOptions::default()
+            });
+        let result = tool
+            .execute(json!({"agent": "researcher", "prompt": "test"}))
+            .await
+            .unwrap();
+        assert!(result.success, "{:?}", result.error);
+        assert!(result.output.contains("from stub"));
+    }
 }
@@ -0,0 +1,135 @@
+use super::traits::{Tool, ToolResult};
+use crate::memory::graph::GraphQuery;
+use crate::memory::Memory;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Let the agent follow relations in the knowledge graph
+pub struct MemoryGraphQueryTool {
+    memory: Arc<dyn Memory>,
+}
+
+impl MemoryGraphQueryTool {
+    pub fn new(memory: Arc<dyn Memory>) -> Self {
+        Self { memory }
+    }
+}
+
+#[async_trait]
+impl Tool for MemoryGraphQueryTool {
+    fn name(&self) -> &str {
+        "memory_graph_query"
+    }
+
+    fn description(&self) -> &str {
+        "Look up an entity (person, project, service, …) in the knowledge graph and return what is known about it and how it relates to other entities. Optionally follow only one relation type or more hops."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "entity": {
+                    "type": "string",
+                    "description": "Entity name, or part of it (e.g. 'billing service')"
+                },
+                "relation": {
+                    "type": "string",
+                    "description": "Only follow this relation type (e.g. 'owns', 'works_on')"
+                },
+                "depth": {
+                    "type": "integer",
+                    "description": "Hops to follow from the entity, 1-3 (default: 1)"
+                },
+                "limit": {
+                    "type": "integer",
+                    "description": "Max relations to return (default: 20)"
+                }
+            },
+            "required": ["entity"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let entity = args
+            .get("entity")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'entity' parameter"))?;
+
+        #[allow(clippy::cast_possible_truncation)]
+        let depth = args
+            .get("depth")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(1, |v| (v as usize).clamp(1, 3));
+        #[allow(clippy::cast_possible_truncation)]
+        let limit = args
+            .get("limit")
+            .and_then(serde_json::Value::as_u64)
+            .map_or(20, |v| v as usize);
+        let query = GraphQuery {
+            entities: vec![entity.to_string()],
+            relation: args
+                .get("relation")
+                .and_then(|v| v.as_str())
+                .map(str::to_string),
+            depth,
+            limit,
+            ..GraphQuery::default()
+        };
+
+        match self.memory.graph_query(&query).await {
+            Ok(graph) if graph.is_empty() => Ok(ToolResult {
+                success: true,
+                output: format!("Nothing in the knowledge graph about '{entity}'."),
+                error: None,
+            }),
+            Ok(graph) => Ok(ToolResult {
+                success: true,
+                output: graph.render(),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Knowledge graph query failed: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::graph::{GraphUpdate, Relation};
+    use crate::memory::{MemoryScope, SqliteMemory};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn finds_relations_by_partial_name() {
+        let tmp = TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        mem.graph_upsert(
+            &GraphUpdate {
+                entities: vec![],
+                relations: vec![Relation {
+                    source: "Alice".into(),
+                    relation: "owns".into(),
+                    target: "Billing Service".into(),
+                }],
+            },
+            &MemoryScope::Global,
+        )
+        .await
+        .unwrap();
+
+        let tool = MemoryGraphQueryTool::new(mem);
+        let result = tool.execute(json!({"entity": "billing"})).await.unwrap();
+        assert!(result.success);
+        assert_eq!(result.output.trim(), "- Alice —owns→ Billing Service");
+
+        let none = tool.execute(json!({"entity": "Bob"})).await.unwrap();
+        assert!(none.output.contains("Nothing in the knowledge graph"));
+        assert!(tool.execute(json!({})).await.is_err());
+    }
+}
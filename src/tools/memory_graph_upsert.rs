@@ -0,0 +1,147 @@
+use super::traits::{Tool, ToolResult};
+use crate::memory::graph::GraphUpdate;
+use crate::memory::{Memory, MemoryScope};
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Let the agent record entities and relations in the knowledge graph
+pub struct MemoryGraphUpsertTool {
+    memory: Arc<dyn Memory>,
+}
+
+impl MemoryGraphUpsertTool {
+    pub fn new(memory: Arc<dyn Memory>) -> Self {
+        Self { memory }
+    }
+}
+
+#[async_trait]
+impl Tool for MemoryGraphUpsertTool {
+    fn name(&self) -> &str {
+        "memory_graph_upsert"
+    }
+
+    fn description(&self) -> &str {
+        "Record entities (people, projects, services, …), short observations about them, and typed relations between them in the knowledge graph. Existing entities are matched by name; repeated facts are ignored."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "entities": {
+                    "type": "array",
+                    "description": "Entities to create or update",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "kind": {
+                                "type": "string",
+                                "description": "person, project, service, organization, place, …"
+                            },
+                            "observations": {
+                                "type": "array",
+                                "items": { "type": "string" },
+                                "description": "Short facts about this entity"
+                            }
+                        },
+                        "required": ["name"]
+                    }
+                },
+                "relations": {
+                    "type": "array",
+                    "description": "Directed edges: source —relation→ target",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "source": { "type": "string" },
+                            "relation": {
+                                "type": "string",
+                                "description": "snake_case verb, e.g. owns, works_on, reports_to"
+                            },
+                            "target": { "type": "string" }
+                        },
+                        "required": ["source", "relation", "target"]
+                    }
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let update: GraphUpdate = serde_json::from_value(args)
+            .map_err(|e| anyhow::anyhow!("Invalid graph update: {e}"))?;
+        if update.entities.is_empty() && update.relations.is_empty() {
+            anyhow::bail!("Provide at least one entity or relation");
+        }
+
+        match self.memory.graph_upsert(&update, &MemoryScope::Global).await {
+            Ok(report) => Ok(ToolResult {
+                success: true,
+                output: format!(
+                    "Knowledge graph updated: {} new entities, {} new relations, {} new observations",
+                    report.entities_added, report.relations_added, report.observations_added
+                ),
+                error: None,
+            }),
+            Err(e) => Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Failed to update knowledge graph: {e}")),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::graph::GraphQuery;
+    use crate::memory::{MarkdownMemory, SqliteMemory};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn upsert_then_query() {
+        let tmp = TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(SqliteMemory::new(tmp.path()).unwrap());
+        let tool = MemoryGraphUpsertTool::new(mem.clone());
+        let args = json!({
+            "entities": [{"name": "Alice", "kind": "person", "observations": ["On call this week"]}],
+            "relations": [{"source": "Alice", "relation": "works on", "target": "Zeroclaw"}]
+        });
+        let result = tool.execute(args.clone()).await.unwrap();
+        assert!(result.success);
+        assert!(result
+            .output
+            .contains("2 new entities, 1 new relations, 1 new observations"));
+        let again = tool.execute(args).await.unwrap();
+        assert!(again
+            .output
+            .contains("0 new entities, 0 new relations, 0 new observations"));
+
+        let graph = mem
+            .graph_query(&GraphQuery {
+                entities: vec!["zeroclaw".into()],
+                ..GraphQuery::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(graph.relations[0].relation, "works_on");
+    }
+
+    #[tokio::test]
+    async fn rejects_empty_and_graphless_backends() {
+        let tmp = TempDir::new().unwrap();
+        let mem: Arc<dyn Memory> = Arc::new(MarkdownMemory::new(tmp.path()));
+        let tool = MemoryGraphUpsertTool::new(mem);
+        assert!(tool.execute(json!({})).await.is_err());
+        let result = tool
+            .execute(json!({"entities": [{"name": "Alice"}]}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("no knowledge graph"));
+    }
+}
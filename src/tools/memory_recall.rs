@@ -1,5 +1,6 @@
 use super::traits::{Tool, ToolResult};
-use crate::memory::Memory;
+use crate::memory::traits::parse_time_bound;
+use crate::memory::{Memory, MemoryCategory, RecallQuery};
 use async_trait::async_trait;
 use serde_json::json;
 use std::fmt::Write;
@@ -23,7 +24,7 @@ impl Tool for MemoryRecallTool {
     }
 
     fn description(&self) -> &str {
-        "Search long-term memory for relevant facts, preferences, or context. Returns scored results ranked by relevance."
+        "Search long-term memory for relevant facts, preferences, or context. Returns scored results ranked by relevance. Optional filters narrow by category, time range, key prefix, session or minimum score."
     }
 
     fn parameters_schema(&self) -> serde_json::Value {
@@ -37,6 +38,30 @@ impl Tool for MemoryRecallTool {
                 "limit": {
                     "type": "integer",
                     "description": "Max results to return (default: 5)"
+                },
+                "category": {
+                    "type": "string",
+                    "description": "Only this category: core, daily, conversation, or a custom name"
+                },
+                "since": {
+                    "type": "string",
+                    "description": "Only entries created at or after this time: YYYY-MM-DD, RFC 3339, or an age like 7d, 12h, 2w"
+                },
+                "until": {
+                    "type": "string",
+                    "description": "Only entries created at or before this time (same formats as 'since')"
+                },
+                "key_prefix": {
+                    "type": "string",
+                    "description": "Only entries whose key starts with this (e.g. 'project_x')"
+                },
+                "session": {
+                    "type": "string",
+                    "description": "Only entries from this session"
+                },
+                "min_score": {
+                    "type": "number",
+                    "description": "Drop results scoring below this"
                 }
             },
             "required": ["query"]
@@ -55,7 +80,18 @@ impl Tool for MemoryRecallTool {
             .and_then(serde_json::Value::as_u64)
             .map_or(5, |v| v as usize);
 
-        match self.memory.recall(query, limit).await {
+        let request = match recall_query(&args, query, limit) {
+            Ok(request) => request,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(e.to_string()),
+                })
+            }
+        };
+
+        match self.memory.recall_query(&request).await {
             Ok(entries) if entries.is_empty() => Ok(ToolResult {
                 success: true,
                 output: "No memories found matching that query.".into(),
@@ -67,11 +103,21 @@ impl Tool for MemoryRecallTool {
                     let score = entry
                         .score
                         .map_or_else(String::new, |s| format!(" [{s:.0}%]"));
-                    let _ = writeln!(
-                        output,
-                        "- [{}] {}: {}{score}",
-                        entry.category, entry.key, entry.content
-                    );
+                    if let Some((source, index)) =
+                        crate::memory::ingest::document_source(&entry.key)
+                    {
+                        let _ = writeln!(
+                            output,
+                            "- [{}] (source: {source}, chunk {index}) {}{score}",
+                            entry.category, entry.content
+                        );
+                    } else {
+                        let _ = writeln!(
+                            output,
+                            "- [{}] {}: {}{score}",
+                            entry.category, entry.key, entry.content
+                        );
+                    }
                 }
                 Ok(ToolResult {
                     success: true,
@@ -88,6 +134,34 @@ impl Tool for MemoryRecallTool {
     }
 }
 
+/// Build the structured query from the tool arguments.
+fn recall_query(
+    args: &serde_json::Value,
+    query: &str,
+    limit: usize,
+) -> anyhow::Result<RecallQuery> {
+    let text = |name: &str| {
+        args.get(name)
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+    };
+    Ok(RecallQuery {
+        category: text("category")
+            .map(|c| c.parse::<MemoryCategory>().unwrap_or_else(|e| match e {})),
+        since: text("since")
+            .map(|s| parse_time_bound(s, false))
+            .transpose()?,
+        until: text("until")
+            .map(|s| parse_time_bound(s, true))
+            .transpose()?,
+        key_prefix: text("key_prefix").map(String::from),
+        session: text("session").map(String::from),
+        min_score: args.get("min_score").and_then(serde_json::Value::as_f64),
+        ..RecallQuery::new(query, limit)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,6 +222,42 @@ mod tests {
         assert!(result.output.contains("Found 3"));
     }
 
+    #[tokio::test]
+    async fn recall_applies_filters() {
+        let (_tmp, mem) = seeded_mem();
+        mem.store("project_x_stack", "Rust backend", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("project_y_stack", "Rust CLI", MemoryCategory::Core)
+            .await
+            .unwrap();
+        mem.store("chat_1", "talked about Rust", MemoryCategory::Conversation)
+            .await
+            .unwrap();
+
+        let tool = MemoryRecallTool::new(mem);
+        let result = tool
+            .execute(json!({"query": "Rust", "category": "core", "key_prefix": "project_x"}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert!(result.output.contains("Found 1"));
+        assert!(result.output.contains("project_x_stack"));
+
+        let result = tool
+            .execute(json!({"query": "Rust", "since": "7d", "category": "conversation"}))
+            .await
+            .unwrap();
+        assert!(result.output.contains("chat_1"));
+
+        let result = tool
+            .execute(json!({"query": "Rust", "since": "last tuesday"}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("invalid time"));
+    }
+
     #[tokio::test]
     async fn recall_missing_query() {
         let (_tmp, mem) = seeded_mem();
@@ -161,6 +271,17 @@ mod tests {
         let (_tmp, mem) = seeded_mem();
         let tool = MemoryRecallTool::new(mem);
         assert_eq!(tool.name(), "memory_recall");
-        assert!(tool.parameters_schema()["properties"]["query"].is_object());
+        let schema = tool.parameters_schema();
+        assert!(schema["properties"]["query"].is_object());
+        for filter in [
+            "category",
+            "since",
+            "until",
+            "key_prefix",
+            "session",
+            "min_score",
+        ] {
+            assert!(schema["properties"][filter].is_object(), "{filter}");
+        }
     }
 }
@@ -41,6 +41,12 @@ impl Tool for MemoryStoreTool {
                     "type": "string",
                     "enum": ["core", "daily", "conversation"],
                     "description": "Memory category: core (permanent), daily (session), conversation (chat)"
+                },
+                "importance": {
+                    "type": "number",
+                    "minimum": 0,
+                    "maximum": 1,
+                    "description": "How much this should outrank other memories in recall (0-1, default 0.5)"
                 }
             },
             "required": ["key", "content"]
@@ -64,7 +70,25 @@ impl Tool for MemoryStoreTool {
             _ => MemoryCategory::Core,
         };
 
-        match self.memory.store(key, content, category).await {
+        let importance = args.get("importance").and_then(serde_json::Value::as_f64);
+        if importance.is_some_and(|i| !(0.0..=1.0).contains(&i)) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("'importance' must be between 0 and 1".into()),
+            });
+        }
+
+        let stored = async {
+            self.memory.store(key, content, category).await?;
+            if let Some(importance) = importance {
+                self.memory.set_importance(key, importance).await?;
+            }
+            anyhow::Ok(())
+        }
+        .await;
+
+        match stored {
             Ok(()) => Ok(ToolResult {
                 success: true,
                 output: format!("Stored memory: {key}"),
@@ -143,4 +167,24 @@ mod tests {
         let result = tool.execute(json!({"key": "no_content"})).await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn store_with_importance() {
+        let (_tmp, mem) = test_mem();
+        let tool = MemoryStoreTool::new(mem.clone());
+        let result = tool
+            .execute(json!({"key": "allergy", "content": "Allergic to peanuts", "importance": 0.9}))
+            .await
+            .unwrap();
+        assert!(result.success);
+        let entry = mem.get("allergy").await.unwrap().unwrap();
+        assert_eq!(entry.importance, Some(0.9));
+
+        let result = tool
+            .execute(json!({"key": "x", "content": "y", "importance": 3}))
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(mem.get("x").await.unwrap().is_none());
+    }
 }
@@ -6,6 +6,8 @@ pub mod file_read;
 pub mod file_write;
 pub mod image_info;
 pub mod memory_forget;
+pub mod memory_graph_query;
+pub mod memory_graph_upsert;
 pub mod memory_recall;
 pub mod memory_store;
 pub mod screenshot;
@@ -20,6 +22,8 @@ pub use file_read::FileReadTool;
 pub use file_write::FileWriteTool;
 pub use image_info::ImageInfoTool;
 pub use memory_forget::MemoryForgetTool;
+pub use memory_graph_query::MemoryGraphQueryTool;
+pub use memory_graph_upsert::MemoryGraphUpsertTool;
 pub use memory_recall::MemoryRecallTool;
 pub use memory_store::MemoryStoreTool;
 pub use screenshot::ScreenshotTool;
@@ -30,9 +34,11 @@ pub use traits::{ToolResult, ToolSpec};
 
 use crate::config::DelegateAgentConfig;
 use crate::memory::Memory;
+use crate::providers::ProviderOptions;
 use crate::runtime::{NativeRuntime, RuntimeAdapter};
 use crate::security::SecurityPolicy;
 use std::collections::HashMap;
+use std::hash::BuildHasher;
 use std::sync::Arc;
 
 /// Create the default tool registry
@@ -53,13 +59,14 @@ pub fn default_tools_with_runtime(
 }
 
 /// Create full tool registry including memory tools and optional Composio
-pub fn all_tools(
+pub fn all_tools<S: BuildHasher>(
     security: &Arc<SecurityPolicy>,
     memory: Arc<dyn Memory>,
     composio_key: Option<&str>,
     browser_config: &crate::config::BrowserConfig,
-    agents: &HashMap<String, DelegateAgentConfig>,
+    agents: &HashMap<String, DelegateAgentConfig, S>,
     fallback_api_key: Option<&str>,
+    provider_options: &ProviderOptions,
 ) -> Vec<Box<dyn Tool>> {
     all_tools_with_runtime(
         security,
@@ -69,18 +76,21 @@ pub fn all_tools(
         browser_config,
         agents,
         fallback_api_key,
+        provider_options,
     )
 }
 
 /// Create full tool registry including memory tools and optional Composio.
-pub fn all_tools_with_runtime(
+#[allow(clippy::too_many_arguments)]
+pub fn all_tools_with_runtime<S: BuildHasher>(
     security: &Arc<SecurityPolicy>,
     runtime: Arc<dyn RuntimeAdapter>,
     memory: Arc<dyn Memory>,
     composio_key: Option<&str>,
     browser_config: &crate::config::BrowserConfig,
-    agents: &HashMap<String, DelegateAgentConfig>,
+    agents: &HashMap<String, DelegateAgentConfig, S>,
     fallback_api_key: Option<&str>,
+    provider_options: &ProviderOptions,
 ) -> Vec<Box<dyn Tool>> {
     let mut tools: Vec<Box<dyn Tool>> = vec![
         Box::new(ShellTool::new(security.clone(), runtime)),
@@ -88,7 +98,9 @@ pub fn all_tools_with_runtime(
         Box::new(FileWriteTool::new(security.clone())),
         Box::new(MemoryStoreTool::new(memory.clone())),
         Box::new(MemoryRecallTool::new(memory.clone())),
-        Box::new(MemoryForgetTool::new(memory)),
+        Box::new(MemoryForgetTool::new(memory.clone())),
+        Box::new(MemoryGraphQueryTool::new(memory.clone())),
+        Box::new(MemoryGraphUpsertTool::new(memory)),
     ];
 
     if browser_config.enabled {
@@ -117,10 +129,16 @@ pub fn all_tools_with_runtime(
 
     // Add delegation tool when agents are configured
     if !agents.is_empty() {
-        tools.push(Box::new(DelegateTool::new(
-            agents.clone(),
-            fallback_api_key.map(String::from),
-        )));
+        tools.push(Box::new(
+            DelegateTool::new(
+                agents
+                    .iter()
+                    .map(|(name, agent)| (name.clone(), agent.clone()))
+                    .collect(),
+                fallback_api_key.map(String::from),
+            )
+            .with_provider_options(provider_options.clone()),
+        ));
     }
 
     tools
@@ -156,7 +174,15 @@ mod tests {
             session_name: None,
         };
 
-        let tools = all_tools(&security, mem, None, &browser, &HashMap::new(), None);
+        let tools = all_tools(
+            &security,
+            mem,
+            None,
+            &browser,
+            &HashMap::new(),
+            None,
+            &ProviderOptions::default(),
+        );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(!names.contains(&"browser_open"));
     }
@@ -178,7 +204,15 @@ mod tests {
             session_name: None,
         };
 
-        let tools = all_tools(&security, mem, None, &browser, &HashMap::new(), None);
+        let tools = all_tools(
+            &security,
+            mem,
+            None,
+            &browser,
+            &HashMap::new(),
+            None,
+            &ProviderOptions::default(),
+        );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(names.contains(&"browser_open"));
     }
@@ -303,7 +337,15 @@ mod tests {
             },
         );
 
-        let tools = all_tools(&security, mem, None, &browser, &agents, Some("sk-test"));
+        let tools = all_tools(
+            &security,
+            mem,
+            None,
+            &browser,
+            &agents,
+            Some("sk-test"),
+            &ProviderOptions::default(),
+        );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(names.contains(&"delegate"));
     }
@@ -321,7 +363,15 @@ mod tests {
 
         let browser = BrowserConfig::default();
 
-        let tools = all_tools(&security, mem, None, &browser, &HashMap::new(), None);
+        let tools = all_tools(
+            &security,
+            mem,
+            None,
+            &browser,
+            &HashMap::new(),
+            None,
+            &ProviderOptions::default(),
+        );
         let names: Vec<&str> = tools.iter().map(|t| t.name()).collect();
         assert!(!names.contains(&"delegate"));
     }
@@ -123,7 +123,7 @@ impl Tunnel for CustomTunnel {
     async fn health_check(&self) -> bool {
         // If a health URL is configured, try to reach it
         if let Some(ref url) = self.health_url {
-            return reqwest::Client::new()
+            return crate::http::client("tunnel", crate::http::Defaults::NONE)
                 .get(url)
                 .timeout(std::time::Duration::from_secs(5))
                 .send()